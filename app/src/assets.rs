@@ -0,0 +1,65 @@
+use std::{
+    borrow::Cow,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use lazy_static::lazy_static;
+
+// Built without `embed-assets`, the frontend is shipped next to the executable instead
+#[cfg(feature = "embed-assets")]
+#[derive(rust_embed::RustEmbed)]
+#[folder = "../target/dist/"]
+struct Embedded;
+
+lazy_static! {
+    static ref ASSET_DIR: Option<PathBuf> = asset_dir();
+}
+
+#[cfg(debug_assertions)]
+fn asset_dir() -> Option<PathBuf> {
+    Some(PathBuf::from("target/dist"))
+}
+
+#[cfg(not(debug_assertions))]
+fn asset_dir() -> Option<PathBuf> {
+    let dir = std::env::current_exe().ok()?.parent()?.join("assets");
+    dir.is_dir().then(|| dir)
+}
+
+// From the `assets` directory if it has it, so a big asset can be left out of the executable,
+// from the embedded ones otherwise
+pub fn get(path: &str) -> Option<Cow<'static, [u8]>> {
+    from_disk(path).map(Cow::Owned).or_else(|| embedded(path))
+}
+
+fn from_disk(path: &str) -> Option<Vec<u8>> {
+    let dir = ASSET_DIR.as_ref()?;
+
+    // Nothing outside of it
+    let path = Path::new(path);
+    if !path.components().all(|component| matches!(component, Component::Normal(_))) {
+        return None;
+    }
+
+    let path = dir.join(path);
+    match fs::read(&path) {
+        Ok(bytes) => Some(bytes),
+        Err(err) => {
+            if path.exists() {
+                tracing::warn!("Failed to read `{}`: {}", path.display(), err);
+            }
+            None
+        }
+    }
+}
+
+#[cfg(feature = "embed-assets")]
+fn embedded(path: &str) -> Option<Cow<'static, [u8]>> {
+    Embedded::get(path).map(|asset| asset.data)
+}
+
+#[cfg(not(feature = "embed-assets"))]
+fn embedded(_: &str) -> Option<Cow<'static, [u8]>> {
+    None
+}
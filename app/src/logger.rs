@@ -0,0 +1,47 @@
+use std::{collections::VecDeque, io};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use tracing::Level;
+use tracing_subscriber::fmt::writer::MakeWriterExt;
+
+// Enough to cover a session without growing forever
+const MAX_LINES: usize = 2000;
+
+lazy_static! {
+    static ref LOGS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::with_capacity(MAX_LINES));
+}
+
+// Keeps the formatted events in memory for the log viewer
+struct LogBuffer;
+
+impl io::Write for LogBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let line = String::from_utf8_lossy(buf).trim_end().to_owned();
+
+        let mut logs = LOGS.lock();
+        if logs.len() == MAX_LINES {
+            logs.pop_front();
+        }
+        logs.push_back(line);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+pub fn init() {
+    let max_level = if cfg!(debug_assertions) { Level::DEBUG } else { Level::INFO };
+
+    tracing_subscriber::fmt()
+        .with_max_level(max_level)
+        .with_ansi(false)
+        .with_writer(io::stdout.and(|| LogBuffer))
+        .init();
+}
+
+pub fn logs() -> Vec<String> {
+    LOGS.lock().iter().cloned().collect()
+}
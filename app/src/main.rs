@@ -5,12 +5,13 @@
 #[cfg(target_os = "windows")]
 mod windows;
 
+mod assets;
+mod logger;
 mod rpc;
 
 use anyhow::Result;
 use clap::{Arg, ArgMatches};
 use image::GenericImageView;
-use rust_embed::RustEmbed;
 use serde_json::json;
 use wry::{
     application::{
@@ -20,19 +21,24 @@ use wry::{
         window::{Icon, WindowBuilder},
     },
     http::{self, status::StatusCode},
-    webview::WebViewBuilder,
+    webview::{WebContext, WebViewBuilder},
 };
 
-#[derive(RustEmbed)]
-#[folder = "../target/dist/"]
-struct Asset;
-
 fn parse_args() -> ArgMatches {
     let app = clap::App::new("Trilogy Save Editor")
         .version(env!("CARGO_PKG_VERSION"))
         .author("by Karlitos")
         .about("A save editor for Mass Effect Trilogy (and Legendary)")
-        .arg(Arg::new("SAVE").help("Mass Effect save file"));
+        .arg(Arg::new("SAVE").help("Mass Effect save file"))
+        .arg(
+            Arg::new("FUZZ")
+                .long("fuzz")
+                .hide(true)
+                .help("Adds a button corrupting the opened save to test the parser"),
+        )
+        .arg(Arg::new("PORTABLE").long("portable").help(
+            "Keeps the settings and backups next to the executable, same as a `portable.txt` file",
+        ));
 
     app.get_matches()
 }
@@ -53,7 +59,14 @@ async fn main() -> Result<()> {
         }
     }
 
+    logger::init();
+    tracing::info!("Trilogy Save Editor v{}", env!("CARGO_PKG_VERSION"));
+
     let args = parse_args();
+    let portable_dir = rpc::portable_dir(&args);
+    if let Some(ref portable_dir) = portable_dir {
+        tracing::info!("Portable mode, data kept in `{}`", portable_dir.display());
+    }
 
     let event_loop = EventLoop::<rpc::Event>::with_user_event();
     let window = WindowBuilder::new()
@@ -67,17 +80,22 @@ async fn main() -> Result<()> {
 
     let mut last_maximized_state = window.is_maximized();
 
-    let proxy = event_loop.create_proxy();
+    // The settings and recent files live in the webview's local storage
+    let webview_dir = portable_dir.map(|portable_dir| portable_dir.join("webview"));
+    let mut web_context = WebContext::new(webview_dir.clone());
+
+    let (events, shutdown) = rpc::events::channel(event_loop.create_proxy());
+    let rpc_events = events.clone();
     let webview = WebViewBuilder::new(window)?
+        .with_web_context(&mut web_context)
         .with_initialization_script(include_str!("init.js"))
         .with_rpc_handler(move |window, req| {
-            rpc::rpc_handler(req, rpc::RpcUtils { window, event_proxy: &proxy, args: &args })
+            rpc::rpc_handler(req, rpc::RpcUtils { window, events: &rpc_events, args: &args })
         })
         .with_custom_protocol(String::from("tse"), protocol)
         .with_url("tse://localhost/")?
         .build()?;
 
-    let proxy = event_loop.create_proxy();
     event_loop.run(move |event, _, control_flow| {
         *control_flow = ControlFlow::Wait;
 
@@ -89,19 +107,21 @@ async fn main() -> Result<()> {
                     let is_maximized = webview.window().is_maximized();
                     if is_maximized != last_maximized_state {
                         last_maximized_state = is_maximized;
-                        let _ = proxy.send_event(rpc::Event::DispatchCustomEvent(
+                        events.dispatch(
                             "tse_maximized_state_changed",
                             json!({ "is_maximized": is_maximized }),
-                        ));
+                        );
                     }
                 }
                 _ => (),
             },
             Event::UserEvent(event) => rpc::event_handler(event, &webview, control_flow),
             Event::LoopDestroyed => {
+                shutdown.run();
+
                 // Clear WebView2 Code Cache
                 #[cfg(target_os = "windows")]
-                windows::clear_code_cache();
+                windows::clear_code_cache(webview_dir.as_deref());
             }
             _ => (),
         }
@@ -118,10 +138,10 @@ fn protocol(request: &http::Request) -> wry::Result<http::Response> {
     }
 
     let response = http::ResponseBuilder::new();
-    match Asset::get(path) {
+    match assets::get(path) {
         Some(asset) => {
             let mime = mime_guess::from_path(path).first_or_octet_stream().to_string();
-            response.mimetype(&mime).body(asset.data.into())
+            response.mimetype(&mime).body(asset.into())
         }
         None => response.status(StatusCode::NOT_FOUND).body(vec![]),
     }
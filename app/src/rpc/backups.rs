@@ -0,0 +1,208 @@
+use std::{
+    cmp::Ordering,
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{bail, Context, Result};
+use clap::ArgMatches;
+use serde::Serialize;
+
+use super::paths;
+
+// For each save, the oldest go past that
+const MAX_BACKUPS: usize = 20;
+const BACKUP_DIR: &str = "backups";
+
+#[derive(Serialize)]
+pub struct Backup {
+    pub path: PathBuf,
+    // Milliseconds since the Unix epoch, from its name
+    pub created: f64,
+    pub size: u64,
+}
+
+// A `backups` folder next to the save, or in the portable data in a folder named after the one of
+// the save
+fn backup_dir(args: &ArgMatches, path: &Path) -> PathBuf {
+    match paths::portable_dir(args) {
+        Some(portable_dir) => {
+            let mut backup_dir = portable_dir.join(BACKUP_DIR);
+            if let Some(parent) = path.parent().and_then(Path::file_name) {
+                backup_dir.push(parent);
+            }
+            backup_dir
+        }
+        None => path.with_file_name(BACKUP_DIR),
+    }
+}
+
+// Copies the file about to be overwritten, `Save.pcsav` => `Save.pcsav.20211231-235959-999.bak`
+// with the UTC time of the copy. Nothing to do for a new file.
+pub fn back_up(args: &ArgMatches, path: &Path) -> Result<()> {
+    let file_name = match path.file_name() {
+        Some(file_name) if path.is_file() => file_name,
+        _ => return Ok(()),
+    };
+
+    let backup_dir = backup_dir(args, path);
+    fs::create_dir_all(&backup_dir)?;
+
+    // Two copies within the same millisecond, such as a restore backing up the save right after
+    // it was written, would have the same name
+    let mut millis = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
+    let backup = loop {
+        let mut backup_name = file_name.to_owned();
+        backup_name.push(format!(".{}.bak", format_timestamp(millis)));
+        let backup = backup_dir.join(backup_name);
+        if !backup.exists() {
+            break backup;
+        }
+        millis += 1;
+    };
+
+    fs::copy(path, &backup)?;
+    tracing::debug!("Backed up `{}`", backup.display());
+
+    for old in list(args, path)?.into_iter().skip(MAX_BACKUPS) {
+        fs::remove_file(&old.path)?;
+        tracing::debug!("Deleted the old backup `{}`", old.path.display());
+    }
+    Ok(())
+}
+
+// Newest first
+pub fn list(args: &ArgMatches, path: &Path) -> Result<Vec<Backup>> {
+    let backup_dir = backup_dir(args, path);
+    let file_name = path.file_name().and_then(OsStr::to_str).unwrap_or_default();
+    if !backup_dir.is_dir() || file_name.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(&backup_dir)?.filter_map(|entry| entry.ok()) {
+        let name = entry.file_name();
+        let timestamp = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(file_name))
+            .and_then(|rest| rest.strip_prefix('.'))
+            .and_then(|rest| rest.strip_suffix(".bak"))
+            .and_then(parse_timestamp);
+
+        if let Some(millis) = timestamp {
+            backups.push(Backup {
+                path: entry.path(),
+                created: millis as f64,
+                size: entry.metadata()?.len(),
+            });
+        }
+    }
+    backups.sort_by(|a, b| b.created.partial_cmp(&a.created).unwrap_or(Ordering::Equal));
+    Ok(backups)
+}
+
+// The save as it is now is backed up too, restoring can be undone the same way
+pub fn restore(args: &ArgMatches, path: &Path, backup: &Path) -> Result<()> {
+    let is_backup_of_save = list(args, path)?.iter().any(|known| known.path == backup);
+    if !is_backup_of_save {
+        bail!("`{}` is not a backup of `{}`", backup.display(), path.display());
+    }
+
+    // Read first, backing up the save may delete the oldest backups
+    let restored = fs::read(backup)?;
+    back_up(args, path)?;
+    fs::write(path, restored)
+        .with_context(|| format!("Failed to restore `{}`", backup.display()))?;
+    tracing::info!("Restored `{}` from `{}`", path.display(), backup.display());
+    Ok(())
+}
+
+// With the save they were made of
+pub fn delete(args: &ArgMatches, path: &Path) -> Result<()> {
+    for backup in list(args, path)? {
+        fs::remove_file(&backup.path)?;
+    }
+    Ok(())
+}
+
+// The backups follow their save when it's renamed
+pub fn rename(args: &ArgMatches, from: &Path, to: &Path) -> Result<()> {
+    let from_name = from.file_name().and_then(OsStr::to_str).unwrap_or_default();
+    let to_name = to.file_name().and_then(OsStr::to_str).unwrap_or_default();
+    for backup in list(args, from)? {
+        let name = backup.path.file_name().and_then(OsStr::to_str).unwrap_or_default();
+        if let Some(rest) = name.strip_prefix(from_name) {
+            let target = backup_dir(args, to).join(format!("{}{}", to_name, rest));
+            fs::create_dir_all(backup_dir(args, to))?;
+            fs::rename(&backup.path, target)?;
+        }
+    }
+    Ok(())
+}
+
+// `20211231-235959-999`, milliseconds since the Unix epoch as a UTC date
+fn format_timestamp(millis: u64) -> String {
+    let (secs, millis) = (millis / 1000, millis % 1000);
+    let (days, secs_of_day) = ((secs / 86_400) as i64, secs % 86_400);
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}-{:03}",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+        millis
+    )
+}
+
+fn parse_timestamp(timestamp: &str) -> Option<u64> {
+    let mut parts = timestamp.split('-');
+    let (date, time, millis) = (parts.next()?, parts.next()?, parts.next()?);
+    let is_digits = |digits: &str| digits.bytes().all(|byte| byte.is_ascii_digit());
+    if parts.next().is_some()
+        || date.len() != 8
+        || time.len() != 6
+        || millis.len() != 3
+        || ![date, time, millis].into_iter().all(is_digits)
+    {
+        return None;
+    }
+    let number = |digits: &str| digits.parse::<u64>().ok();
+    let (year, month, day) = (number(&date[..4])?, number(&date[4..6])?, number(&date[6..])?);
+    let (hours, minutes, secs) = (number(&time[..2])?, number(&time[2..4])?, number(&time[4..])?);
+
+    let days = days_from_civil(year as i64, month as u32, day as u32);
+    let secs = u64::try_from(days).ok()? * 86_400 + hours * 3600 + minutes * 60 + secs;
+    Some(secs * 1000 + number(millis)?)
+}
+
+// Days since 1970-01-01 to a date of the proleptic Gregorian calendar, and back.
+// From http://howardhinnant.github.io/date_algorithms.html
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = (if month_index < 10 { month_index + 3 } else { month_index - 9 }) as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
@@ -0,0 +1,113 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::{dialog::bioware_dir, paths};
+
+const LE_DIR: &str = "Mass Effect Legendary Edition";
+const LE_STEAM_APP_ID: &str = "1328670";
+
+#[derive(Serialize)]
+pub struct CloudSave {
+    pub path: PathBuf,
+    pub modified: f64,
+    pub opened_modified: f64,
+}
+
+// The Legendary Edition keeps its saves in `Documents/BioWare/Mass Effect Legendary Edition`
+// and Steam Cloud mirrors them in `userdata/<user>/1328670/remote`.
+// Returns the other copy of the opened save if there is one
+pub fn find_cloud_save(opened: &Path) -> Result<Option<CloudSave>> {
+    let relative = match relative_save_path(opened) {
+        Some(relative) => relative,
+        None => return Ok(None),
+    };
+
+    let local = bioware_dir().and_then(|dir| paths::find_ignoring_case(&dir, LE_DIR));
+    let other = local
+        .into_iter()
+        .chain(steam_remote_dirs())
+        .filter_map(|dir| paths::find_ignoring_case(&dir, &relative));
+
+    let opened = opened.canonicalize()?;
+    let other = other
+        .filter(|path| path.is_file())
+        .find(|path| path.canonicalize().map(|path| path != opened).unwrap_or(false));
+
+    match other {
+        Some(path) => Ok(Some(CloudSave {
+            modified: modified(&path)?,
+            opened_modified: modified(&opened)?,
+            path,
+        })),
+        None => Ok(None),
+    }
+}
+
+// `Save/ME2/Career/Save.pcsav` from either of the known locations, whatever their case
+fn relative_save_path(path: &Path) -> Option<PathBuf> {
+    let components: Vec<_> = path.components().collect();
+    let root = components.iter().rposition(|component| {
+        let component = component.as_os_str();
+        paths::same_name(component, LE_DIR) || paths::same_name(component, "remote")
+    })?;
+    let relative = &components[root + 1..];
+    let is_save = relative
+        .first()
+        .map(|component| paths::same_name(component.as_os_str(), "Save"))
+        .unwrap_or(false);
+    is_save.then(|| relative.iter().collect())
+}
+
+fn steam_remote_dirs() -> Vec<PathBuf> {
+    let userdata = match steam_dir() {
+        Some(steam_dir) => steam_dir.join("userdata"),
+        None => return Vec::new(),
+    };
+
+    fs::read_dir(userdata)
+        .map(|users| {
+            users
+                .filter_map(|user| user.ok())
+                .filter_map(|user| {
+                    paths::find_ignoring_case(
+                        &user.path(),
+                        Path::new(LE_STEAM_APP_ID).join("remote"),
+                    )
+                })
+                .filter(|remote| remote.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn modified(path: &Path) -> Result<f64> {
+    let modified = fs::metadata(path)?.modified()?;
+    Ok(modified.duration_since(UNIX_EPOCH)?.as_millis() as f64)
+}
+
+#[cfg(target_os = "windows")]
+fn steam_dir() -> Option<PathBuf> {
+    std::env::var_os("ProgramFiles(x86)").and_then(|program_files| {
+        let path = PathBuf::from(program_files).join("Steam");
+        path.is_dir().then(|| path)
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn steam_dir() -> Option<PathBuf> {
+    dirs::home_dir().and_then(|mut path| {
+        path.push(".steam/root");
+        path.is_dir().then(|| path)
+    })
+}
+
+#[cfg(all(not(target_os = "linux"), not(target_os = "windows")))]
+fn steam_dir() -> Option<PathBuf> {
+    None
+}
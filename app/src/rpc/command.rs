@@ -1,12 +1,24 @@
 use std::{
     fs,
     path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, UNIX_EPOCH},
 };
 
-use anyhow::{Error, Result};
+use anyhow::{Context, Error, Result};
+use filetime::FileTime;
 use serde::{Deserialize, Serialize};
+use wry::application::clipboard::Clipboard;
 
-use super::{dialog, Event, RpcUtils};
+use crate::logger;
+
+use super::{
+    backups::{self, Backup},
+    cloud_save::{self, CloudSave},
+    dialog, external_editor, paths,
+    save_library::{self, Career},
+    Event, RpcUtils,
+};
 
 // Commands
 pub fn init(utils: &RpcUtils) {
@@ -27,16 +39,18 @@ pub fn drag_window(utils: &RpcUtils) {
 }
 
 pub fn close(utils: &RpcUtils) {
-    let _ = utils.event_proxy.send_event(Event::CloseWindow);
+    if let Err(err) = utils.events.send(Event::CloseWindow) {
+        tracing::error!("Failed to close the window: {}", err);
+    }
 }
 
 #[cfg(target_os = "windows")]
 pub fn check_for_update(utils: &RpcUtils) -> Result<()> {
     use crate::windows::auto_update::AUTO_UPDATE;
 
-    let proxy = utils.event_proxy.clone();
-    tokio::spawn(async move {
-        AUTO_UPDATE.check_for_update(proxy).await;
+    let events = utils.events.clone();
+    utils.events.spawn("check_for_update", async move {
+        AUTO_UPDATE.check_for_update(&events).await;
     });
 
     Ok(())
@@ -46,14 +60,22 @@ pub fn check_for_update(utils: &RpcUtils) -> Result<()> {
 pub fn download_and_install_update(utils: &RpcUtils) -> Result<()> {
     use crate::windows::auto_update::AUTO_UPDATE;
 
-    let proxy = utils.event_proxy.clone();
-    tokio::spawn(async move {
-        AUTO_UPDATE.download_and_install(proxy).await;
+    let events = utils.events.clone();
+    utils.events.spawn("download_and_install_update", async move {
+        AUTO_UPDATE.download_and_install(&events).await;
     });
 
     Ok(())
 }
 
+#[cfg(target_os = "windows")]
+pub fn cancel_update(_: &RpcUtils) -> Result<()> {
+    use crate::windows::auto_update::AUTO_UPDATE;
+
+    AUTO_UPDATE.cancel();
+    Ok(())
+}
+
 #[cfg(not(target_os = "windows"))]
 pub fn check_for_update(_: &RpcUtils) -> Result<()> {
     Ok(())
@@ -64,6 +86,11 @@ pub fn download_and_install_update(_: &RpcUtils) -> Result<()> {
     Ok(())
 }
 
+#[cfg(not(target_os = "windows"))]
+pub fn cancel_update(_: &RpcUtils) -> Result<()> {
+    Ok(())
+}
+
 pub fn open_external_link(_: &RpcUtils, link: PathBuf) -> Result<()> {
     opener::open(link).map_err(Error::from)
 }
@@ -72,6 +99,31 @@ pub fn save_file(_: &RpcUtils, rpc_file: RpcFile) -> Result<()> {
     write_file(rpc_file)
 }
 
+// The game's load menu sorts the careers by date
+pub fn save_save_file(utils: &RpcUtils, params: SaveFileParams) -> Result<()> {
+    let SaveFileParams { rpc_file, timestamp } = params;
+    let path = rpc_file.path.clone();
+
+    let modified = match timestamp {
+        FileTimestamp::Now => None,
+        // A new file has nothing to keep
+        FileTimestamp::Keep => fs::metadata(&path).and_then(|metadata| metadata.modified()).ok(),
+        FileTimestamp::Set(millis) => {
+            Some(UNIX_EPOCH + Duration::from_millis(millis.max(0.0) as u64))
+        }
+    };
+
+    // Overwriting a save can't be taken back otherwise
+    backups::back_up(utils.args, &path)?;
+    write_file(rpc_file)?;
+
+    if let Some(modified) = modified {
+        filetime::set_file_mtime(&path, FileTime::from_system_time(modified))?;
+        tracing::debug!("Set `{}` modified time", path.display());
+    }
+    Ok(())
+}
+
 pub fn open_save(utils: &RpcUtils, last_dir: bool) -> Result<Option<RpcFile>> {
     match dialog::open_save(utils.window, last_dir) {
         Some(path) => open_file(path).map(Some),
@@ -100,34 +152,216 @@ pub fn export_head_morph_dialog(utils: &RpcUtils) -> Result<Option<PathBuf>> {
     Ok(result)
 }
 
-pub fn load_database(_: &RpcUtils, path: PathBuf) -> Result<RpcFile> {
+pub fn export_gibbed_head_morph_dialog(
+    utils: &RpcUtils, extension: String,
+) -> Result<Option<PathBuf>> {
+    let result = dialog::export_gibbed_head_morph(utils.window, extension);
+    Ok(result)
+}
+
+pub fn import_war_assets(utils: &RpcUtils) -> Result<Option<RpcFile>> {
+    match dialog::import_war_assets(utils.window) {
+        Some(path) => open_file(path).map(Some),
+        None => Ok(None),
+    }
+}
+
+pub fn export_war_assets_dialog(utils: &RpcUtils) -> Result<Option<PathBuf>> {
+    let result = dialog::export_war_assets(utils.window);
+    Ok(result)
+}
+
+pub fn export_statistics_dialog(utils: &RpcUtils) -> Result<Option<PathBuf>> {
+    let result = dialog::export_statistics(utils.window);
+    Ok(result)
+}
+
+pub fn export_plot_coverage_dialog(utils: &RpcUtils) -> Result<Option<PathBuf>> {
+    let result = dialog::export_plot_coverage(utils.window);
+    Ok(result)
+}
+
+pub fn export_plot_research_dialog(utils: &RpcUtils) -> Result<Option<PathBuf>> {
+    let result = dialog::export_plot_research(utils.window);
+    Ok(result)
+}
+
+pub fn import_bookmarks(utils: &RpcUtils) -> Result<Option<RpcFile>> {
+    match dialog::import_bookmarks(utils.window) {
+        Some(path) => open_file(path).map(Some),
+        None => Ok(None),
+    }
+}
+
+pub fn export_bookmarks_dialog(utils: &RpcUtils) -> Result<Option<PathBuf>> {
+    let result = dialog::export_bookmarks(utils.window);
+    Ok(result)
+}
+
+pub fn import_save_json(utils: &RpcUtils) -> Result<Option<RpcFile>> {
+    match dialog::import_save_json(utils.window) {
+        Some(path) => open_file(path).map(Some),
+        None => Ok(None),
+    }
+}
+
+pub fn export_save_json_dialog(utils: &RpcUtils) -> Result<Option<PathBuf>> {
+    let result = dialog::export_save_json(utils.window);
+    Ok(result)
+}
+
+pub fn load_database(utils: &RpcUtils, path: PathBuf) -> Result<RpcFile> {
+    // A portable copy can have its own databases, for a modded game
+    if let Some(portable_dir) = paths::portable_dir(utils.args) {
+        let override_path = portable_dir.join(&path);
+        if override_path.is_file() {
+            return open_file(override_path);
+        }
+    }
+
     #[cfg(not(debug_assertions))]
     let path = std::env::current_exe()?.parent().map(|parent| parent.join(&path)).unwrap_or(path);
 
     open_file(path)
 }
 
+pub fn import_tlk(utils: &RpcUtils) -> Result<Option<RpcFile>> {
+    match dialog::import_tlk(utils.window) {
+        Some(path) => open_file(path).map(Some),
+        None => Ok(None),
+    }
+}
+
+pub fn load_tlk(_: &RpcUtils, path: PathBuf) -> Result<RpcFile> {
+    open_file(path)
+}
+
+pub fn find_cloud_save(_: &RpcUtils, path: PathBuf) -> Result<Option<CloudSave>> {
+    cloud_save::find_cloud_save(&path)
+}
+
+pub fn list_careers(_: &RpcUtils) -> Result<Vec<Career>> {
+    save_library::list_careers()
+}
+
+pub fn delete_saves(utils: &RpcUtils, saves: Vec<PathBuf>) -> Result<()> {
+    save_library::delete_saves(utils.args, saves)
+}
+
+pub fn renumber_saves(utils: &RpcUtils, saves: Vec<PathBuf>) -> Result<()> {
+    save_library::renumber_saves(utils.args, saves)
+}
+
+pub fn list_backups(utils: &RpcUtils, path: PathBuf) -> Result<Vec<Backup>> {
+    backups::list(utils.args, &path)
+}
+
+// Read again once restored, to be reopened
+pub fn restore_backup(utils: &RpcUtils, params: RestoreBackupParams) -> Result<RpcFile> {
+    let RestoreBackupParams { path, backup } = params;
+    backups::restore(utils.args, &path, &backup)?;
+    open_file(path)
+}
+
+// A URI such as `steam://rungameid/...` or the executable of the game
+pub fn launch_game(_: &RpcUtils, target: String) -> Result<()> {
+    let target = target.trim();
+    if target.contains("://") {
+        opener::open(target)?;
+    } else {
+        let executable = Path::new(target);
+        let mut command = Command::new(executable);
+        if let Some(dir) = executable.parent() {
+            command.current_dir(dir);
+        }
+        command.spawn().with_context(|| format!("Failed to start `{}`", target))?;
+    }
+    tracing::info!("Launched `{}`", target);
+    Ok(())
+}
+
+pub fn load_notes(_: &RpcUtils, path: PathBuf) -> Result<String> {
+    let notes_path = notes_path(&path);
+    if notes_path.is_file() {
+        fs::read_to_string(notes_path).map_err(Error::from)
+    } else {
+        Ok(String::new())
+    }
+}
+
+pub fn save_notes(_: &RpcUtils, params: NotesParams) -> Result<()> {
+    let NotesParams { path, notes } = params;
+    let notes_path = notes_path(&path);
+
+    // No empty sidecar lying around
+    if notes.trim().is_empty() {
+        if notes_path.is_file() {
+            fs::remove_file(notes_path)?;
+        }
+        return Ok(());
+    }
+    fs::write(notes_path, notes).map_err(Error::from)
+}
+
+pub fn edit_externally(utils: &RpcUtils, params: ExternalEditParams) -> Result<()> {
+    let ExternalEditParams { id, text } = params;
+    external_editor::edit(utils.events, id, &text)
+}
+
 // Utils
+pub fn fuzzing_enabled(utils: &RpcUtils) -> Result<bool> {
+    Ok(utils.args.is_present("FUZZ"))
+}
+
+pub fn get_logs(_: &RpcUtils) -> Result<Vec<String>> {
+    Ok(logger::logs())
+}
+
+pub fn copy_logs(_: &RpcUtils) -> Result<()> {
+    let mut clipboard = Clipboard::new();
+    clipboard.write_text(logger::logs().join("\n"));
+    Ok(())
+}
+
+pub fn copy_to_clipboard(_: &RpcUtils, text: String) -> Result<()> {
+    let mut clipboard = Clipboard::new();
+    clipboard.write_text(text);
+    Ok(())
+}
+
+// Events of the frontend services (save parsing, databases, ...) end up in the same logs
+pub fn log(_: &RpcUtils, params: LogParams) -> Result<()> {
+    let LogParams { level, message } = params;
+    match level {
+        LogLevel::Error => tracing::error!(target: "frontend", "{}", message),
+        LogLevel::Warn => tracing::warn!(target: "frontend", "{}", message),
+        LogLevel::Info => tracing::info!(target: "frontend", "{}", message),
+        LogLevel::Debug => tracing::debug!(target: "frontend", "{}", message),
+    }
+    Ok(())
+}
+
 fn open_file(path: PathBuf) -> Result<RpcFile> {
     let file = fs::read(path.canonicalize()?)?;
+    tracing::info!("Read `{}` ({} bytes)", path.display(), file.len());
     let unencoded_size = file.len();
     let base64 = base64::encode(file);
     Ok(RpcFile { path, file: Base64File { unencoded_size, base64 } })
 }
 
+// `Save.pcsav` => `Save.pcsav.notes.txt`
+pub(super) fn notes_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().map(ToOwned::to_owned).unwrap_or_default();
+    file_name.push(".notes.txt");
+    path.with_file_name(file_name)
+}
+
 fn write_file(rpc_file: RpcFile) -> Result<()> {
     let RpcFile { path, file } = rpc_file;
 
-    // Backup if file exists
-    if path.exists() {
-        if let Some(ext) = path.extension() {
-            let mut ext = ext.to_owned();
-            ext.push(".bak");
-            let to = Path::with_extension(&path, ext);
-            fs::copy(&path, to)?;
-        }
-    }
-    fs::write(path, file.decode()?)?;
+    let file = file.decode()?;
+    tracing::info!("Write `{}` ({} bytes)", path.display(), file.len());
+    fs::write(path, file)?;
 
     Ok(())
 }
@@ -138,6 +372,44 @@ pub struct DialogParams {
     pub filters: Vec<(String, Vec<String>)>,
 }
 
+#[derive(Deserialize, Default)]
+pub struct RestoreBackupParams {
+    pub path: PathBuf,
+    pub backup: PathBuf,
+}
+
+#[derive(Deserialize, Default)]
+pub struct NotesParams {
+    pub path: PathBuf,
+    pub notes: String,
+}
+
+#[derive(Deserialize, Default)]
+pub struct ExternalEditParams {
+    pub id: u32,
+    pub text: String,
+}
+
+#[derive(Deserialize, Default)]
+pub struct SaveFileParams {
+    pub rpc_file: RpcFile,
+    pub timestamp: FileTimestamp,
+}
+
+// Modified time of a written save, `Set` in milliseconds since the Unix epoch
+#[derive(Deserialize, Clone, Copy)]
+pub enum FileTimestamp {
+    Now,
+    Keep,
+    Set(f64),
+}
+
+impl Default for FileTimestamp {
+    fn default() -> Self {
+        FileTimestamp::Now
+    }
+}
+
 #[derive(Deserialize, Serialize, Default)]
 pub struct RpcFile {
     pub path: PathBuf,
@@ -157,3 +429,23 @@ impl Base64File {
         Ok(vec)
     }
 }
+
+#[derive(Deserialize)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl Default for LogLevel {
+    fn default() -> Self {
+        LogLevel::Info
+    }
+}
+
+#[derive(Deserialize, Default)]
+pub struct LogParams {
+    level: LogLevel,
+    message: String,
+}
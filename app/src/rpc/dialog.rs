@@ -3,7 +3,7 @@ use std::path::PathBuf;
 
 use wry::application::window::Window;
 
-use super::command::DialogParams;
+use super::{command::DialogParams, paths};
 
 pub fn open_save(window: &Window, last_dir: bool) -> Option<PathBuf> {
     let mut dialog = rfd::FileDialog::new()
@@ -59,12 +59,76 @@ pub fn export_head_morph(window: &Window) -> Option<PathBuf> {
     with_parent(dialog, window).save_file()
 }
 
+pub fn export_gibbed_head_morph(window: &Window, extension: String) -> Option<PathBuf> {
+    let dialog = rfd::FileDialog::new().add_filter("Gibbed's Head Morph", &[&extension]);
+    with_parent(dialog, window).save_file()
+}
+
+pub fn import_war_assets(window: &Window) -> Option<PathBuf> {
+    let dialog =
+        rfd::FileDialog::new().add_filter("War Assets", &["ron"]).add_filter("All Files", &["*"]);
+
+    with_parent(dialog, window).pick_file()
+}
+
+pub fn export_war_assets(window: &Window) -> Option<PathBuf> {
+    let dialog = rfd::FileDialog::new().add_filter("War Assets", &["ron"]);
+    with_parent(dialog, window).save_file()
+}
+
+pub fn export_statistics(window: &Window) -> Option<PathBuf> {
+    let dialog = rfd::FileDialog::new().add_filter("Statistics", &["json"]);
+    with_parent(dialog, window).save_file()
+}
+
+pub fn export_plot_coverage(window: &Window) -> Option<PathBuf> {
+    let dialog = rfd::FileDialog::new().add_filter("Plot coverage", &["json"]);
+    with_parent(dialog, window).save_file()
+}
+
+pub fn export_plot_research(window: &Window) -> Option<PathBuf> {
+    let dialog = rfd::FileDialog::new().add_filter("Plot research", &["json"]);
+    with_parent(dialog, window).save_file()
+}
+
+pub fn import_bookmarks(window: &Window) -> Option<PathBuf> {
+    let dialog = rfd::FileDialog::new()
+        .add_filter("Bookmark pack", &["json"])
+        .add_filter("All Files", &["*"]);
+
+    with_parent(dialog, window).pick_file()
+}
+
+pub fn export_bookmarks(window: &Window) -> Option<PathBuf> {
+    let dialog = rfd::FileDialog::new().add_filter("Bookmark pack", &["json"]);
+    with_parent(dialog, window).save_file()
+}
+
+pub fn import_save_json(window: &Window) -> Option<PathBuf> {
+    let dialog = rfd::FileDialog::new()
+        .add_filter("Save as JSON", &["json"])
+        .add_filter("All Files", &["*"]);
+
+    with_parent(dialog, window).pick_file()
+}
+
+pub fn export_save_json(window: &Window) -> Option<PathBuf> {
+    let dialog = rfd::FileDialog::new().add_filter("Save as JSON", &["json"]);
+    with_parent(dialog, window).save_file()
+}
+
+pub fn import_tlk(window: &Window) -> Option<PathBuf> {
+    let dialog =
+        rfd::FileDialog::new().add_filter("Talk Table", &["tlk"]).add_filter("All Files", &["*"]);
+
+    with_parent(dialog, window).pick_file()
+}
+
 #[cfg(target_os = "windows")]
-fn bioware_dir() -> Option<PathBuf> {
-    dirs::document_dir().and_then(|mut path| {
-        path.push("BioWare\\");
-        path.is_dir().then(|| path)
-    })
+pub(super) fn bioware_dir() -> Option<PathBuf> {
+    dirs::document_dir()
+        .and_then(|dir| paths::find_ignoring_case(&dir, "BioWare"))
+        .filter(|path| path.is_dir())
 }
 
 // FIXME: Find some nicer way of finding where the game saves are.
@@ -72,15 +136,18 @@ fn bioware_dir() -> Option<PathBuf> {
 // Mass Effect games installed in the default steam library, in
 // the user's home directory.
 #[cfg(target_os = "linux")]
-fn bioware_dir() -> Option<PathBuf> {
-    dirs::home_dir().and_then(|mut path| {
-        path.push(".steam/root/steamapps/compatdata/1328670/pfx/drive_c/users/steamuser/My Documents/BioWare/");
-        path.is_dir().then(|| path)
-    })
+pub(super) fn bioware_dir() -> Option<PathBuf> {
+    const PROTON_DOCUMENTS: &str =
+        ".steam/root/steamapps/compatdata/1328670/pfx/drive_c/users/steamuser/My Documents";
+
+    dirs::home_dir()
+        .and_then(|dir| paths::find_ignoring_case(&dir, PROTON_DOCUMENTS))
+        .and_then(|dir| paths::find_ignoring_case(&dir, "BioWare"))
+        .filter(|path| path.is_dir())
 }
 
 #[cfg(all(not(target_os = "linux"), not(target_os = "windows")))]
-fn bioware_dir() -> Option<PathBuf> {
+pub(super) fn bioware_dir() -> Option<PathBuf> {
     None
 }
 
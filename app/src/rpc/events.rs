@@ -0,0 +1,133 @@
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context, Result};
+use parking_lot::{Condvar, Mutex};
+use serde_json::Value;
+use tokio::sync::{oneshot, watch};
+use wry::{application::event_loop::EventLoopProxy, webview::WebView};
+
+use super::Event;
+
+// How long the running tasks get to wrap up once the window is closed
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub fn channel(proxy: EventLoopProxy<Event>) -> (EventSender, Shutdown) {
+    let (sender, receiver) = watch::channel(false);
+    let tasks = Arc::new(Tasks::default());
+    let events = EventSender { proxy, shutdown: receiver, tasks: Arc::clone(&tasks) };
+    (events, Shutdown { sender, tasks })
+}
+
+// How the commands and the tasks they spawn reach the event loop, which owns the webview
+#[derive(Clone)]
+pub struct EventSender {
+    proxy: EventLoopProxy<Event>,
+    shutdown: watch::Receiver<bool>,
+    tasks: Arc<Tasks>,
+}
+
+impl EventSender {
+    pub fn send(&self, event: Event) -> Result<()> {
+        self.proxy.send_event(event).map_err(|_| anyhow!("the event loop is closed"))
+    }
+
+    // Nothing to wait for, but a lost event still ends up in the logs
+    pub fn dispatch(&self, event: &'static str, detail: Value) {
+        if let Err(err) = self.send(Event::DispatchCustomEvent(event, detail)) {
+            tracing::warn!("`{}` not dispatched: {}", event, err);
+        }
+    }
+
+    // Runs `f` on the event loop and waits for its result, for what only the main thread can do
+    pub async fn request<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&WebView) -> T + Send + 'static,
+    {
+        let (sender, receiver) = oneshot::channel();
+        self.send(Event::Request(Box::new(move |webview| {
+            if sender.send(f(webview)).is_err() {
+                tracing::debug!("Request answered after its task was cancelled");
+            }
+        })))?;
+        receiver.await.context("the event loop dropped the request")
+    }
+
+    // Resolves once the app is closing
+    pub async fn shutting_down(&self) {
+        let mut shutdown = self.shutdown.clone();
+        while !*shutdown.borrow() {
+            if shutdown.changed().await.is_err() {
+                break;
+            }
+        }
+    }
+
+    // Cancelled when the app closes, which waits for it to be dropped
+    pub fn spawn<F>(&self, name: &'static str, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let events = self.clone();
+        let running = self.running();
+        tokio::spawn(async move {
+            let _running = running;
+            tokio::select! {
+                _ = task => (),
+                _ = events.shutting_down() => tracing::info!("`{}` cancelled on exit", name),
+            }
+        });
+    }
+
+    fn running(&self) -> Running {
+        *self.tasks.running.lock() += 1;
+        Running(Arc::clone(&self.tasks))
+    }
+}
+
+// The tasks still running, the event loop is woken up as each of them ends
+#[derive(Default)]
+struct Tasks {
+    running: Mutex<usize>,
+    ended: Condvar,
+}
+
+// Counts a task as running until dropped
+struct Running(Arc<Tasks>);
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        *self.0.running.lock() -= 1;
+        self.0.ended.notify_all();
+    }
+}
+
+// Kept by the event loop
+pub struct Shutdown {
+    sender: watch::Sender<bool>,
+    tasks: Arc<Tasks>,
+}
+
+impl Shutdown {
+    // Tells the tasks to stop then blocks until they are done, or until the timeout. The event
+    // loop runs within the runtime, where the blocking waits of tokio would panic.
+    pub fn run(&self) {
+        if self.sender.send(true).is_err() {
+            // No `EventSender` left, so no task either
+            return;
+        }
+
+        let deadline = Instant::now() + SHUTDOWN_TIMEOUT;
+        let mut running = self.tasks.running.lock();
+        while *running > 0 {
+            if self.tasks.ended.wait_until(&mut running, deadline).timed_out() {
+                tracing::warn!("{} tasks still running on exit", *running);
+                break;
+            }
+        }
+    }
+}
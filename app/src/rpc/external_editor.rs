@@ -0,0 +1,100 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use anyhow::{Context, Result};
+use serde_json::json;
+use tokio::{
+    process::{Child, Command},
+    time,
+};
+
+use super::events::EventSender;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+// Without an editor process to wait for, nothing says when the user is done
+const MAX_WATCH: Duration = Duration::from_secs(60 * 60);
+
+// Writes the value to a temp file, opens it in the user's editor then sends every save of it
+// back to the frontend as a `tse_external_edit` event, until the editor is closed
+pub fn edit(events: &EventSender, id: u32, text: &str) -> Result<()> {
+    let path = env::temp_dir().join(format!("trilogy-save-editor-{}.txt", id));
+    fs::write(&path, text)?;
+    let temp_file = TempFile(path);
+    let child = open_editor(&temp_file.0)?;
+
+    let task_events = events.clone();
+    events.spawn("edit_externally", async move {
+        watch(&task_events, id, &temp_file.0, child).await;
+
+        // Back to the save editor, the external one was probably on top of it
+        let result = task_events.request(|webview| webview.window().set_minimized(false)).await;
+        if let Err(err) = result {
+            tracing::warn!("Failed to restore the window: {}", err);
+        }
+    });
+    Ok(())
+}
+
+// `VISUAL` or `EDITOR` if set, the program associated with `.txt` files otherwise
+fn open_editor(path: &Path) -> Result<Option<Child>> {
+    let editor = env::var_os("VISUAL").or_else(|| env::var_os("EDITOR"));
+    match editor {
+        Some(editor) => {
+            let child = Command::new(&editor)
+                .arg(path)
+                .spawn()
+                .with_context(|| format!("Failed to launch `{}`", editor.to_string_lossy()))?;
+            Ok(Some(child))
+        }
+        None => {
+            opener::open(path)?;
+            Ok(None)
+        }
+    }
+}
+
+async fn watch(events: &EventSender, id: u32, path: &Path, mut child: Option<Child>) {
+    let start = Instant::now();
+    let mut last_modified = modified(path);
+
+    loop {
+        time::sleep(POLL_INTERVAL).await;
+
+        let modified = modified(path);
+        if modified.is_some() && modified != last_modified {
+            last_modified = modified;
+            match fs::read_to_string(path) {
+                Ok(text) => {
+                    events.dispatch("tse_external_edit", json!({ "id": id, "text": text }));
+                }
+                Err(err) => tracing::warn!("Failed to read `{}`: {}", path.display(), err),
+            }
+        }
+
+        let is_done = match child {
+            Some(ref mut child) => !matches!(child.try_wait(), Ok(None)),
+            None => start.elapsed() > MAX_WATCH,
+        };
+        if is_done || modified.is_none() {
+            break;
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+// Removed once the watch is over, even if it's cancelled on exit
+struct TempFile(PathBuf);
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if let Err(err) = fs::remove_file(&self.0) {
+            tracing::warn!("Failed to remove `{}`: {}", self.0.display(), err);
+        }
+    }
+}
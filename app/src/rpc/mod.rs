@@ -1,5 +1,11 @@
+mod backups;
+mod cloud_save;
 mod command;
 mod dialog;
+pub mod events;
+mod external_editor;
+mod paths;
+mod save_library;
 
 use std::env;
 use std::path::PathBuf;
@@ -8,13 +14,13 @@ use anyhow::{bail, Context, Result};
 use clap::ArgMatches;
 use serde_json::{json, Value};
 use wry::{
-    application::{
-        event_loop::{ControlFlow, EventLoopProxy},
-        window::Window,
-    },
+    application::{event_loop::ControlFlow, window::Window},
     webview::{RpcRequest, RpcResponse, WebView},
 };
 
+use self::events::EventSender;
+pub use self::paths::portable_dir;
+
 macro_rules! notify_commands {
     ($req:ident, $utils:ident => [$(command::$command:ident),* $(,)?]) => {
         $(
@@ -55,7 +61,7 @@ macro_rules! call_commands_with_param {
 
 pub struct RpcUtils<'a> {
     pub window: &'a Window,
-    pub event_proxy: &'a EventLoopProxy<Event>,
+    pub events: &'a EventSender,
     pub args: &'a ArgMatches,
 }
 
@@ -86,17 +92,46 @@ pub fn rpc_handler(mut req: RpcRequest, utils: RpcUtils) -> Option<RpcResponse>
         call_commands!(req, utils => [
             command::check_for_update,
             command::download_and_install_update,
+            command::cancel_update,
             command::import_head_morph,
             command::export_head_morph_dialog,
+            command::import_war_assets,
+            command::export_war_assets_dialog,
+            command::export_statistics_dialog,
+            command::export_plot_coverage_dialog,
+            command::export_plot_research_dialog,
+            command::import_bookmarks,
+            command::export_bookmarks_dialog,
+            command::import_save_json,
+            command::export_save_json_dialog,
+            command::import_tlk,
+            command::list_careers,
+            command::fuzzing_enabled,
+            command::get_logs,
+            command::copy_logs,
         ]);
 
         call_commands_with_param!(req, utils => [
             command::open_external_link,
             command::open_save,
             command::save_file,
+            command::save_save_file,
             command::save_save_dialog,
+            command::export_gibbed_head_morph_dialog,
             command::reload_save,
             command::load_database,
+            command::load_tlk,
+            command::find_cloud_save,
+            command::delete_saves,
+            command::renumber_saves,
+            command::list_backups,
+            command::restore_backup,
+            command::launch_game,
+            command::load_notes,
+            command::save_notes,
+            command::edit_externally,
+            command::log,
+            command::copy_to_clipboard,
         ]);
 
         bail!("Wrong RPC method, got: {}", req.method)
@@ -105,20 +140,25 @@ pub fn rpc_handler(mut req: RpcRequest, utils: RpcUtils) -> Option<RpcResponse>
     match handle_request() {
         Ok(None) => None,
         Ok(Some(response)) => Some(RpcResponse::new_result(req.id.take(), Some(response))),
-        Err(error) => Some(RpcResponse::new_error(req.id.take(), Some(json!(error.to_string())))),
+        Err(error) => {
+            tracing::error!("`{}` failed: {:#}", req.method, error);
+            Some(RpcResponse::new_error(req.id.take(), Some(json!(error.to_string()))))
+        }
     }
 }
 
 pub enum Event {
     CloseWindow,
     DispatchCustomEvent(&'static str, serde_json::Value),
+    // Sent by `EventSender::request`, which waits for the answer
+    Request(Box<dyn FnOnce(&WebView) + Send>),
 }
 
 pub fn event_handler(event: Event, webview: &WebView, control_flow: &mut ControlFlow) {
     match event {
         Event::CloseWindow => *control_flow = ControlFlow::Exit,
         Event::DispatchCustomEvent(event, detail) => {
-            let _ = webview.evaluate_script(&format!(
+            let result = webview.evaluate_script(&format!(
                 r#"
                 (() => {{
                     const event = new CustomEvent("{event}", {{
@@ -130,6 +170,10 @@ pub fn event_handler(event: Event, webview: &WebView, control_flow: &mut Control
                 event = event,
                 detail = detail,
             ));
+            if let Err(err) = result {
+                tracing::warn!("Failed to dispatch `{}`: {}", event, err);
+            }
         }
+        Event::Request(request) => request(webview),
     }
 }
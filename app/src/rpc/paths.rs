@@ -0,0 +1,54 @@
+use std::{
+    env,
+    ffi::OsStr,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use clap::ArgMatches;
+use unicode_normalization::UnicodeNormalization;
+
+// Next to the executable, for a copy run from a USB stick or one kept per mod setup
+const PORTABLE_MARKER: &str = "portable.txt";
+const PORTABLE_DIR: &str = "portable_data";
+
+// Where the settings, backups and database overrides are kept in portable mode, turned on by
+// `--portable` or a `portable.txt` file next to the executable
+pub fn portable_dir(args: &ArgMatches) -> Option<PathBuf> {
+    let exe_dir = env::current_exe().ok()?.parent()?.to_owned();
+    let is_portable = args.is_present("PORTABLE") || exe_dir.join(PORTABLE_MARKER).is_file();
+    is_portable.then(|| exe_dir.join(PORTABLE_DIR))
+}
+
+// Windows doesn't care about the case of a path and Proton prefixes or copied saves keep whatever
+// case they were created with, so a known location is looked up one component at a time.
+// Returns the path as it is on disk, if it exists
+pub fn find_ignoring_case(dir: &Path, relative: impl AsRef<Path>) -> Option<PathBuf> {
+    relative.as_ref().components().try_fold(dir.to_owned(), |dir, component| {
+        let name = match component {
+            Component::Normal(name) => name,
+            _ => return Some(dir.join(component)),
+        };
+
+        let exact = dir.join(name);
+        if exact.exists() {
+            return Some(exact);
+        }
+
+        let name = name.to_str()?;
+        fs::read_dir(&dir)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .find(|entry| same_name(&entry.file_name(), name))
+            .map(|entry| entry.path())
+    })
+}
+
+// macOS decomposes the accents of the file names, Windows and Linux keep them composed
+pub fn same_name(name: &OsStr, other: &str) -> bool {
+    fn normalize(name: &str) -> String {
+        name.nfc().flat_map(char::to_lowercase).collect()
+    }
+
+    name.to_str().map(|name| normalize(name) == normalize(other)).unwrap_or(false)
+}
@@ -0,0 +1,269 @@
+use std::{
+    cmp::Ordering,
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use anyhow::{bail, Context, Result};
+use clap::ArgMatches;
+use serde::Serialize;
+
+use super::{backups, command::notes_path, dialog::bioware_dir, paths};
+
+// Game, saves directory relative to `Documents/BioWare`, one sub directory per career
+const SAVE_DIRS: &[(&str, &str)] = &[
+    ("Mass Effect", "Mass Effect/Save"),
+    ("Mass Effect 2", "Mass Effect 2/Save"),
+    ("Mass Effect 3", "Mass Effect 3/Save"),
+    ("Mass Effect LE", "Mass Effect Legendary Edition/Save/ME1"),
+    ("Mass Effect 2 LE", "Mass Effect Legendary Edition/Save/ME2"),
+    ("Mass Effect 3 LE", "Mass Effect Legendary Edition/Save/ME3"),
+];
+
+const SAVE_EXTENSIONS: &[&str] = &["pcsav", "MassEffectSave"];
+
+#[derive(Serialize)]
+pub struct Career {
+    pub game: &'static str,
+    pub name: String,
+    pub path: PathBuf,
+    // Everything in the career directory, backups and notes included
+    pub size: u64,
+    pub saves: Vec<SaveFile>,
+}
+
+#[derive(Serialize)]
+pub struct SaveFile {
+    pub path: PathBuf,
+    pub kind: SaveKind,
+    pub size: u64,
+    pub modified: f64,
+}
+
+#[derive(Serialize, Clone, Copy)]
+pub enum SaveKind {
+    Auto,
+    Quick,
+    // `Save_0001`
+    Manual(u32),
+    Other,
+}
+
+impl SaveKind {
+    fn of(path: &Path) -> Self {
+        let stem = path.file_stem().and_then(OsStr::to_str).unwrap_or_default().to_lowercase();
+        if stem.starts_with("autosave") || stem.starts_with("chaptersave") {
+            SaveKind::Auto
+        } else if stem.starts_with("quicksave") {
+            SaveKind::Quick
+        } else {
+            stem.strip_prefix("save_")
+                .and_then(|number| number.parse().ok())
+                .map(SaveKind::Manual)
+                .unwrap_or(SaveKind::Other)
+        }
+    }
+}
+
+pub fn list_careers() -> Result<Vec<Career>> {
+    let bioware_dir = match bioware_dir() {
+        Some(bioware_dir) => bioware_dir,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut careers = Vec::new();
+    for (game, relative) in SAVE_DIRS {
+        let save_dir = match paths::find_ignoring_case(&bioware_dir, relative) {
+            Some(save_dir) if save_dir.is_dir() => save_dir,
+            _ => continue,
+        };
+
+        for entry in fs::read_dir(&save_dir)?.filter_map(|entry| entry.ok()) {
+            let path = entry.path();
+            if path.is_dir() {
+                careers.push(read_career(game, path)?);
+            }
+        }
+    }
+    Ok(careers)
+}
+
+fn read_career(game: &'static str, path: PathBuf) -> Result<Career> {
+    let mut size = 0;
+    let mut saves = Vec::new();
+    for entry in fs::read_dir(&path)?.filter_map(|entry| entry.ok()) {
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+        size += metadata.len();
+
+        let path = entry.path();
+        if is_save(&path) {
+            let modified = metadata.modified()?.duration_since(UNIX_EPOCH)?.as_millis() as f64;
+            saves.push(SaveFile {
+                kind: SaveKind::of(&path),
+                size: metadata.len(),
+                modified,
+                path,
+            });
+        }
+    }
+    // Newest first, as in the game's load menu
+    saves.sort_by(|a, b| b.modified.partial_cmp(&a.modified).unwrap_or(Ordering::Equal));
+
+    let name = path.file_name().map(OsStr::to_string_lossy).unwrap_or_default().into_owned();
+    Ok(Career { game, name, path, size, saves })
+}
+
+fn is_save(path: &Path) -> bool {
+    path.extension()
+        .map(|extension| SAVE_EXTENSIONS.iter().any(|known| paths::same_name(extension, known)))
+        .unwrap_or(false)
+}
+
+// With their notes, the backups of the editor go along
+pub fn delete_saves(args: &ArgMatches, saves: Vec<PathBuf>) -> Result<()> {
+    for path in saves {
+        if !is_save(&path) {
+            bail!("`{}` is not a save", path.display());
+        }
+
+        fs::remove_file(&path).with_context(|| format!("Failed to delete `{}`", path.display()))?;
+        tracing::info!("Deleted `{}`", path.display());
+
+        // Along with the single `.bak` the older versions kept next to the save
+        for sidecar in [notes_path(&path), legacy_backup_path(&path)] {
+            if sidecar.is_file() {
+                fs::remove_file(&sidecar)?;
+            }
+        }
+        backups::delete(args, &path)?;
+    }
+    Ok(())
+}
+
+// `Save_0001`, `Save_0002`... in the given order, all from the same career.
+// Goes through temporary names so that no save overwrites another
+pub fn renumber_saves(args: &ArgMatches, saves: Vec<PathBuf>) -> Result<()> {
+    let career = match saves.first().and_then(|path| path.parent()) {
+        Some(career) => career.to_owned(),
+        None => return Ok(()),
+    };
+    if saves.iter().any(|path| !is_save(path) || path.parent() != Some(career.as_path())) {
+        bail!("Only saves of the same career can be renumbered");
+    }
+
+    // Notes and backups follow their save, through a temporary name too
+    let rename = |from: &Path, to: &Path| -> Result<()> {
+        fs::rename(from, to)?;
+        let notes = notes_path(from);
+        if notes.is_file() {
+            fs::rename(notes, notes_path(to))?;
+        }
+        backups::rename(args, from, to)
+    };
+
+    let renames: Vec<_> = saves
+        .iter()
+        .enumerate()
+        .map(|(idx, path)| {
+            let extension = path.extension().unwrap_or_default();
+            let target = career.join(format!("Save_{:04}", idx + 1)).with_extension(extension);
+            (path, path.with_extension("renumbering"), target)
+        })
+        .collect();
+
+    // Checked before anything is renamed, a save stopped halfway would be left out of the game's
+    // load menu. A target is only free if it's one of the saves moved out of the way.
+    let is_renumbered = |target: &Path| {
+        let name = target.file_name().and_then(OsStr::to_str).unwrap_or_default();
+        saves.iter().any(|path| {
+            path.file_name().map(|other| paths::same_name(other, name)).unwrap_or(false)
+        })
+    };
+    for (_, temporary, target) in &renames {
+        if temporary.exists() {
+            bail!("`{}` already exists, nothing was renumbered", temporary.display());
+        }
+        if target.exists() && !is_renumbered(target) {
+            bail!("`{}` already exists, nothing was renumbered", target.display());
+        }
+    }
+
+    for (path, temporary, _) in &renames {
+        rename(path, temporary)?;
+    }
+    for (path, temporary, target) in &renames {
+        rename(temporary, target)?;
+        tracing::info!("Renamed `{}` to `{}`", path.display(), target.display());
+    }
+    Ok(())
+}
+
+// `Save.pcsav` => `Save.pcsav.bak`
+fn legacy_backup_path(path: &Path) -> PathBuf {
+    let mut extension = path.extension().unwrap_or_default().to_owned();
+    extension.push(".bak");
+    path.with_extension(extension)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env, process};
+
+    use clap::{App, Arg};
+
+    use super::*;
+
+    fn career(name: &str, saves: &[&str]) -> Result<PathBuf> {
+        let career = env::temp_dir().join(format!("tse-{}-{}", name, process::id()));
+        if career.exists() {
+            fs::remove_dir_all(&career)?;
+        }
+        fs::create_dir_all(&career)?;
+        for save in saves {
+            fs::write(career.join(save), save)?;
+        }
+        Ok(career)
+    }
+
+    fn args() -> ArgMatches {
+        App::new("test").arg(Arg::new("PORTABLE").long("portable")).get_matches_from(["test"])
+    }
+
+    #[test]
+    fn renumber_saves_in_order() -> Result<()> {
+        let career = career("renumber", &["Save_0003.pcsav", "Save_0001.pcsav"])?;
+        let saves = vec![career.join("Save_0003.pcsav"), career.join("Save_0001.pcsav")];
+
+        renumber_saves(&args(), saves)?;
+        assert_eq!(fs::read_to_string(career.join("Save_0001.pcsav"))?, "Save_0003.pcsav");
+        assert_eq!(fs::read_to_string(career.join("Save_0002.pcsav"))?, "Save_0001.pcsav");
+        assert!(!career.join("Save_0003.pcsav").exists());
+
+        fs::remove_dir_all(career)?;
+        Ok(())
+    }
+
+    #[test]
+    fn renumber_saves_onto_another_save() -> Result<()> {
+        // `Save_0001` isn't in the list, it would be overwritten
+        let career =
+            career("conflict", &["Save_0001.pcsav", "Save_0005.pcsav", "Save_0007.pcsav"])?;
+        let saves = vec![career.join("Save_0005.pcsav"), career.join("Save_0007.pcsav")];
+
+        assert!(renumber_saves(&args(), saves).is_err());
+        let mut names: Vec<_> = fs::read_dir(&career)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.file_name())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["Save_0001.pcsav", "Save_0005.pcsav", "Save_0007.pcsav"]);
+
+        fs::remove_dir_all(career)?;
+        Ok(())
+    }
+}
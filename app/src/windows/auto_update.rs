@@ -1,4 +1,7 @@
-use std::env;
+use std::{
+    env,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use anyhow::Error;
 use lazy_static::lazy_static;
@@ -6,9 +9,8 @@ use parking_lot::Mutex;
 use serde::Deserialize;
 use serde_json::json;
 use tokio::{fs, process};
-use wry::application::event_loop::EventLoopProxy;
 
-use crate::rpc;
+use crate::rpc::{self, events::EventSender};
 
 const GITHUB_API: &str =
     "https://api.github.com/repos/KarlitosVII/trilogy-save-editor/releases/latest";
@@ -41,14 +43,20 @@ lazy_static! {
 
 pub struct AutoUpdate {
     update_available: Mutex<Option<GithubAsset>>,
+    is_cancelled: AtomicBool,
 }
 
 impl AutoUpdate {
     fn new() -> Self {
-        AutoUpdate { update_available: Mutex::new(None) }
+        AutoUpdate { update_available: Mutex::new(None), is_cancelled: AtomicBool::new(false) }
     }
 
-    pub async fn check_for_update(&self, proxy: EventLoopProxy<rpc::Event>) {
+    // Stops the download in progress, the update stays available
+    pub fn cancel(&self) {
+        self.is_cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub async fn check_for_update(&self, events: &EventSender) {
         let result = async {
             let response = REQWEST.get(GITHUB_API).send().await?.json().await?;
             let GithubResponse { tag_name, prerelease, assets } = response;
@@ -58,48 +66,43 @@ impl AutoUpdate {
                     assets.into_iter().find(|asset| asset.name.ends_with("setup.exe"))
                 {
                     *self.update_available.lock() = Some(update_available);
-                    let _ = proxy.send_event(rpc::Event::DispatchCustomEvent(
-                        "tse_update_available",
-                        json!({}),
-                    ));
+                    events.dispatch("tse_update_available", json!({}));
                 }
             }
             Ok::<_, Error>(())
         };
 
         if let Err(err) = result.await {
-            let _ = proxy.send_event(rpc::Event::DispatchCustomEvent(
-                "tse_update_error",
-                json!({ "error": err.to_string() }),
-            ));
+            events.dispatch("tse_update_error", json!({ "error": err.to_string() }));
         }
     }
 
-    pub async fn download_and_install(&self, proxy: EventLoopProxy<rpc::Event>) {
+    pub async fn download_and_install(&self, events: &EventSender) {
+        self.is_cancelled.store(false, Ordering::SeqCst);
         let asset = self.update_available.lock().take();
-        if let Some(GithubAsset { name, browser_download_url, size }) = asset {
+        if let Some(asset) = asset {
             let result = async {
                 let send_progress = |progress: f64| {
-                    let _ = proxy.send_event(rpc::Event::DispatchCustomEvent(
-                        "tse_update_progress",
-                        json!({ "progress": progress }),
-                    ));
+                    events.dispatch("tse_update_progress", json!({ "progress": progress }));
                 };
 
                 // Download
-                let mut response = REQWEST.get(browser_download_url).send().await?;
-                let mut setup = Vec::with_capacity(size);
+                let mut response = REQWEST.get(&asset.browser_download_url).send().await?;
+                let mut setup = Vec::with_capacity(asset.size);
 
                 send_progress(0.0);
-                let size = size as f64;
+                let size = asset.size as f64;
                 while let Some(chunk) = response.chunk().await? {
+                    if self.is_cancelled.swap(false, Ordering::SeqCst) {
+                        return Ok(Some(asset));
+                    }
                     setup.extend(chunk);
                     send_progress(setup.len() as f64 / size);
                 }
 
                 // Install
                 let temp_dir = env::temp_dir().join("trilogy-save-editor");
-                let path = temp_dir.join(name);
+                let path = temp_dir.join(&asset.name);
 
                 // If not exists
                 if fs::metadata(&temp_dir).await.is_err() {
@@ -107,18 +110,23 @@ impl AutoUpdate {
                 }
                 fs::write(&path, setup).await?;
 
+                // Out of the way before the setup starts, no more edits to lose
+                events.request(|webview| webview.window().set_visible(false)).await?;
                 process::Command::new(path).arg("/SILENT").arg("/NOICONS").spawn()?;
+                events.send(rpc::Event::CloseWindow)?;
 
-                let _ = proxy.send_event(rpc::Event::CloseWindow);
-
-                Ok::<_, Error>(())
+                Ok::<_, Error>(None)
             };
 
-            if let Err(err) = result.await {
-                let _ = proxy.send_event(rpc::Event::DispatchCustomEvent(
-                    "tse_update_error",
-                    json!({ "error": err.to_string() }),
-                ));
+            match result.await {
+                Ok(Some(asset)) => {
+                    *self.update_available.lock() = Some(asset);
+                    events.dispatch("tse_update_cancelled", json!({}));
+                }
+                Ok(None) => (),
+                Err(err) => {
+                    events.dispatch("tse_update_error", json!({ "error": err.to_string() }))
+                }
             }
         }
     }
@@ -1,4 +1,4 @@
-use std::env;
+use std::{env, path::Path};
 
 use anyhow::{bail, Result};
 use tokio::{fs, process};
@@ -42,12 +42,18 @@ pub async fn install_webview2() -> Result<()> {
     Ok(())
 }
 
-pub fn clear_code_cache() {
+pub fn clear_code_cache(webview_dir: Option<&Path>) {
     use std::fs;
     let execute = || -> Result<()> {
-        let mut code_cache_dir = env::current_exe()?;
-        code_cache_dir.set_extension("exe.WebView2");
-        code_cache_dir.push("EBWebView\\Default\\Code Cache\\wasm");
+        let mut code_cache_dir = match webview_dir {
+            Some(webview_dir) => webview_dir.to_owned(),
+            None => {
+                let mut default_dir = env::current_exe()?;
+                default_dir.set_extension("exe.WebView2");
+                default_dir
+            }
+        };
+        code_cache_dir.extend(["EBWebView", "Default", "Code Cache", "wasm"]);
 
         if code_cache_dir.is_dir() {
             const THRESHOLD: u64 = 1024 * 1024; // 1mo
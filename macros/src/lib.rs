@@ -14,7 +14,7 @@ pub fn rcize_fields(_: TokenStream, input: TokenStream) -> TokenStream {
     rcize::rcize_fields(input)
 }
 
-#[proc_macro_derive(RawUi)]
+#[proc_macro_derive(RawUi, attributes(raw_ui))]
 pub fn raw_ui_derive(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
 
@@ -26,7 +26,7 @@ pub fn raw_ui_derive(input: TokenStream) -> TokenStream {
     .into()
 }
 
-#[proc_macro_derive(RawUiRoot)]
+#[proc_macro_derive(RawUiRoot, attributes(raw_ui))]
 pub fn raw_ui_derive_root(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
 
@@ -37,7 +37,7 @@ pub fn raw_ui_derive_root(input: TokenStream) -> TokenStream {
     .into()
 }
 
-#[proc_macro_derive(RawUiChildren)]
+#[proc_macro_derive(RawUiChildren, attributes(raw_ui))]
 pub fn raw_ui_children_derive(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
 
@@ -3,7 +3,10 @@ use quote::{quote, quote_spanned};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::token::Comma;
-use syn::{self, DeriveInput, Fields, Variant};
+use syn::{
+    self, DeriveInput, Field, Fields, GenericArgument, Lit, Meta, NestedMeta, PathArguments, Type,
+    Variant,
+};
 
 #[allow(clippy::enum_variant_names)]
 pub enum Derive {
@@ -12,6 +15,67 @@ pub enum Derive {
     RawUiChildren,
 }
 
+// `#[raw_ui(label = "Credits", order = 1, group = "Resources", step = 1000, precision = 2)]`,
+// `#[raw_ui(string_ref)]` for the `i32` ids of the TLK
+#[derive(Default)]
+struct FieldAttrs {
+    label: Option<String>,
+    order: Option<i64>,
+    group: Option<String>,
+    // Number fields only
+    step: Option<f64>,
+    precision: Option<usize>,
+    string_ref: bool,
+}
+
+impl FieldAttrs {
+    fn parse(field: &Field) -> Self {
+        let mut attrs = FieldAttrs::default();
+        for attr in field.attrs.iter().filter(|attr| attr.path.is_ident("raw_ui")) {
+            let list = match attr.parse_meta() {
+                Ok(Meta::List(list)) => list,
+                _ => panic!("expected `#[raw_ui(...)]`"),
+            };
+
+            for nested in list.nested {
+                let name_value = match nested {
+                    NestedMeta::Meta(Meta::NameValue(name_value)) => name_value,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("string_ref") => {
+                        attrs.string_ref = true;
+                        continue;
+                    }
+                    _ => panic!("expected `name = value` or `string_ref` in `#[raw_ui(...)]`"),
+                };
+                let name = name_value.path.get_ident().map(ToString::to_string).unwrap_or_default();
+                match (name.as_str(), name_value.lit) {
+                    ("label", Lit::Str(lit)) => attrs.label = Some(lit.value()),
+                    ("group", Lit::Str(lit)) => attrs.group = Some(lit.value()),
+                    ("order", Lit::Int(lit)) => {
+                        attrs.order = Some(lit.base10_parse().expect("invalid `order`"))
+                    }
+                    ("step", Lit::Int(lit)) => {
+                        attrs.step = Some(lit.base10_parse().expect("invalid `step`"))
+                    }
+                    ("step", Lit::Float(lit)) => {
+                        attrs.step = Some(lit.base10_parse().expect("invalid `step`"))
+                    }
+                    ("precision", Lit::Int(lit)) => {
+                        attrs.precision = Some(lit.base10_parse().expect("invalid `precision`"))
+                    }
+                    _ => panic!("unknown or malformed raw_ui attribute `{}`", name),
+                }
+            }
+        }
+        attrs
+    }
+}
+
+enum Entry {
+    Field(proc_macro2::TokenStream),
+    // Group label, fields
+    Group(String, Vec<proc_macro2::TokenStream>),
+}
+
 pub fn impl_struct(
     ast: &DeriveInput, fields: &Fields, raw_ui_impl: Derive,
 ) -> proc_macro2::TokenStream {
@@ -22,54 +86,110 @@ pub fn impl_struct(
 
     let name = &ast.ident;
 
-    let view_fields = fields.iter().filter_map(|field| {
-        (!field.ident.as_ref().unwrap().to_string().starts_with('_')).then(|| {
-            let field_name = &field.ident;
-            let field_string = field_name.as_ref().unwrap().to_string().to_title_case();
+    let mut fields: Vec<_> = fields
+        .iter()
+        .filter(|field| !field.ident.as_ref().unwrap().to_string().starts_with('_'))
+        .map(|field| (field, FieldAttrs::parse(field)))
+        .collect();
+    // Fields with an explicit order come first, the others keep their declaration order
+    fields.sort_by_key(|(_, attrs)| (attrs.order.is_none(), attrs.order));
+
+    // A group is displayed where its first field would have been
+    let mut entries: Vec<Entry> = Vec::new();
+    for (field, attrs) in fields {
+        let field_name = &field.ident;
+        let field_string =
+            attrs.label.unwrap_or_else(|| field_name.as_ref().unwrap().to_string().to_title_case());
+        let view = if attrs.string_ref {
+            // String ref
+            quote_spanned! {field.span()=>
+                crate::gui::raw_ui::RawUiRenderer::string_ref(renderer, #field_string, &self.borrow().#field_name)
+            }
+        } else if is_dummy(&field.ty) {
+            // Unknown bytes, the same field of every save shares its annotations
+            let region = format!("{}.{}", name, field_name.as_ref().unwrap());
+            quote_spanned! {field.span()=>
+                crate::gui::raw_ui::RawUiRenderer::bytes(renderer, #field_string, #region, &self.borrow().#field_name)
+            }
+        } else if attrs.step.is_some() || attrs.precision.is_some() {
+            // Number with its format
+            let step = match attrs.step {
+                Some(step) => quote! { Some(#step) },
+                None => quote! { None },
+            };
+            let precision = match attrs.precision {
+                Some(precision) => quote! { Some(#precision) },
+                None => quote! { None },
+            };
+            quote_spanned! {field.span()=>
+                crate::gui::raw_ui::RawUiRenderer::formatted_number(
+                    renderer,
+                    #field_string,
+                    crate::gui::components::NumberType::from(&self.borrow().#field_name),
+                    crate::gui::components::NumberFormat { step: #step, precision: #precision },
+                )
+            }
+        } else {
             quote_spanned! {field.span()=>
-                crate::gui::raw_ui::RawUi::view(&self.borrow().#field_name, #field_string)
+                crate::gui::raw_ui::RawUi::render(&self.borrow().#field_name, renderer, #field_string, false)
+            }
+        };
+
+        match attrs.group {
+            Some(group) => {
+                let existing = entries.iter_mut().find_map(|entry| match entry {
+                    Entry::Group(label, views) if *label == group => Some(views),
+                    _ => None,
+                });
+                match existing {
+                    Some(views) => views.push(view),
+                    None => entries.push(Entry::Group(group, vec![view])),
+                }
             }
-        })
+            None => entries.push(Entry::Field(view)),
+        }
+    }
+
+    let view_fields = entries.into_iter().map(|entry| match entry {
+        Entry::Field(view) => view,
+        Entry::Group(label, views) => quote! {
+            {
+                let fields = vec![#(#views),*];
+                crate::gui::raw_ui::RawUiRenderer::structure(renderer, #label, false, fields)
+            }
+        },
     });
 
     match raw_ui_impl {
         Derive::RawUi => quote! {
             impl crate::gui::raw_ui::RawUi for crate::save_data::RcRef<#name> {
-                fn view(&self, label: &str) -> yew::Html {
-                    self.view_opened(label, false)
+                fn render<R: crate::gui::raw_ui::RawUiRenderer>(
+                    &self, renderer: &mut R, label: &str, opened: bool,
+                ) -> R::Output {
+                    let fields = vec![#(#view_fields),*];
+                    renderer.structure(label, opened, fields)
                 }
 
                 fn view_opened(&self, label: &str, opened: bool) -> yew::Html {
-                    use crate::gui::components::raw_ui::RawUiStruct;
-                    let fields = [#(#view_fields),*];
-                    yew::html! {
-                        <RawUiStruct label={label.to_owned()} {opened}>
-                            { for fields }
-                        </RawUiStruct>
-                    }
+                    self.render(&mut crate::gui::raw_ui::HtmlRenderer, label, opened)
                 }
             }
         },
         Derive::RawUiRoot => quote! {
             impl crate::gui::raw_ui::RawUi for crate::save_data::RcRef<#name> {
-                fn view(&self, label: &str) -> yew::Html {
-                    self.view_opened(label, false)
-                }
-
-                fn view_opened(&self, _: &str, _: bool) -> yew::Html {
-                    use crate::gui::components::Table;
-                    let fields = [#(#view_fields),*];
-                    yew::html! {
-                        <Table>
-                            { for fields }
-                        </Table>
-                    }
+                fn render<R: crate::gui::raw_ui::RawUiRenderer>(
+                    &self, renderer: &mut R, _: &str, _: bool,
+                ) -> R::Output {
+                    let fields = vec![#(#view_fields),*];
+                    renderer.root(fields)
                 }
             }
         },
         Derive::RawUiChildren => quote! {
             impl crate::gui::raw_ui::RawUiChildren for crate::save_data::RcRef<#name> {
-                fn children(&self) -> Vec<yew::Html> {
+                fn render_children<R: crate::gui::raw_ui::RawUiRenderer>(
+                    &self, renderer: &mut R,
+                ) -> Vec<R::Output> {
                     vec![#(#view_fields),*]
                 }
             }
@@ -77,6 +197,25 @@ pub fn impl_struct(
     }
 }
 
+// `Dummy<LEN>`, rcized into `RcRef<Dummy<LEN>>`
+fn is_dummy(ty: &Type) -> bool {
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last(),
+        _ => None,
+    };
+    match segment {
+        Some(segment) if segment.ident == "Dummy" => true,
+        Some(segment) if segment.ident == "RcRef" => match segment.arguments {
+            PathArguments::AngleBracketed(ref args) => match args.args.first() {
+                Some(GenericArgument::Type(ty)) => is_dummy(ty),
+                _ => false,
+            },
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
 pub fn impl_enum(
     ast: &DeriveInput, variants: &Punctuated<Variant, Comma>,
 ) -> proc_macro2::TokenStream {
@@ -133,12 +272,10 @@ pub fn impl_enum(
         }
 
         impl crate::gui::raw_ui::RawUi for crate::save_data::RcRef<#name> {
-            fn view(&self, label: &str) -> yew::Html {
-                use crate::gui::components::raw_ui::RawUiEnum;
-
-                yew::html!{
-                    <RawUiEnum<#name> label={label.to_owned()} items={#name::variants()} value={self.clone()} />
-                }
+            fn render<R: crate::gui::raw_ui::RawUiRenderer>(
+                &self, renderer: &mut R, label: &str, _: bool,
+            ) -> R::Output {
+                renderer.enumeration(label, #name::variants(), self)
             }
         }
     }
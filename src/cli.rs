@@ -0,0 +1,188 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::services::{
+    save_game::{Plots, SaveGame},
+    script::Script,
+};
+
+const USAGE: &str = "\
+Edits a save without opening the editor, the output platform follows its extension.
+
+USAGE:
+    trilogy-save-editor [EDITS]... <INPUT> <OUTPUT>
+
+EDITS:
+        --set-plot <ID>=<true|false>    Sets a boolean plot
+        --set-int-plot <ID>=<VALUE>     Sets an integer plot
+        --set-float-plot <ID>=<VALUE>   Sets a float plot
+        --set <PATH>=<VALUE>            Sets a value of the Raw Data tab by its path,
+                                        e.g. `Player > Credits=500` or `Difficulty=Insanity`
+        --script <FILE>                 Runs a script, as written in the Scripts panel of the
+                                        editor
+    -h, --help                          Prints help information
+
+The edits run in order.";
+
+enum Edit {
+    Plot(Plot),
+    Raw { path: String, value: String },
+    Script(Script),
+}
+
+#[derive(Clone, Copy)]
+enum Plot {
+    Boolean(usize, bool),
+    Integer(usize, i32),
+    Float(usize, f32),
+}
+
+struct Args {
+    edits: Vec<Edit>,
+    input: PathBuf,
+    output: PathBuf,
+}
+
+/// Applies the edits of the command line to a save and writes it, returns the exit code
+pub fn run(args: Vec<String>) -> i32 {
+    let result = match parse_args(args) {
+        Ok(Some(args)) => edit(args),
+        Ok(None) => {
+            println!("{}", USAGE);
+            return 0;
+        }
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("Error: {:#}", err);
+            1
+        }
+    }
+}
+
+fn parse_args(args: Vec<String>) -> Result<Option<Args>> {
+    let mut edits = Vec::new();
+    let mut paths = Vec::new();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let flag = match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--set-plot" | "--set-int-plot" | "--set-float-plot" | "--set" | "--script" => arg,
+            _ if arg.starts_with('-') => bail!("Unknown argument `{}`\n\n{}", arg, USAGE),
+            _ => {
+                paths.push(PathBuf::from(arg));
+                continue;
+            }
+        };
+
+        let param = args.next().with_context(|| format!("`{}` needs a value", flag))?;
+        if flag == "--script" {
+            let text = fs::read_to_string(&param)
+                .with_context(|| format!("Failed to read the script {}", param))?;
+            let script =
+                Script::compile(&text).with_context(|| format!("In the script {}", param))?;
+            edits.push(Edit::Script(script));
+            continue;
+        }
+
+        // A path can't contain a `=` but a text value could
+        let (key, value) = param
+            .split_once('=')
+            .with_context(|| format!("`{} {}` isn't of the form `<KEY>=<VALUE>`", flag, param))?;
+        let (key, value) = (key.trim(), value.trim());
+
+        let edit = match flag.as_str() {
+            "--set" => Edit::Raw { path: key.to_owned(), value: value.to_owned() },
+            _ => {
+                let id = key.parse().with_context(|| format!("Invalid plot id `{}`", key))?;
+                let plot = match flag.as_str() {
+                    "--set-plot" => Plot::Boolean(id, value.parse()?),
+                    "--set-int-plot" => Plot::Integer(id, value.parse()?),
+                    _ => Plot::Float(id, value.parse()?),
+                };
+                Edit::Plot(plot)
+            }
+        };
+        edits.push(edit);
+    }
+
+    let (input, output) = match <[PathBuf; 2]>::try_from(paths) {
+        Ok([input, output]) => (input, output),
+        Err(_) => bail!("Expected an input and an output save\n\n{}", USAGE),
+    };
+    if edits.is_empty() {
+        bail!("Nothing to edit\n\n{}", USAGE);
+    }
+    Ok(Some(Args { edits, input, output }))
+}
+
+fn edit(args: Args) -> Result<()> {
+    let Args { edits, input, output } = args;
+
+    let bytes = fs::read(&input).with_context(|| format!("Failed to read {}", input.display()))?;
+    let save_game = SaveGame::parse(input.clone(), &bytes, &mut Vec::new())
+        .with_context(|| format!("Failed to open {}", input.display()))?;
+
+    // Nothing is written if an edit fails
+    for edit in edits {
+        match edit {
+            Edit::Plot(plot) => {
+                let before = save_game.with_plots(|plots| plot.set(plots))?;
+                println!("{}: {} -> {}", plot, before, plot.value());
+            }
+            Edit::Raw { path, value } => {
+                let (before, after) = save_game.raw_value(&path, Some(&value))?;
+                println!("{}: {} -> {}", path, before, after);
+            }
+            Edit::Script(script) => {
+                for edit in script.run(&save_game)? {
+                    println!("{}: {} -> {}", edit.target, edit.before, edit.after);
+                }
+            }
+        }
+    }
+
+    let bytes = save_game
+        .to_bytes(&output)
+        .with_context(|| format!("Failed to save {}", output.display()))?;
+    fs::write(&output, bytes).with_context(|| format!("Failed to write {}", output.display()))?;
+    Ok(())
+}
+
+impl Plot {
+    fn value(&self) -> String {
+        match *self {
+            Plot::Boolean(_, value) => value.to_string(),
+            Plot::Integer(_, value) => value.to_string(),
+            Plot::Float(_, value) => value.to_string(),
+        }
+    }
+
+    // Returns the value it had, within the plot tables of the save like the Raw Plot tab
+    fn set(self, plots: Plots) -> Result<String> {
+        match self {
+            Plot::Boolean(id, value) => {
+                plots.set_boolean(id, value).map(|before| before.to_string())
+            }
+            Plot::Integer(id, value) => {
+                plots.set_integer(id, value).map(|before| before.to_string())
+            }
+            Plot::Float(id, value) => plots.set_float(id, value).map(|before| before.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for Plot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Plot::Boolean(id, _) => write!(f, "Boolean {}", id),
+            Plot::Integer(id, _) => write!(f, "Integer {}", id),
+            Plot::Float(id, _) => write!(f, "Float {}", id),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use std::cell::Ref;
 use std::mem;
+use std::rc::Rc;
 
 use anyhow::Error;
 use gloo::timers::future::TimeoutFuture;
@@ -7,23 +8,36 @@ use yew::prelude::*;
 
 use crate::{
     gui::{
-        components::{AutoUpdate, NavBar, Tab, TabBar, Table},
+        components::{
+            raw_ui::{self, GuidIndex, RawUiPinned},
+            AutoUpdate, CharacterHeader, CloudSaveWarning, DatabaseBrowser, LogViewer, NavBar,
+            ResearchPanel, SaveLibrary, ScriptPanel, SearchPanel, Settings, StartScreen, Tab,
+            TabBar, Table, WatchPanel,
+        },
         format_code,
         mass_effect_1::{Me1General, Me1Plot, Me1RawData, Me1RawPlot},
-        mass_effect_1_le::{Me1LeGeneral, Me1LeInventory},
+        mass_effect_1_le::{Me1LeGeneral, Me1LeImport, Me1LeInventory},
         mass_effect_2::{Me2General, Me2Plot, Me2RawPlot, Me2Type},
         mass_effect_3::{Me3General, Me3Plot, Me3RawPlot},
         raw_ui::RawUi,
-        shared::HeadMorph,
+        shared::{Carryover, Changes, Downstream, HeadMorph, HeadMorphGame, Notes},
         shared::{FloatPlotType, IntPlotType},
     },
     save_data::{
-        mass_effect_1::Me1SaveGame, mass_effect_1_le::Me1LeSaveData, mass_effect_3::Me3SaveGame,
+        mass_effect_1::Me1SaveGame,
+        mass_effect_1_le::Me1LeSaveData,
+        mass_effect_3::{plot::PlotTable as Me3PlotTable, Me3SaveGame},
+        shared::plot::PlotTable,
         RcRef,
     },
     services::{
+        appearance::Appearance,
         database::DatabaseProvider,
-        save_handler::{SaveGame, SaveHandler, SaveHandlerProvider},
+        derived_state::DerivedStateProvider,
+        document::DocumentProvider,
+        save_game::SaveGame,
+        save_handler::{SaveHandler, SaveHandlerProvider},
+        tlk::StringTableProvider,
     },
 };
 
@@ -35,6 +49,7 @@ pub enum Msg {
 }
 
 pub struct App {
+    _appearance: Appearance,
     notification: Option<&'static str>,
     error: Option<Error>,
 }
@@ -44,7 +59,7 @@ impl Component for App {
     type Properties = ();
 
     fn create(_ctx: &Context<Self>) -> Self {
-        App { notification: None, error: None }
+        App { _appearance: Appearance::init(), notification: None, error: None }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -84,12 +99,27 @@ impl Component for App {
                     onnotification={link.callback(Msg::Notification)}
                     onerror={link.callback(Msg::Error)}
                 >
-                    <NavBar>
-                        <AutoUpdate onerror={link.callback(Msg::Error)} />
-                    </NavBar>
-                    <DatabaseProvider onerror={link.callback(Msg::Error)}>
-                        <SaveContent/>
-                    </DatabaseProvider>
+                    <DocumentProvider>
+                        <StringTableProvider onerror={link.callback(Msg::Error)}>
+                            <DatabaseProvider onerror={link.callback(Msg::Error)}>
+                                <NavBar>
+                                    <Settings />
+                                    <SearchPanel />
+                                    <WatchPanel />
+                                    <ScriptPanel />
+                                    <ResearchPanel />
+                                    <SaveLibrary onerror={link.callback(Msg::Error)} />
+                                    <LogViewer onerror={link.callback(Msg::Error)} />
+                                    <AutoUpdate onerror={link.callback(Msg::Error)} />
+                                </NavBar>
+                                <CloudSaveWarning />
+                                <DerivedStateProvider>
+                                    <CharacterHeader />
+                                    <SaveContent/>
+                                </DerivedStateProvider>
+                            </DatabaseProvider>
+                        </StringTableProvider>
+                    </DocumentProvider>
                 </SaveHandlerProvider>
                 { for notification }
                 { for error }
@@ -117,7 +147,7 @@ impl App {
             ]}>
                 { notification }
                 <div class="relative h-0.5 bg-theme-bg">
-                    <div class="absolute notification-animation w-full h-0.5 bg-white"></div>
+                    <div class="absolute notification-animation w-full h-0.5 bg-fg"></div>
                 </div>
             </div>
         }
@@ -137,7 +167,7 @@ impl App {
             }
         });
         html! {
-            <div class="absolute w-screen h-[calc(100vh-28px)] grid place-content-center bg-white/30 z-50">
+            <div class="absolute w-screen h-[calc(100vh-28px)] grid place-content-center bg-fg/30 z-50">
                 <div class="border border-default-border bg-default-bg max-w-xl">
                     <div class="px-1 bg-theme-tab select-none">{"Error"}</div>
                     <div class="p-1 pt-0.5">
@@ -160,7 +190,7 @@ impl App {
 fn save_content() -> Html {
     let save_handler = use_context::<SaveHandler>().expect("no save handler provider");
     if let Some(save_game) = save_handler.save_game {
-        match save_game.as_ref() {
+        let content = match save_game.as_ref() {
             SaveGame::MassEffect1 { save_game, .. } => mass_effect_1(save_game.borrow()),
             SaveGame::MassEffect1Le { save_game, .. } => {
                 mass_effect_1_le(RcRef::clone(&save_game.borrow().save_data))
@@ -176,9 +206,38 @@ fn save_content() -> Html {
             }
 
             SaveGame::MassEffect3 { save_game, .. } => mass_effect_3(RcRef::clone(save_game)),
+            SaveGame::MassEffect2Quick { save_game, .. } => {
+                mass_effect_2_quick(RcRef::clone(&save_game.borrow().plot))
+            }
+            SaveGame::MassEffect3Quick { save_game, .. } => {
+                mass_effect_3_quick(RcRef::clone(&save_game.borrow().plot))
+            }
+        };
+        // Rebuilt from scratch when the save is edited from outside its tabs
+        html! {
+            <div key={save_handler.revision} class="contents">{ content }</div>
         }
     } else {
-        changelog()
+        home()
+    }
+}
+
+// Before any save is opened
+fn home() -> Html {
+    html! {
+        <section class="flex-auto flex p-1">
+            <TabBar is_main_tab_bar=true>
+                <Tab title="Start">
+                    <StartScreen />
+                </Tab>
+                <Tab title="Changelog">
+                    { changelog() }
+                </Tab>
+                <Tab title="Plot databases">
+                    <DatabaseBrowser />
+                </Tab>
+            </TabBar>
+        </section>
     }
 }
 
@@ -192,6 +251,7 @@ fn mass_effect_1(save_game: Ref<'_, Me1SaveGame>) -> Html {
                 <Tab title="General">
                     <Me1General
                         player={RcRef::clone(&save_game.player)}
+                        state={RcRef::clone(&save_game.state)}
                         plot={RcRef::clone(&state.plot)}
                     />
                 </Tab>
@@ -201,16 +261,24 @@ fn mass_effect_1(save_game: Ref<'_, Me1SaveGame>) -> Html {
                         integers={IntPlotType::Vec(RcRef::clone(&plot.integers))}
                     />
                 </Tab>
-                <Tab title="Raw Data">
-                    <Me1RawData player={RcRef::clone(&save_game.player)} />
+                <Tab title="Raw Data" monospace=true>
+                    <RawUiPinned pins_key="me1_raw_data_pins">
+                        <Me1RawData player={RcRef::clone(&save_game.player)} />
+                    </RawUiPinned>
                 </Tab>
-                <Tab title="Raw Plot">
+                <Tab title="Raw Plot" monospace=true>
                     <Me1RawPlot
                         booleans={RcRef::clone(&plot.booleans)}
                         integers={IntPlotType::Vec(RcRef::clone(&plot.integers))}
                         floats={FloatPlotType::Vec(RcRef::clone(&plot.floats))}
                     />
                 </Tab>
+                <Tab title="Changes">
+                    <Changes />
+                </Tab>
+                <Tab title="Notes">
+                    <Notes />
+                </Tab>
             </TabBar>
         </section>
     }
@@ -240,38 +308,54 @@ fn mass_effect_1_le(save_game: RcRef<Me1LeSaveData>) -> Html {
                     />
                 </Tab>
                 <Tab title="Head Morph">
-                    <HeadMorph {head_morph} />
+                    <HeadMorph {head_morph} game={HeadMorphGame::MassEffect1} />
+                </Tab>
+                <Tab title="ME1 Import">
+                    <Me1LeImport />
                 </Tab>
-                <Tab title="Raw Data">
-                    { save_game.view_opened("Mass Effect 1", true) }
+                <Tab title="Raw Data" monospace=true>
+                    <RawUiPinned pins_key="me1_le_raw_data_pins">
+                        { save_game.view_opened("Mass Effect 1", true) }
+                    </RawUiPinned>
                 </Tab>
-                <Tab title="Raw Plot">
+                <Tab title="Raw Plot" monospace=true>
                     <Me1RawPlot
                         booleans={RcRef::clone(&plot.booleans)}
                         integers={IntPlotType::Vec(RcRef::clone(&plot.integers))}
                         floats={FloatPlotType::Vec(RcRef::clone(&plot.floats))}
                     />
                 </Tab>
+                <Tab title="Changes">
+                    <Changes />
+                </Tab>
+                <Tab title="Notes">
+                    <Notes />
+                </Tab>
             </TabBar>
         </section>
     }
 }
 
 fn mass_effect_2(save_game: Me2Type) -> Html {
-    let (raw_data, plot, me1_plot, head_morph) = match save_game {
+    let (raw_data, guids, randomizer, plot, me1_plot, head_morph) = match save_game {
         Me2Type::Vanilla(ref me2) => (
             me2.view_opened("Mass Effect 2", true),
+            GuidIndex::new(me2),
+            raw_ui::randomizer(RcRef::clone(me2)),
             RcRef::clone(&me2.borrow().plot),
             RcRef::clone(&me2.borrow().me1_plot),
             RcRef::clone(&me2.borrow().player().appearance().head_morph),
         ),
         Me2Type::Legendary(ref me2) => (
             me2.view_opened("Mass Effect 2", true),
+            GuidIndex::new(me2),
+            raw_ui::randomizer(RcRef::clone(me2)),
             RcRef::clone(&me2.borrow().plot),
             RcRef::clone(&me2.borrow().me1_plot),
             RcRef::clone(&me2.borrow().player().appearance().head_morph),
         ),
     };
+    let downstream = Downstream::Me2 { me1_plot: RcRef::clone(&me1_plot) };
     let (plot, me1_plot) = (plot.borrow(), me1_plot.borrow());
 
     html! {
@@ -289,18 +373,29 @@ fn mass_effect_2(save_game: Me2Type) -> Html {
                     />
                 </Tab>
                 <Tab title="Head Morph">
-                    <HeadMorph {head_morph} />
+                    <HeadMorph {head_morph} game={HeadMorphGame::MassEffect2} />
                 </Tab>
-                <Tab title="Raw Data">
-                    { raw_data }
+                <Tab title="Raw Data" monospace=true>
+                    <RawUiPinned pins_key="me2_raw_data_pins" guids={Rc::new(guids)} onrandomize={randomizer}>
+                        { raw_data }
+                    </RawUiPinned>
                 </Tab>
-                <Tab title="Raw Plot">
+                <Tab title="Raw Plot" monospace=true>
                     <Me2RawPlot
                         booleans={RcRef::clone(&plot.booleans)}
                         integers={IntPlotType::Vec(RcRef::clone(&plot.integers))}
                         floats={FloatPlotType::Vec(RcRef::clone(&plot.floats))}
                     />
                 </Tab>
+                <Tab title="Carryover">
+                    <Carryover {downstream} />
+                </Tab>
+                <Tab title="Changes">
+                    <Changes />
+                </Tab>
+                <Tab title="Notes">
+                    <Notes />
+                </Tab>
             </TabBar>
         </section>
     }
@@ -325,18 +420,81 @@ fn mass_effect_3(save_game: RcRef<Me3SaveGame>) -> Html {
                     />
                 </Tab>
                 <Tab title="Head Morph">
-                    <HeadMorph {head_morph} />
+                    <HeadMorph {head_morph} game={HeadMorphGame::MassEffect3} />
+                </Tab>
+                <Tab title="Raw Data" monospace=true>
+                    <RawUiPinned pins_key="me3_raw_data_pins" guids={Rc::new(GuidIndex::new(&save_game))}
+                        onrandomize={raw_ui::randomizer(RcRef::clone(&save_game))}
+                    >
+                        { save_game.view_opened("Mass Effect 3", true) }
+                    </RawUiPinned>
+                </Tab>
+                <Tab title="Raw Plot" monospace=true>
+                    <Me3RawPlot
+                        booleans={RcRef::clone(&plot.booleans)}
+                        integers={IntPlotType::IndexMap(RcRef::clone(&plot.integers))}
+                        floats={FloatPlotType::IndexMap(RcRef::clone(&plot.floats))}
+                    />
+                </Tab>
+                <Tab title="Carryover">
+                    <Carryover downstream={Downstream::Me3 {
+                        booleans: RcRef::clone(&plot.booleans),
+                        integers: IntPlotType::IndexMap(RcRef::clone(&plot.integers)),
+                    }} />
                 </Tab>
-                <Tab title="Raw Data">
-                    { save_game.view_opened("Mass Effect 3", true) }
+                <Tab title="Changes">
+                    <Changes />
                 </Tab>
-                <Tab title="Raw Plot">
+                <Tab title="Notes">
+                    <Notes />
+                </Tab>
+            </TabBar>
+        </section>
+    }
+}
+
+// Only the plot table has been parsed
+fn mass_effect_2_quick(plot: RcRef<PlotTable>) -> Html {
+    let plot = plot.borrow();
+    html! {
+        <section class="flex-auto flex p-1">
+            <TabBar is_main_tab_bar=true>
+                <Tab title="Raw Plot" monospace=true>
+                    <Me2RawPlot
+                        booleans={RcRef::clone(&plot.booleans)}
+                        integers={IntPlotType::Vec(RcRef::clone(&plot.integers))}
+                        floats={FloatPlotType::Vec(RcRef::clone(&plot.floats))}
+                    />
+                </Tab>
+                <Tab title="Changes">
+                    <Changes />
+                </Tab>
+                <Tab title="Notes">
+                    <Notes />
+                </Tab>
+            </TabBar>
+        </section>
+    }
+}
+
+fn mass_effect_3_quick(plot: RcRef<Me3PlotTable>) -> Html {
+    let plot = plot.borrow();
+    html! {
+        <section class="flex-auto flex p-1">
+            <TabBar is_main_tab_bar=true>
+                <Tab title="Raw Plot" monospace=true>
                     <Me3RawPlot
                         booleans={RcRef::clone(&plot.booleans)}
                         integers={IntPlotType::IndexMap(RcRef::clone(&plot.integers))}
                         floats={FloatPlotType::IndexMap(RcRef::clone(&plot.floats))}
                     />
                 </Tab>
+                <Tab title="Changes">
+                    <Changes />
+                </Tab>
+                <Tab title="Notes">
+                    <Notes />
+                </Tab>
             </TabBar>
         </section>
     }
@@ -373,14 +531,8 @@ fn changelog() -> Html {
     });
 
     html! {
-        <section class="flex-auto flex flex-col gap-1 p-1">
-            <div>
-                <p>{ "Changelog" }</p>
-                <hr class="border-t border-default-border" />
-            </div>
-            <div class="flex-auto flex flex-col gap-1 h-0 overflow-y-auto">
-                { for logs }
-            </div>
-        </section>
+        <div class="flex-auto flex flex-col gap-1">
+            { for logs }
+        </div>
     }
 }
@@ -11,7 +11,7 @@ use wasm_bindgen_futures as futures;
 use web_sys::CustomEvent;
 use yew::prelude::*;
 
-use crate::services::rpc;
+use crate::services::{logger, rpc};
 
 enum UpdateState {
     None,
@@ -22,6 +22,7 @@ enum UpdateState {
 pub enum Msg {
     UpdateAvailable,
     InstallUpdate,
+    CancelUpdate,
     DownloadProgress(f64),
     Error(Error),
 }
@@ -33,6 +34,7 @@ pub struct Props {
 
 pub struct AutoUpdate {
     _update_listener: EventListener,
+    _cancelled_listener: EventListener,
     _progress_listener: EventListener,
     _error_listener: EventListener,
     update_state: UpdateState,
@@ -50,6 +52,14 @@ impl Component for AutoUpdate {
             })
         };
 
+        // Back to the update being available
+        let cancelled_listener = {
+            let link = ctx.link().clone();
+            EventListener::new(&utils::document(), "tse_update_cancelled", move |_| {
+                link.send_message(Msg::UpdateAvailable);
+            })
+        };
+
         let progress_listener = {
             let link = ctx.link().clone();
             EventListener::new(&utils::document(), "tse_update_progress", move |event| {
@@ -87,6 +97,7 @@ impl Component for AutoUpdate {
 
         AutoUpdate {
             _update_listener: update_listener,
+            _cancelled_listener: cancelled_listener,
             _progress_listener: progress_listener,
             _error_listener: error_listener,
             update_state: UpdateState::None,
@@ -106,6 +117,14 @@ impl Component for AutoUpdate {
                 });
                 false
             }
+            Msg::CancelUpdate => {
+                futures::spawn_local(async {
+                    if let Err(err) = rpc::cancel_update().await {
+                        logger::warn(format!("Failed to cancel the update: {:#}", err));
+                    }
+                });
+                false
+            }
             Msg::DownloadProgress(progress) => {
                 self.update_state = UpdateState::DownloadProgress(progress);
                 true
@@ -131,7 +150,14 @@ impl Component for AutoUpdate {
                 </div>
             },
             UpdateState::DownloadProgress(progress) => html! {
-                <div class="flex-auto px-1 text-right">{ format!("Downloading update: {}%", (progress * 100.0) as usize) }</div>
+                <div class="flex-auto flex items-center gap-2 px-1">
+                    <div class="flex-auto text-right">{ format!("Downloading update: {}%", (progress * 100.0) as usize) }</div>
+                    <button class="button"
+                        onclick={ctx.link().callback(|_| Msg::CancelUpdate)}
+                    >
+                        {"Cancel"}
+                    </button>
+                </div>
             },
             UpdateState::None => Default::default(),
         }
@@ -0,0 +1,86 @@
+use yew::{context::ContextHandle, prelude::*};
+
+use super::PrintReport;
+use crate::services::derived_state::DerivedState;
+
+pub enum Msg {
+    DerivedStateChanged(DerivedState),
+}
+
+pub struct CharacterHeader {
+    _state_handle: ContextHandle<DerivedState>,
+    state: DerivedState,
+}
+
+impl Component for CharacterHeader {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (state, _state_handle) = ctx
+            .link()
+            .context::<DerivedState>(ctx.link().callback(Msg::DerivedStateChanged))
+            .expect("no derived state provider");
+
+        CharacterHeader { _state_handle, state }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DerivedStateChanged(state) => {
+                self.state = state;
+                true
+            }
+        }
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        let sheet = match self.state.character_sheet {
+            Some(ref sheet) => sheet,
+            None => return Html::default(),
+        };
+
+        let name = sheet.name.as_ref().map(|name| {
+            let name = if name.is_empty() { "<unnamed>" } else { name.as_str() };
+            html! { <span class="font-bold truncate">{ name }</span> }
+        });
+        let class = sheet.class.as_ref().map(|class| html! { <span>{ class }</span> });
+        let level = sheet.level.map(|level| html! { <span>{ format!("Level {}", level) }</span> });
+        let credits =
+            sheet.credits.map(|credits| html! { <span>{ format!("{} credits", credits) }</span> });
+        let paragon = sheet.paragon.map(|paragon| {
+            html! { <span>{ format!("Paragon {}", paragon) }</span> }
+        });
+        let renegade = sheet.renegade.map(|renegade| {
+            html! { <span>{ format!("Renegade {}", renegade) }</span> }
+        });
+        let playtime = sheet.seconds_played.map(|seconds_played| {
+            html! { <span title="Play time">{ Self::format_playtime(seconds_played) }</span> }
+        });
+
+        html! {
+            <div class="flex items-center gap-3 px-1 py-0.5 bg-menu-bar border-b border-default-border select-none whitespace-nowrap">
+                { for name }
+                { for class }
+                { for level }
+                { for credits }
+                { for paragon }
+                { for renegade }
+                <span class="flex-auto" />
+                { for playtime }
+                <PrintReport />
+            </div>
+        }
+    }
+}
+
+impl CharacterHeader {
+    pub(super) fn format_playtime(seconds_played: f32) -> String {
+        // An invalid play time is for the Save Info table to repair
+        if !seconds_played.is_finite() || seconds_played < 0.0 {
+            return String::from("Invalid play time");
+        }
+        let minutes = seconds_played as u64 / 60;
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    }
+}
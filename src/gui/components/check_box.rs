@@ -1,9 +1,13 @@
-use yew::prelude::*;
+use yew::{context::ContextHandle, prelude::*};
 
-use crate::save_data::RcCell;
+use crate::{gui::components::raw_ui::Originals, save_data::RcCell, services::document};
 
 pub enum Msg {
     Toggle,
+    OpenRevert(i32, i32),
+    CloseRevert,
+    Revert,
+    Originals(Originals),
 }
 
 #[derive(Properties, PartialEq)]
@@ -13,25 +17,50 @@ pub struct Props {
     pub onchange: Option<Callback<bool>>,
 }
 
-pub struct CheckBox;
+pub struct CheckBox {
+    revert: Option<(i32, i32)>,
+    originals: Originals,
+    _originals_handle: Option<ContextHandle<Originals>>,
+}
 
 impl Component for CheckBox {
     type Message = Msg;
     type Properties = Props;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        CheckBox
+    fn create(ctx: &Context<Self>) -> Self {
+        // Nothing to revert to outside of a save handler
+        let (originals, _originals_handle) =
+            match ctx.link().context::<Originals>(ctx.link().callback(Msg::Originals)) {
+                Some((originals, handle)) => (originals, Some(handle)),
+                None => (Originals::default(), None),
+            };
+        CheckBox { revert: None, originals, _originals_handle }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::Toggle => {
                 let value = !ctx.props().value.get();
-                ctx.props().value.set(value);
-
-                if let Some(ref callback) = ctx.props().onchange {
-                    callback.emit(value);
+                self.set_value(ctx, value);
+                true
+            }
+            Msg::OpenRevert(x, y) => {
+                self.revert = Some((x, y));
+                true
+            }
+            Msg::CloseRevert => {
+                self.revert = None;
+                true
+            }
+            Msg::Revert => {
+                if let Some(original) = self.originals.modified(&ctx.props().value) {
+                    self.set_value(ctx, original);
                 }
+                self.revert = None;
+                true
+            }
+            Msg::Originals(originals) => {
+                self.originals = originals;
                 true
             }
         }
@@ -40,11 +69,60 @@ impl Component for CheckBox {
     fn view(&self, ctx: &Context<Self>) -> Html {
         let checked = ctx.props().value.get();
         let onchange = ctx.link().callback(|_| Msg::Toggle);
+
+        // Only a modified field has its own context menu
+        let modified = self.originals.modified(&ctx.props().value);
+        let oncontextmenu = modified.is_some().then(|| {
+            ctx.link().callback(|e: MouseEvent| {
+                e.prevent_default();
+                e.stop_propagation();
+                Msg::OpenRevert(e.client_x(), e.client_y())
+            })
+        });
+        let title = modified.map(|_| "Modified, right-click to revert");
+        let revert = modified.zip(self.revert).map(|(original, (x, y))| {
+            let link = ctx.link();
+            html! {
+                <>
+                    <div class="fixed inset-0 z-40"
+                        onclick={link.callback(|_| Msg::CloseRevert)}
+                        oncontextmenu={link.callback(|e: MouseEvent| {
+                            e.prevent_default();
+                            Msg::CloseRevert
+                        })}
+                    />
+                    <div class="fixed z-50 flex flex-col border border-default-border bg-default-bg"
+                        style={format!("left: {}px; top: {}px;", x, y)}
+                    >
+                        <button class="rounded-none hover:bg-theme-hover active:bg-theme-active px-1 text-left"
+                            onclick={link.callback(|_| Msg::Revert)}
+                        >
+                            { if original { "Revert to checked" } else { "Revert to unchecked" } }
+                        </button>
+                    </div>
+                </>
+            }
+        });
+
         html! {
-            <label class="flex items-center gap-1">
-                <input type="checkbox" class="checkbox" {checked} {onchange} />
-                { &ctx.props().label }
-            </label>
+            <>
+                <label class="flex items-center gap-1" {title} {oncontextmenu}>
+                    <input type="checkbox" class="checkbox" {checked} {onchange} />
+                    { &ctx.props().label }
+                </label>
+                { for revert }
+            </>
+        }
+    }
+}
+
+impl CheckBox {
+    fn set_value(&self, ctx: &Context<Self>, value: bool) {
+        ctx.props().value.set(value);
+
+        if let Some(ref callback) = ctx.props().onchange {
+            callback.emit(value);
         }
+        document::edited(ctx);
     }
 }
@@ -0,0 +1,89 @@
+use js_sys::Date;
+use wasm_bindgen::JsValue;
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{CheckBox, Helper},
+    save_data::RcCell,
+    services::save_handler::{Action, SaveHandler},
+};
+
+pub enum Msg {
+    SaveHandlerChanged(SaveHandler),
+    SaveBothCopies(bool),
+}
+
+pub struct CloudSaveWarning {
+    _db_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+}
+
+impl Component for CloudSaveWarning {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _db_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandlerChanged))
+            .expect("no save handler provider");
+
+        CloudSaveWarning { _db_handle, save_handler }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SaveHandlerChanged(save_handler) => {
+                self.save_handler = save_handler;
+                true
+            }
+            Msg::SaveBothCopies(save_both_copies) => {
+                self.save_handler.action(Action::SaveBothCopies(save_both_copies));
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let cloud_save = match self.save_handler.cloud_save {
+            Some(ref cloud_save) if cloud_save.is_diverging() => cloud_save,
+            _ => return Html::default(),
+        };
+
+        let format_date = |millis: f64| -> String {
+            Date::new(&JsValue::from_f64(millis))
+                .to_locale_string("default", &JsValue::UNDEFINED)
+                .into()
+        };
+        let which = if cloud_save.is_newer() { "newer" } else { "older" };
+
+        html! {
+            <div class="flex items-center gap-2 px-1 py-0.5 bg-menu-bar border-b border-default-border">
+                <span class="text-title-bar-close select-none">{ "⚠" }</span>
+                <span
+                    class="flex-auto truncate"
+                    title={cloud_save.path.to_string_lossy().into_owned()}
+                >
+                    { format!(
+                        "Another copy of this save is {} ({} / opened: {}), the cloud sync may overwrite your changes.",
+                        which,
+                        format_date(cloud_save.modified),
+                        format_date(cloud_save.opened_modified),
+                    ) }
+                </span>
+                <Helper text=
+                    "Steam Cloud keeps a copy of the Legendary Edition saves.\n\
+                    If the copies differ, the game may replace the edited save with the other one.\n\
+                    • Close the game and Steam before saving\n\
+                    • Either disable Steam Cloud for the game or save to both copies\n\
+                    • When Steam asks about a conflict, keep the local files"
+                />
+                <CheckBox
+                    label="Save to both copies"
+                    value={RcCell::new(self.save_handler.save_both_copies)}
+                    onchange={ctx.link().callback(Msg::SaveBothCopies)}
+                />
+            </div>
+        }
+    }
+}
@@ -7,6 +7,7 @@ use yew::prelude::*;
 use crate::{
     gui::components::{CallbackType, InputNumber, NumberType},
     save_data::{shared::appearance::LinearColor, RcRef},
+    services::document,
 };
 
 pub enum Msg {
@@ -79,6 +80,7 @@ impl Component for ColorPicker {
                         color.r = r as f32 / 255.0;
                         color.g = g as f32 / 255.0;
                         color.b = b as f32 / 255.0;
+                        document::edited(ctx);
                         return true;
                     }
                 }
@@ -0,0 +1,172 @@
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+use wasm_bindgen_futures as futures;
+use web_sys::HtmlInputElement;
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{Helper, Select},
+    save_data::shared::plot::RawPlotDb,
+    services::{database::Databases, logger, rpc},
+};
+
+const GAMES: &[&str] = &["Mass Effect 1", "Mass Effect 2", "Mass Effect 3"];
+const PLOT_TYPES: &[&str] = &["Booleans", "Integers", "Floats"];
+// More than that is too slow to render, the search has to be refined instead
+const MAX_RESULTS: usize = 500;
+
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    Game(usize),
+    PlotType(usize),
+    Search(InputEvent),
+    CopyId(usize),
+}
+
+pub struct DatabaseBrowser {
+    _databases_handle: ContextHandle<Databases>,
+    databases: Databases,
+    game: usize,
+    plot_type: usize,
+    search: String,
+}
+
+impl Component for DatabaseBrowser {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _databases_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        DatabaseBrowser {
+            _databases_handle,
+            databases,
+            game: 0,
+            plot_type: 0,
+            search: String::new(),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(databases) => {
+                self.databases = databases;
+                true
+            }
+            Msg::Game(game) => {
+                self.game = game;
+                true
+            }
+            Msg::PlotType(plot_type) => {
+                self.plot_type = plot_type;
+                true
+            }
+            Msg::Search(event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    self.search = input.value();
+                }
+                true
+            }
+            Msg::CopyId(id) => {
+                futures::spawn_local(async move {
+                    if let Err(err) = rpc::copy_to_clipboard(&id.to_string()).await {
+                        logger::error(format!("Failed to copy the plot id: {:#}", err));
+                    }
+                });
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+
+        let results = match self.raw_plot_db() {
+            Some(raw_plot_db) => self.view_results(ctx, &raw_plot_db),
+            None => html! { <p>{ "Loading database..." }</p> },
+        };
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                <div class="flex items-center gap-1">
+                    <Select options={GAMES} current_idx={self.game} onselect={link.callback(Msg::Game)} />
+                    <Select options={PLOT_TYPES} current_idx={self.plot_type} onselect={link.callback(Msg::PlotType)} />
+                    <label class="flex-auto flex items-center gap-1">
+                        <input type="text" class="flex-auto input" placeholder="Label or id"
+                            value={self.search.clone()}
+                            oninput={link.callback(Msg::Search)}
+                        />
+                        { "Search" }
+                    </label>
+                    <Helper text=
+                        "Every plot known to the databases of the editor, no save needed.\n\
+                        Click an id to copy it, to look it up in the `Raw Plot` tab of a save \
+                        or in the game files."
+                    />
+                </div>
+                <hr class="border-t border-default-border" />
+                { results }
+            </div>
+        }
+    }
+}
+
+impl DatabaseBrowser {
+    fn raw_plot_db(&self) -> Option<Rc<RawPlotDb>> {
+        let databases = self.databases.clone();
+        match self.game {
+            0 => databases.get_me1_raw_plot(),
+            1 => databases.get_me2_raw_plot(),
+            _ => databases.get_me3_raw_plot(),
+        }
+    }
+
+    fn view_results(&self, ctx: &Context<Self>, raw_plot_db: &RawPlotDb) -> Html {
+        let plots: &IndexMap<usize, String> = match self.plot_type {
+            0 => &raw_plot_db.booleans,
+            1 => &raw_plot_db.integers,
+            _ => &raw_plot_db.floats,
+        };
+
+        // An id is searched from its first digits, a label anywhere in it
+        let search = self.search.trim().to_lowercase();
+        let mut found = plots.iter().filter(|(id, label)| {
+            search.is_empty()
+                || id.to_string().starts_with(&search)
+                || label.to_lowercase().contains(&search)
+        });
+
+        let rows = found.by_ref().take(MAX_RESULTS).map(|(&id, label)| {
+            html! {
+                <div class="flex items-center gap-1 hover:bg-theme-hover">
+                    <button class="button w-20 font-mono"
+                        title="Copy the id"
+                        onclick={ctx.link().callback(move |_| Msg::CopyId(id))}
+                    >
+                        { id }
+                    </button>
+                    <span class="break-all">{ label }</span>
+                </div>
+            }
+        });
+        let rows = rows.collect::<Vec<_>>();
+        let more = found.count();
+        let nothing = rows.is_empty().then(|| {
+            html! { <p class="text-fg/50">{ "No plot found." }</p> }
+        });
+
+        html! {
+            <div class="flex-auto flex flex-col gap-px h-0 overflow-y-auto">
+                { for rows }
+                { for nothing }
+                { for (more > 0).then(|| html! {
+                    <p class="text-fg/50">{ format!("{} more plots, refine the search to see them.", more) }</p>
+                }) }
+            </div>
+        }
+    }
+}
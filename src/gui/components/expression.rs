@@ -0,0 +1,135 @@
+use std::{iter::Peekable, str::Chars};
+
+// Nested parentheses and signs past this are refused, as they would run out of stack
+const MAX_DEPTH: usize = 64;
+
+// What the number inputs accept: `12000*4`, `current+500`, `(current - 10) / 2`...
+// `current` being the value before the edit
+pub fn evaluate_expression(input: &str, current: f64) -> Option<f64> {
+    let mut parser = Parser { chars: input.chars().peekable(), current, depth: 0 };
+    let value = parser.sum()?;
+    parser.skip_spaces();
+    (parser.chars.peek().is_none() && value.is_finite()).then(|| value)
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    current: f64,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_spaces(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn operator(&mut self, operators: &[char]) -> Option<char> {
+        self.skip_spaces();
+        self.chars.next_if(|c| operators.contains(c))
+    }
+
+    fn sum(&mut self) -> Option<f64> {
+        let mut value = self.product()?;
+        while let Some(operator) = self.operator(&['+', '-']) {
+            let rhs = self.product()?;
+            value = if operator == '+' { value + rhs } else { value - rhs };
+        }
+        Some(value)
+    }
+
+    fn product(&mut self) -> Option<f64> {
+        let mut value = self.unary()?;
+        while let Some(operator) = self.operator(&['*', '/']) {
+            let rhs = self.unary()?;
+            value = if operator == '*' { value * rhs } else { value / rhs };
+        }
+        Some(value)
+    }
+
+    fn nested(&mut self, parse: fn(&mut Self) -> Option<f64>) -> Option<f64> {
+        if self.depth == MAX_DEPTH {
+            return None;
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn unary(&mut self) -> Option<f64> {
+        match self.operator(&['+', '-']) {
+            Some('-') => self.nested(Self::unary).map(|value| -value),
+            Some(_) => self.nested(Self::unary),
+            None => self.atom(),
+        }
+    }
+
+    fn atom(&mut self) -> Option<f64> {
+        self.skip_spaces();
+        match *self.chars.peek()? {
+            '(' => {
+                self.chars.next();
+                let value = self.nested(Self::sum)?;
+                self.operator(&[')']).map(|_| value)
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut number = String::new();
+                while let Some(c) = self.chars.next_if(|c| c.is_ascii_digit() || *c == '.') {
+                    number.push(c);
+                }
+                // Exponents, as in `1e6` or a float shown as `1.5e-7`
+                if self.chars.next_if(|c| *c == 'e' || *c == 'E').is_some() {
+                    number.push('e');
+                    if let Some(sign) = self.chars.next_if(|c| *c == '+' || *c == '-') {
+                        number.push(sign);
+                    }
+                    while let Some(c) = self.chars.next_if(char::is_ascii_digit) {
+                        number.push(c);
+                    }
+                }
+                number.parse().ok()
+            }
+            c if c.is_alphabetic() => {
+                let mut name = String::new();
+                while let Some(c) = self.chars.next_if(|c| c.is_alphabetic()) {
+                    name.push(c);
+                }
+                name.eq_ignore_ascii_case("current").then(|| self.current)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn evaluate() {
+        assert_eq!(evaluate_expression("42", 0.0), Some(42.0));
+        assert_eq!(evaluate_expression(" -1.5 ", 0.0), Some(-1.5));
+        assert_eq!(evaluate_expression("1e6", 0.0), Some(1_000_000.0));
+        assert_eq!(evaluate_expression("12000*4", 0.0), Some(48_000.0));
+        assert_eq!(evaluate_expression("current+500", 1000.0), Some(1500.0));
+        assert_eq!(evaluate_expression("Current * 2 - 1", 10.0), Some(19.0));
+        assert_eq!(evaluate_expression("2 + 3 * 4", 0.0), Some(14.0));
+        assert_eq!(evaluate_expression("(2 + 3) * 4", 0.0), Some(20.0));
+        assert_eq!(evaluate_expression("-(current - 10) / 2", 4.0), Some(3.0));
+
+        assert_eq!(evaluate_expression("", 0.0), None);
+        assert_eq!(evaluate_expression("1 +", 0.0), None);
+        assert_eq!(evaluate_expression("(1 + 2", 0.0), None);
+        assert_eq!(evaluate_expression("1 2", 0.0), None);
+        assert_eq!(evaluate_expression("1.2.3", 0.0), None);
+        assert_eq!(evaluate_expression("max + 1", 0.0), None);
+        assert_eq!(evaluate_expression("1 / 0", 0.0), None);
+
+        // Too deep to be parsed
+        let nested = format!("{}1{}", "(".repeat(MAX_DEPTH), ")".repeat(MAX_DEPTH));
+        assert_eq!(evaluate_expression(&nested, 0.0), Some(1.0));
+        let nested = format!("{}1{}", "(".repeat(100_000), ")".repeat(100_000));
+        assert_eq!(evaluate_expression(&nested, 0.0), None);
+        assert_eq!(evaluate_expression(&"-".repeat(100_000), 0.0), None);
+    }
+}
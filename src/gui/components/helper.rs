@@ -66,7 +66,7 @@ impl Component for Helper {
         });
         html! {
             <div class="relative">
-                <div class="text-white/50 select-none"
+                <div class="text-fg/50 select-none"
                     onmouseover={ctx.link().callback(|_| Msg::Hover)}
                     onmouseout={ctx.link().callback(|_| Msg::Out)}
                 >
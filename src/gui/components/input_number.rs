@@ -1,7 +1,16 @@
+use gloo::{
+    events::{EventListener, EventListenerOptions},
+    utils,
+};
+use wasm_bindgen::JsCast;
 use web_sys::HtmlInputElement;
-use yew::prelude::*;
+use yew::{context::ContextHandle, prelude::*};
 
-use crate::{gui::components::Helper, save_data::RcCell};
+use crate::{
+    gui::components::{evaluate_expression, raw_ui::Originals, Helper},
+    save_data::RcCell,
+    services::document,
+};
 
 use super::CallbackType;
 
@@ -12,6 +21,79 @@ pub enum NumberType {
     Float(RcCell<f32>),
 }
 
+impl NumberType {
+    pub fn get(&self) -> f64 {
+        match self {
+            NumberType::Byte(byte) => byte.get() as f64,
+            NumberType::Int(integer) => integer.get() as f64,
+            NumberType::Float(float) => float.get() as f64,
+        }
+    }
+
+    pub fn set(&self, value: f64) -> CallbackType {
+        match self {
+            NumberType::Byte(byte) => {
+                let value = value as u8;
+                byte.set(value);
+                CallbackType::Byte(value)
+            }
+            NumberType::Int(integer) => {
+                let value = value as i32;
+                integer.set(value);
+                CallbackType::Int(value)
+            }
+            NumberType::Float(float) => {
+                let value = value.clamp(f32::MIN as f64, f32::MAX as f64) as f32;
+                float.set(value);
+                CallbackType::Float(value)
+            }
+        }
+    }
+
+    // The value parsed from the file, if it has been changed since
+    pub fn original(&self, originals: &Originals) -> Option<f64> {
+        match self {
+            NumberType::Byte(byte) => originals.modified(byte).map(f64::from),
+            NumberType::Int(integer) => originals.modified(integer).map(f64::from),
+            NumberType::Float(float) => originals.modified(float).map(f64::from),
+        }
+    }
+
+    fn default_step(&self) -> f64 {
+        match self {
+            NumberType::Byte(_) | NumberType::Int(_) => 1.0,
+            NumberType::Float(_) => 0.01,
+        }
+    }
+
+    // Leaves room for the game to add to it without overflowing, floats stay exact up to 2^24
+    fn max_safe(&self) -> f64 {
+        match self {
+            NumberType::Byte(_) => u8::MAX as f64,
+            NumberType::Int(_) => 999_999_999.0,
+            NumberType::Float(_) => 16_777_216.0,
+        }
+    }
+}
+
+impl From<&RcCell<u8>> for NumberType {
+    fn from(byte: &RcCell<u8>) -> Self {
+        NumberType::Byte(RcCell::clone(byte))
+    }
+}
+
+impl From<&RcCell<i32>> for NumberType {
+    fn from(integer: &RcCell<i32>) -> Self {
+        NumberType::Int(RcCell::clone(integer))
+    }
+}
+
+impl From<&RcCell<f32>> for NumberType {
+    fn from(float: &RcCell<f32>) -> Self {
+        NumberType::Float(RcCell::clone(float))
+    }
+}
+
 impl PartialEq for NumberType {
     fn eq(&self, other: &NumberType) -> bool {
         match (self, other) {
@@ -23,8 +105,33 @@ impl PartialEq for NumberType {
     }
 }
 
+// `#[raw_ui(step = 1000, precision = 2)]` on a number field
+#[derive(Clone, Copy, Default)]
+pub struct NumberFormat {
+    pub step: Option<f64>,
+    pub precision: Option<usize>,
+}
+
+#[derive(Clone, Copy)]
+pub enum Operation {
+    Add,
+    Multiply,
+    MaxSafe,
+}
+
 pub enum Msg {
     Change(Event),
+    // Up or down by a step, by ten with Shift
+    Step(f64, bool),
+    DragStart(MouseEvent),
+    DragMove(i32, bool),
+    DragEnd,
+    OpenCalculator(i32, i32),
+    CloseCalculator,
+    Operand(Event),
+    Calculate(Operation),
+    Revert,
+    Originals(Originals),
 }
 
 #[derive(Properties, PartialEq)]
@@ -33,59 +140,189 @@ pub struct Props {
     pub value: NumberType,
     pub helper: Option<&'static str>,
     pub onchange: Option<Callback<CallbackType>>,
+    pub step: Option<f64>,
+    pub precision: Option<usize>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
 }
 
-pub struct InputNumber;
+impl Props {
+    fn step(&self) -> f64 {
+        self.step.unwrap_or_else(|| self.value.default_step())
+    }
+}
+
+struct Drag {
+    start_x: i32,
+    start_value: f64,
+    _move_listener: EventListener,
+    _up_listener: EventListener,
+}
+
+pub struct InputNumber {
+    input_ref: NodeRef,
+    _wheel_listener: Option<EventListener>,
+    drag: Option<Drag>,
+    calculator: Option<(i32, i32)>,
+    operand: f64,
+    originals: Originals,
+    _originals_handle: Option<ContextHandle<Originals>>,
+}
 
 impl Component for InputNumber {
     type Message = Msg;
     type Properties = Props;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        InputNumber
+    fn create(ctx: &Context<Self>) -> Self {
+        // Nothing to revert to outside of a save handler
+        let (originals, _originals_handle) =
+            match ctx.link().context::<Originals>(ctx.link().callback(Msg::Originals)) {
+                Some((originals, handle)) => (originals, Some(handle)),
+                None => (Originals::default(), None),
+            };
+        InputNumber {
+            input_ref: Default::default(),
+            _wheel_listener: None,
+            drag: None,
+            calculator: None,
+            operand: 1000.0,
+            originals,
+            _originals_handle,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::Change(event) => {
                 if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
-                    let value = input.value_as_number();
-
-                    if value.is_nan() {
-                        return true;
+                    let current = ctx.props().value.get();
+                    // Re-rendered with the current value if it doesn't evaluate
+                    if let Some(value) = evaluate_expression(&input.value(), current) {
+                        self.set_value(ctx, value);
                     }
+                    true
+                } else {
+                    false
+                }
+            }
+            Msg::Step(direction, fast) => {
+                let step = ctx.props().step() * if fast { 10.0 } else { 1.0 };
+                let value = ctx.props().value.get() + direction * step;
+                self.set_value(ctx, value);
+                true
+            }
+            Msg::DragStart(event) => {
+                event.prevent_default();
 
-                    match ctx.props().value {
-                        NumberType::Byte(ref byte) => {
-                            let value = value as u8;
-                            byte.set(value);
-
-                            if let Some(ref callback) = ctx.props().onchange {
-                                callback.emit(CallbackType::Byte(value));
-                            }
-                        }
-                        NumberType::Int(ref integer) => {
-                            let value = value as i32;
-                            integer.set(value);
-
-                            if let Some(ref callback) = ctx.props().onchange {
-                                callback.emit(CallbackType::Int(value));
-                            }
-                        }
-                        NumberType::Float(ref float) => {
-                            let value = value.clamp(f32::MIN as f64, f32::MAX as f64) as f32;
-                            float.set(value);
-
-                            if let Some(ref callback) = ctx.props().onchange {
-                                callback.emit(CallbackType::Float(value));
-                            }
+                let link = ctx.link().clone();
+                let _move_listener = EventListener::new(&utils::window(), "mousemove", {
+                    let link = link.clone();
+                    move |event| {
+                        if let Some(event) = event.dyn_ref::<MouseEvent>() {
+                            link.send_message(Msg::DragMove(event.client_x(), event.shift_key()));
                         }
                     }
+                });
+                let _up_listener = EventListener::new(&utils::window(), "mouseup", move |_| {
+                    link.send_message(Msg::DragEnd)
+                });
+
+                self.drag = Some(Drag {
+                    start_x: event.client_x(),
+                    start_value: ctx.props().value.get(),
+                    _move_listener,
+                    _up_listener,
+                });
+                false
+            }
+            Msg::DragMove(x, fast) => {
+                if let Some(Drag { start_x, start_value, .. }) = self.drag {
+                    let step = ctx.props().step() * if fast { 10.0 } else { 1.0 };
+                    let value = start_value + (x - start_x) as f64 * step;
+                    self.set_value(ctx, value);
                     true
                 } else {
                     false
                 }
             }
+            Msg::DragEnd => {
+                self.drag = None;
+                false
+            }
+            Msg::OpenCalculator(x, y) => {
+                self.calculator = Some((x, y));
+                true
+            }
+            Msg::CloseCalculator => {
+                self.calculator = None;
+                true
+            }
+            Msg::Operand(event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    let current = ctx.props().value.get();
+                    if let Some(value) = evaluate_expression(&input.value(), current) {
+                        self.operand = value;
+                    }
+                }
+                true
+            }
+            Msg::Calculate(operation) => {
+                let current = ctx.props().value.get();
+                let value = match operation {
+                    Operation::Add => current + self.operand,
+                    Operation::Multiply => current * self.operand,
+                    Operation::MaxSafe => {
+                        ctx.props().max.unwrap_or_else(|| ctx.props().value.max_safe())
+                    }
+                };
+                self.set_value(ctx, value);
+                self.calculator = None;
+                true
+            }
+            Msg::Revert => {
+                // As it was parsed, without the precision and bounds of the input
+                if let Some(original) = ctx.props().value.original(&self.originals) {
+                    let value = ctx.props().value.set(original);
+                    if let Some(ref callback) = ctx.props().onchange {
+                        callback.emit(value);
+                    }
+                    document::edited(ctx);
+                }
+                self.calculator = None;
+                true
+            }
+            Msg::Originals(originals) => {
+                self.originals = originals;
+                true
+            }
+        }
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if first_render {
+            // Registered by hand to be able to prevent the page from scrolling
+            if let Some(input) = self.input_ref.cast::<HtmlInputElement>() {
+                let target = input.clone();
+                let link = ctx.link().clone();
+                let options = EventListenerOptions::enable_prevent_default();
+                let listener =
+                    EventListener::new_with_options(&target, "wheel", options, move |event| {
+                        // Only while editing, to not hijack the page scrolling
+                        let is_focused = utils::document()
+                            .active_element()
+                            .map(|element| element == **input)
+                            .unwrap_or(false);
+
+                        if let (true, Some(event)) = (is_focused, event.dyn_ref::<WheelEvent>()) {
+                            event.prevent_default();
+                            if event.delta_y() != 0.0 {
+                                let direction = -event.delta_y().signum();
+                                link.send_message(Msg::Step(direction, event.shift_key()));
+                            }
+                        }
+                    });
+                self._wheel_listener = Some(listener);
+            }
         }
     }
 
@@ -93,10 +330,13 @@ impl Component for InputNumber {
         let (value, placeholder) = match ctx.props().value {
             NumberType::Byte(ref byte) => (byte.get().to_string(), "<byte>"),
             NumberType::Int(ref integer) => (integer.get().to_string(), "<integer>"),
-            NumberType::Float(ref float) => {
-                let mut ryu = ryu::Buffer::new();
-                (ryu.format(float.get()).trim_end_matches(".0").to_owned(), "<float>")
-            }
+            NumberType::Float(ref float) => match ctx.props().precision {
+                Some(precision) => (format!("{:.*}", precision, float.get()), "<float>"),
+                None => {
+                    let mut ryu = ryu::Buffer::new();
+                    (ryu.format(float.get()).trim_end_matches(".0").to_owned(), "<float>")
+                }
+            },
         };
 
         let helper = ctx.props().helper.as_ref().map(|&helper| {
@@ -105,16 +345,127 @@ impl Component for InputNumber {
             }
         });
 
+        let oncontextmenu = ctx.link().callback(|e: MouseEvent| {
+            e.prevent_default();
+            // Not the context menu of a raw plot row
+            e.stop_propagation();
+            Msg::OpenCalculator(e.client_x(), e.client_y())
+        });
+        // What a number input would do with the arrows
+        let onkeydown = ctx.link().batch_callback(|e: KeyboardEvent| {
+            let direction = match e.key().as_str() {
+                "ArrowUp" => 1.0,
+                "ArrowDown" => -1.0,
+                _ => return None,
+            };
+            e.prevent_default();
+            Some(Msg::Step(direction, e.shift_key()))
+        });
+        let calculator = self.calculator.map(|(x, y)| self.view_calculator(ctx, x, y));
+        let title = if ctx.props().value.original(&self.originals).is_some() {
+            "Modified, right-click for the calculator or to revert. Accepts `current+500`..."
+        } else {
+            "Right-click for the calculator. Accepts `12000*4`, `current+500`..."
+        };
+
+        // Not a number input, it would refuse `current+500` and the other expressions. The bounds
+        // and the precision are enforced on commit, the arrows and the wheel step like it would.
         html! {
+            <>
             <label class="flex items-center gap-1">
-                <input type="number" class="input w-[110px]" step="any"
+                <input type="text" class="input w-[110px]"
+                    ref={self.input_ref.clone()}
+                    {title}
                     {placeholder}
                     {value}
                     onchange={ctx.link().callback(Msg::Change)}
+                    {onkeydown}
+                    {oncontextmenu}
                 />
-                { &ctx.props().label }
+                <span class="cursor-ew-resize select-none"
+                    title="Drag to adjust, hold Shift to go faster"
+                    onmousedown={ctx.link().callback(Msg::DragStart)}
+                >
+                    { &ctx.props().label }
+                </span>
                 { for helper }
             </label>
+            { for calculator }
+            </>
+        }
+    }
+}
+
+impl InputNumber {
+    fn view_calculator(&self, ctx: &Context<Self>, x: i32, y: i32) -> Html {
+        let link = ctx.link();
+        let button = |text: &'static str, operation: Operation| {
+            html! {
+                <button class="rounded-none hover:bg-theme-hover active:bg-theme-active px-1 text-left"
+                    onclick={link.callback(move |_| Msg::Calculate(operation))}
+                >
+                    { text }
+                </button>
+            }
+        };
+
+        let revert = ctx.props().value.original(&self.originals).map(|original| {
+            let original = match ctx.props().value {
+                NumberType::Float(_) => (original as f32).to_string(),
+                _ => original.to_string(),
+            };
+            html! {
+                <button class="rounded-none hover:bg-theme-hover active:bg-theme-active px-1 text-left border-t border-default-border"
+                    onclick={link.callback(|_| Msg::Revert)}
+                >
+                    { format!("Revert to {}", original) }
+                </button>
+            }
+        });
+
+        html! {
+            <>
+                <div class="fixed inset-0 z-40"
+                    onclick={link.callback(|_| Msg::CloseCalculator)}
+                    oncontextmenu={link.callback(|e: MouseEvent| {
+                        e.prevent_default();
+                        Msg::CloseCalculator
+                    })}
+                />
+                <div class="fixed z-50 flex flex-col min-w-[120px] border border-default-border bg-default-bg"
+                    style={format!("left: {}px; top: {}px;", x, y)}
+                >
+                    <div class="px-1 bg-theme-tab">{ "Calculator" }</div>
+                    <div class="p-1">
+                        <input type="text" class="input w-[110px]"
+                            value={self.operand.to_string()}
+                            onchange={link.callback(Msg::Operand)}
+                        />
+                    </div>
+                    { button("Add", Operation::Add) }
+                    { button("Multiply by", Operation::Multiply) }
+                    { button("Set to max safe", Operation::MaxSafe) }
+                    { for revert }
+                </div>
+            </>
+        }
+    }
+
+    fn set_value(&self, ctx: &Context<Self>, value: f64) {
+        let value = match (&ctx.props().value, ctx.props().precision) {
+            (NumberType::Float(_), Some(precision)) => {
+                let factor = 10f64.powi(precision as i32);
+                (value * factor).round() / factor
+            }
+            _ => value,
+        };
+        let value =
+            value.max(ctx.props().min.unwrap_or(f64::MIN)).min(ctx.props().max.unwrap_or(f64::MAX));
+
+        let value = ctx.props().value.set(value);
+        if let Some(ref callback) = ctx.props().onchange {
+            callback.emit(value);
         }
+        document::edited(ctx);
     }
 }
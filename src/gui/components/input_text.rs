@@ -1,13 +1,39 @@
-use std::cell::{Ref, RefMut};
-use web_sys::HtmlInputElement;
-use yew::prelude::*;
+use std::{
+    cell::{Ref, RefMut},
+    sync::atomic::{AtomicU32, Ordering},
+};
 
-use crate::{gui::components::Helper, save_data::RcRef};
+use gloo::{events::EventListener, utils};
+use serde::Deserialize;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures as futures;
+use web_sys::{CustomEvent, HtmlInputElement};
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{raw_ui::Originals, Helper},
+    save_data::RcRef,
+    services::{
+        document, logger,
+        rpc::{self, ExternalEditParams},
+    },
+};
 
 use super::CallbackType;
 
+// Values that don't fit comfortably in the field can be edited in an external editor
+const EXTERNAL_EDIT_MIN_LEN: usize = 64;
+
+static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+
 pub enum Msg {
     Input(InputEvent),
+    EditExternally,
+    ExternalEdit(String),
+    OpenRevert(i32, i32),
+    CloseRevert,
+    Revert,
+    Originals(Originals),
 }
 
 #[derive(Properties, PartialEq)]
@@ -28,14 +54,57 @@ impl Props {
     }
 }
 
-pub struct InputText;
+pub struct InputText {
+    id: u32,
+    _external_edit_listener: EventListener,
+    revert: Option<(i32, i32)>,
+    originals: Originals,
+    _originals_handle: Option<ContextHandle<Originals>>,
+}
 
 impl Component for InputText {
     type Message = Msg;
     type Properties = Props;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        InputText
+    fn create(ctx: &Context<Self>) -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+
+        // Sent by the backend every time the temp file is saved by the editor
+        let external_edit_listener = {
+            let link = ctx.link().clone();
+            EventListener::new(&utils::document(), "tse_external_edit", move |event| {
+                if let Some(event) = event.dyn_ref::<CustomEvent>() {
+                    #[derive(Deserialize)]
+                    struct ExternalEdit {
+                        id: u32,
+                        text: String,
+                    }
+
+                    let edit: ExternalEdit = match serde_wasm_bindgen::from_value(event.detail()) {
+                        Ok(edit) => edit,
+                        Err(_) => return,
+                    };
+                    if edit.id == id {
+                        link.send_message(Msg::ExternalEdit(edit.text));
+                    }
+                }
+            })
+        };
+
+        // Nothing to revert to outside of a save handler
+        let (originals, _originals_handle) =
+            match ctx.link().context::<Originals>(ctx.link().callback(Msg::Originals)) {
+                Some((originals, handle)) => (originals, Some(handle)),
+                None => (Originals::default(), None),
+            };
+
+        InputText {
+            id,
+            _external_edit_listener: external_edit_listener,
+            revert: None,
+            originals,
+            _originals_handle,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -47,9 +116,52 @@ impl Component for InputText {
                     }
 
                     *ctx.props().value_mut() = input.value();
+                    document::edited(ctx);
                 }
                 false
             }
+            Msg::EditExternally => {
+                let params = ExternalEditParams { id: self.id, text: ctx.props().value().clone() };
+                futures::spawn_local(async move {
+                    if let Err(err) = rpc::edit_externally(params).await {
+                        logger::error(format!("Failed to open an external editor: {:#}", err));
+                    }
+                });
+                false
+            }
+            Msg::ExternalEdit(text) => {
+                if let Some(ref callback) = ctx.props().oninput {
+                    callback.emit(CallbackType::String(text.clone()));
+                }
+
+                *ctx.props().value_mut() = text;
+                document::edited(ctx);
+                true
+            }
+            Msg::OpenRevert(x, y) => {
+                self.revert = Some((x, y));
+                true
+            }
+            Msg::CloseRevert => {
+                self.revert = None;
+                true
+            }
+            Msg::Revert => {
+                if let Some(original) = self.originals.modified_text(&ctx.props().value) {
+                    if let Some(ref callback) = ctx.props().oninput {
+                        callback.emit(CallbackType::String(original.clone()));
+                    }
+
+                    *ctx.props().value_mut() = original;
+                    document::edited(ctx);
+                }
+                self.revert = None;
+                true
+            }
+            Msg::Originals(originals) => {
+                self.originals = originals;
+                true
+            }
         }
     }
 
@@ -60,13 +172,63 @@ impl Component for InputText {
             }
         });
         let value = ctx.props().value().clone();
+        let edit_externally =
+            (value.len() > EXTERNAL_EDIT_MIN_LEN || value.contains('\n')).then(|| {
+                html! {
+                    <button class="button" title="Edit in an external editor"
+                        onclick={ctx.link().callback(|_| Msg::EditExternally)}
+                    >
+                        {"..."}
+                    </button>
+                }
+            });
         let oninput = ctx.link().callback(Msg::Input);
+
+        // Only a modified field has its own context menu
+        let modified = self.originals.modified_text(&ctx.props().value);
+        let oncontextmenu = modified.is_some().then(|| {
+            ctx.link().callback(|e: MouseEvent| {
+                e.prevent_default();
+                e.stop_propagation();
+                Msg::OpenRevert(e.client_x(), e.client_y())
+            })
+        });
+        let title = modified.as_ref().map(|_| "Modified, right-click to revert");
+        let revert = modified.zip(self.revert).map(|(original, (x, y))| {
+            let link = ctx.link();
+            let original = if original.is_empty() { "<empty>".to_owned() } else { original };
+            html! {
+                <>
+                    <div class="fixed inset-0 z-40"
+                        onclick={link.callback(|_| Msg::CloseRevert)}
+                        oncontextmenu={link.callback(|e: MouseEvent| {
+                            e.prevent_default();
+                            Msg::CloseRevert
+                        })}
+                    />
+                    <div class="fixed z-50 flex flex-col border border-default-border bg-default-bg"
+                        style={format!("left: {}px; top: {}px;", x, y)}
+                    >
+                        <button class="rounded-none hover:bg-theme-hover active:bg-theme-active px-1 text-left truncate max-w-md"
+                            onclick={link.callback(|_| Msg::Revert)}
+                        >
+                            { format!("Revert to {}", original) }
+                        </button>
+                    </div>
+                </>
+            }
+        });
+
         html! {
-            <label class="flex-auto flex items-center gap-1">
-                <input type="text" class="input w-2/3" placeholder="<empty>" {value} {oninput} />
-                { &ctx.props().label }
-                { for helper }
-            </label>
+            <>
+                <label class="flex-auto flex items-center gap-1" {title} {oncontextmenu}>
+                    <input type="text" class="input w-2/3" placeholder="<empty>" {value} {oninput} />
+                    { for edit_externally }
+                    { &ctx.props().label }
+                    { for helper }
+                </label>
+                { for revert }
+            </>
         }
     }
 }
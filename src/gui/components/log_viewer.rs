@@ -0,0 +1,119 @@
+use anyhow::{Context as ErrorContext, Error};
+use yew::prelude::*;
+
+use crate::services::rpc;
+
+pub enum Msg {
+    Open,
+    Close,
+    Refresh,
+    Loaded(Vec<String>),
+    Copy,
+    Copied,
+    Error(Error),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub onerror: Callback<Error>,
+}
+
+pub struct LogViewer {
+    logs: Option<Vec<String>>,
+    copied: bool,
+}
+
+impl Component for LogViewer {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        LogViewer { logs: None, copied: false }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Open | Msg::Refresh => {
+                ctx.link().send_future(async {
+                    match rpc::get_logs().await.context("Failed to get the logs") {
+                        Ok(logs) => Msg::Loaded(logs),
+                        Err(err) => Msg::Error(err),
+                    }
+                });
+                false
+            }
+            Msg::Close => {
+                self.logs = None;
+                true
+            }
+            Msg::Loaded(logs) => {
+                self.logs = Some(logs);
+                self.copied = false;
+                true
+            }
+            Msg::Copy => {
+                ctx.link().send_future(async {
+                    match rpc::copy_logs().await.context("Failed to copy the logs") {
+                        Ok(()) => Msg::Copied,
+                        Err(err) => Msg::Error(err),
+                    }
+                });
+                false
+            }
+            Msg::Copied => {
+                self.copied = true;
+                true
+            }
+            Msg::Error(err) => {
+                ctx.props().onerror.emit(err);
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let logs = self.logs.as_ref().map(|logs| {
+            let lines = logs.iter().map(|line| html! { <p>{ line }</p> });
+            let empty = logs.is_empty().then(|| html! { <p>{ "<empty>" }</p> });
+            let copy_label = if self.copied { "Copied !" } else { "Copy logs" };
+            html! {
+                <div class="fixed top-7 left-0 w-screen h-[calc(100vh-28px)] grid place-content-center bg-fg/30 z-50">
+                    <div class="border border-default-border bg-default-bg w-[80vw] flex flex-col">
+                        <div class="px-1 bg-theme-tab">{ "Logs" }</div>
+                        <div class="p-1 pt-0.5 flex flex-col gap-1">
+                            <div class="h-[60vh] overflow-auto font-mono text-sm whitespace-pre select-text">
+                                { for lines }
+                                { for empty }
+                            </div>
+                            <hr class="border-t border-default-border" />
+                            <div class="flex gap-2">
+                                <button class="button" onclick={link.callback(|_| Msg::Refresh)}>
+                                    { "Refresh" }
+                                </button>
+                                <button class="button" onclick={link.callback(|_| Msg::Copy)}>
+                                    { copy_label }
+                                </button>
+                                <button class="button" onclick={link.callback(|_| Msg::Close)}>
+                                    { "Close" }
+                                </button>
+                            </div>
+                        </div>
+                    </div>
+                </div>
+            }
+        });
+
+        html! {
+            <div class="flex items-center px-1">
+                <button class="button"
+                    title="Show what the editor did, useful when reporting a problem"
+                    onclick={link.callback(|_| Msg::Open)}
+                >
+                    { "Logs" }
+                </button>
+                { for logs }
+            </div>
+        }
+    }
+}
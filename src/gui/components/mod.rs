@@ -1,18 +1,33 @@
 mod auto_update;
+mod character_header;
 mod check_box;
+mod cloud_save_warning;
 mod color_picker;
+mod database_browser;
+mod expression;
 mod helper;
 mod input_number;
 mod input_text;
+mod log_viewer;
 mod nav_bar;
+mod print_report;
 pub mod raw_ui;
+mod research_panel;
+mod save_library;
+mod script_panel;
+mod search_panel;
 mod select;
+mod settings;
+mod start_screen;
 mod tab_bar;
 mod table;
+mod watch_panel;
 
 pub use self::{
-    auto_update::*, check_box::*, color_picker::*, helper::*, input_number::*, input_text::*,
-    nav_bar::*, select::*, tab_bar::*, table::*,
+    auto_update::*, character_header::*, check_box::*, cloud_save_warning::*, color_picker::*,
+    database_browser::*, expression::*, helper::*, input_number::*, input_text::*, log_viewer::*,
+    nav_bar::*, print_report::*, research_panel::*, save_library::*, script_panel::*,
+    search_panel::*, select::*, settings::*, start_screen::*, tab_bar::*, table::*, watch_panel::*,
 };
 
 pub enum CallbackType {
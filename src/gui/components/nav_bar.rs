@@ -1,12 +1,17 @@
+use std::path::PathBuf;
+
 use wasm_bindgen_futures as futures;
 use web_sys::HtmlElement;
 use yew::{context::ContextHandle, prelude::*};
 
 use crate::{
-    gui::components::{Tab, TabBar},
+    gui::components::{SaveLibrary, Tab, TabBar},
     services::{
-        rpc,
+        rpc::{self, Backup},
+        save_game::SaveGame,
         save_handler::{Action, SaveHandler},
+        session,
+        tlk::StringTable,
     },
 };
 
@@ -17,8 +22,22 @@ const DONATION_LINK: &str = "https://www.paypal.com/donate/?business=karlitos.vi
 pub enum Msg {
     SaveLoaded(SaveHandler),
     OpenSave,
+    OpenQuickPlot,
     SaveSave,
+    AllowOverwrite,
+    SaveAndLaunch,
+    ConvertPlatform,
     ReloadSave,
+    ExportStatistics,
+    ExportJson,
+    ImportJson,
+    FuzzingEnabled(bool),
+    FuzzSave,
+    ImportTlk,
+    BackupsOpen,
+    BackupsListed(Vec<Backup>),
+    BackupsClose,
+    RestoreBackup(PathBuf),
     MenuOpen,
     MenuClose,
     MenuBlur,
@@ -34,9 +53,16 @@ pub struct Props {
 pub struct NavBar {
     _db_handle: ContextHandle<SaveHandler>,
     save_handler: SaveHandler,
+    _string_table_handle: ContextHandle<StringTable>,
+    string_table: StringTable,
     about_ref: NodeRef,
     about_opened: bool,
+    // Listed again each time the menu is opened, a save adds one
+    backups: Option<Vec<Backup>>,
+    backups_ref: NodeRef,
     licenses_opened: bool,
+    // Only with `--fuzz` on the command line
+    fuzzing_enabled: bool,
 }
 
 impl Component for NavBar {
@@ -48,17 +74,27 @@ impl Component for NavBar {
             .link()
             .context::<SaveHandler>(ctx.link().callback(Msg::SaveLoaded))
             .expect("no save handler provider");
+        let (string_table, _string_table_handle) =
+            ctx.link().context::<StringTable>(Callback::noop()).expect("no string table provider");
+        ctx.link().send_future(async {
+            Msg::FuzzingEnabled(rpc::fuzzing_enabled().await.unwrap_or_default())
+        });
 
         NavBar {
             _db_handle,
             save_handler,
+            _string_table_handle,
+            string_table,
             about_ref: Default::default(),
             about_opened: false,
+            backups: None,
+            backups_ref: Default::default(),
             licenses_opened: false,
+            fuzzing_enabled: false,
         }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::SaveLoaded(save_handler) => {
                 self.save_handler = save_handler;
@@ -69,15 +105,75 @@ impl Component for NavBar {
                 self.save_handler.action(Action::OpenSave);
                 false
             }
+            Msg::OpenQuickPlot => {
+                self.save_handler.action(Action::OpenQuickPlot);
+                false
+            }
             Msg::SaveSave => {
                 self.save_handler.action(Action::SaveSave);
                 false
             }
+            Msg::AllowOverwrite => {
+                self.save_handler.action(Action::AllowOverwrite);
+                false
+            }
+            Msg::SaveAndLaunch => {
+                self.save_handler.action(Action::SaveAndLaunch);
+                false
+            }
+            Msg::ConvertPlatform => {
+                self.save_handler.action(Action::ConvertPlatform);
+                false
+            }
             Msg::ReloadSave => {
                 self.save_handler.action(Action::ReloadSave);
                 false
             }
+            Msg::ExportStatistics => {
+                self.save_handler.action(Action::ExportStatistics);
+                false
+            }
+            Msg::ExportJson => {
+                self.save_handler.action(Action::ExportJson);
+                false
+            }
+            Msg::ImportJson => {
+                self.save_handler.action(Action::ImportJson);
+                false
+            }
+            Msg::FuzzingEnabled(fuzzing_enabled) => {
+                self.fuzzing_enabled = fuzzing_enabled;
+                true
+            }
+            Msg::FuzzSave => {
+                self.save_handler.action(Action::FuzzSave);
+                false
+            }
+            Msg::ImportTlk => {
+                self.string_table.import();
+                false
+            }
             // Menus
+            Msg::BackupsOpen => {
+                self.save_handler
+                    .action(Action::ListBackups(ctx.link().callback(Msg::BackupsListed)));
+                false
+            }
+            Msg::BackupsListed(backups) => {
+                self.backups = Some(backups);
+                true
+            }
+            Msg::BackupsClose => {
+                self.backups = None;
+                true
+            }
+            Msg::RestoreBackup(backup) => {
+                self.save_handler.action(Action::RestoreBackup(backup));
+                if let Some(menu) = self.backups_ref.cast::<HtmlElement>() {
+                    let _ = menu.blur();
+                }
+                false
+            }
             Msg::MenuOpen => {
                 self.about_opened = true;
                 true
@@ -107,15 +203,115 @@ impl Component for NavBar {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let loaded_buttons = self.save_handler.save_game.is_some().then(|| {
+        let fuzz_button = self.fuzzing_enabled.then(|| {
             html! { <>
-                <button class="button" onclick={ctx.link().callback(|_| Msg::SaveSave)}>
-                    {"Save"}
+                <span>{"-"}</span>
+                <button class="button"
+                    title="Parse hundreds of corrupted copies of the save, findings go to the logs"
+                    onclick={ctx.link().callback(|_| Msg::FuzzSave)}
+                >
+                    {"Fuzz"}
+                </button>
+            </> }
+        });
+        // Only between the PC and PS4 saves of ME1LE
+        let convert_button = matches!(
+            self.save_handler.save_game.as_deref(),
+            Some(SaveGame::MassEffect1Le { .. } | SaveGame::MassEffect1LePs4 { .. })
+        )
+        .then(|| {
+            html! { <>
+                <span>{"-"}</span>
+                <button class="button"
+                    title="Save a copy for the other platform, PC or PS4"
+                    onclick={ctx.link().callback(|_| Msg::ConvertPlatform)}
+                >
+                    {"Convert platform"}
+                </button>
+            </> }
+        });
+        // ME1 and ME1LE saves keep compressed parts JSON can't describe
+        let export_json_button = matches!(
+            self.save_handler.save_game.as_deref(),
+            Some(
+                SaveGame::MassEffect2 { .. }
+                    | SaveGame::MassEffect2Le { .. }
+                    | SaveGame::MassEffect3 { .. }
+            )
+        )
+        .then(|| {
+            html! {
+                <button class="button"
+                    title="Export the whole save to a JSON file, to edit it with other tools \
+                    then import it back (Mass Effect 2, 2 LE and 3 only)"
+                    onclick={ctx.link().callback(|_| Msg::ExportJson)}
+                >
+                    {"Export JSON"}
                 </button>
+            }
+        });
+        let launch_button = self.save_handler.save_game.as_ref().and_then(|save_game| {
+            let game = save_game.launch_game()?;
+            Some(html! { <>
+                <span>{"-"}</span>
+                <button class="button"
+                    title={format!("Save, then start {}. Set how in the settings", game.label())}
+                    onclick={ctx.link().callback(|_| Msg::SaveAndLaunch)}
+                >
+                    {"Save and launch"}
+                </button>
+            </> })
+        });
+        let loaded_buttons = self.save_handler.save_game.as_ref().map(|save_game| {
+            let target = session::save_target(save_game.file_path());
+            let target_name = target.file_name().unwrap_or_default().to_string_lossy();
+            let save_button = if self.save_handler.write_protected {
+                html! { <>
+                    <button class="button"
+                        title={format!("The opened save is write protected, writes `{}` next to it", target_name)}
+                        onclick={ctx.link().callback(|_| Msg::SaveSave)}
+                    >
+                        {"Save a copy"}
+                    </button>
+                    <span class="text-fg/50" title={target.display().to_string()}>
+                        { target_name.clone() }
+                    </span>
+                    <button class="button"
+                        title="Allow overwriting the opened saves until the editor is closed"
+                        onclick={ctx.link().callback(|_| Msg::AllowOverwrite)}
+                    >
+                        {"Unprotect"}
+                    </button>
+                </> }
+            } else {
+                html! {
+                    <button class="button"
+                        title={format!("Overwrites `{}`", target_name)}
+                        onclick={ctx.link().callback(|_| Msg::SaveSave)}
+                    >
+                        {"Save"}
+                    </button>
+                }
+            };
+            html! { <>
+                { save_button }
+                { for launch_button }
+                { for convert_button }
                 <span>{"-"}</span>
                 <button class="button" onclick={ctx.link().callback(|_| Msg::ReloadSave)}>
                     {"Reload"}
                 </button>
+                { self.view_backups_menu(ctx) }
+                <span>{"-"}</span>
+                <button class="button"
+                    title="Export anonymous statistics (class, choices, completion) to a local JSON file, \
+                    nothing is sent anywhere"
+                    onclick={ctx.link().callback(|_| Msg::ExportStatistics)}
+                >
+                    {"Statistics"}
+                </button>
+                { for export_json_button }
+                { for fuzz_button }
             </> }
         });
 
@@ -125,7 +321,29 @@ impl Component for NavBar {
                     <button class="button" onclick={ctx.link().callback(|_| Msg::OpenSave)}>
                         {"Open"}
                     </button>
+                    <button class="button"
+                        title="Open a Mass Effect 2 or 3 save with only its plot table, much faster to open and save, \
+                        everything else is written back untouched"
+                        onclick={ctx.link().callback(|_| Msg::OpenQuickPlot)}
+                    >
+                        {"Quick plot edit"}
+                    </button>
+                    <button class="button"
+                        title="Open a Mass Effect 2, 2 LE or 3 save exported as JSON, saving it writes \
+                        the binary save for the platform it was exported from"
+                        onclick={ctx.link().callback(|_| Msg::ImportJson)}
+                    >
+                        {"Import JSON"}
+                    </button>
                     { for loaded_buttons }
+                    <span>{"-"}</span>
+                    <button class="button"
+                        title="Import a talk table (.tlk) of Mass Effect 2 or 3 to display the \
+                        localized strings of its saves"
+                        onclick={ctx.link().callback(|_| Msg::ImportTlk)}
+                    >
+                        {"Load TLK"}
+                    </button>
                     { self.view_about_menu(ctx) }
                 </div>
                 { ctx.props().children.clone() }
@@ -135,6 +353,75 @@ impl Component for NavBar {
 }
 
 impl NavBar {
+    fn view_backups_menu(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let onclick = match self.backups {
+            None => link.callback(|_| Msg::BackupsOpen),
+            Some(_) => link.callback(|_| Msg::BackupsClose),
+        };
+
+        let entries = self.backups.as_ref().map(|backups| {
+            let restore = backups.iter().map(|backup| {
+                let Backup { path, created, size } = backup;
+                let title = path.display().to_string();
+                let path = path.clone();
+                html! {
+                    <a class={classes![
+                            "flex",
+                            "justify-between",
+                            "gap-4",
+                            "px-1",
+                            "hover:bg-theme-hover",
+                            "active:bg-theme-active",
+                            "whitespace-nowrap",
+                            "cursor-pointer",
+                        ]}
+                        {title}
+                        onclick={link.callback(move |_| Msg::RestoreBackup(path.clone()))}
+                    >
+                        <span>{ SaveLibrary::format_date(*created) }</span>
+                        <span class="text-fg/50">{ SaveLibrary::format_size(*size) }</span>
+                    </a>
+                }
+            });
+            let empty = backups.is_empty().then(|| {
+                html! { <span class="px-1 whitespace-nowrap text-fg/50">{"No backup yet"}</span> }
+            });
+
+            html! {
+                <div class="absolute left-0 flex flex-col gap-px bg-popup/90 border border-default-border p-1 z-40">
+                    <span class="px-1 whitespace-nowrap">
+                        {"Copies made before the save was overwritten, click one to restore it"}
+                    </span>
+                    <hr class="border-default-border" />
+                    { for restore }
+                    { for empty }
+                </div>
+            }
+        });
+
+        html! {
+            <div class="relative" tabindex="0"
+                onblur={self.backups.is_some().then(|| link.callback(|_| Msg::BackupsClose))}
+                ref={self.backups_ref.clone()}
+            >
+                <a
+                    class={classes![
+                        "hover:bg-theme-hover",
+                        "px-2",
+                        "py-px",
+                        "cursor-pointer",
+                        self.backups.is_some().then(|| "bg-theme-hover" )
+                    ]}
+                    {onclick}
+                >
+                    {"Backups"}
+                </a>
+                { for entries }
+            </div>
+        }
+    }
+
     fn view_about_menu(&self, ctx: &Context<Self>) -> Html {
         let link = ctx.link();
         let onclick = if !self.about_opened {
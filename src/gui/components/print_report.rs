@@ -0,0 +1,222 @@
+use gloo::utils;
+use yew::{context::ContextHandle, create_portal, prelude::*};
+
+use super::CharacterHeader;
+use crate::{
+    save_data::shared::character_report::CharacterReport,
+    services::{database::Databases, logger, save_game::SaveGame, save_handler::SaveHandler},
+};
+
+pub enum Msg {
+    SaveHandler(SaveHandler),
+    DatabaseLoaded(Databases),
+    Print,
+}
+
+pub struct PrintReport {
+    _save_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    _databases_handle: ContextHandle<Databases>,
+    databases: Databases,
+    // Waiting for the plot database to build the report
+    is_pending: bool,
+    // Only built to be printed, it would be outdated by the next edit
+    report: Option<CharacterReport>,
+    is_printing: bool,
+}
+
+impl Component for PrintReport {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+        let (databases, _databases_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        PrintReport {
+            _save_handle,
+            save_handler,
+            _databases_handle,
+            databases,
+            is_pending: false,
+            report: None,
+            is_printing: false,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SaveHandler(save_handler) => {
+                self.save_handler = save_handler;
+                self.is_pending = false;
+                self.report = None;
+                true
+            }
+            Msg::DatabaseLoaded(databases) => {
+                self.databases = databases;
+                self.is_pending && self.build()
+            }
+            Msg::Print => {
+                self.is_pending = true;
+                self.build()
+            }
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if self.is_printing {
+            self.is_printing = false;
+            if let Err(err) = utils::window().print() {
+                logger::error(format!("Failed to print the report: {:?}", err));
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        // Next to the app, the print style hides everything else
+        let report = self
+            .report
+            .as_ref()
+            .map(|report| create_portal(Self::view_report(report), utils::body().into()));
+
+        html! {
+            <>
+                <button class="button"
+                    title="Print a sheet of the character and its playthrough, \
+                    the print dialog can also save it as a PDF"
+                    onclick={ctx.link().callback(|_| Msg::Print)}
+                >
+                    { "Print report" }
+                </button>
+                { for report }
+            </>
+        }
+    }
+}
+
+impl PrintReport {
+    // Returns whether the report was built, the plot database of its game being loaded
+    fn build(&mut self) -> bool {
+        let save_game = match self.save_handler.save_game.as_deref() {
+            Some(save_game) => save_game,
+            None => return false,
+        };
+
+        let databases = self.databases.clone();
+        let report = match save_game {
+            SaveGame::MassEffect1 { save_game, .. } => databases
+                .get_me1_plot()
+                .map(|plot_db| CharacterReport::me1(&save_game.borrow(), &plot_db)),
+            SaveGame::MassEffect1Le { save_game, .. } => databases.get_me1_plot().map(|plot_db| {
+                let save_game = save_game.borrow();
+                let save_data = save_game.save_data.borrow();
+                CharacterReport::me1_le(&save_data, &plot_db)
+            }),
+            SaveGame::MassEffect1LePs4 { save_game, .. } => databases
+                .get_me1_plot()
+                .map(|plot_db| CharacterReport::me1_le(&save_game.borrow(), &plot_db)),
+            SaveGame::MassEffect2 { save_game, .. } => databases
+                .get_me2_plot()
+                .map(|plot_db| CharacterReport::me2(&save_game.borrow(), &plot_db)),
+            SaveGame::MassEffect2Le { save_game, .. } => databases
+                .get_me2_plot()
+                .map(|plot_db| CharacterReport::me2_le(&save_game.borrow(), &plot_db)),
+            SaveGame::MassEffect3 { save_game, .. } => databases
+                .get_me3_plot()
+                .map(|plot_db| CharacterReport::me3(&save_game.borrow(), &plot_db)),
+            // No character in the plot table
+            SaveGame::MassEffect2Quick { .. } | SaveGame::MassEffect3Quick { .. } => {
+                self.is_pending = false;
+                return false;
+            }
+        };
+
+        match report {
+            Some(report) => {
+                self.report = Some(report);
+                self.is_pending = false;
+                self.is_printing = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn view_report(report: &CharacterReport) -> Html {
+        let CharacterReport { sheet, statistics, powers, choices, war_assets } = report;
+
+        let identity = [
+            ("Class", sheet.class.clone()),
+            ("Level", sheet.level.map(|level| level.to_string())),
+            ("Difficulty", statistics.difficulty.map(str::to_owned)),
+            ("Credits", sheet.credits.map(|credits| credits.to_string())),
+            ("Paragon", sheet.paragon.map(|paragon| paragon.to_string())),
+            ("Renegade", sheet.renegade.map(|renegade| renegade.to_string())),
+            ("Play time", sheet.seconds_played.map(CharacterHeader::format_playtime)),
+            ("End of the game", statistics.end_game_state.map(str::to_owned)),
+        ];
+        let identity =
+            identity.into_iter().filter_map(|(label, value)| Some(Self::row(label, &value?)));
+
+        let no_power = powers.is_empty().then(|| Self::empty("No power in the save"));
+        let powers = powers.iter().map(|(name, rank)| Self::row(name, &rank.to_string()));
+
+        let no_choice = choices
+            .is_empty()
+            .then(|| Self::empty("No major choice known to the plot database yet"));
+        let choices =
+            choices.iter().map(|choice| html! { <div class="table-row">{ choice }</div> });
+
+        let war_assets = war_assets.as_ref().map(|war_assets| {
+            let content = html! { <>
+                { Self::row("War assets", &war_assets.count.to_string()) }
+                { Self::row("Total strength", &war_assets.total_strength.to_string()) }
+            </> };
+            Self::section("Galaxy at War", content)
+        });
+
+        let name = sheet.name.as_deref().filter(|name| !name.is_empty()).unwrap_or("<unnamed>");
+        let version = format!("Trilogy Save Editor v{}", env!("CARGO_PKG_VERSION"));
+        html! {
+            <div id="character-report" class="hidden print:flex flex-col gap-3 p-4 bg-default-bg text-fg">
+                <header class="flex items-baseline gap-3 px-2 py-1 bg-theme-bg">
+                    <h1 class="text-2xl font-bold">{ name }</h1>
+                    <span class="flex-auto">{ statistics.game }</span>
+                    <span class="text-fg/50">{ version }</span>
+                </header>
+                { Self::section("Identity", html! { <>{ for identity }</> }) }
+                { Self::section("Powers", html! { <>{ for powers }{ for no_power }</> }) }
+                { Self::section("Major choices", html! { <>{ for choices }{ for no_choice }</> }) }
+                { for war_assets }
+            </div>
+        }
+    }
+
+    fn section(title: &str, content: Html) -> Html {
+        html! {
+            <section class="break-inside-avoid">
+                <h2 class="px-1 bg-theme-tab font-bold">{ title }</h2>
+                <div class="flex flex-col">{ content }</div>
+            </section>
+        }
+    }
+
+    fn row(label: &str, value: &str) -> Html {
+        html! {
+            <div class="table-row flex gap-2">
+                <span class="w-1/3 text-fg/50">{ label }</span>
+                <span class="flex-1">{ value }</span>
+            </div>
+        }
+    }
+
+    fn empty(text: &str) -> Html {
+        html! { <p class="table-row text-fg/50">{ text }</p> }
+    }
+}
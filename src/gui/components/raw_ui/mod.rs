@@ -1,11 +1,48 @@
+mod raw_ui_bytes;
 mod raw_ui_enum;
 mod raw_ui_guid;
 mod raw_ui_index_map;
 mod raw_ui_option;
+mod raw_ui_originals;
+mod raw_ui_pinned;
+mod raw_ui_random;
+mod raw_ui_set;
+mod raw_ui_string_ref;
 mod raw_ui_struct;
 mod raw_ui_vec;
 
 pub use self::{
-    raw_ui_enum::*, raw_ui_guid::*, raw_ui_index_map::*, raw_ui_option::*, raw_ui_struct::*,
-    raw_ui_vec::*,
+    raw_ui_bytes::*, raw_ui_enum::*, raw_ui_guid::*, raw_ui_index_map::*, raw_ui_option::*,
+    raw_ui_originals::*, raw_ui_pinned::*, raw_ui_random::*, raw_ui_set::*, raw_ui_string_ref::*,
+    raw_ui_struct::*, raw_ui_vec::*,
 };
+
+use anyhow::{Context as ErrorContext, Error, Result};
+use serde::{de::DeserializeOwned, Serialize};
+use yew::prelude::*;
+
+use crate::{
+    services::{
+        logger,
+        save_handler::{Action, SaveHandler},
+    },
+    unreal,
+};
+
+// A derived `Clone` would share the inner `RcRef` / `RcCell` with the original,
+// going through the serializer gives a copy that can be edited on its own
+fn deep_clone<T>(value: &T) -> Result<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let bytes = unreal::Serializer::to_vec(value).context("Failed to copy the item")?;
+    unreal::Deserializer::from_bytes(&bytes).context("Failed to read back the copied item")
+}
+
+// Shown like the other errors, only logged outside of a save handler
+fn emit_error<C: Component>(ctx: &Context<C>, err: Error) {
+    match ctx.link().context::<SaveHandler>(Callback::noop()) {
+        Some((save_handler, _)) => save_handler.action(Action::Error(err)),
+        None => logger::error(format!("{:#}", err)),
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+
+use gloo::storage::{LocalStorage, Storage};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::services::document;
+
+// `FF`, `0xff` or `ff`
+fn parse_byte(value: &str) -> Option<u8> {
+    let value = value.trim();
+    let value = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")).unwrap_or(value);
+    (!value.is_empty() && value.len() <= 2).then(|| u8::from_str_radix(value, 16).ok()).flatten()
+}
+
+// Kept by the browser, the offsets of a region mean the same in every save
+fn annotations_key(region: &str) -> String {
+    format!("raw_ui_annotations_{}", region)
+}
+
+pub enum Msg {
+    Change(usize, Event),
+    Select(usize),
+    Annotate(Event),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub label: String,
+    pub region: &'static str,
+    pub bytes: Vec<u8>,
+    pub onchange: Callback<(usize, u8)>,
+}
+
+pub struct RawUiBytes {
+    bytes: Vec<u8>,
+    // Offset => note
+    annotations: BTreeMap<usize, String>,
+    selected: Option<usize>,
+}
+
+impl Component for RawUiBytes {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let Props { region, bytes, .. } = ctx.props();
+        RawUiBytes {
+            bytes: bytes.clone(),
+            annotations: LocalStorage::get(annotations_key(region)).unwrap_or_default(),
+            selected: None,
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        let Props { region, bytes, .. } = ctx.props();
+        self.bytes = bytes.clone();
+        self.annotations = LocalStorage::get(annotations_key(region)).unwrap_or_default();
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Change(idx, event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    match parse_byte(&input.value()) {
+                        Some(byte) => {
+                            self.bytes[idx] = byte;
+                            ctx.props().onchange.emit((idx, byte));
+                            document::edited(ctx);
+                        }
+                        None => input.set_value(&format!("{:02X}", self.bytes[idx])),
+                    }
+                }
+                true
+            }
+            Msg::Select(idx) => {
+                let changed = self.selected != Some(idx);
+                self.selected = Some(idx);
+                changed
+            }
+            Msg::Annotate(event) => {
+                if let (Some(input), Some(idx)) =
+                    (event.target_dyn_into::<HtmlInputElement>(), self.selected)
+                {
+                    let note = input.value().trim().to_owned();
+                    if note.is_empty() {
+                        self.annotations.remove(&idx);
+                    } else {
+                        self.annotations.insert(idx, note);
+                    }
+
+                    let key = annotations_key(ctx.props().region);
+                    if self.annotations.is_empty() {
+                        LocalStorage::delete(key);
+                    } else {
+                        let _ = LocalStorage::set(key, &self.annotations);
+                    }
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let Props { label, region, .. } = ctx.props();
+        let size = format!("{} unknown bytes", self.bytes.len());
+
+        let bytes = self.bytes.iter().enumerate().map(|(idx, byte)| {
+            let annotation = self.annotations.get(&idx);
+            let title = match annotation {
+                Some(note) => format!("{}: {}", idx, note),
+                None => idx.to_string(),
+            };
+            let class = classes![
+                "input",
+                "w-[3ch]",
+                "px-0",
+                "text-center",
+                annotation.is_some().then(|| "underline decoration-dotted"),
+                (self.selected == Some(idx)).then(|| "bg-theme-active"),
+            ];
+            html! {
+                <input type="text" maxlength="4" {class} {title}
+                    value={format!("{:02X}", byte)}
+                    onfocus={ctx.link().callback(move |_| Msg::Select(idx))}
+                    onchange={ctx.link().callback(move |event| Msg::Change(idx, event))}
+                />
+            }
+        });
+
+        let annotate = self.selected.map(|idx| {
+            let value = self.annotations.get(&idx).cloned().unwrap_or_default();
+            html! {
+                <label class="flex items-center gap-1">
+                    <input type="text" class="input flex-auto" placeholder="<no annotation>"
+                        {value}
+                        onchange={ctx.link().callback(Msg::Annotate)}
+                    />
+                    { format!("Byte {}", idx) }
+                </label>
+            }
+        });
+
+        html! {
+            <div class="flex-auto flex flex-col gap-px">
+                <div class="flex items-center gap-1" title={*region}>
+                    { label }
+                    <span class="text-fg/50">{ size }</span>
+                </div>
+                <div class="flex flex-wrap gap-px font-mono">
+                    { for bytes }
+                </div>
+                { for annotate }
+            </div>
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_bytes() {
+        assert_eq!(parse_byte("FF"), Some(0xFF));
+        assert_eq!(parse_byte(" 0x0a "), Some(0x0A));
+        assert_eq!(parse_byte("7"), Some(0x07));
+
+        assert_eq!(parse_byte(""), None);
+        assert_eq!(parse_byte("0x"), None);
+        assert_eq!(parse_byte("100"), None);
+        assert_eq!(parse_byte("zz"), None);
+    }
+}
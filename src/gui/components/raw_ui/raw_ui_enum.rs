@@ -1,6 +1,6 @@
 use yew::prelude::*;
 
-use crate::{gui::components::Select, save_data::RcRef};
+use crate::{gui::components::Select, save_data::RcRef, services::document::Document};
 
 #[derive(Properties)]
 pub struct Props<T>
@@ -28,9 +28,15 @@ where
 {
     let options = props.items;
     let current_idx: usize = props.value.borrow().clone().into();
+    let document = use_context::<Document>();
     let onselect = {
         let value = RcRef::clone(&props.value);
-        Callback::from(move |idx| *value.borrow_mut() = T::from(idx))
+        Callback::from(move |idx| {
+            *value.borrow_mut() = T::from(idx);
+            if let Some(ref document) = document {
+                document.edited();
+            }
+        })
     };
     html! {
         <div class="flex items-center gap-1 cursor-default">
@@ -1,14 +1,180 @@
-use std::cell::{Ref, RefMut};
+use std::{
+    cell::{Ref, RefMut},
+    collections::HashMap,
+    fmt::Display,
+};
 
+use serde::{de::DeserializeOwned, Serialize};
 use uuid::Uuid;
 use web_sys::HtmlInputElement;
-use yew::prelude::*;
+use yew::{context::ContextHandle, prelude::*};
 
-use crate::save_data::Guid;
-use crate::save_data::RcRef;
+use super::{child_path, IndexMapKeyType, RawUiPins};
+use crate::{
+    gui::{
+        components::NumberType,
+        raw_ui::{RawUi, RawUiRenderer},
+    },
+    save_data::{shared::appearance::LinearColor, Dummy, Guid, RcCell, RcRef},
+    services::document,
+};
+
+// The field holding the GUID of the structure it belongs to
+const OWN_GUID_LABEL: &str = "Guid";
+
+/// Where every GUID of a save is defined, keyed by its hyphenated form.
+/// Records, doors or placeables have a `Guid` field of their own, the same GUID found
+/// anywhere else is a reference to them.
+#[derive(Default, PartialEq)]
+pub struct GuidIndex(HashMap<String, String>);
+
+impl GuidIndex {
+    pub fn new<T: RawUi>(root: &T) -> Self {
+        let tree = root.render(&mut GuidIndexer, "", true);
+        let mut index = GuidIndex::default();
+        index.walk(&tree, "");
+        index
+    }
+
+    // Path of the structure owning `guid`
+    pub fn get(&self, guid: &str) -> Option<&String> {
+        self.0.get(guid)
+    }
+
+    fn walk(&mut self, node: &GuidNode, path: &str) {
+        match node {
+            GuidNode::Leaf => (),
+            GuidNode::Guid { label, guid } => {
+                if label == OWN_GUID_LABEL {
+                    self.0.entry(guid.clone()).or_insert_with(|| path.to_owned());
+                }
+            }
+            GuidNode::Branch { label, children } => {
+                // Options aren't a level of the path, their content takes their label
+                let path = match label {
+                    Some(label) => child_path(path, label),
+                    None => path.to_owned(),
+                };
+                for child in children {
+                    self.walk(child, &path);
+                }
+            }
+        }
+    }
+}
+
+// The raw tree without the html, labelled as the raw components label their nodes
+enum GuidNode {
+    Leaf,
+    Guid { label: String, guid: String },
+    Branch { label: Option<String>, children: Vec<GuidNode> },
+}
+
+struct GuidIndexer;
+
+impl RawUiRenderer for GuidIndexer {
+    type Output = GuidNode;
+
+    fn number(&mut self, _: &str, _: NumberType) -> GuidNode {
+        GuidNode::Leaf
+    }
+
+    fn check_box(&mut self, _: &str, _: &RcCell<bool>) -> GuidNode {
+        GuidNode::Leaf
+    }
+
+    fn text(&mut self, _: &str, _: &RcRef<String>) -> GuidNode {
+        GuidNode::Leaf
+    }
+
+    fn string_ref(&mut self, _: &str, _: &RcCell<i32>) -> GuidNode {
+        GuidNode::Leaf
+    }
+
+    fn guid(&mut self, label: &str, value: &RcRef<Guid>) -> GuidNode {
+        GuidNode::Guid { label: label.to_owned(), guid: value.borrow().hyphenated() }
+    }
+
+    fn color(&mut self, _: &str, _: &RcRef<LinearColor>) -> GuidNode {
+        GuidNode::Leaf
+    }
+
+    fn bytes<const LEN: usize>(
+        &mut self, _: &str, _: &'static str, _: &RcRef<Dummy<LEN>>,
+    ) -> GuidNode {
+        GuidNode::Leaf
+    }
+
+    fn enumeration<E>(&mut self, _: &str, _: &'static [&'static str], _: &RcRef<E>) -> GuidNode
+    where
+        E: From<usize> + Into<usize> + Clone + 'static,
+    {
+        GuidNode::Leaf
+    }
+
+    fn option<T>(&mut self, label: &str, option: &RcRef<Option<T>>) -> GuidNode
+    where
+        T: RawUi,
+    {
+        let children = option.borrow().iter().map(|some| some.render(self, label, false)).collect();
+        GuidNode::Branch { label: None, children }
+    }
+
+    fn vec<T>(&mut self, label: &str, vec: &RcRef<Vec<T>>, _: bool) -> GuidNode
+    where
+        T: RawUi + Default + Display + Serialize + DeserializeOwned,
+    {
+        let children = vec
+            .borrow()
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| {
+                let label = item.to_string();
+                let label = if label.is_empty() { idx.to_string() } else { label };
+                item.render(self, &label, false)
+            })
+            .collect();
+        GuidNode::Branch { label: Some(label.to_owned()), children }
+    }
+
+    fn index_map<V>(&mut self, label: &str, index_map: IndexMapKeyType<V>) -> GuidNode
+    where
+        V: RawUi + Default + Serialize + DeserializeOwned,
+    {
+        let mut entry = |key: String, value: &V| GuidNode::Branch {
+            label: Some(key),
+            children: vec![value.render(self, "Value", false)],
+        };
+        let children = match index_map {
+            IndexMapKeyType::I32(index_map) => index_map
+                .borrow()
+                .iter()
+                .map(|(key, value)| entry(key.to_string(), value))
+                .collect(),
+            IndexMapKeyType::String(index_map) => {
+                index_map.borrow().iter().map(|(key, value)| entry(key.clone(), value)).collect()
+            }
+        };
+        GuidNode::Branch { label: Some(label.to_owned()), children }
+    }
+
+    fn structure(&mut self, label: &str, _: bool, children: Vec<GuidNode>) -> GuidNode {
+        GuidNode::Branch { label: Some(label.to_owned()), children }
+    }
+
+    fn root(&mut self, children: Vec<GuidNode>) -> GuidNode {
+        GuidNode::Branch { label: None, children }
+    }
+
+    fn opaque<T: RawUi>(&mut self, _: &str, _: Option<&T>) -> GuidNode {
+        GuidNode::Leaf
+    }
+}
 
 pub enum Msg {
     Change(Event),
+    Pins(RawUiPins),
+    Jump(String),
 }
 
 #[derive(Properties, PartialEq)]
@@ -27,14 +193,17 @@ impl Props {
     }
 }
 
-pub struct RawUiGuid;
+pub struct RawUiGuid {
+    pins: Option<(RawUiPins, ContextHandle<RawUiPins>)>,
+}
 
 impl Component for RawUiGuid {
     type Message = Msg;
     type Properties = Props;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        RawUiGuid
+    fn create(ctx: &Context<Self>) -> Self {
+        let pins = ctx.link().context::<RawUiPins>(ctx.link().callback(Msg::Pins));
+        RawUiGuid { pins }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -43,23 +212,82 @@ impl Component for RawUiGuid {
                 if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
                     if let Ok(guid) = Uuid::parse_str(&input.value()) {
                         *ctx.props().guid_mut() = Guid::from(guid);
+                        document::edited(ctx);
                     }
                     true
                 } else {
                     false
                 }
             }
+            Msg::Pins(new_pins) => {
+                if let Some((ref mut pins, _)) = self.pins {
+                    *pins = new_pins;
+                }
+                true
+            }
+            Msg::Jump(path) => {
+                if let Some((ref pins, _)) = self.pins {
+                    pins.jump(path);
+                }
+                false
+            }
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let value = ctx.props().guid().hyphenated();
+        let target = self.pins.as_ref().and_then(|(pins, _)| pins.guid_target(&value));
+        let go_to = target.map(|path| {
+            let title = format!("Go to {}", path);
+            html! {
+                <a class={classes![
+                        "rounded-none",
+                        "select-none",
+                        "hover:bg-theme-hover",
+                        "active:bg-theme-active",
+                        "bg-theme-bg",
+                        "px-1",
+                        "py-0",
+                        "cursor-pointer",
+                    ]}
+                    {title}
+                    onclick={ctx.link().callback(move |_| Msg::Jump(path.clone()))}
+                >
+                    {"go to"}
+                </a>
+            }
+        });
         let onchange = ctx.link().callback(Msg::Change);
         html! {
             <label class="flex-auto flex items-center gap-1">
                 <input type="text" class="input w-1/3" placeholder="<empty>" {value} {onchange} />
+                { for go_to }
                 { &ctx.props().label }
             </label>
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_3::Me3SaveGame, unreal};
+
+    #[test]
+    fn index_guids() -> Result<()> {
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let index = GuidIndex::new(&RcRef::new(me3_save_game));
+
+        // Owned by the structure holding them, only doors have a GUID of their own in this save
+        assert!(!index.0.is_empty());
+        assert!(index.0.values().all(|path| path.starts_with(&child_path("Doors", ""))));
+        assert!(index.0.values().all(|path| !path.ends_with(OWN_GUID_LABEL)));
+
+        Ok(())
+    }
+}
@@ -1,8 +1,10 @@
 use std::{any::Any, marker::PhantomData};
 
 use indexmap::IndexMap;
-use yew::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use yew::{context::ContextHandle, prelude::*};
 
+use super::{deep_clone, emit_error, RawUiPins};
 use crate::{
     gui::{
         components::{
@@ -14,12 +16,13 @@ use crate::{
         mass_effect_1_le::legacy::{Level, Map},
         RcRef,
     },
+    services::{document, reveal},
 };
 
 #[derive(Clone, From)]
 pub enum IndexMapKeyType<T>
 where
-    T: RawUi + Default,
+    T: RawUi + Default + Serialize + DeserializeOwned,
 {
     I32(RcRef<IndexMap<i32, T>>),
     String(RcRef<IndexMap<String, T>>),
@@ -27,7 +30,7 @@ where
 
 impl<T> PartialEq for IndexMapKeyType<T>
 where
-    T: RawUi + Default,
+    T: RawUi + Default + Serialize + DeserializeOwned,
 {
     fn eq(&self, other: &IndexMapKeyType<T>) -> bool {
         match (self, other) {
@@ -41,14 +44,16 @@ where
 pub enum Msg {
     Toggle,
     Add,
+    Duplicate(usize),
     Remove(usize),
+    Pins(RawUiPins),
     EditKey(usize, CallbackType),
 }
 
 #[derive(Properties, PartialEq)]
 pub struct Props<T>
 where
-    T: RawUi + Default,
+    T: RawUi + Default + Serialize + DeserializeOwned,
 {
     pub label: String,
     pub index_map: IndexMapKeyType<T>,
@@ -56,22 +61,36 @@ where
 
 pub struct RawUiIndexMap<T>
 where
-    T: RawUi + Default,
+    T: RawUi + Default + Serialize + DeserializeOwned,
 {
     _marker: PhantomData<T>,
     opened: bool,
     new_item_idx: usize,
+    // Added or duplicated, to be revealed once rendered
+    new_item_ref: NodeRef,
+    is_revealing: bool,
+    pins: Option<(RawUiPins, ContextHandle<RawUiPins>)>,
 }
 
 impl<T> Component for RawUiIndexMap<T>
 where
-    T: RawUi + Default,
+    T: RawUi + Default + Serialize + DeserializeOwned,
 {
     type Message = Msg;
     type Properties = Props<T>;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        RawUiIndexMap { _marker: PhantomData, opened: false, new_item_idx: 0 }
+    fn create(ctx: &Context<Self>) -> Self {
+        let pins = ctx.link().context::<RawUiPins>(ctx.link().callback(Msg::Pins));
+        let opened = pins.as_ref().map(|(pins, _)| pins.child(&ctx.props().label).is_opened());
+        RawUiIndexMap {
+            _marker: PhantomData,
+            opened: opened.unwrap_or(false),
+            // Opened by a pin, no item to open with it
+            new_item_idx: usize::MAX,
+            new_item_ref: NodeRef::default(),
+            is_revealing: false,
+            pins,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -93,13 +112,59 @@ where
                         // Open added item
                         self.new_item_idx = index_map.borrow().len();
                         index_map.borrow_mut().entry(-1).or_default();
+                        self.is_revealing = true;
                     }
                     IndexMapKeyType::String(ref index_map) => {
                         // Open added item
                         self.new_item_idx = index_map.borrow().len();
                         index_map.borrow_mut().entry(Default::default()).or_default();
+                        self.is_revealing = true;
                     }
                 }
+                document::edited(ctx);
+                true
+            }
+            Msg::Duplicate(idx) => {
+                // The copy gets a free key and is opened
+                match ctx.props().index_map {
+                    IndexMapKeyType::I32(ref index_map) => {
+                        let copy =
+                            index_map.borrow().get_index(idx).map(|(_, value)| deep_clone(value));
+                        match copy {
+                            Some(Ok(copy)) => {
+                                let mut index_map = index_map.borrow_mut();
+                                let key =
+                                    index_map.keys().max().map(|max| max + 1).unwrap_or_default();
+                                self.new_item_idx = index_map.len();
+                                index_map.insert(key, copy);
+                                self.is_revealing = true;
+                            }
+                            Some(Err(err)) => emit_error(ctx, err),
+                            None => (),
+                        }
+                    }
+                    IndexMapKeyType::String(ref index_map) => {
+                        let copy = index_map
+                            .borrow()
+                            .get_index(idx)
+                            .map(|(key, value)| (key.clone(), deep_clone(value)));
+                        match copy {
+                            Some((key, Ok(copy))) => {
+                                let mut index_map = index_map.borrow_mut();
+                                let mut key = format!("{}_copy", key);
+                                while index_map.contains_key(&key) {
+                                    key.push_str("_copy");
+                                }
+                                self.new_item_idx = index_map.len();
+                                index_map.insert(key, copy);
+                                self.is_revealing = true;
+                            }
+                            Some((_, Err(err))) => emit_error(ctx, err),
+                            None => (),
+                        }
+                    }
+                }
+                document::edited(ctx);
                 true
             }
             Msg::Remove(idx) => {
@@ -111,6 +176,7 @@ where
                         index_map.borrow_mut().shift_remove_index(idx);
                     }
                 }
+                document::edited(ctx);
                 true
             }
             Msg::EditKey(idx, new_key) => match ctx.props().index_map {
@@ -133,11 +199,28 @@ where
                     _ => false,
                 },
             },
+            Msg::Pins(new_pins) => {
+                if new_pins.child(&ctx.props().label).is_opened() {
+                    self.opened = true;
+                }
+                if let Some((ref mut pins, _)) = self.pins {
+                    *pins = new_pins;
+                }
+                true
+            }
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if self.is_revealing {
+            self.is_revealing = false;
+            reveal::reveal(&self.new_item_ref);
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let chevron = if self.opened { "table-chevron-down" } else { "table-chevron-right" };
+        let pins = self.pins.as_ref().map(|(pins, _)| pins.child(&ctx.props().label));
 
         let content = self
             .opened
@@ -153,25 +236,32 @@ where
                         vec![RawUi::view(value, "Value")]
                     };
 
+                    let edit_button = |text: &'static str, msg: fn(usize) -> Msg| html! {
+                        <div class="py-px">
+                            <a class={classes![
+                                    "rounded-none",
+                                    "select-none",
+                                    "hover:bg-theme-hover",
+                                    "active:bg-theme-active",
+                                    "bg-theme-bg",
+                                    "px-1",
+                                    "py-0",
+                                    "cursor-pointer",
+                                ]}
+                                onclick={ctx.link().callback(move |_| msg(idx))}
+                            >
+                                { text }
+                            </a>
+                        </div>
+                    };
+
+                    let opened = self.new_item_idx == idx;
+                    let node_ref = opened.then(|| self.new_item_ref.clone()).unwrap_or_default();
                     html! {
-                        <div class="flex gap-1">
-                            <div class="py-px">
-                                <a class={classes![
-                                        "rounded-none",
-                                        "select-none",
-                                        "hover:bg-theme-hover",
-                                        "active:bg-theme-active",
-                                        "bg-theme-bg",
-                                        "px-1",
-                                        "py-0",
-                                        "cursor-pointer",
-                                    ]}
-                                    onclick={ctx.link().callback(move |_| Msg::Remove(idx))}
-                                >
-                                    {"remove"}
-                                </a>
-                            </div>
-                            <RawUiStruct {label} opened={self.new_item_idx == idx}>
+                        <div class="flex gap-1" ref={node_ref}>
+                            { edit_button("remove", Msg::Remove) }
+                            { edit_button("duplicate", Msg::Duplicate) }
+                            <RawUiStruct {label} {opened}>
                                 { key }
                                 { for value.into_iter() }
                             </RawUiStruct>
@@ -229,9 +319,15 @@ where
                 }
             });
 
+        let toggle = pins.as_ref().map(RawUiPins::view_toggle);
+        let content = content.map(|content| match pins {
+            Some(ref pins) => pins.provide(content),
+            None => content,
+        });
+
         html! {
-            <div class="flex-auto flex flex-col">
-                <div class="p-px">
+            <div class="flex-auto flex flex-col" id={pins.as_ref().map(RawUiPins::id)}>
+                <div class="p-px flex gap-1 group">
                     <button class={classes![
                             "rounded-none",
                             "hover:bg-theme-hover",
@@ -246,6 +342,7 @@ where
                     >
                         { &ctx.props().label }
                     </button>
+                    { for toggle }
                 </div>
                 { for content }
             </div>
@@ -5,7 +5,7 @@ use std::{
 
 use yew::prelude::*;
 
-use crate::{gui::raw_ui::RawUi, save_data::RcRef};
+use crate::{gui::raw_ui::RawUi, save_data::RcRef, services::document};
 
 pub enum Msg {
     Remove,
@@ -55,6 +55,7 @@ where
         match msg {
             Msg::Remove => {
                 *ctx.props().option_mut() = None;
+                document::edited(ctx);
                 true
             }
         }
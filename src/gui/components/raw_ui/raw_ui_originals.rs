@@ -0,0 +1,452 @@
+use std::{any::Any, collections::HashMap, fmt::Display, rc::Rc};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{child_path, IndexMapKeyType};
+use crate::{
+    gui::{
+        components::NumberType,
+        raw_ui::{RawUi, RawUiChildren, RawUiRenderer},
+    },
+    save_data::{
+        mass_effect_1::{
+            data::{ArrayType, Property, StructType},
+            player::Player,
+        },
+        mass_effect_1_le::legacy::{BaseObject, Object, OptionObjectProxy},
+        mass_effect_3::plot::PlotTable as Me3PlotTable,
+        shared::{appearance::LinearColor, plot::PlotTable},
+        Dummy, Guid, List, RcCell, RcRef,
+    },
+};
+
+/// A field an input can revert
+#[derive(Clone)]
+pub enum Field {
+    Byte(RcCell<u8>),
+    Int(RcCell<i32>),
+    Float(RcCell<f32>),
+    Bool(RcCell<bool>),
+    Text(RcRef<String>),
+}
+
+impl Field {
+    fn address(&self) -> usize {
+        match self {
+            Field::Byte(byte) => byte.address(),
+            Field::Int(integer) => integer.address(),
+            Field::Float(float) => float.address(),
+            Field::Bool(boolean) => boolean.address(),
+            Field::Text(text) => text.address(),
+        }
+    }
+}
+
+impl From<NumberType> for Field {
+    fn from(number: NumberType) -> Self {
+        match number {
+            NumberType::Byte(byte) => Field::Byte(byte),
+            NumberType::Int(integer) => Field::Int(integer),
+            NumberType::Float(float) => Field::Float(float),
+        }
+    }
+}
+
+/// The values the cells of a field can be compared with
+pub trait Revertable: Copy + PartialEq + 'static {
+    fn from_field(field: &Field) -> Option<Self>;
+}
+
+impl Revertable for u8 {
+    fn from_field(field: &Field) -> Option<Self> {
+        match field {
+            Field::Byte(byte) => Some(byte.get()),
+            _ => None,
+        }
+    }
+}
+
+impl Revertable for i32 {
+    fn from_field(field: &Field) -> Option<Self> {
+        match field {
+            Field::Int(integer) => Some(integer.get()),
+            _ => None,
+        }
+    }
+}
+
+impl Revertable for f32 {
+    fn from_field(field: &Field) -> Option<Self> {
+        match field {
+            Field::Float(float) => Some(float.get()),
+            _ => None,
+        }
+    }
+}
+
+impl Revertable for bool {
+    fn from_field(field: &Field) -> Option<Self> {
+        match field {
+            Field::Bool(boolean) => Some(boolean.get()),
+            _ => None,
+        }
+    }
+}
+
+/// Every field of a save with its path in the raw tree, see [`fields_of`]
+pub type Fields = Vec<(String, Field)>;
+
+/// The fields of `value` and of everything under it, with the fields of the types that have a
+/// view of their own: the plot tables and the objects of ME1LE
+pub fn fields_of<T: RawUi>(value: &T) -> Fields {
+    value.render(&mut FieldWalker, "", true)
+}
+
+/// The fields of the ME1 player, its properties by index of their data and name id, and its names
+pub fn me1_player_fields(player: &Player) -> Fields {
+    let datas = player
+        .datas
+        .iter()
+        .enumerate()
+        .map(|(idx, data)| nest(&idx.to_string(), properties_fields(&data.properties)));
+    let names = player
+        .names
+        .borrow()
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| {
+            (child_path("Names", &idx.to_string()), Field::Text(RcRef::clone(&name.string)))
+        })
+        .collect::<Fields>();
+    nest("Datas", datas.flatten().collect()).into_iter().chain(names).collect()
+}
+
+/// The value each field of the save had in the file as it was opened, by the cell of the field
+/// in the edited save. They come from a second parse of the file, kept by the save handler and
+/// paired with the edited one field by field.
+#[derive(Clone, Default)]
+pub struct Originals(Rc<HashMap<usize, (Field, Field)>>);
+
+impl PartialEq for Originals {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Originals {
+    /// Pairs the fields of the edited save with those of the original one at the same path. The
+    /// items of a vector that grew or shrank since have none, their path has its length.
+    pub fn pair(edited: Fields, original: Fields) -> Self {
+        let mut original: HashMap<String, Field> = original.into_iter().collect();
+        let pairs = edited
+            .into_iter()
+            .filter_map(|(path, edited)| {
+                let original = original.remove(&path)?;
+                // The edited cell is kept alive so its address is never given to another one
+                Some((edited.address(), (edited, original)))
+            })
+            .collect();
+        Originals(Rc::new(pairs))
+    }
+
+    /// The original value, `None` for a field added after opening the save
+    pub fn original<T: Revertable>(&self, cell: &RcCell<T>) -> Option<T> {
+        let (_, original) = self.0.get(&cell.address())?;
+        T::from_field(original)
+    }
+
+    /// The original value, only if the field has been changed since
+    pub fn modified<T: Revertable>(&self, cell: &RcCell<T>) -> Option<T> {
+        self.original(cell).filter(|original| *original != cell.get())
+    }
+
+    /// The original text, only if it has been changed since
+    pub fn modified_text(&self, text: &RcRef<String>) -> Option<String> {
+        match self.0.get(&text.address())? {
+            (_, Field::Text(original)) if *original.borrow() != *text.borrow() => {
+                Some(original.borrow().clone())
+            }
+            _ => None,
+        }
+    }
+}
+
+fn nest(label: &str, fields: Fields) -> Fields {
+    fields.into_iter().map(|(path, field)| (child_path(label, &path), field)).collect()
+}
+
+fn properties_fields(properties: &List<RcRef<Property>>) -> Fields {
+    properties.iter().flat_map(|property| property_fields(&property.borrow())).collect()
+}
+
+fn property_fields(property: &Property) -> Fields {
+    let leaf = |name_id: &u32, field: Field| vec![(name_id.to_string(), field)];
+    match property {
+        Property::Array { name_id, array, .. } => {
+            let items = array.iter().enumerate().flat_map(|(idx, item)| {
+                let idx = idx.to_string();
+                match item {
+                    ArrayType::Int(integer) => vec![(idx, Field::Int(RcCell::clone(integer)))],
+                    ArrayType::Vector(vector) => vector.render(&mut FieldWalker, &idx, false),
+                    ArrayType::String(string) => vec![(idx, Field::Text(RcRef::clone(string)))],
+                    ArrayType::Properties(properties) => nest(&idx, properties_fields(properties)),
+                    ArrayType::Object(_) => Vec::new(),
+                }
+            });
+            nest(&format!("{}[{}]", name_id, array.len()), items.collect())
+        }
+        Property::Bool { name_id, value, .. } => leaf(name_id, Field::Bool(RcCell::clone(value))),
+        Property::Byte { name_id, value, .. } => leaf(name_id, Field::Byte(RcCell::clone(value))),
+        Property::Float { name_id, value, .. } => leaf(name_id, Field::Float(RcCell::clone(value))),
+        Property::Int { name_id, value, .. } | Property::StringRef { name_id, value, .. } => {
+            leaf(name_id, Field::Int(RcCell::clone(value)))
+        }
+        Property::Str { name_id, string, .. } => leaf(name_id, Field::Text(RcRef::clone(string))),
+        Property::Struct { name_id, struct_type, .. } => {
+            let label = name_id.to_string();
+            match struct_type {
+                StructType::Vector(vector) => vector.render(&mut FieldWalker, &label, false),
+                StructType::Rotator(rotator) => rotator.render(&mut FieldWalker, &label, false),
+                StructType::Properties(properties) => nest(&label, properties_fields(properties)),
+                StructType::LinearColor(_) => Vec::new(),
+            }
+        }
+        Property::Name { .. } | Property::Object { .. } | Property::None { .. } => Vec::new(),
+    }
+}
+
+fn base_object_fields(object: &BaseObject) -> Fields {
+    let BaseObject { owner_name, owner_class, _object, .. } = object;
+    let walker = &mut FieldWalker;
+    let object_fields = match _object {
+        Object::PawnBehavior(pawn_behavior) => pawn_behavior.render_children(walker),
+        Object::Pawn(pawn) => pawn.render_children(walker),
+        Object::BaseSquad(squad) => squad.render_children(walker),
+        Object::Shop(shop) => shop.render_children(walker),
+        Object::Inventory(inventory) => inventory.render_children(walker),
+        Object::Item(item) => item.render_children(walker),
+        Object::ItemMod(item_mod) => item_mod.render_children(walker),
+        Object::ArtPlaceableBehavior(art_placeable_behavior) => {
+            art_placeable_behavior.render_children(walker)
+        }
+        Object::ArtPlaceable(art_placeable) => art_placeable.render_children(walker),
+        Object::VehicleBehavior(vehicle_behavior) => vehicle_behavior.render_children(walker),
+        Object::Vehicle(vehicle) => vehicle.render_children(walker),
+        Object::World(world) => world.render_children(walker),
+        Object::Default => Vec::new(),
+    };
+    let mut fields = owner_name.render(walker, "Owner Name", false);
+    fields.extend(owner_class.render(walker, "Owner Class", false));
+    fields.extend(object_fields.into_iter().flatten());
+    fields
+}
+
+// The raw tree flattened to the fields the inputs can revert
+struct FieldWalker;
+
+impl RawUiRenderer for FieldWalker {
+    type Output = Fields;
+
+    fn number(&mut self, label: &str, value: NumberType) -> Fields {
+        vec![(label.to_owned(), value.into())]
+    }
+
+    fn check_box(&mut self, label: &str, value: &RcCell<bool>) -> Fields {
+        vec![(label.to_owned(), Field::Bool(RcCell::clone(value)))]
+    }
+
+    fn text(&mut self, label: &str, value: &RcRef<String>) -> Fields {
+        vec![(label.to_owned(), Field::Text(RcRef::clone(value)))]
+    }
+
+    fn string_ref(&mut self, label: &str, value: &RcCell<i32>) -> Fields {
+        vec![(label.to_owned(), Field::Int(RcCell::clone(value)))]
+    }
+
+    fn guid(&mut self, _: &str, _: &RcRef<Guid>) -> Fields {
+        Vec::new()
+    }
+
+    fn color(&mut self, _: &str, _: &RcRef<LinearColor>) -> Fields {
+        Vec::new()
+    }
+
+    fn bytes<const LEN: usize>(
+        &mut self, _: &str, _: &'static str, _: &RcRef<Dummy<LEN>>,
+    ) -> Fields {
+        Vec::new()
+    }
+
+    fn enumeration<E>(&mut self, _: &str, _: &'static [&'static str], _: &RcRef<E>) -> Fields
+    where
+        E: From<usize> + Into<usize> + Clone + 'static,
+    {
+        Vec::new()
+    }
+
+    fn option<T>(&mut self, label: &str, option: &RcRef<Option<T>>) -> Fields
+    where
+        T: RawUi,
+    {
+        // Options aren't a level of the path, their content takes their label
+        option.borrow().iter().flat_map(|some| some.render(self, label, false)).collect()
+    }
+
+    fn vec<T>(&mut self, label: &str, vec: &RcRef<Vec<T>>, _: bool) -> Fields
+    where
+        T: RawUi + Default + Display + Serialize + DeserializeOwned,
+    {
+        let vec = vec.borrow();
+        let items = vec
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, item)| item.render(self, &idx.to_string(), false))
+            .collect();
+        nest(&format!("{}[{}]", label, vec.len()), items)
+    }
+
+    fn index_map<V>(&mut self, label: &str, index_map: IndexMapKeyType<V>) -> Fields
+    where
+        V: RawUi + Default + Serialize + DeserializeOwned,
+    {
+        let entries: Fields = match index_map {
+            IndexMapKeyType::I32(index_map) => index_map
+                .borrow()
+                .iter()
+                .flat_map(|(key, value)| value.render(self, &key.to_string(), false))
+                .collect(),
+            IndexMapKeyType::String(index_map) => index_map
+                .borrow()
+                .iter()
+                .flat_map(|(key, value)| value.render(self, key, false))
+                .collect(),
+        };
+        nest(label, entries)
+    }
+
+    fn structure(&mut self, label: &str, _: bool, fields: Vec<Fields>) -> Fields {
+        nest(label, fields.into_iter().flatten().collect())
+    }
+
+    fn root(&mut self, fields: Vec<Fields>) -> Fields {
+        fields.into_iter().flatten().collect()
+    }
+
+    fn opaque<T: RawUi>(&mut self, label: &str, value: Option<&T>) -> Fields {
+        let value = match value {
+            Some(value) => value as &dyn Any,
+            None => return Vec::new(),
+        };
+
+        let fields = if let Some(plot) = value.downcast_ref::<RcRef<PlotTable>>() {
+            let plot = plot.borrow();
+            let mut fields = plot.integers.render(self, "Integers", false);
+            fields.extend(plot.floats.render(self, "Floats", false));
+            fields
+        } else if let Some(plot) = value.downcast_ref::<RcRef<Me3PlotTable>>() {
+            let plot = plot.borrow();
+            let mut fields = plot.integers.render(self, "Integers", false);
+            fields.extend(plot.floats.render(self, "Floats", false));
+            fields
+        } else if let Some(object) = value.downcast_ref::<RcRef<BaseObject>>() {
+            base_object_fields(&object.borrow())
+        } else if let Some(proxy) = value.downcast_ref::<RcRef<OptionObjectProxy>>() {
+            return proxy.borrow().proxy.render(self, label, false);
+        } else {
+            Vec::new()
+        };
+        nest(label, fields)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::{
+            mass_effect_1::Me1SaveGame, mass_effect_1_le::Me1LeSaveGame,
+            mass_effect_2::Me2LeSaveGame,
+        },
+        unreal,
+    };
+
+    #[test]
+    fn revert_to_the_opened_save() -> Result<()> {
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let edited: RcRef<Me2LeSaveGame> = unreal::Deserializer::from_bytes(&input)?;
+        let original: RcRef<Me2LeSaveGame> = unreal::Deserializer::from_bytes(&input)?;
+        let originals = Originals::pair(fields_of(&edited), fields_of(&original));
+
+        let (credits, first_name) = {
+            let edited = edited.borrow();
+            let player = edited.player();
+            (RcCell::clone(&player.credits), RcRef::clone(&player.first_name))
+        };
+        let was = credits.get();
+        assert_eq!(originals.modified(&credits), None);
+        assert_eq!(originals.modified_text(&first_name), None);
+
+        credits.set(was + 1000);
+        *first_name.borrow_mut() = "Jane".to_owned();
+        assert_eq!(originals.modified(&credits), Some(was));
+        assert!(originals.modified_text(&first_name).is_some());
+
+        // Added after opening, nothing to revert to
+        assert_eq!(originals.original(&RcCell::new(0i32)), None);
+
+        // The plot of its own view
+        let integers = RcRef::clone(&edited.borrow().plot.borrow().integers);
+        let first = RcCell::clone(&integers.borrow()[0]);
+        first.update(|integer| integer + 1);
+        assert_eq!(originals.modified(&first), Some(first.get() - 1));
+        Ok(())
+    }
+
+    #[test]
+    fn grown_vecs_are_not_paired() -> Result<()> {
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let edited: RcRef<Me2LeSaveGame> = unreal::Deserializer::from_bytes(&input)?;
+        let original: RcRef<Me2LeSaveGame> = unreal::Deserializer::from_bytes(&input)?;
+
+        let integers = RcRef::clone(&edited.borrow().plot.borrow().integers);
+        integers.borrow_mut().push(RcCell::new(1));
+        let originals = Originals::pair(fields_of(&edited), fields_of(&original));
+
+        assert_eq!(originals.original(&integers.borrow()[0]), None);
+        let credits = RcCell::clone(&edited.borrow().player().credits);
+        assert_eq!(originals.original(&credits), Some(credits.get()));
+        Ok(())
+    }
+
+    #[test]
+    fn me1_fields() -> Result<()> {
+        // The properties of the player
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let edited: Me1SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let original: Me1SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let originals = Originals::pair(
+            me1_player_fields(&edited.player()),
+            me1_player_fields(&original.player()),
+        );
+        let player = edited.player();
+        let m_player = player.m_player().unwrap();
+        let level = player.find_int(&m_player.properties, "m_XPLevel").unwrap();
+        let was = level.get();
+        level.set(was + 1);
+        assert_eq!(originals.modified(&level), Some(was));
+
+        // The legacy objects of ME1LE
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let edited: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let original: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let edited_fields = fields_of(&edited.save_data);
+        assert!(edited_fields.iter().any(|(path, _)| path.contains("Owner Name")));
+        let originals = Originals::pair(edited_fields, fields_of(&original.save_data));
+        assert!(!originals.0.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,276 @@
+use std::rc::Rc;
+
+use gloo::{
+    storage::{LocalStorage, Storage},
+    timers::future::TimeoutFuture,
+    utils,
+};
+use yew::prelude::*;
+
+use super::GuidIndex;
+use crate::{gui::components::Table, services::document};
+
+const SEPARATOR: &str = " > ";
+
+fn node_id(path: &str) -> String {
+    format!("raw-{}", path)
+}
+
+pub(super) fn child_path(path: &str, label: &str) -> String {
+    if path.is_empty() {
+        label.to_owned()
+    } else {
+        format!("{}{}{}", path, SEPARATOR, label)
+    }
+}
+
+// Given by each raw node to its children so they know their path in the tree
+#[derive(Clone, PartialEq)]
+pub struct RawUiPins {
+    path: String,
+    pinned: Rc<Vec<String>>,
+    // Opened to show the target of a jump
+    revealed: Option<String>,
+    toggle: Callback<String>,
+    guids: Rc<GuidIndex>,
+    jump: Callback<String>,
+    // Only for the saves that can be walked from their root
+    randomize: Option<Callback<String>>,
+}
+
+impl RawUiPins {
+    pub fn child(&self, label: &str) -> Self {
+        RawUiPins { path: child_path(&self.path, label), ..self.clone() }
+    }
+
+    pub fn is_pinned(&self) -> bool {
+        self.pinned.contains(&self.path)
+    }
+
+    // Pinned or revealed itself, or an ancestor of such a node
+    pub fn is_opened(&self) -> bool {
+        let prefix = format!("{}{}", self.path, SEPARATOR);
+        self.pinned
+            .iter()
+            .chain(self.revealed.iter())
+            .any(|pin| *pin == self.path || pin.starts_with(&prefix))
+    }
+
+    // Path of the structure owning `guid`, unless it is this one
+    pub fn guid_target(&self, guid: &str) -> Option<String> {
+        self.guids.get(guid).filter(|path| **path != self.path).cloned()
+    }
+
+    // Opens every node leading to `path` and scrolls to it
+    pub fn jump(&self, path: String) {
+        self.jump.emit(path);
+    }
+
+    pub fn id(&self) -> String {
+        node_id(&self.path)
+    }
+
+    pub fn view_randomize(&self) -> Option<Html> {
+        let onclick = {
+            let (randomize, path) = (self.randomize.clone()?, self.path.clone());
+            Callback::from(move |_| randomize.emit(path.clone()))
+        };
+        Some(html! {
+            <div class="py-px">
+                <a class={classes![
+                        "rounded-none",
+                        "select-none",
+                        "hover:bg-theme-hover",
+                        "active:bg-theme-active",
+                        "bg-theme-bg",
+                        "px-1",
+                        "py-0",
+                        "cursor-pointer",
+                        "invisible",
+                        "group-hover:visible",
+                    ]}
+                    title="Give every number, boolean and enum of this node a random value, edge cases first. Listed in the logs"
+                    {onclick}
+                >
+                    { "randomize" }
+                </a>
+            </div>
+        })
+    }
+
+    pub fn view_toggle(&self) -> Html {
+        let pinned = self.is_pinned();
+        let onclick = {
+            let (toggle, path) = (self.toggle.clone(), self.path.clone());
+            Callback::from(move |_| toggle.emit(path.clone()))
+        };
+        html! {
+            <div class="py-px">
+                <a class={classes![
+                        "rounded-none",
+                        "select-none",
+                        "hover:bg-theme-hover",
+                        "active:bg-theme-active",
+                        "bg-theme-bg",
+                        "px-1",
+                        "py-0",
+                        "cursor-pointer",
+                        (!pinned).then(|| classes!["invisible", "group-hover:visible"]),
+                    ]}
+                    title={if pinned { "Remove from the pinned nodes" } else { "Keep this node opened and listed in the pinned nodes" }}
+                    {onclick}
+                >
+                    { if pinned { "unpin" } else { "pin" } }
+                </a>
+            </div>
+        }
+    }
+
+    pub fn provide(&self, content: Html) -> Html {
+        html! {
+            <ContextProvider<RawUiPins> context={self.clone()}>
+                { content }
+            </ContextProvider<RawUiPins>>
+        }
+    }
+}
+
+pub enum Msg {
+    Toggle(String),
+    Randomize(String),
+    Jump(String),
+    ScrollTo(String),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub children: Children,
+    pub pins_key: &'static str,
+    #[prop_or_default]
+    pub guids: Rc<GuidIndex>,
+    pub onrandomize: Option<Callback<String>>,
+}
+
+pub struct RawUiPinned {
+    pinned: Rc<Vec<String>>,
+    revealed: Option<String>,
+    // The raw nodes only read their value when created, a randomized tree is built again
+    revision: usize,
+}
+
+impl Component for RawUiPinned {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        RawUiPinned {
+            pinned: Rc::new(LocalStorage::get(ctx.props().pins_key).unwrap_or_default()),
+            revealed: None,
+            revision: 0,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Toggle(path) => {
+                let pinned = Rc::make_mut(&mut self.pinned);
+                match pinned.iter().position(|pin| *pin == path) {
+                    Some(idx) => {
+                        pinned.remove(idx);
+                    }
+                    None => {
+                        pinned.push(path);
+                        pinned.sort();
+                    }
+                }
+                let _ = LocalStorage::set(ctx.props().pins_key, &*self.pinned);
+                true
+            }
+            Msg::Randomize(path) => {
+                if let Some(ref onrandomize) = ctx.props().onrandomize {
+                    onrandomize.emit(path.clone());
+                    document::edited(ctx);
+                    self.revision += 1;
+                    ctx.link().send_message(Msg::Jump(path));
+                }
+                false
+            }
+            Msg::Jump(path) => {
+                self.revealed = Some(path.clone());
+
+                // The nodes opened by the reveal are only there once rendered
+                ctx.link().send_future(async {
+                    TimeoutFuture::new(0).await;
+                    Msg::ScrollTo(path)
+                });
+                true
+            }
+            Msg::ScrollTo(path) => {
+                if let Some(element) = utils::document().get_element_by_id(&node_id(&path)) {
+                    element.scroll_into_view();
+                }
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let pins = RawUiPins {
+            path: String::new(),
+            pinned: Rc::clone(&self.pinned),
+            revealed: self.revealed.clone(),
+            toggle: link.callback(Msg::Toggle),
+            guids: Rc::clone(&ctx.props().guids),
+            jump: link.callback(Msg::Jump),
+            randomize: ctx.props().onrandomize.as_ref().map(|_| link.callback(Msg::Randomize)),
+        };
+
+        let pinned = self.pinned.iter().map(|path| {
+            let (jump, unpin) = (path.clone(), path.clone());
+            html! {
+                <div class="flex gap-1">
+                    <div class="py-px">
+                        <a class={classes![
+                                "rounded-none",
+                                "select-none",
+                                "hover:bg-theme-hover",
+                                "active:bg-theme-active",
+                                "bg-theme-bg",
+                                "px-1",
+                                "py-0",
+                                "cursor-pointer",
+                            ]}
+                            onclick={link.callback(move |_| Msg::Toggle(unpin.clone()))}
+                        >
+                            {"unpin"}
+                        </a>
+                    </div>
+                    <button class="rounded-none hover:bg-theme-hover active:bg-theme-active px-1 flex-auto text-left"
+                        title="Scroll to this node"
+                        onclick={link.callback(move |_| Msg::Jump(jump.clone()))}
+                    >
+                        { path }
+                    </button>
+                </div>
+            }
+        });
+        let empty = self.pinned.is_empty().then(|| {
+            html! {
+                <p>{ "Hover a node and click `pin` to keep it opened from one session to the next." }</p>
+            }
+        });
+
+        html! {
+            <div class="flex flex-col gap-1">
+                <Table title="Pinned" opened={!self.pinned.is_empty()}>
+                    { for pinned }
+                    { for empty }
+                </Table>
+                <div key={self.revision} class="contents">
+                    { pins.provide(html! { for ctx.props().children.iter() }) }
+                </div>
+            </div>
+        }
+    }
+}
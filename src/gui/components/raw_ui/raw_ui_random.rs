@@ -0,0 +1,289 @@
+use std::fmt::Display;
+
+use js_sys::Date;
+use serde::{de::DeserializeOwned, Serialize};
+use yew::Callback;
+
+use super::{child_path, IndexMapKeyType};
+use crate::{
+    gui::{
+        components::NumberType,
+        raw_ui::{RawUi, RawUiRenderer},
+    },
+    save_data::{shared::appearance::LinearColor, Dummy, Guid, RcCell, RcRef},
+    services::logger,
+    unreal::fuzz::Mutator,
+};
+
+/// A value changed by [`randomize`], by its path in the raw tree
+pub struct Randomized {
+    pub path: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Gives the numbers, booleans and enums of the node at `path` and of everything under it a
+/// random value, to see how the game copes with them. The numbers get the edge cases more
+/// often than not, the enums stay within their variants.
+/// The same seed gives the same values.
+pub fn randomize<T: RawUi>(root: &T, path: &str, seed: u64) -> Vec<Randomized> {
+    let tree = root.render(&mut Randomizer, "", true);
+    let mut walker = Walker { target: path, mutator: Mutator::new(seed), randomized: Vec::new() };
+    walker.walk(tree, "", false);
+    walker.randomized
+}
+
+/// Randomizes the node of `root` at the path it is given, with every value changed in the logs
+pub fn randomizer<T: RawUi>(root: T) -> Callback<String> {
+    Callback::from(move |path: String| {
+        let seed = Date::now() as u64;
+        let randomized = randomize(&root, &path, seed);
+        logger::info(format!(
+            "Randomized {} values of `{}` with the seed {}",
+            randomized.len(),
+            path,
+            seed
+        ));
+        for Randomized { path, before, after } in randomized {
+            logger::info(format!("{}: {} -> {}", path, before, after));
+        }
+    })
+}
+
+type Randomize = Box<dyn FnOnce(&mut Mutator) -> (String, String)>;
+
+// The raw tree without the html, with what randomizes each value
+enum RandomNode {
+    Leaf,
+    Value { label: String, randomize: Randomize },
+    Branch { label: Option<String>, children: Vec<RandomNode> },
+}
+
+struct Walker<'a> {
+    target: &'a str,
+    mutator: Mutator,
+    randomized: Vec<Randomized>,
+}
+
+impl Walker<'_> {
+    fn walk(&mut self, node: RandomNode, path: &str, is_inside: bool) {
+        match node {
+            RandomNode::Leaf => (),
+            RandomNode::Value { label, randomize } => {
+                let path = child_path(path, &label);
+                if is_inside || path == self.target {
+                    let (before, after) = randomize(&mut self.mutator);
+                    self.randomized.push(Randomized { path, before, after });
+                }
+            }
+            RandomNode::Branch { label, children } => {
+                // Options aren't a level of the path, their content takes their label
+                let path = match label {
+                    Some(label) => child_path(path, &label),
+                    None => path.to_owned(),
+                };
+                let is_inside = is_inside || path == self.target;
+                for child in children {
+                    self.walk(child, &path, is_inside);
+                }
+            }
+        }
+    }
+}
+
+fn random_i32(mutator: &mut Mutator) -> i32 {
+    match mutator.below(6) {
+        0 => 0,
+        1 => 1,
+        2 => -1,
+        3 => i32::MAX,
+        4 => i32::MIN,
+        _ => mutator.next_u64() as i32,
+    }
+}
+
+fn random_u8(mutator: &mut Mutator) -> u8 {
+    match mutator.below(4) {
+        0 => 0,
+        1 => 1,
+        2 => u8::MAX,
+        _ => mutator.next_u64() as u8,
+    }
+}
+
+fn random_f32(mutator: &mut Mutator) -> f32 {
+    match mutator.below(6) {
+        0 => 0.0,
+        1 => 1.0,
+        2 => -1.0,
+        3 => f32::MAX,
+        4 => f32::MIN,
+        _ => (mutator.next_u64() % 2_000_001) as f32 / 1000.0 - 1000.0,
+    }
+}
+
+struct Randomizer;
+
+impl RawUiRenderer for Randomizer {
+    type Output = RandomNode;
+
+    fn number(&mut self, label: &str, value: NumberType) -> RandomNode {
+        let randomize: Randomize = match value {
+            NumberType::Byte(value) => Box::new(move |mutator| {
+                let before = value.get();
+                value.set(random_u8(mutator));
+                (before.to_string(), value.get().to_string())
+            }),
+            NumberType::Int(value) => Box::new(move |mutator| {
+                let before = value.get();
+                value.set(random_i32(mutator));
+                (before.to_string(), value.get().to_string())
+            }),
+            NumberType::Float(value) => Box::new(move |mutator| {
+                let before = value.get();
+                value.set(random_f32(mutator));
+                (before.to_string(), value.get().to_string())
+            }),
+        };
+        RandomNode::Value { label: label.to_owned(), randomize }
+    }
+
+    fn check_box(&mut self, label: &str, value: &RcCell<bool>) -> RandomNode {
+        let value = RcCell::clone(value);
+        let randomize: Randomize = Box::new(move |mutator| {
+            let before = value.get();
+            value.set(mutator.below(2) == 1);
+            (before.to_string(), value.get().to_string())
+        });
+        RandomNode::Value { label: label.to_owned(), randomize }
+    }
+
+    fn text(&mut self, _: &str, _: &RcRef<String>) -> RandomNode {
+        RandomNode::Leaf
+    }
+
+    fn string_ref(&mut self, label: &str, value: &RcCell<i32>) -> RandomNode {
+        self.number(label, NumberType::Int(RcCell::clone(value)))
+    }
+
+    fn guid(&mut self, _: &str, _: &RcRef<Guid>) -> RandomNode {
+        RandomNode::Leaf
+    }
+
+    fn color(&mut self, _: &str, _: &RcRef<LinearColor>) -> RandomNode {
+        RandomNode::Leaf
+    }
+
+    fn bytes<const LEN: usize>(
+        &mut self, _: &str, _: &'static str, _: &RcRef<Dummy<LEN>>,
+    ) -> RandomNode {
+        RandomNode::Leaf
+    }
+
+    fn enumeration<E>(
+        &mut self, label: &str, variants: &'static [&'static str], value: &RcRef<E>,
+    ) -> RandomNode
+    where
+        E: From<usize> + Into<usize> + Clone + 'static,
+    {
+        let value = RcRef::clone(value);
+        let randomize: Randomize = Box::new(move |mutator| {
+            let before: usize = value.borrow().clone().into();
+            let after = mutator.below(variants.len());
+            *value.borrow_mut() = E::from(after);
+            let name = |idx: usize| variants.get(idx).copied().unwrap_or_default().to_owned();
+            (name(before), name(after))
+        });
+        RandomNode::Value { label: label.to_owned(), randomize }
+    }
+
+    fn option<T>(&mut self, label: &str, option: &RcRef<Option<T>>) -> RandomNode
+    where
+        T: RawUi,
+    {
+        let children = option.borrow().iter().map(|some| some.render(self, label, false)).collect();
+        RandomNode::Branch { label: None, children }
+    }
+
+    fn vec<T>(&mut self, label: &str, vec: &RcRef<Vec<T>>, _: bool) -> RandomNode
+    where
+        T: RawUi + Default + Display + Serialize + DeserializeOwned,
+    {
+        let children = vec
+            .borrow()
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| {
+                let label = item.to_string();
+                let label = if label.is_empty() { idx.to_string() } else { label };
+                item.render(self, &label, false)
+            })
+            .collect();
+        RandomNode::Branch { label: Some(label.to_owned()), children }
+    }
+
+    fn index_map<V>(&mut self, label: &str, index_map: IndexMapKeyType<V>) -> RandomNode
+    where
+        V: RawUi + Default + Serialize + DeserializeOwned,
+    {
+        let mut entry = |key: String, value: &V| RandomNode::Branch {
+            label: Some(key),
+            children: vec![value.render(self, "Value", false)],
+        };
+        let children = match index_map {
+            IndexMapKeyType::I32(index_map) => index_map
+                .borrow()
+                .iter()
+                .map(|(key, value)| entry(key.to_string(), value))
+                .collect(),
+            IndexMapKeyType::String(index_map) => {
+                index_map.borrow().iter().map(|(key, value)| entry(key.clone(), value)).collect()
+            }
+        };
+        RandomNode::Branch { label: Some(label.to_owned()), children }
+    }
+
+    fn structure(&mut self, label: &str, _: bool, children: Vec<RandomNode>) -> RandomNode {
+        RandomNode::Branch { label: Some(label.to_owned()), children }
+    }
+
+    fn root(&mut self, children: Vec<RandomNode>) -> RandomNode {
+        RandomNode::Branch { label: None, children }
+    }
+
+    fn opaque<T: RawUi>(&mut self, _: &str, _: Option<&T>) -> RandomNode {
+        RandomNode::Leaf
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_2::Me2LeSaveGame, unreal};
+
+    #[test]
+    fn randomize_a_structure() -> Result<()> {
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let save_game: RcRef<Me2LeSaveGame> = unreal::Deserializer::from_bytes(&input)?;
+
+        let randomized = randomize(&save_game, "Player", 42);
+        assert!(!randomized.is_empty());
+        assert!(randomized.iter().all(|value| value.path.starts_with("Player > ")));
+
+        // Replayed from its seed
+        let save_game: RcRef<Me2LeSaveGame> = unreal::Deserializer::from_bytes(&input)?;
+        let again = randomize(&save_game, "Player", 42);
+        let afters =
+            |values: &[Randomized]| values.iter().map(|v| v.after.clone()).collect::<Vec<_>>();
+        assert_eq!(afters(&randomized), afters(&again));
+
+        // Nothing outside of the tree
+        assert!(randomize(&save_game, "Nowhere", 42).is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+use std::fmt::Display;
+
+use anyhow::{bail, Context, Result};
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{child_path, IndexMapKeyType};
+use crate::{
+    gui::{
+        components::{evaluate_expression, NumberType},
+        raw_ui::{RawUi, RawUiRenderer},
+    },
+    save_data::{shared::appearance::LinearColor, Dummy, Guid, RcCell, RcRef},
+};
+
+/// Sets the value at `path` in the raw tree of `root` from its text, the numbers, booleans,
+/// texts and enums the Raw Data tab edits. Returns the value it had and the one it has now.
+pub fn set_raw_value<T: RawUi>(root: &T, path: &str, value: &str) -> Result<(String, String)> {
+    let tree = root.render(&mut Setter, "", true);
+    match find(tree, "", path) {
+        Some((_, set)) => {
+            set(value.trim()).with_context(|| format!("Invalid value for `{}`", path))
+        }
+        None => bail!("No value at `{}`", path),
+    }
+}
+
+/// The value at `path` in the raw tree of `root`, as text like `set_raw_value` takes it
+pub fn get_raw_value<T: RawUi>(root: &T, path: &str) -> Result<String> {
+    let tree = root.render(&mut Setter, "", true);
+    match find(tree, "", path) {
+        Some((get, _)) => Ok(get()),
+        None => bail!("No value at `{}`", path),
+    }
+}
+
+/// Sets a number from what its input would accept, `current + 1000` included. Returns the value
+/// it had and the one it has now.
+pub fn set_number(value: &NumberType, text: &str) -> Result<(String, String)> {
+    let before = display_number(value);
+    let number = evaluate_expression(text, value.get())
+        .with_context(|| format!("expected a number, got `{}`", text))?;
+    value.set(number);
+    Ok((before, display_number(value)))
+}
+
+fn display_number(value: &NumberType) -> String {
+    match value {
+        NumberType::Byte(byte) => byte.get().to_string(),
+        NumberType::Int(integer) => integer.get().to_string(),
+        NumberType::Float(float) => float.get().to_string(),
+    }
+}
+
+type Get = Box<dyn FnOnce() -> String>;
+type Set = Box<dyn FnOnce(&str) -> Result<(String, String)>>;
+
+// The raw tree without the html, with what reads and sets each value
+enum SetNode {
+    Leaf,
+    Value { label: String, get: Get, set: Set },
+    Branch { label: Option<String>, children: Vec<SetNode> },
+}
+
+fn find(node: SetNode, path: &str, target: &str) -> Option<(Get, Set)> {
+    match node {
+        SetNode::Leaf => None,
+        SetNode::Value { label, get, set } => {
+            (child_path(path, &label) == target).then(|| (get, set))
+        }
+        SetNode::Branch { label, children } => {
+            // Options aren't a level of the path, their content takes their label
+            let path = match label {
+                Some(label) => child_path(path, &label),
+                None => path.to_owned(),
+            };
+            if !target.starts_with(&path) {
+                return None;
+            }
+            children.into_iter().find_map(|child| find(child, &path, target))
+        }
+    }
+}
+
+struct Setter;
+
+impl RawUiRenderer for Setter {
+    type Output = SetNode;
+
+    fn number(&mut self, label: &str, value: NumberType) -> SetNode {
+        let get: Get = {
+            let value = value.clone();
+            Box::new(move || display_number(&value))
+        };
+        let set: Set = Box::new(move |text| set_number(&value, text));
+        SetNode::Value { label: label.to_owned(), get, set }
+    }
+
+    fn check_box(&mut self, label: &str, value: &RcCell<bool>) -> SetNode {
+        let value = RcCell::clone(value);
+        let get: Get = {
+            let value = RcCell::clone(&value);
+            Box::new(move || value.get().to_string())
+        };
+        let set: Set = Box::new(move |text| {
+            let before = value.get();
+            value.set(text.parse()?);
+            Ok((before.to_string(), value.get().to_string()))
+        });
+        SetNode::Value { label: label.to_owned(), get, set }
+    }
+
+    fn text(&mut self, label: &str, value: &RcRef<String>) -> SetNode {
+        let value = RcRef::clone(value);
+        let get: Get = {
+            let value = RcRef::clone(&value);
+            Box::new(move || value.borrow().clone())
+        };
+        let set: Set = Box::new(move |text| {
+            let before = std::mem::replace(&mut *value.borrow_mut(), text.to_owned());
+            Ok((before, text.to_owned()))
+        });
+        SetNode::Value { label: label.to_owned(), get, set }
+    }
+
+    fn string_ref(&mut self, label: &str, value: &RcCell<i32>) -> SetNode {
+        self.number(label, NumberType::Int(RcCell::clone(value)))
+    }
+
+    fn guid(&mut self, _: &str, _: &RcRef<Guid>) -> SetNode {
+        SetNode::Leaf
+    }
+
+    fn color(&mut self, _: &str, _: &RcRef<LinearColor>) -> SetNode {
+        SetNode::Leaf
+    }
+
+    fn bytes<const LEN: usize>(
+        &mut self, _: &str, _: &'static str, _: &RcRef<Dummy<LEN>>,
+    ) -> SetNode {
+        SetNode::Leaf
+    }
+
+    fn enumeration<E>(
+        &mut self, label: &str, variants: &'static [&'static str], value: &RcRef<E>,
+    ) -> SetNode
+    where
+        E: From<usize> + Into<usize> + Clone + 'static,
+    {
+        let value = RcRef::clone(value);
+        let get: Get = {
+            let value = RcRef::clone(&value);
+            Box::new(move || {
+                let idx: usize = value.borrow().clone().into();
+                variants.get(idx).copied().unwrap_or_default().to_owned()
+            })
+        };
+        // By the name of the variant, whatever the case, or its index
+        let set: Set = Box::new(move |text| {
+            let idx = variants
+                .iter()
+                .position(|variant| variant.eq_ignore_ascii_case(text))
+                .or_else(|| text.parse().ok().filter(|&idx: &usize| idx < variants.len()))
+                .with_context(|| format!("expected one of {}", variants.join(", ")))?;
+            let before: usize = value.borrow().clone().into();
+            *value.borrow_mut() = E::from(idx);
+            let before = variants.get(before).copied().unwrap_or_default();
+            Ok((before.to_owned(), variants[idx].to_owned()))
+        });
+        SetNode::Value { label: label.to_owned(), get, set }
+    }
+
+    fn option<T>(&mut self, label: &str, option: &RcRef<Option<T>>) -> SetNode
+    where
+        T: RawUi,
+    {
+        let children = option.borrow().iter().map(|some| some.render(self, label, false)).collect();
+        SetNode::Branch { label: None, children }
+    }
+
+    fn vec<T>(&mut self, label: &str, vec: &RcRef<Vec<T>>, _: bool) -> SetNode
+    where
+        T: RawUi + Default + Display + Serialize + DeserializeOwned,
+    {
+        let children = vec
+            .borrow()
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| {
+                let label = item.to_string();
+                let label = if label.is_empty() { idx.to_string() } else { label };
+                item.render(self, &label, false)
+            })
+            .collect();
+        SetNode::Branch { label: Some(label.to_owned()), children }
+    }
+
+    fn index_map<V>(&mut self, label: &str, index_map: IndexMapKeyType<V>) -> SetNode
+    where
+        V: RawUi + Default + Serialize + DeserializeOwned,
+    {
+        let mut entry = |key: String, value: &V| SetNode::Branch {
+            label: Some(key),
+            children: vec![value.render(self, "Value", false)],
+        };
+        let children = match index_map {
+            IndexMapKeyType::I32(index_map) => index_map
+                .borrow()
+                .iter()
+                .map(|(key, value)| entry(key.to_string(), value))
+                .collect(),
+            IndexMapKeyType::String(index_map) => {
+                index_map.borrow().iter().map(|(key, value)| entry(key.clone(), value)).collect()
+            }
+        };
+        SetNode::Branch { label: Some(label.to_owned()), children }
+    }
+
+    fn structure(&mut self, label: &str, _: bool, children: Vec<SetNode>) -> SetNode {
+        SetNode::Branch { label: Some(label.to_owned()), children }
+    }
+
+    fn root(&mut self, children: Vec<SetNode>) -> SetNode {
+        SetNode::Branch { label: None, children }
+    }
+
+    fn opaque<T: RawUi>(&mut self, _: &str, _: Option<&T>) -> SetNode {
+        SetNode::Leaf
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_2::Me2LeSaveGame, unreal};
+
+    #[test]
+    fn set_by_path() -> Result<()> {
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let save_game: RcRef<Me2LeSaveGame> = unreal::Deserializer::from_bytes(&input)?;
+
+        let before = save_game.borrow().player().credits();
+        let (old, new) = set_raw_value(&save_game, "Player > Credits", " 1234 ")?;
+        assert_eq!((old, new.as_str()), (before.to_string(), "1234"));
+        assert_eq!(save_game.borrow().player().credits(), 1234);
+
+        // Numbers as their input takes them
+        set_raw_value(&save_game, "Player > Credits", "current + 1000")?;
+        assert_eq!(save_game.borrow().player().credits(), 2234);
+
+        // Enums by name
+        let (_, new) = set_raw_value(&save_game, "Difficulty", "insanity")?;
+        assert_eq!(new, "Insanity");
+        assert!(set_raw_value(&save_game, "Difficulty", "Nightmare").is_err());
+
+        assert!(set_raw_value(&save_game, "Player > Credits", "a lot").is_err());
+        assert!(set_raw_value(&save_game, "Nowhere", "1").is_err());
+
+        // Read back as they are set
+        assert_eq!(get_raw_value(&save_game, "Player > Credits")?, "2234");
+        assert_eq!(get_raw_value(&save_game, "Difficulty")?, "Insanity");
+        assert!(get_raw_value(&save_game, "Nowhere").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{InputNumber, NumberType},
+    save_data::RcCell,
+    services::tlk::StringTable,
+};
+
+pub enum Msg {
+    StringTableLoaded(StringTable),
+    Changed,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub label: String,
+    pub value: RcCell<i32>,
+}
+
+pub struct RawUiStringRef {
+    _string_table_handle: ContextHandle<StringTable>,
+    string_table: StringTable,
+}
+
+impl Component for RawUiStringRef {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (string_table, _string_table_handle) = ctx
+            .link()
+            .context::<StringTable>(ctx.link().callback(Msg::StringTableLoaded))
+            .expect("no string table provider");
+
+        RawUiStringRef { _string_table_handle, string_table }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::StringTableLoaded(string_table) => {
+                self.string_table = string_table;
+                true
+            }
+            Msg::Changed => true,
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let Props { label, value } = ctx.props();
+        let text = self.string_table.resolve(value.get());
+
+        html! {
+            <div class="flex items-center gap-1 min-w-0">
+                <InputNumber
+                    label={label.clone()}
+                    value={NumberType::Int(RcCell::clone(value))}
+                    onchange={ctx.link().callback(|_| Msg::Changed)}
+                />
+                <span class="truncate opacity-60" title={text.clone()}>{ format!("({})", text) }</span>
+            </div>
+        }
+    }
+}
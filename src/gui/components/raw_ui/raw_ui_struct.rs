@@ -1,9 +1,11 @@
-use yew::prelude::*;
+use yew::{context::ContextHandle, prelude::*};
 
+use super::RawUiPins;
 use crate::gui::components::Table;
 
 pub enum Msg {
     Toggle,
+    Pins(RawUiPins),
 }
 
 #[derive(Properties, PartialEq)]
@@ -16,6 +18,7 @@ pub struct Props {
 
 pub struct RawUiStruct {
     opened: bool,
+    pins: Option<(RawUiPins, ContextHandle<RawUiPins>)>,
 }
 
 impl Component for RawUiStruct {
@@ -23,21 +26,33 @@ impl Component for RawUiStruct {
     type Properties = Props;
 
     fn create(ctx: &Context<Self>) -> Self {
-        RawUiStruct { opened: ctx.props().opened }
+        let pins = ctx.link().context::<RawUiPins>(ctx.link().callback(Msg::Pins));
+        let pinned = pins.as_ref().map(|(pins, _)| pins.child(&ctx.props().label).is_opened());
+        RawUiStruct { opened: ctx.props().opened || pinned.unwrap_or(false), pins }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::Toggle => {
                 self.opened = !self.opened;
                 true
             }
+            Msg::Pins(new_pins) => {
+                if new_pins.child(&ctx.props().label).is_opened() {
+                    self.opened = true;
+                }
+                if let Some((ref mut pins, _)) = self.pins {
+                    *pins = new_pins;
+                }
+                true
+            }
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let Props { ref label, ref children, .. } = ctx.props();
         let chevron = if self.opened { "table-chevron-down" } else { "table-chevron-right" };
+        let pins = self.pins.as_ref().map(|(pins, _)| pins.child(label));
 
         let content = self.opened.then(|| {
             html! {
@@ -49,9 +64,16 @@ impl Component for RawUiStruct {
             }
         });
 
+        let toggle = pins.as_ref().map(RawUiPins::view_toggle);
+        let randomize = pins.as_ref().and_then(RawUiPins::view_randomize);
+        let content = content.map(|content| match pins {
+            Some(ref pins) => pins.provide(content),
+            None => content,
+        });
+
         html! {
-            <div class="flex-auto flex flex-col">
-                <div class="p-px">
+            <div class="flex-auto flex flex-col" id={pins.as_ref().map(RawUiPins::id)}>
+                <div class="p-px flex gap-1 group">
                     <button
                         class={classes![
                             "rounded-none",
@@ -67,6 +89,8 @@ impl Component for RawUiStruct {
                     >
                         { label }
                     </button>
+                    { for randomize }
+                    { for toggle }
                 </div>
                 { for content }
             </div>
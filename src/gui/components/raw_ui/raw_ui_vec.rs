@@ -5,23 +5,28 @@ use std::{
     marker::PhantomData,
 };
 
-use yew::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use yew::{context::ContextHandle, prelude::*};
 
+use super::{deep_clone, emit_error, RawUiPins};
 use crate::{
     gui::{components::Table, raw_ui::RawUi},
     save_data::RcRef,
+    services::{document, reveal},
 };
 
 pub enum Msg {
     Toggle,
     Add,
+    Duplicate(usize),
     Remove(usize),
+    Pins(RawUiPins),
 }
 
 #[derive(Properties, PartialEq)]
 pub struct Props<T>
 where
-    T: RawUi + Default + Display,
+    T: RawUi + Default + Display + Serialize + DeserializeOwned,
 {
     pub label: String,
     pub vec: RcRef<Vec<T>>,
@@ -31,7 +36,7 @@ where
 
 impl<T> Props<T>
 where
-    T: RawUi + Default + Display,
+    T: RawUi + Default + Display + Serialize + DeserializeOwned,
 {
     fn vec(&self) -> Ref<'_, Vec<T>> {
         self.vec.borrow()
@@ -44,22 +49,36 @@ where
 
 pub struct RawUiVec<T>
 where
-    T: RawUi + Default + Display,
+    T: RawUi + Default + Display + Serialize + DeserializeOwned,
 {
     _marker: PhantomData<T>,
     opened: bool,
     new_item_idx: usize,
+    // Added or duplicated, to be revealed once rendered
+    new_item_ref: NodeRef,
+    is_revealing: bool,
+    pins: Option<(RawUiPins, ContextHandle<RawUiPins>)>,
 }
 
 impl<T> Component for RawUiVec<T>
 where
-    T: RawUi + Default + Display,
+    T: RawUi + Default + Display + Serialize + DeserializeOwned,
 {
     type Message = Msg;
     type Properties = Props<T>;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        RawUiVec { _marker: PhantomData, opened: false, new_item_idx: 0 }
+    fn create(ctx: &Context<Self>) -> Self {
+        let pins = ctx.link().context::<RawUiPins>(ctx.link().callback(Msg::Pins));
+        let opened = pins.as_ref().map(|(pins, _)| pins.child(&ctx.props().label).is_opened());
+        RawUiVec {
+            _marker: PhantomData,
+            opened: opened.unwrap_or(false),
+            // Opened by a pin, no item to open with it
+            new_item_idx: usize::MAX,
+            new_item_ref: NodeRef::default(),
+            is_revealing: false,
+            pins,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -75,19 +94,58 @@ where
             Msg::Add => {
                 // Open added item
                 self.new_item_idx = ctx.props().vec().len();
+                self.is_revealing = true;
 
                 ctx.props().vec_mut().push(Default::default());
+                document::edited(ctx);
                 true
             }
+            Msg::Duplicate(idx) => {
+                let copy = ctx.props().vec().get(idx).map(deep_clone);
+                match copy {
+                    Some(Ok(copy)) => {
+                        // Open the copy, right after the original
+                        self.new_item_idx = idx + 1;
+                        self.is_revealing = true;
+
+                        ctx.props().vec_mut().insert(idx + 1, copy);
+                        document::edited(ctx);
+                        true
+                    }
+                    Some(Err(err)) => {
+                        emit_error(ctx, err);
+                        false
+                    }
+                    None => false,
+                }
+            }
             Msg::Remove(idx) => {
                 ctx.props().vec_mut().remove(idx);
+                document::edited(ctx);
+                true
+            }
+            Msg::Pins(new_pins) => {
+                if new_pins.child(&ctx.props().label).is_opened() {
+                    self.opened = true;
+                }
+                if let Some((ref mut pins, _)) = self.pins {
+                    *pins = new_pins;
+                }
                 true
             }
         }
     }
 
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if self.is_revealing {
+            self.is_revealing = false;
+            reveal::reveal(&self.new_item_ref);
+        }
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
         let chevron = if self.opened { "table-chevron-down" } else { "table-chevron-right" };
+        let pins = self.pins.as_ref().map(|(pins, _)| pins.child(&ctx.props().label));
 
         let content = self
             .opened
@@ -106,13 +164,14 @@ where
                 let items = vec.iter().enumerate().map(|(idx, item)| {
                     let label = item.to_string();
                     let opened = self.new_item_idx == idx;
+                    let node_ref = opened.then(|| self.new_item_ref.clone()).unwrap_or_default();
                     let item = if display_idx || label.is_empty() {
                         item.view_opened(&idx.to_string(), opened)
                     } else {
                         item.view_opened(&label, opened)
                     };
 
-                    let remove = is_editable.then(|| html!{
+                    let edit_button = |text: &'static str, msg: fn(usize) -> Msg| html!{
                         <div class="py-px">
                             <a class={classes![
                                     "rounded-none",
@@ -124,16 +183,19 @@ where
                                     "py-0",
                                     "cursor-pointer",
                                 ]}
-                                onclick={ctx.link().callback(move |_| Msg::Remove(idx))}
+                                onclick={ctx.link().callback(move |_| msg(idx))}
                             >
-                                {"remove"}
+                                { text }
                             </a>
                         </div>
-                    });
+                    };
+                    let remove = is_editable.then(|| edit_button("remove", Msg::Remove));
+                    let duplicate = is_editable.then(|| edit_button("duplicate", Msg::Duplicate));
 
                     html! {
-                        <div class="flex gap-1">
+                        <div class="flex gap-1" ref={node_ref}>
                             { for remove }
+                            { for duplicate }
                             { item }
                         </div>
                     }
@@ -160,9 +222,15 @@ where
                 }
             });
 
+        let toggle = pins.as_ref().map(RawUiPins::view_toggle);
+        let content = content.map(|content| match pins {
+            Some(ref pins) => pins.provide(content),
+            None => content,
+        });
+
         html! {
-            <div class="flex-auto flex flex-col">
-                <div class="p-px">
+            <div class="flex-auto flex flex-col" id={pins.as_ref().map(RawUiPins::id)}>
+                <div class="p-px flex gap-1 group">
                     <button class={classes![
                             "rounded-none",
                             "hover:bg-theme-hover",
@@ -177,6 +245,7 @@ where
                     >
                         { &ctx.props().label }
                     </button>
+                    { for toggle }
                 </div>
                 { for content }
             </div>
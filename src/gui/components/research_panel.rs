@@ -0,0 +1,139 @@
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::Helper,
+    services::save_handler::{Action, SaveHandler},
+};
+
+pub enum Msg {
+    Toggle,
+    SaveHandler(SaveHandler),
+    Start,
+    Stop,
+    Export,
+}
+
+pub struct ResearchPanel {
+    _db_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    is_opened: bool,
+}
+
+impl Component for ResearchPanel {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _db_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+
+        ResearchPanel { _db_handle, save_handler, is_opened: false }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Toggle => {
+                self.is_opened = !self.is_opened;
+                true
+            }
+            Msg::SaveHandler(save_handler) => {
+                self.save_handler = save_handler;
+                true
+            }
+            Msg::Start => {
+                self.save_handler.action(Action::StartResearch);
+                false
+            }
+            Msg::Stop => {
+                self.save_handler.action(Action::StopResearch);
+                false
+            }
+            Msg::Export => {
+                self.save_handler.action(Action::ExportResearch);
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let panel = self.is_opened.then(|| {
+            let content = match self.save_handler.research {
+                None => html! { <>
+                    <p>{ "1. Open the save made right before the mission." }</p>
+                    <div class="flex items-center gap-1">
+                        <button class="button"
+                            disabled={self.save_handler.save_game.is_none()}
+                            onclick={link.callback(|_| Msg::Start)}
+                        >
+                            { "Take a snapshot of its plots" }
+                        </button>
+                        <Helper text=
+                            "The plots are compared with the next save of the same game opened \
+                            or reloaded, the ones that changed are exported in a JSON file to \
+                            complete the plot databases."
+                        />
+                    </div>
+                </> },
+                Some(ref research) => {
+                    let file_name = research
+                        .snapshot_path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    let result = match research.result {
+                        Some(ref result) => html! {
+                            <div class="flex items-center gap-1">
+                                <span>{ format!("{} plots changed", result.changes.len()) }</span>
+                                <button class="button"
+                                    disabled={result.changes.is_empty()}
+                                    onclick={link.callback(|_| Msg::Export)}
+                                >
+                                    { "Export" }
+                                </button>
+                            </div>
+                        },
+                        None => html! {
+                            <p class="text-fg/50">{ "Waiting for the next save..." }</p>
+                        },
+                    };
+                    html! { <>
+                        <p title={research.snapshot_path.display().to_string()}>
+                            { format!("Snapshot of `{}` ({})", file_name, research.game) }
+                        </p>
+                        <p>{ "2. Play the mission in game, then save." }</p>
+                        <p>{ "3. Open or reload the new save, its changes are exported right away." }</p>
+                        { result }
+                        <hr class="border-t border-default-border" />
+                        <button class="button w-12" onclick={link.callback(|_| Msg::Stop)}>
+                            { "Stop" }
+                        </button>
+                    </> }
+                }
+            };
+
+            html! {
+                <div class="fixed bottom-1 left-1 w-[420px] border border-default-border bg-default-bg flex flex-col z-40">
+                    <div class="px-1 bg-theme-tab">{ "Research" }</div>
+                    <div class="p-1 pt-0.5 flex flex-col gap-1">
+                        { content }
+                    </div>
+                </div>
+            }
+        });
+
+        html! {
+            <div class="flex items-center px-1">
+                <button class="button"
+                    title="Find out which plots a mission changes"
+                    onclick={link.callback(|_| Msg::Toggle)}
+                >
+                    { "Research" }
+                </button>
+                { for panel }
+            </div>
+        }
+    }
+}
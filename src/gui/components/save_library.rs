@@ -0,0 +1,372 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use anyhow::{Context as ErrorContext, Error};
+use js_sys::Date;
+use wasm_bindgen::JsValue;
+use yew::prelude::*;
+
+use crate::{
+    gui::components::{CheckBox, Helper, Table},
+    save_data::RcCell,
+    services::rpc::{self, Career, SaveFile, SaveKind},
+};
+
+pub enum Msg {
+    Open,
+    Close,
+    Refresh,
+    Loaded(Vec<Career>),
+    Toggle(PathBuf),
+    SelectOld,
+    ClearSelection,
+    Move(usize, usize, bool),
+    Renumber(usize),
+    Delete,
+    ConfirmDelete,
+    CancelDelete,
+    Error(Error),
+    // Part of it may have been done
+    Failed(Error),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub onerror: Callback<Error>,
+}
+
+pub struct SaveLibrary {
+    careers: Option<Vec<Career>>,
+    selected: HashSet<PathBuf>,
+    confirming_delete: bool,
+}
+
+impl Component for SaveLibrary {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        SaveLibrary { careers: None, selected: HashSet::new(), confirming_delete: false }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Open | Msg::Refresh => {
+                ctx.link().send_future(async {
+                    match rpc::list_careers().await.context("Failed to list the saves") {
+                        Ok(careers) => Msg::Loaded(careers),
+                        Err(err) => Msg::Error(err),
+                    }
+                });
+                false
+            }
+            Msg::Close => {
+                self.careers = None;
+                self.selected.clear();
+                self.confirming_delete = false;
+                true
+            }
+            Msg::Loaded(mut careers) => {
+                careers.iter_mut().for_each(Self::sort_slots);
+                // Whatever has gone in the meantime
+                self.selected.retain(|path| {
+                    careers.iter().flat_map(|career| &career.saves).any(|save| save.path == *path)
+                });
+                self.careers = Some(careers);
+                true
+            }
+            Msg::Toggle(path) => {
+                if !self.selected.remove(&path) {
+                    self.selected.insert(path);
+                }
+                self.confirming_delete = false;
+                true
+            }
+            Msg::SelectOld => {
+                if let Some(ref careers) = self.careers {
+                    self.selected.extend(careers.iter().flat_map(Self::old_saves));
+                }
+                true
+            }
+            Msg::ClearSelection => {
+                self.selected.clear();
+                self.confirming_delete = false;
+                true
+            }
+            Msg::Move(career_idx, idx, up) => {
+                let saves = match self.careers {
+                    Some(ref mut careers) => &mut careers[career_idx].saves,
+                    None => return false,
+                };
+                let other = if up { idx.checked_sub(1) } else { Some(idx + 1) };
+                match other {
+                    Some(other) if saves.get(other).map(Self::is_manual).unwrap_or(false) => {
+                        saves.swap(idx, other);
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            Msg::Renumber(career_idx) => {
+                let saves = match self.careers {
+                    Some(ref careers) => Self::manual_saves(&careers[career_idx])
+                        .map(|save| save.path.clone())
+                        .collect::<Vec<_>>(),
+                    None => return false,
+                };
+                ctx.link().send_future(async {
+                    match rpc::renumber_saves(saves).await.context("Failed to renumber the saves") {
+                        Ok(()) => Msg::Refresh,
+                        Err(err) => Msg::Failed(err),
+                    }
+                });
+                false
+            }
+            Msg::Delete => {
+                self.confirming_delete = !self.selected.is_empty();
+                true
+            }
+            Msg::ConfirmDelete => {
+                let saves = self.selected.drain().collect::<Vec<_>>();
+                self.confirming_delete = false;
+                ctx.link().send_future(async {
+                    match rpc::delete_saves(saves).await.context("Failed to delete the saves") {
+                        Ok(()) => Msg::Refresh,
+                        Err(err) => Msg::Failed(err),
+                    }
+                });
+                true
+            }
+            Msg::CancelDelete => {
+                self.confirming_delete = false;
+                true
+            }
+            Msg::Error(err) => {
+                ctx.props().onerror.emit(err);
+                false
+            }
+            Msg::Failed(err) => {
+                ctx.props().onerror.emit(err);
+                ctx.link().send_message(Msg::Refresh);
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let library = self.careers.as_ref().map(|careers| {
+            let total: u64 = careers.iter().map(|career| career.size).sum();
+            let empty = careers.is_empty().then(|| {
+                html! { <p>{ "No save found in the BioWare documents folder." }</p> }
+            });
+            let careers = careers
+                .iter()
+                .enumerate()
+                .map(|(career_idx, career)| self.view_career(ctx, career_idx, career));
+
+            html! {
+                <div class="fixed top-7 left-0 w-screen h-[calc(100vh-28px)] grid place-content-center bg-fg/30 z-50">
+                    <div class="border border-default-border bg-default-bg w-[80vw] flex flex-col">
+                        <div class="px-1 bg-theme-tab">{ "Saves" }</div>
+                        <div class="p-1 pt-0.5 flex flex-col gap-1">
+                            <div class="h-[60vh] overflow-auto flex flex-col gap-1">
+                                { for careers }
+                                { for empty }
+                            </div>
+                            <hr class="border-t border-default-border" />
+                            { self.view_actions(ctx, total) }
+                            <div class="flex gap-2">
+                                <button class="button" onclick={link.callback(|_| Msg::Refresh)}>
+                                    { "Refresh" }
+                                </button>
+                                <button class="button" onclick={link.callback(|_| Msg::Close)}>
+                                    { "Close" }
+                                </button>
+                            </div>
+                        </div>
+                    </div>
+                </div>
+            }
+        });
+
+        html! {
+            <div class="flex items-center px-1">
+                <button class="button"
+                    title="Clean up and renumber the saves of every career"
+                    onclick={link.callback(|_| Msg::Open)}
+                >
+                    { "Saves" }
+                </button>
+                { for library }
+            </div>
+        }
+    }
+}
+
+impl SaveLibrary {
+    fn view_career(&self, ctx: &Context<Self>, career_idx: usize, career: &Career) -> Html {
+        let link = ctx.link();
+        let manual_count = Self::manual_saves(career).count();
+
+        let saves = career.saves.iter().enumerate().map(|(idx, save)| {
+            let SaveFile { path, kind, size, modified } = save;
+            let name = path.file_name().map(|name| name.to_string_lossy().into_owned());
+            let kind = match kind {
+                SaveKind::Auto => "Auto",
+                SaveKind::Quick => "Quick",
+                SaveKind::Manual(_) => "Manual",
+                SaveKind::Other => "Other",
+            };
+            let onchange = {
+                let path = path.clone();
+                link.callback(move |_| Msg::Toggle(path.clone()))
+            };
+            let move_buttons = Self::is_manual(save).then(|| {
+                html! { <>
+                    <button class="button w-6" title="Move up" disabled={idx == 0}
+                        onclick={link.callback(move |_| Msg::Move(career_idx, idx, true))}
+                    >
+                        { "▲" }
+                    </button>
+                    <button class="button w-6" title="Move down" disabled={idx + 1 >= manual_count}
+                        onclick={link.callback(move |_| Msg::Move(career_idx, idx, false))}
+                    >
+                        { "▼" }
+                    </button>
+                </> }
+            });
+
+            html! {
+                <div class="flex items-center gap-2">
+                    <div class="flex-auto">
+                        <CheckBox
+                            label={name.unwrap_or_default()}
+                            value={RcCell::new(self.selected.contains(path))}
+                            {onchange}
+                        />
+                    </div>
+                    <span class="w-14 text-fg/50">{ kind }</span>
+                    <span class="w-20 text-right">{ Self::format_size(*size) }</span>
+                    <span class="w-44 text-right">{ Self::format_date(*modified) }</span>
+                    <div class="w-14 flex gap-px">{ for move_buttons }</div>
+                </div>
+            }
+        });
+
+        let renumber = Self::needs_renumber(career).then(|| {
+            html! {
+                <div class="flex items-center gap-1">
+                    <button class="button"
+                        onclick={link.callback(move |_| Msg::Renumber(career_idx))}
+                    >
+                        { "Renumber slots" }
+                    </button>
+                    <Helper text=
+                        "Renames the manual saves `Save_0001`, `Save_0002`... in the order above, \
+                        without gaps. Notes follow their save."
+                    />
+                </div>
+            }
+        });
+
+        let title =
+            format!("{} - {} ({})", career.game, career.name, Self::format_size(career.size));
+        html! {
+            <Table {title}>
+                { for saves }
+                { for renumber }
+            </Table>
+        }
+    }
+
+    fn view_actions(&self, ctx: &Context<Self>, total: u64) -> Html {
+        let link = ctx.link();
+        let selected = self.selected.len();
+
+        let delete = if self.confirming_delete {
+            html! { <>
+                <span>{ format!("Delete {} saves for good ?", selected) }</span>
+                <button class="button" onclick={link.callback(|_| Msg::ConfirmDelete)}>
+                    { "Delete" }
+                </button>
+                <button class="button" onclick={link.callback(|_| Msg::CancelDelete)}>
+                    { "Cancel" }
+                </button>
+            </> }
+        } else {
+            html! {
+                <button class="button" disabled={selected == 0}
+                    onclick={link.callback(|_| Msg::Delete)}
+                >
+                    { format!("Delete selected ({})", selected) }
+                </button>
+            }
+        };
+
+        html! {
+            <div class="flex items-center gap-2">
+                <button class="button" onclick={link.callback(|_| Msg::SelectOld)}>
+                    { "Select old auto / quick saves" }
+                </button>
+                <Helper text=
+                    "Selects the auto and quick saves that aren't the latest save of their career, \
+                    the one the game resumes."
+                />
+                <button class="button" onclick={link.callback(|_| Msg::ClearSelection)}>
+                    { "Clear selection" }
+                </button>
+                { delete }
+                <span class="flex-auto" />
+                <span>{ format!("Total: {}", Self::format_size(total)) }</span>
+            </div>
+        }
+    }
+
+    fn is_manual(save: &SaveFile) -> bool {
+        matches!(save.kind, SaveKind::Manual(_))
+    }
+
+    fn manual_saves(career: &Career) -> impl Iterator<Item = &SaveFile> {
+        career.saves.iter().filter(|save| Self::is_manual(save))
+    }
+
+    // Manual saves by slot first, the others newest first as given
+    fn sort_slots(career: &mut Career) {
+        career.saves.sort_by_key(|save| match save.kind {
+            SaveKind::Manual(number) => (0, number),
+            _ => (1, 0),
+        });
+    }
+
+    // Gaps or another order than the slots
+    fn needs_renumber(career: &Career) -> bool {
+        Self::manual_saves(career).zip(1..).any(
+            |(save, expected)| !matches!(save.kind, SaveKind::Manual(number) if number == expected),
+        )
+    }
+
+    fn old_saves(career: &Career) -> impl Iterator<Item = PathBuf> + '_ {
+        let latest = career.saves.iter().map(|save| save.modified).fold(f64::MIN, f64::max);
+        career
+            .saves
+            .iter()
+            .filter(move |save| {
+                matches!(save.kind, SaveKind::Auto | SaveKind::Quick) && save.modified < latest
+            })
+            .map(|save| save.path.clone())
+    }
+
+    pub(super) fn format_size(size: u64) -> String {
+        match size {
+            size if size >= 1 << 20 => format!("{:.1} MB", size as f64 / (1 << 20) as f64),
+            size if size >= 1 << 10 => format!("{:.1} KB", size as f64 / (1 << 10) as f64),
+            size => format!("{} B", size),
+        }
+    }
+
+    pub(super) fn format_date(millis: f64) -> String {
+        Date::new(&JsValue::from_f64(millis))
+            .to_locale_string("default", &JsValue::UNDEFINED)
+            .into()
+    }
+}
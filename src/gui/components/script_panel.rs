@@ -0,0 +1,169 @@
+use std::rc::Rc;
+
+use gloo::storage::{LocalStorage, Storage};
+use web_sys::HtmlTextAreaElement;
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::Helper,
+    services::{
+        save_handler::{Action, SaveHandler},
+        script::ScriptEdit,
+    },
+};
+
+const SCRIPT_KEY: &str = "script";
+// A range of plots makes as many edits, the rest is only counted
+const MAX_SHOWN_EDITS: usize = 200;
+
+pub enum Msg {
+    Toggle,
+    SaveHandler(SaveHandler),
+    Input(InputEvent),
+    Run,
+    Ran(Vec<ScriptEdit>),
+}
+
+pub struct ScriptPanel {
+    _save_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    is_opened: bool,
+    script: String,
+    // Of the last run on the opened save
+    edits: Option<Vec<ScriptEdit>>,
+}
+
+impl Component for ScriptPanel {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+
+        ScriptPanel {
+            _save_handle,
+            save_handler,
+            is_opened: false,
+            script: LocalStorage::get(SCRIPT_KEY).unwrap_or_default(),
+            edits: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Toggle => {
+                self.is_opened = !self.is_opened;
+                true
+            }
+            Msg::SaveHandler(save_handler) => {
+                // The edits made by a run stay listed once the tabs have been rebuilt
+                let is_same_save = match (&self.save_handler.save_game, &save_handler.save_game) {
+                    (Some(this), Some(other)) => Rc::ptr_eq(this, other),
+                    _ => false,
+                };
+                if !is_same_save {
+                    self.edits = None;
+                }
+                self.save_handler = save_handler;
+                true
+            }
+            Msg::Input(event) => {
+                if let Some(textarea) = event.target_dyn_into::<HtmlTextAreaElement>() {
+                    self.script = textarea.value();
+                    let _ = LocalStorage::set(SCRIPT_KEY, &self.script);
+                }
+                false
+            }
+            Msg::Run => {
+                let callback = ctx.link().callback(Msg::Ran);
+                self.save_handler.action(Action::RunScript(self.script.clone(), callback));
+                false
+            }
+            Msg::Ran(edits) => {
+                self.edits = Some(edits);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let panel = self.is_opened.then(|| {
+            let no_save = self.save_handler.save_game.is_none();
+            let edits = self.edits.as_ref().map(|edits| {
+                let rows = edits.iter().take(MAX_SHOWN_EDITS).map(|edit| {
+                    let ScriptEdit { target, before, after } = edit;
+                    html! {
+                        <div class="flex gap-2 min-w-0">
+                            <span class="w-1/2 truncate" title={target.clone()}>{ target }</span>
+                            <span class="flex-1 truncate select-text">
+                                { format!("{} -> {}", before, after) }
+                            </span>
+                        </div>
+                    }
+                });
+                let more = (edits.len() > MAX_SHOWN_EDITS).then(|| {
+                    let more = format!("And {} more", edits.len() - MAX_SHOWN_EDITS);
+                    html! { <p class="text-fg/50">{ more }</p> }
+                });
+                html! { <>
+                    <hr class="border-t border-default-border" />
+                    <div class="flex flex-col overflow-y-auto font-mono text-sm">
+                        { for rows }
+                        { for more }
+                    </div>
+                </> }
+            });
+
+            html! {
+                <div class="fixed top-8 left-1 w-[480px] max-h-[80vh] border border-default-border bg-default-bg flex flex-col z-40">
+                    <div class="px-1 bg-theme-tab">{ "Scripts" }</div>
+                    <div class="p-1 pt-0.5 flex flex-col gap-1 min-h-0">
+                        <textarea class="input h-40 resize-y p-1 font-mono text-sm"
+                            placeholder={"// Rhai, see rhai.rs\nfor id in 1510..=1512 {\n    \
+                                set_boolean(id, true);\n}\nset_integer(10, integer(10) + 1);\n\
+                                set(\"Player > Credits\", 999999);"}
+                            value={self.script.clone()}
+                            oninput={link.callback(Msg::Input)}
+                        />
+                        <div class="flex items-center gap-1">
+                            <button class="button" disabled={no_save}
+                                onclick={link.callback(|_| Msg::Run)}
+                            >
+                                { "Run" }
+                            </button>
+                            <Helper text=
+                                "A Rhai script (rhai.rs), with loops, conditions and variables.\n\
+                                Plots: `boolean(id)`, `integer(id)` and `float(id)` read them, \
+                                `set_boolean(id, value)`, `set_integer(id, value)` and \
+                                `set_float(id, value)` set them.\n\
+                                Raw Data: `get(path)` reads a value by its path, \
+                                `Player > Credits`, as text, `set(path, value)` sets it. Numbers \
+                                also take what their input does: `\"current + 1000\"`, enums go \
+                                by their name.\n\
+                                The script can't reach anything else than the opened save. \
+                                Nothing is edited if it fails."
+                            />
+                        </div>
+                        { for edits }
+                    </div>
+                </div>
+            }
+        });
+
+        html! {
+            <div class="flex items-center px-1">
+                <button class="button"
+                    title="Run a list of edits on the opened save"
+                    onclick={link.callback(|_| Msg::Toggle)}
+                >
+                    { "Scripts" }
+                </button>
+                { for panel }
+            </div>
+        }
+    }
+}
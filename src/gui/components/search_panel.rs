@@ -0,0 +1,291 @@
+use std::rc::Rc;
+
+use gloo::timers::future::TimeoutFuture;
+use wasm_bindgen_futures as futures;
+use web_sys::HtmlInputElement;
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::Helper,
+    save_data::{search::SearchIndex, shared::plot::RawPlotDb},
+    services::{
+        database::Databases,
+        logger, rpc,
+        save_game::SaveGame,
+        save_handler::{Action, SaveHandler},
+    },
+    unreal,
+};
+
+// Enough to find something, more is only noise
+const MAX_RESULTS: usize = 50;
+
+pub enum Msg {
+    Toggle,
+    SaveHandler(SaveHandler),
+    DatabaseLoaded(Databases),
+    Build(Rc<SaveGame>),
+    Search(InputEvent),
+    Evaluated(Vec<Option<String>>),
+    CopyPath(String),
+}
+
+pub struct SearchPanel {
+    _save_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    _databases_handle: ContextHandle<Databases>,
+    databases: Databases,
+    is_opened: bool,
+    // Of the save it was built from
+    index: Option<(Rc<SaveGame>, SearchIndex)>,
+    query: String,
+    // Path and label of the results, with their value as of now
+    results: Vec<(String, Option<String>)>,
+    values: Vec<Option<String>>,
+}
+
+impl Component for SearchPanel {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+        let (databases, _databases_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        let mut this = SearchPanel {
+            _save_handle,
+            save_handler,
+            _databases_handle,
+            databases,
+            is_opened: false,
+            index: None,
+            query: String::new(),
+            results: Vec::new(),
+            values: Vec::new(),
+        };
+        this.schedule_build(ctx);
+        this
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Toggle => {
+                self.is_opened = !self.is_opened;
+                self.search(ctx);
+                true
+            }
+            Msg::SaveHandler(save_handler) => {
+                self.save_handler = save_handler;
+                self.schedule_build(ctx);
+                true
+            }
+            Msg::DatabaseLoaded(databases) => {
+                self.databases = databases;
+                if self.add_plots() {
+                    self.search(ctx);
+                }
+                true
+            }
+            Msg::Build(save_game) => {
+                // Another save may have been opened in the meantime
+                let is_current = (self.save_handler.save_game.as_ref())
+                    .map_or(false, |current| Rc::ptr_eq(current, &save_game));
+                if !is_current {
+                    return false;
+                }
+                match Self::build(&save_game) {
+                    Ok(index) => {
+                        self.index = Some((save_game, index));
+                        self.add_plots();
+                        self.search(ctx);
+                    }
+                    Err(err) => logger::error(format!("Failed to index the save: {}", err)),
+                }
+                true
+            }
+            Msg::Search(event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    self.query = input.value();
+                    self.search(ctx);
+                }
+                true
+            }
+            Msg::Evaluated(values) => {
+                self.values = values;
+                true
+            }
+            Msg::CopyPath(path) => {
+                futures::spawn_local(async move {
+                    if let Err(err) = rpc::copy_to_clipboard(&path).await {
+                        logger::error(format!("Failed to copy the path: {:#}", err));
+                    }
+                });
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let panel = self.is_opened.then(|| {
+            let status = match (&self.save_handler.save_game, &self.index) {
+                (None, _) => Some("Open a save to search it"),
+                (Some(_), None) => Some("Indexing the save..."),
+                _ if self.query.trim().is_empty() => None,
+                _ if self.results.is_empty() => Some("Nothing found"),
+                _ => None,
+            };
+            let status = status.map(|status| html! { <p class="text-fg/50">{ status }</p> });
+
+            let rows = self.results.iter().enumerate().map(|(idx, (path, label))| {
+                let value = match self.values.get(idx) {
+                    Some(Some(value)) => value.clone(),
+                    _ => String::new(),
+                };
+                let title = match label {
+                    Some(label) => format!("{}\n{}", path, label),
+                    None => path.clone(),
+                };
+                let copy = {
+                    let path = path.clone();
+                    link.callback(move |_| Msg::CopyPath(path.clone()))
+                };
+                html! {
+                    <div class="flex gap-2 min-w-0">
+                        <a class="w-1/2 truncate cursor-pointer hover:bg-theme-hover" {title} onclick={copy}>
+                            { label.as_ref().unwrap_or(path) }
+                        </a>
+                        <span class="flex-1 truncate select-text">{ value }</span>
+                    </div>
+                }
+            });
+
+            let indexed = self.index.as_ref().map(|(_, index)| {
+                html! {
+                    <span class="text-fg/50 whitespace-nowrap">
+                        { format!("{} entries", index.len()) }
+                    </span>
+                }
+            });
+
+            html! {
+                <div class="fixed top-8 right-1 w-[480px] max-h-[60vh] border border-default-border bg-default-bg flex flex-col z-40">
+                    <div class="px-1 bg-theme-tab">{ "Search" }</div>
+                    <div class="p-1 pt-0.5 flex flex-col gap-1 min-h-0">
+                        <label class="flex items-center gap-1">
+                            <input type="text" class="input flex-1" placeholder="credits, Boolean 1234, Tali..."
+                                value={self.query.clone()}
+                                oninput={link.callback(Msg::Search)}
+                            />
+                            { for indexed }
+                            <Helper text=
+                                "Every field of the save by its path and value, and every plot known \
+                                to the database by its name, found from all the words typed.\n\
+                                Click a result to copy its path, for the Watch panel or the Raw Plot tab."
+                            />
+                        </label>
+                        <hr class="border-t border-default-border" />
+                        <div class="flex flex-col overflow-y-auto font-mono text-sm">
+                            { for rows }
+                        </div>
+                        { for status }
+                    </div>
+                </div>
+            }
+        });
+
+        html! {
+            <div class="flex items-center px-1">
+                <button class="button"
+                    title="Search every field and known plot of the save"
+                    onclick={link.callback(|_| Msg::Toggle)}
+                >
+                    { "Search" }
+                </button>
+                { for panel }
+            </div>
+        }
+    }
+}
+
+impl SearchPanel {
+    // Once the opened save is shown, flattening a whole save takes a moment
+    fn schedule_build(&mut self, ctx: &Context<Self>) {
+        let save_game = match self.save_handler.save_game {
+            Some(ref save_game) => save_game,
+            None => {
+                self.index = None;
+                self.results.clear();
+                return;
+            }
+        };
+        if matches!(self.index, Some((ref indexed, _)) if Rc::ptr_eq(indexed, save_game)) {
+            return;
+        }
+
+        self.index = None;
+        self.results.clear();
+        let save_game = Rc::clone(save_game);
+        ctx.link().send_future(async move {
+            TimeoutFuture::new(0).await;
+            Msg::Build(save_game)
+        });
+    }
+
+    fn build(save_game: &SaveGame) -> unreal::Result<SearchIndex> {
+        save_game.flatten(None).map(SearchIndex::new)
+    }
+
+    // Returns whether the plots of the save's game were added, its database being loaded
+    fn add_plots(&mut self) -> bool {
+        let (save_game, index) = match self.index {
+            Some((ref save_game, ref mut index)) if !index.has_plots() => (save_game, index),
+            _ => return false,
+        };
+
+        let databases = self.databases.clone();
+        let raw_plot_db: Option<Rc<RawPlotDb>> = match **save_game {
+            SaveGame::MassEffect1 { .. }
+            | SaveGame::MassEffect1Le { .. }
+            | SaveGame::MassEffect1LePs4 { .. } => databases.get_me1_raw_plot(),
+            SaveGame::MassEffect2 { .. }
+            | SaveGame::MassEffect2Le { .. }
+            | SaveGame::MassEffect2Quick { .. } => databases.get_me2_raw_plot(),
+            SaveGame::MassEffect3 { .. } | SaveGame::MassEffect3Quick { .. } => {
+                databases.get_me3_raw_plot()
+            }
+        };
+
+        match raw_plot_db {
+            Some(raw_plot_db) => {
+                index.add_plots(&raw_plot_db);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn search(&mut self, ctx: &Context<Self>) {
+        self.results = match (self.is_opened, &self.index) {
+            (true, Some((_, index))) => index
+                .search(&self.query)
+                .take(MAX_RESULTS)
+                .map(|entry| (entry.path.clone(), entry.label.clone()))
+                .collect(),
+            _ => Vec::new(),
+        };
+        self.values.clear();
+
+        if !self.results.is_empty() {
+            let paths = self.results.iter().map(|(path, _)| path.clone()).collect();
+            self.save_handler
+                .action(Action::WatchValues(paths, ctx.link().callback(Msg::Evaluated)));
+        }
+    }
+}
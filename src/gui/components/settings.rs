@@ -0,0 +1,275 @@
+use js_sys::Date;
+use wasm_bindgen::JsValue;
+use web_sys::HtmlInputElement;
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::Select,
+    services::{
+        appearance::{Accent, Font, Preference},
+        rpc::FileTimestamp,
+        save_handler::{Action, SaveHandler},
+        session::{self, LaunchGame},
+    },
+};
+
+const REOPEN_OPTIONS: &[&str] = &["No", "Yes"];
+const WRITE_PROTECTION_OPTIONS: &[&str] = &["Off", "On"];
+const TIMESTAMP_OPTIONS: &[&str] = &["Now", "Keep the original", "Custom"];
+
+pub enum Msg {
+    SaveHandler(SaveHandler),
+    Open,
+    Close,
+    Change(Preference, usize),
+    ChangeFont(Font, usize),
+    ChangeAccent(usize),
+    ReopenLastSave(usize),
+    WriteProtection(usize),
+    SaveTimestamp(usize),
+    CustomTimestamp(Event),
+    LaunchTarget(LaunchGame, Event),
+}
+
+pub struct Settings {
+    _save_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    opened: bool,
+}
+
+impl Component for Settings {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+
+        Settings { _save_handle, save_handler, opened: false }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SaveHandler(save_handler) => {
+                self.save_handler = save_handler;
+                false
+            }
+            Msg::Open => {
+                self.opened = true;
+                true
+            }
+            Msg::Close => {
+                self.opened = false;
+                true
+            }
+            Msg::Change(preference, idx) => {
+                preference.set(idx);
+                true
+            }
+            Msg::ChangeFont(font, idx) => {
+                font.set(idx);
+                true
+            }
+            Msg::ChangeAccent(idx) => {
+                Accent::set(idx);
+                true
+            }
+            Msg::ReopenLastSave(idx) => {
+                session::set_reopen_last_save(idx == 1);
+                true
+            }
+            Msg::WriteProtection(idx) => {
+                // Through the save handler, for the menu bar to follow
+                self.save_handler.action(Action::WriteProtection(idx == 1));
+                true
+            }
+            Msg::SaveTimestamp(idx) => {
+                let timestamp = match idx {
+                    0 => FileTimestamp::Now,
+                    1 => FileTimestamp::Keep,
+                    _ => FileTimestamp::Set(Date::now()),
+                };
+                session::set_save_timestamp(timestamp);
+                true
+            }
+            Msg::CustomTimestamp(event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    // Local time, as typed
+                    let millis = Date::new(&JsValue::from_str(&input.value())).get_time();
+                    if !millis.is_nan() {
+                        session::set_save_timestamp(FileTimestamp::Set(millis));
+                    }
+                }
+                true
+            }
+            Msg::LaunchTarget(game, event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    session::set_launch_target(game, &input.value());
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let settings = self.opened.then(|| {
+            let preferences = Preference::all().into_iter().map(|preference| {
+                html! {
+                    <div class="flex items-center gap-1 cursor-default">
+                        <Select
+                            options={preference.options()}
+                            current_idx={preference.get()}
+                            onselect={link.callback(move |idx| Msg::Change(preference, idx))}
+                        />
+                        { preference.label() }
+                    </div>
+                }
+            });
+            let fonts = Font::all().into_iter().map(|font| {
+                html! {
+                    <div class="flex items-center gap-1 cursor-default">
+                        <Select
+                            options={font.options()}
+                            current_idx={font.get()}
+                            onselect={link.callback(move |idx| Msg::ChangeFont(font, idx))}
+                        />
+                        { font.label() }
+                    </div>
+                }
+            });
+
+            let launch_targets = LaunchGame::all().into_iter().map(|game| {
+                html! {
+                    <label class="flex items-center gap-1">
+                        <input type="text" class="input w-72"
+                            placeholder={game.default_target()}
+                            value={session::launch_target(game)}
+                            onchange={link.callback(move |event| Msg::LaunchTarget(game, event))}
+                        />
+                        { game.label() }
+                    </label>
+                }
+            });
+
+            let save_timestamp = session::save_timestamp();
+            let custom_timestamp = match save_timestamp {
+                FileTimestamp::Set(millis) => Some(html! {
+                    <input type="datetime-local" class="input"
+                        value={Self::datetime_local(millis)}
+                        onchange={link.callback(Msg::CustomTimestamp)}
+                    />
+                }),
+                _ => None,
+            };
+            let timestamp_idx = match save_timestamp {
+                FileTimestamp::Now => 0,
+                FileTimestamp::Keep => 1,
+                FileTimestamp::Set(_) => 2,
+            };
+
+            html! {
+                <div class="fixed top-7 left-0 w-screen h-[calc(100vh-28px)] grid place-content-center bg-fg/30 z-50">
+                    <div class="border border-default-border bg-default-bg min-w-[300px] flex flex-col">
+                        <div class="px-1 bg-theme-tab">{ "Settings" }</div>
+                        <div class="p-1 pt-0.5 flex flex-col gap-1">
+                            { for preferences }
+                            <p class="text-fg/50">
+                                { "System follows the Windows settings and updates with them." }
+                            </p>
+                            <hr class="border-t border-default-border" />
+                            { for fonts }
+                            <p class="text-fg/50">
+                                { "The raw data and raw plot tabs always use a monospace font." }
+                            </p>
+                            <hr class="border-t border-default-border" />
+                            <div class="flex items-center gap-1 cursor-default">
+                                <Select
+                                    options={Accent::options()}
+                                    current_idx={Accent::get()}
+                                    onselect={link.callback(Msg::ChangeAccent)}
+                                />
+                                { "Accent" }
+                            </div>
+                            <p class="text-fg/50">
+                                { "Original Mass Effect 1 saves and quick plot edits keep the colors of the game." }
+                            </p>
+                            <hr class="border-t border-default-border" />
+                            <div class="flex items-center gap-1 cursor-default">
+                                <Select
+                                    options={REOPEN_OPTIONS}
+                                    current_idx={session::reopen_last_save() as usize}
+                                    onselect={link.callback(Msg::ReopenLastSave)}
+                                />
+                                { "Reopen last save" }
+                            </div>
+                            <p class="text-fg/50">
+                                { "On launch, back on the tab you were on. A save given on the command line comes first." }
+                            </p>
+                            <hr class="border-t border-default-border" />
+                            <div class="flex items-center gap-1 cursor-default">
+                                <Select
+                                    options={WRITE_PROTECTION_OPTIONS}
+                                    current_idx={session::write_protection() as usize}
+                                    onselect={link.callback(Msg::WriteProtection)}
+                                />
+                                { "Write protection" }
+                            </div>
+                            <p class="text-fg/50">
+                                { "The opened saves are never overwritten, `Save` writes a `_edited` copy next to them instead. It can be lifted from the menu bar until the editor is closed." }
+                            </p>
+                            <hr class="border-t border-default-border" />
+                            <div class="flex items-center gap-1 cursor-default">
+                                <Select
+                                    options={TIMESTAMP_OPTIONS}
+                                    current_idx={timestamp_idx}
+                                    onselect={link.callback(Msg::SaveTimestamp)}
+                                />
+                                { for custom_timestamp }
+                                { "Saved file date" }
+                            </div>
+                            <p class="text-fg/50">
+                                { "The game lists the careers and their saves by date, keep it to edit a save without moving it to the top." }
+                            </p>
+                            <hr class="border-t border-default-border" />
+                            { for launch_targets }
+                            <p class="text-fg/50">
+                                { "What `Save and launch` starts, the path to the game's executable or a Steam URI. Empty for the Steam release." }
+                            </p>
+                            <hr class="border-t border-default-border" />
+                            <button class="button w-12" onclick={link.callback(|_| Msg::Close)}>
+                                { "Close" }
+                            </button>
+                        </div>
+                    </div>
+                </div>
+            }
+        });
+
+        html! {
+            <div class="flex items-center px-1">
+                <button class="button" onclick={link.callback(|_| Msg::Open)}>
+                    { "Settings" }
+                </button>
+                { for settings }
+            </div>
+        }
+    }
+}
+
+impl Settings {
+    // `YYYY-MM-DDTHH:MM` in local time, what a `datetime-local` input takes
+    fn datetime_local(millis: f64) -> String {
+        let date = Date::new(&JsValue::from_f64(millis));
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}",
+            date.get_full_year(),
+            date.get_month() + 1,
+            date.get_date(),
+            date.get_hours(),
+            date.get_minutes()
+        )
+    }
+}
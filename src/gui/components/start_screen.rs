@@ -0,0 +1,222 @@
+use std::{
+    cmp::Ordering,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context as ErrorContext, Error};
+use js_sys::Date;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures as futures;
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{Helper, Table},
+    services::{
+        logger,
+        rpc::{self, Career, SaveFile},
+        save_handler::{Action, SaveHandler},
+        session,
+    },
+};
+
+const HELP_LINK: &str = "https://github.com/KarlitosVII/trilogy-save-editor#readme";
+
+pub enum Msg {
+    SaveHandler(SaveHandler),
+    OpenSave,
+    OpenQuickPlot,
+    OpenPath(PathBuf),
+    CareersLoaded(Vec<Career>),
+    OpenHelp,
+    Error(Error),
+}
+
+pub struct StartScreen {
+    _save_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    recent_saves: Vec<PathBuf>,
+    // Game, career and its latest save
+    careers: Option<Vec<(String, String, SaveFile)>>,
+}
+
+impl Component for StartScreen {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+
+        ctx.link().send_future(async {
+            match rpc::list_careers().await.context("Failed to list the saves") {
+                Ok(careers) => Msg::CareersLoaded(careers),
+                Err(err) => Msg::Error(err),
+            }
+        });
+
+        StartScreen {
+            _save_handle,
+            save_handler,
+            recent_saves: session::recent_saves(),
+            careers: None,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SaveHandler(save_handler) => {
+                self.save_handler = save_handler;
+                // A recent save that failed to open is forgotten
+                self.recent_saves = session::recent_saves();
+                true
+            }
+            Msg::OpenSave => {
+                self.save_handler.action(Action::OpenSave);
+                false
+            }
+            Msg::OpenQuickPlot => {
+                self.save_handler.action(Action::OpenQuickPlot);
+                false
+            }
+            Msg::OpenPath(path) => {
+                self.save_handler.action(Action::OpenPath(path));
+                false
+            }
+            Msg::CareersLoaded(careers) => {
+                let careers = careers.into_iter().filter_map(|career| {
+                    let Career { game, name, saves, .. } = career;
+                    let latest = saves.into_iter().max_by(|a, b| {
+                        a.modified.partial_cmp(&b.modified).unwrap_or(Ordering::Equal)
+                    })?;
+                    Some((game, name, latest))
+                });
+                let mut careers: Vec<_> = careers.collect();
+                careers.sort_by(|(_, _, a), (_, _, b)| {
+                    b.modified.partial_cmp(&a.modified).unwrap_or(Ordering::Equal)
+                });
+                self.careers = Some(careers);
+                true
+            }
+            Msg::OpenHelp => {
+                futures::spawn_local(async {
+                    if let Err(err) = rpc::open_external_link(HELP_LINK).await {
+                        logger::error(format!("Failed to open the help: {:#}", err));
+                    }
+                });
+                false
+            }
+            Msg::Error(err) => {
+                // No BioWare documents folder isn't worth an error
+                logger::warn(format!("{:#}", err));
+                self.careers = Some(Vec::new());
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                <div class="flex items-center gap-1">
+                    <button class="button" onclick={link.callback(|_| Msg::OpenSave)}>
+                        { "Open a save" }
+                    </button>
+                    <button class="button" onclick={link.callback(|_| Msg::OpenQuickPlot)}>
+                        { "Quick plot edit" }
+                    </button>
+                    <button class="button" onclick={link.callback(|_| Msg::OpenHelp)}>
+                        { "Help" }
+                    </button>
+                    <Helper text=
+                        "A save can also be dropped anywhere on the window to open it.\n\
+                        `Quick plot edit` only reads the plots of a Mass Effect 2 or 3 save, \
+                        to flip a few flags without waiting for the whole save."
+                    />
+                </div>
+                { self.view_recent_saves(ctx) }
+                { self.view_careers(ctx) }
+            </div>
+        }
+    }
+}
+
+impl StartScreen {
+    fn view_recent_saves(&self, ctx: &Context<Self>) -> Html {
+        let saves = self.recent_saves.iter().map(|path| {
+            let open = {
+                let path = path.clone();
+                ctx.link().callback(move |_| Msg::OpenPath(path.clone()))
+            };
+            html! {
+                <div class="flex items-center gap-1 min-w-0">
+                    <button class="button" onclick={open}>{ Self::file_name(path) }</button>
+                    <span class="text-fg/50 truncate" title={path.display().to_string()}>
+                        { path.parent().map(|dir| dir.display().to_string()).unwrap_or_default() }
+                    </span>
+                </div>
+            }
+        });
+        let empty = self.recent_saves.is_empty().then(|| {
+            html! { <p class="text-fg/50">{ "No save opened yet." }</p> }
+        });
+
+        html! {
+            <Table title="Recent saves">
+                { for saves }
+                { for empty }
+            </Table>
+        }
+    }
+
+    fn view_careers(&self, ctx: &Context<Self>) -> Html {
+        let content = match self.careers {
+            None => html! { <p class="text-fg/50">{ "Looking for saves..." }</p> },
+            Some(ref careers) if careers.is_empty() => html! {
+                <p class="text-fg/50">{ "No save found in the BioWare documents folder." }</p>
+            },
+            Some(ref careers) => {
+                let careers = careers.iter().map(|(game, name, latest)| {
+                    let open = {
+                        let path = latest.path.clone();
+                        ctx.link().callback(move |_| Msg::OpenPath(path.clone()))
+                    };
+                    html! {
+                        <div class="flex items-center gap-2">
+                            <button class="button" title={latest.path.display().to_string()}
+                                onclick={open}
+                            >
+                                { "Open latest" }
+                            </button>
+                            <span class="w-32 text-fg/50">{ game }</span>
+                            <span class="flex-auto truncate">{ name }</span>
+                            <span class="w-44 text-right">{ Self::format_date(latest.modified) }</span>
+                        </div>
+                    }
+                });
+                html! { <>{ for careers }</> }
+            }
+        };
+
+        html! {
+            <Table title="Careers" helper=
+                "The careers found in the BioWare documents folder, latest played first. \
+                The `Saves` menu cleans them up."
+            >
+                { content }
+            </Table>
+        }
+    }
+
+    fn file_name(path: &Path) -> String {
+        path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default()
+    }
+
+    fn format_date(millis: f64) -> String {
+        Date::new(&JsValue::from_f64(millis))
+            .to_locale_string("default", &JsValue::UNDEFINED)
+            .into()
+    }
+}
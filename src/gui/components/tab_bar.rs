@@ -1,15 +1,16 @@
 use gloo::{events::EventListener, utils};
 use wasm_bindgen::JsCast;
-use web_sys::PopStateEvent;
+use web_sys::{Element, PopStateEvent};
 use yew::{html::Scope, prelude::*};
 
-use crate::gui::Theme;
+use crate::{gui::Theme, services::session};
 
 const MAIN_BUTTON: i16 = 0;
 
 pub enum Msg {
     TabClicked(MouseEvent, String),
     MainTabChanged(String),
+    Scrolled,
 }
 
 #[derive(Properties, PartialEq)]
@@ -22,6 +23,8 @@ pub struct Props {
 pub struct TabBar {
     main_tab_listener: Option<EventListener>,
     current_tab: String,
+    content_ref: NodeRef,
+    scroll_to_restore: Option<i32>,
 }
 
 impl Component for TabBar {
@@ -29,7 +32,20 @@ impl Component for TabBar {
     type Properties = Props;
 
     fn create(ctx: &Context<Self>) -> Self {
-        let current_tab = Self::first_tab(&ctx.props().children);
+        let children = &ctx.props().children;
+        let mut current_tab = Self::first_tab(children);
+        let mut scroll_to_restore = None;
+
+        // Back where the user left off when the last save is reopened
+        if ctx.props().is_main_tab_bar {
+            if let Some((tab, scroll_top)) = session::take_tab_to_restore() {
+                if children.iter().any(|child| child.props.title == tab) {
+                    current_tab = tab;
+                    scroll_to_restore = Some(scroll_top);
+                }
+            }
+        }
+
         let main_tab_listener = ctx.props().is_main_tab_bar.then(|| {
             let link = ctx.link().clone();
             Self::event_listener(link)
@@ -37,13 +53,21 @@ impl Component for TabBar {
 
         // TODO: Tab history
 
-        TabBar { current_tab, main_tab_listener }
+        TabBar {
+            current_tab,
+            main_tab_listener,
+            content_ref: Default::default(),
+            scroll_to_restore,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::TabClicked(event, title) => {
                 if event.button() == MAIN_BUTTON {
+                    if ctx.props().is_main_tab_bar {
+                        session::set_last_tab(&title);
+                    }
                     self.current_tab = title;
                     true
                 } else {
@@ -57,8 +81,15 @@ impl Component for TabBar {
                 } else {
                     self.current_tab = Self::first_tab(children);
                 }
+                session::set_last_tab(&self.current_tab);
                 true
             }
+            Msg::Scrolled => {
+                if let Some(content) = self.content_ref.cast::<Element>() {
+                    session::set_scroll_top(content.scroll_top());
+                }
+                false
+            }
         }
     }
 
@@ -81,6 +112,14 @@ impl Component for TabBar {
         true
     }
 
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if let Some(scroll_top) = self.scroll_to_restore.take() {
+            if let Some(content) = self.content_ref.cast::<Element>() {
+                content.set_scroll_top(scroll_top);
+            }
+        }
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
         let tabs = ctx.props().children.iter().map(|child| {
             let title = child.props.title.clone();
@@ -108,16 +147,18 @@ impl Component for TabBar {
             }
         });
 
+        let onscroll = ctx.props().is_main_tab_bar.then(|| ctx.link().callback(|_| Msg::Scrolled));
         let content = ctx.props().children.iter().find_map(|content| {
             (content.props.title == self.current_tab).then(|| {
                 html! {
-                    <div class={classes![
+                    <div ref={self.content_ref.clone()} onscroll={onscroll.clone()} class={classes![
                         "flex-auto",
                         "flex",
                         "flex-col",
                         "h-0",
                         "overflow-y-auto",
                         content.props.theme,
+                        content.props.monospace.then(|| "font-mono"),
                     ]}>
                         { content }
                     </div>
@@ -160,6 +201,9 @@ pub struct TabProps {
     #[prop_or_default]
     pub children: Children,
     pub theme: Option<Theme>,
+    // For the raw views, ids and GUIDs are easier to read in a monospace font
+    #[prop_or(false)]
+    pub monospace: bool,
 }
 
 pub struct Tab;
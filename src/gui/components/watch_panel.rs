@@ -0,0 +1,186 @@
+use std::{collections::HashMap, rc::Rc};
+
+use gloo::storage::{LocalStorage, Storage};
+use web_sys::HtmlInputElement;
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::Helper,
+    services::{
+        document::{Document, Subscription},
+        save_handler::SaveHandler,
+    },
+};
+
+const WATCH_KEY: &str = "watch_paths";
+
+pub enum Msg {
+    Toggle,
+    SaveHandler(SaveHandler),
+    Add(Event),
+    Remove(usize),
+    Value(String, Option<String>),
+}
+
+pub struct WatchPanel {
+    _db_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    _document_handle: ContextHandle<Document>,
+    document: Document,
+    // One by path while opened, only a watched value that changes renders the panel again
+    subscriptions: Option<Vec<Subscription>>,
+    paths: Rc<Vec<String>>,
+    // Missing until known
+    values: HashMap<String, Option<String>>,
+}
+
+impl Component for WatchPanel {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _db_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+        let (document, _document_handle) =
+            ctx.link().context::<Document>(Callback::noop()).expect("no document provider");
+
+        WatchPanel {
+            _db_handle,
+            save_handler,
+            _document_handle,
+            document,
+            subscriptions: None,
+            paths: Rc::new(LocalStorage::get(WATCH_KEY).unwrap_or_default()),
+            values: HashMap::new(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Toggle => {
+                self.subscriptions = match self.subscriptions {
+                    Some(_) => None,
+                    None => {
+                        let subscribe = |path: &String| Self::subscribe(&self.document, ctx, path);
+                        Some(self.paths.iter().map(subscribe).collect())
+                    }
+                };
+                self.values.clear();
+                true
+            }
+            Msg::SaveHandler(save_handler) => {
+                let was_opened = self.save_handler.save_game.is_some();
+                self.save_handler = save_handler;
+                // The values follow on their own
+                self.subscriptions.is_some() && was_opened != self.save_handler.save_game.is_some()
+            }
+            Msg::Add(event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    let path = input.value().trim().to_owned();
+                    if !path.is_empty() && !self.paths.contains(&path) {
+                        if let Some(ref mut subscriptions) = self.subscriptions {
+                            subscriptions.push(Self::subscribe(&self.document, ctx, &path));
+                        }
+                        Rc::make_mut(&mut self.paths).push(path);
+                        self.save();
+                    }
+                    input.set_value("");
+                }
+                true
+            }
+            Msg::Remove(idx) => {
+                let path = Rc::make_mut(&mut self.paths).remove(idx);
+                if let Some(ref mut subscriptions) = self.subscriptions {
+                    subscriptions.remove(idx);
+                }
+                self.values.remove(&path);
+                self.save();
+                true
+            }
+            Msg::Value(path, value) => {
+                let changed = self.values.get(&path) != Some(&value);
+                self.values.insert(path, value);
+                changed
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let panel = self.subscriptions.is_some().then(|| {
+            let rows = self.paths.iter().enumerate().map(|(idx, path)| {
+                let value = match self.values.get(path) {
+                    Some(Some(value)) => value.clone(),
+                    Some(None) => String::from("<none>"),
+                    None => String::new(),
+                };
+                html! {
+                    <div class="flex gap-2 min-w-0">
+                        <span class="w-1/2 truncate" title={path.clone()}>{ path }</span>
+                        <span class="flex-1 truncate select-text">{ value }</span>
+                        <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 py-0 cursor-pointer"
+                            title="Stop watching"
+                            onclick={link.callback(move |_| Msg::Remove(idx))}
+                        >
+                            { "x" }
+                        </a>
+                    </div>
+                }
+            });
+            let empty = self.save_handler.save_game.is_none().then(|| {
+                html! { <p class="text-fg/50">{ "Open a save to see the values" }</p> }
+            });
+
+            html! {
+                <div class="fixed bottom-1 right-1 w-[420px] max-h-[50vh] border border-default-border bg-default-bg flex flex-col z-40">
+                    <div class="px-1 bg-theme-tab">{ "Watch" }</div>
+                    <div class="p-1 pt-0.5 flex flex-col gap-1 min-h-0">
+                        <label class="flex items-center gap-1">
+                            <input type="text" class="input flex-1" placeholder="player.credits, Boolean 1234..."
+                                onchange={link.callback(Msg::Add)}
+                            />
+                            <Helper text=
+                                "Fields go by their path as shown in the Changes tab, \
+                                plots by their kind and id: `Boolean 1234`, `Integer 12` or `Float 3`.\n\
+                                The values follow every edit made in the other tabs."
+                            />
+                        </label>
+                        <hr class="border-t border-default-border" />
+                        <div class="flex flex-col overflow-y-auto font-mono text-sm">
+                            { for rows }
+                        </div>
+                        { for empty }
+                    </div>
+                </div>
+            }
+        });
+
+        html! {
+            <div class="flex items-center px-1">
+                <button class="button"
+                    title="Follow the value of a few fields or plots while editing"
+                    onclick={link.callback(|_| Msg::Toggle)}
+                >
+                    { "Watch" }
+                </button>
+                { for panel }
+            </div>
+        }
+    }
+}
+
+impl WatchPanel {
+    fn subscribe(document: &Document, ctx: &Context<Self>, path: &str) -> Subscription {
+        let callback = {
+            let path = path.to_owned();
+            ctx.link().callback(move |value| Msg::Value(path.clone(), value))
+        };
+        document.subscribe(path.to_owned(), callback)
+    }
+
+    fn save(&self) {
+        let _ = LocalStorage::set(WATCH_KEY, &*self.paths);
+    }
+}
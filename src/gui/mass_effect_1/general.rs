@@ -1,31 +1,41 @@
-use std::cell::Ref;
+use std::{cell::Ref, rc::Rc};
 
-use yew::prelude::*;
+use yew::{context::ContextHandle, prelude::*};
 
 use crate::save_data::{
     mass_effect_1::{
         data::{Data, Property as DataProperty},
         player::Player,
+        state::{State, AREAS},
+        talents::BONUS_TALENTS,
     },
+    mass_effect_1_le::item_db::Me1ItemDb,
     shared::plot::PlotTable,
     List, RcCell,
 };
 use crate::{
     gui::{
-        components::{Select, Table},
+        components::{Helper, Select, Table},
         mass_effect_1::property::Property,
         raw_ui::RawUi,
     },
     save_data::{mass_effect_1::data::StructType, RcRef},
+    services::{database::Databases, document, logger},
 };
 
 pub enum Msg {
-    Difficulty(usize),
+    DatabaseLoaded(Databases),
+    GameOption(&'static str, usize),
+    UpgradeGear,
+    SyncSquadLevel,
+    ToggleBonusTalent(usize),
+    MoveTo(usize),
 }
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
     pub player: RcRef<Player>,
+    pub state: RcRef<State>,
     pub plot: RcRef<PlotTable>,
 }
 
@@ -39,104 +49,119 @@ impl Props {
     }
 }
 
-pub struct Me1General;
+pub struct Me1General {
+    _db_handle: ContextHandle<Databases>,
+    item_db: Option<Rc<Me1ItemDb>>,
+    upgraded_gear: Option<usize>,
+    synced_squad: Option<usize>,
+}
 
 impl Component for Me1General {
     type Message = Msg;
     type Properties = Props;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Me1General
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        Me1General {
+            _db_handle,
+            item_db: databases.get_me1_item_db(),
+            upgraded_gear: None,
+            synced_squad: None,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
-            Msg::Difficulty(new_difficulty_idx) => {
+            Msg::DatabaseLoaded(dbs) => {
+                self.item_db = dbs.get_me1_item_db();
+                true
+            }
+            Msg::GameOption(option_name, new_idx) => {
                 let player = ctx.props().player();
-
-                // Worst thing ever >>
-                // Find current game
-                // Then find game options
-                // Then find difficulty option
-                let value = player
-                    .objects
-                    .iter()
-                    .enumerate()
-                    .find_map(|(i, object)| {
-                        let object_name = player.get_name(object.object_name_id);
-                        (object_name == "CurrentGame").then(|| player.get_data(i as i32 + 1))
-                    })
-                    .and_then(|current_game| {
-                        let m_game_options =
-                            Self::find_property(ctx, &current_game.properties, "m_GameOptions")?
-                                .borrow();
-                        match *m_game_options {
-                            DataProperty::Struct {
-                                struct_type: StructType::Properties(ref properties),
-                                ..
-                            } => Some(properties),
-                            _ => None,
-                        }
-                        .and_then(|properties| {
-                            Self::find_property(ctx, properties, "m_nCombatDifficulty").and_then(
-                                |p| match *p.borrow() {
-                                    DataProperty::Int { ref value, .. } => {
-                                        Some(RcCell::clone(value))
-                                    }
-                                    _ => None,
-                                },
-                            )
-                        })
-                    });
-
-                // Then set new difficulty
-                if let Some(value) = value {
-                    value.set(new_difficulty_idx as i32);
+                let current_game = Self::current_game(&player);
+                if let Some(value) = current_game
+                    .and_then(|current_game| Self::game_option(ctx, current_game, option_name))
+                {
+                    value.set(new_idx as i32);
                 }
-
+                document::edited(ctx);
+                true
+            }
+            Msg::UpgradeGear => {
+                if let Some(ref item_db) = self.item_db {
+                    self.upgraded_gear = Some(ctx.props().player().upgrade_gear(item_db));
+                    document::edited(ctx);
+                }
+                true
+            }
+            Msg::SyncSquadLevel => {
+                self.synced_squad = Some(ctx.props().player().sync_squad_level());
+                document::edited(ctx);
+                true
+            }
+            Msg::ToggleBonusTalent(talent_idx) => {
+                let has_bonus_talent = ctx.props().player().has_bonus_talent(talent_idx);
+                let result = if has_bonus_talent {
+                    ctx.props().player.borrow_mut().remove_bonus_talent(talent_idx).map(drop)
+                } else {
+                    ctx.props().player().add_bonus_talent(talent_idx).map(drop)
+                };
+                if let Err(err) = result {
+                    logger::error(format!("Failed to change the bonus talent: {:#}", err));
+                }
+                document::edited(ctx);
+                true
+            }
+            Msg::MoveTo(area_idx) => {
+                ctx.props().state.borrow().move_to(&AREAS[area_idx]);
+                document::edited(ctx);
                 true
             }
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        html! { for Self::try_view(ctx) }
+        html! { for self.try_view(ctx) }
     }
 }
 
 impl Me1General {
-    fn try_view(ctx: &Context<Self>) -> Option<Html> {
+    fn try_view(&self, ctx: &Context<Self>) -> Option<Html> {
         let player = ctx.props().player();
 
-        let current_game = player.objects.iter().enumerate().find_map(|(i, object)| {
-            let object_name = player.get_name(object.object_name_id);
-            (object_name == "CurrentGame").then(|| player.get_data(i as i32 + 1))
-        })?;
+        let current_game = Self::current_game(&player)?;
 
         let m_player = {
             let object_id = Self::find_object_id(ctx, &current_game.properties, "m_Player")?;
-            player.get_data(object_id)
+            player.get_data(object_id)?
         };
 
         let m_squad = {
             let object_id = Self::find_object_id(ctx, &m_player.properties, "m_Squad")?;
-            player.get_data(object_id)
+            player.get_data(object_id)?
         };
 
         let m_inventory = {
             let object_id = Self::find_object_id(ctx, &m_squad.properties, "m_Inventory")?;
-            player.get_data(object_id)
+            player.get_data(object_id)?
         };
 
         Some(html! {
             <div class="flex divide-solid divide-x divide-default-border">
                 <div class="flex-1 pr-1 flex flex-col gap-1">
                     { Self::role_play(ctx, m_player) }
-                    { Self::gameplay(ctx, m_player, m_squad) }
+                    { self.gameplay(ctx, m_player, m_squad) }
+                    { Self::bonus_talents(ctx, &player) }
+                    { self.gear(ctx) }
                     { Self::morality(ctx) }
                 </div>
                 <div class="flex-1 pl-1 flex flex-col gap-1">
-                    { for Self::general(ctx, &current_game.properties) }
+                    { Self::general(ctx, current_game) }
+                    { Self::location(ctx) }
                     { Self::resources(ctx, m_inventory) }
                 </div>
             </div>
@@ -163,7 +188,7 @@ impl Me1General {
         }
     }
 
-    fn gameplay(ctx: &Context<Self>, m_player: &Data, m_squad: &Data) -> Html {
+    fn gameplay(&self, ctx: &Context<Self>, m_player: &Data, m_squad: &Data) -> Html {
         let class = Self::find_property(ctx, &m_player.properties, "m_ClassBase")
             .map(|p| Self::view_property(ctx, p, "Class"));
         let level = Self::find_property(ctx, &m_player.properties, "m_XPLevel")
@@ -171,11 +196,94 @@ impl Me1General {
         let curent_xp = Self::find_property(ctx, &m_squad.properties, "m_nSquadExperience")
             .map(|p| Self::view_property(ctx, p, "Current XP"));
 
+        let synced = self.synced_squad.map(|synced| match synced {
+            0 => "Everyone is up to level".to_owned(),
+            1 => "1 squad member leveled up".to_owned(),
+            _ => format!("{} squad members leveled up", synced),
+        });
+
         html! {
             <Table title="Gameplay">
                 { for class }
                 { for level }
                 { for curent_xp }
+                <div class="flex items-center gap-1">
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::SyncSquadLevel)}>
+                        {"Sync squad level to Shepard"}
+                    </button>
+                    <Helper text=
+                        "Raise every squad member below Shepard's level to it, with 2 talent points \
+                        for each level gained to spend in game.\n\
+                        The experience is shared by the whole squad."
+                    />
+                    { for synced }
+                </div>
+            </Table>
+        }
+    }
+
+    fn bonus_talents(ctx: &Context<Self>, player: &Player) -> Html {
+        let talents = BONUS_TALENTS.iter().enumerate().map(|(i, &(complex_id, _, label))| {
+            let selected = player.has_bonus_talent(i);
+            // Already there as a class talent
+            let from_class = !selected && player.has_talent(complex_id);
+            html! {
+                <button
+                    class={classes![
+                        "rounded-none",
+                        "hover:bg-theme-hover",
+                        "active:bg-theme-active",
+                        "px-1",
+                        "w-full",
+                        "text-left",
+                        selected.then(|| "bg-theme-bg"),
+                        from_class.then(|| "text-fg/50"),
+                    ]}
+                    disabled={from_class}
+                    onclick={ctx.link().callback(move |_| Msg::ToggleBonusTalent(i))}
+                >
+                    { label }
+                </button>
+            }
+        });
+
+        html! {
+            <Table title="Bonus Talents" helper=
+                "Any bonus talent can be given to Shepard, \
+                not only the one picked at character creation.\n\
+                Removing one gives back the talent points spent in it. \
+                The ones greyed out are already part of Shepard's class.\n\
+                The achievements unlocking them in the game's profile are not changed."
+            >
+                { for talents }
+            </Table>
+        }
+    }
+
+    fn gear(&self, ctx: &Context<Self>) -> Html {
+        let upgraded = self.upgraded_gear.map(|upgraded| match upgraded {
+            0 => "Nothing to upgrade".to_owned(),
+            1 => "1 item upgraded".to_owned(),
+            _ => format!("{} items upgraded", upgraded),
+        });
+
+        html! {
+            <Table title="Gear">
+                <div class="flex items-center gap-1">
+                    <button class="button"
+                        disabled={self.item_db.is_none()}
+                        onclick={ctx.link().callback(|_| Msg::UpgradeGear)}
+                    >
+                        {"Upgrade squad gear"}
+                    </button>
+                    <Helper text=
+                        "Raise the equipped armors, weapons, amps and omni-tools of Shepard and every \
+                        squad member to the item level found at Shepard's level, \
+                        Spectre Master Gear included once it reaches X.\n\
+                        Grenades and better items are left untouched."
+                    />
+                    { for upgraded }
+                </div>
             </Table>
         }
     }
@@ -190,43 +298,75 @@ impl Me1General {
         }
     }
 
-    fn general(ctx: &Context<Self>, current_game: &List<RcRef<DataProperty>>) -> Option<Html> {
+    fn general(ctx: &Context<Self>, current_game: &Data) -> Html {
         let difficulty: &'static [&'static str] =
             &["Casual", "Normal", "Veteran", "Hardcore", "Insanity"];
 
-        let m_game_options = Self::find_property(ctx, current_game, "m_GameOptions")?.borrow();
-        let difficulty = match *m_game_options {
-            DataProperty::Struct {
-                struct_type: StructType::Properties(ref properties), ..
-            } => Some(properties),
-            _ => None,
-        }
-        .and_then(|properties| {
-            Self::find_property(ctx, properties, "m_nCombatDifficulty").and_then(|p| {
-                match *p.borrow() {
-                    DataProperty::Int { ref value, .. } => Some(value.get() as usize),
-                    _ => None,
-                }
-            })
-        })
-        .map(|current_idx| {
-            html! {
+        // The lowest one is what the difficulty achievements check
+        let game_options = [
+            ("m_nCombatDifficulty", "Difficulty", difficulty),
+            ("m_nLowestCombatDifficultyUsed", "Lowest Difficulty Used", difficulty),
+        ];
+        let game_options = game_options.into_iter().filter_map(|(option_name, label, options)| {
+            let current_idx = Self::game_option(ctx, current_game, option_name)?.get() as usize;
+            Some(html! {
                 <div class="flex items-center gap-1 cursor-default">
                     <Select
-                        options={difficulty}
+                        {options}
                         {current_idx}
-                        onselect={ctx.link().callback(Msg::Difficulty)}
+                        onselect={ctx.link().callback(move |idx| Msg::GameOption(option_name, idx))}
                     />
-                    { "Difficulty" }
+                    { label }
                 </div>
-            }
+            })
         });
 
-        Some(html! {
+        html! {
             <Table title="General">
-                { for difficulty }
+                { for game_options }
             </Table>
-        })
+        }
+    }
+
+    fn location(ctx: &Context<Self>) -> Html {
+        let state = ctx.props().state.borrow();
+        let current = state.area().map(|area| area.level);
+
+        let areas = AREAS.iter().enumerate().map(|(i, area)| {
+            let selected = current == Some(area.level);
+            html! {
+                <button
+                    class={classes![
+                        "rounded-none",
+                        "hover:bg-theme-hover",
+                        "active:bg-theme-active",
+                        "px-1",
+                        "text-left",
+                        selected.then(|| "bg-theme-bg"),
+                    ]}
+                    title={area.level}
+                    onclick={ctx.link().callback(move |_| Msg::MoveTo(i))}
+                >
+                    { area.name }
+                </button>
+            }
+        });
+
+        html! {
+            <Table title="Location" helper=
+                "Where the save loads, to get Shepard or the Mako out of a stuck spot.\n\
+                The position is kept when changing the area, set one inside the new area \
+                or Shepard may load out of the map. A position found in another save of \
+                the same area is the safest."
+            >
+                { state.base_level_name.view("Level") }
+                <div class="flex flex-wrap">
+                    { for areas }
+                </div>
+                { state.location.view("Position") }
+                { state.rotation.view("Rotation") }
+            </Table>
+        }
     }
 
     fn resources(ctx: &Context<Self>, m_inventory: &Data) -> Html {
@@ -276,11 +416,40 @@ impl Me1General {
             | DataProperty::StringRef { name_id, .. }
             | DataProperty::Struct { name_id, .. }
             | DataProperty::None { name_id, .. } => {
-                (player.get_name(name_id) == property_name).then(|| property)
+                (player.get_name(name_id).as_deref() == Some(property_name)).then(|| property)
             }
         })
     }
 
+    fn current_game<'a>(player: &'a Player) -> Option<&'a Data> {
+        let idx = player.objects.iter().position(|object| {
+            player.get_name(object.object_name_id).as_deref() == Some("CurrentGame")
+        })?;
+        player.get_data(idx as i32 + 1)
+    }
+
+    // Looked up in the game options first, then in the current game itself
+    fn game_option(
+        ctx: &Context<Self>, current_game: &Data, option_name: &str,
+    ) -> Option<RcCell<i32>> {
+        let m_game_options = Self::find_property(ctx, &current_game.properties, "m_GameOptions")?;
+        let in_game_options = match *m_game_options.borrow() {
+            DataProperty::Struct {
+                struct_type: StructType::Properties(ref properties), ..
+            } => Self::find_property(ctx, properties, option_name).map(RcRef::clone),
+            _ => None,
+        };
+
+        let property = in_game_options.or_else(|| {
+            Self::find_property(ctx, &current_game.properties, option_name).map(RcRef::clone)
+        })?;
+        let value = match *property.borrow() {
+            DataProperty::Int { ref value, .. } => Some(RcCell::clone(value)),
+            _ => None,
+        };
+        value
+    }
+
     fn find_object_id(
         ctx: &Context<Self>, properties: &List<RcRef<DataProperty>>, property_name: &str,
     ) -> Option<i32> {
@@ -32,16 +32,10 @@ impl Component for Me1RawData {
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let player = ctx.props().player();
-        let object_id = player
-            .objects
-            .iter()
-            .enumerate()
-            .find_map(|(idx, object)| {
-                (player.get_name(object.object_name_id) == "CurrentGame").then(|| idx as i32 + 1)
-            })
-            .unwrap_or_default();
-
-        let properties = &player.get_data(object_id).properties;
+        let properties = match player.current_game() {
+            Some(current_game) => &current_game.properties,
+            None => return Html::default(),
+        };
         let len = properties.len();
         let take = if len > 0 { len - 1 } else { 0 };
         let properties = properties.iter().take(take).map(|property| {
@@ -5,7 +5,7 @@ use yew::prelude::*;
 use crate::{
     gui::{
         components::{raw_ui::RawUiStruct, CallbackType, InputText},
-        raw_ui::RawUi,
+        raw_ui::{view_string_ref, RawUi},
     },
     save_data::{
         mass_effect_1::{
@@ -72,13 +72,18 @@ impl Component for Property {
     fn view(&self, ctx: &Context<Self>) -> Html {
         let player = ctx.props().player();
 
+        // A corrupted save may point out of the tables
+        let name_or_id = |name_id: u32| -> String {
+            player.get_name(name_id).unwrap_or_else(|| format!("Unknown name {}", name_id))
+        };
+
         let get_name = |name_id: &u32| -> String {
             // Label override
             if let Some(ref label) = ctx.props().label {
                 return label.clone();
             }
 
-            let name = player.get_name(*name_id);
+            let name = name_or_id(*name_id);
             name.trim_start_matches("m_a") // Array
                 .trim_start_matches("m_b") // Bool
                 .trim_start_matches("m_e") // Byte
@@ -99,6 +104,17 @@ impl Component for Property {
             }
         }
 
+        let view_object = |object_id: i32, label: &str| -> Html {
+            match (player.get_object(object_id), player.get_data(object_id)) {
+                (Some(object), Some(data)) => {
+                    let object_name = name_or_id(object.object_name_id);
+                    let label = format!("{} : {}", object_name, label);
+                    self.view_properties(ctx, label, &data.properties)
+                }
+                _ => view_text(format!("Unknown object {}", object_id), label.to_owned()),
+            }
+        };
+
         match &*ctx.props().property() {
             DataProperty::Array { name_id, array, .. } => {
                 let label = get_name(name_id);
@@ -108,12 +124,7 @@ impl Component for Property {
                     ArrayType::Object(object_id) => {
                         if *object_id != 0 {
                             // Object
-                            let object = player.get_object(*object_id);
-                            let object_name = player.get_name(object.object_name_id);
-
-                            let label = format!("{} : {}", object_name, idx);
-                            let properties = &player.get_data(*object_id).properties;
-                            self.view_properties(ctx, label, properties)
+                            view_object(*object_id, &idx.to_string())
                         } else {
                             // Null
                             html! { "Null" }
@@ -150,7 +161,11 @@ impl Component for Property {
             }
             DataProperty::Name { name_id, value_name_id, .. } => {
                 let label = get_name(name_id);
-                let name = &player.names.borrow()[value_name_id.get() as usize];
+                let names = player.names.borrow();
+                let name = match names.get(value_name_id.get() as usize) {
+                    Some(name) => name,
+                    None => return view_text(name_or_id(value_name_id.get()), label),
+                };
 
                 if name.is_duplicate {
                     html! {
@@ -174,17 +189,14 @@ impl Component for Property {
                 match object_id.cmp(&0) {
                     Ordering::Greater => {
                         // Object
-                        let object = player.get_object(*object_id);
-                        let object_name = player.get_name(object.object_name_id);
-
-                        let label = format!("{} : {}", object_name, label);
-                        let properties = &player.get_data(*object_id).properties;
-                        self.view_properties(ctx, label, properties)
+                        view_object(*object_id, &label)
                     }
                     Ordering::Less => {
                         // Class
-                        let class = player.get_class(*object_id);
-                        let class_name = get_name(&class.class_name_id);
+                        let class_name = match player.get_class(*object_id) {
+                            Some(class) => get_name(&class.class_name_id),
+                            None => format!("Unknown class {}", object_id),
+                        };
                         view_text(class_name, label)
                     }
                     Ordering::Equal => {
@@ -199,7 +211,7 @@ impl Component for Property {
             }
             DataProperty::StringRef { name_id, value, .. } => {
                 let label = get_name(name_id);
-                value.view(&label)
+                view_string_ref(value, &label)
             }
             DataProperty::Struct { name_id, struct_name_id, struct_type, .. } => {
                 let name = get_name(name_id);
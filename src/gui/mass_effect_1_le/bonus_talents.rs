@@ -3,29 +3,13 @@ use yew::prelude::*;
 use crate::{
     gui::components::Table,
     save_data::{
+        mass_effect_1::talents::BONUS_TALENTS,
         mass_effect_1_le::player::{ComplexTalent, SimpleTalent},
         RcRef,
     },
+    services::document,
 };
 
-const BONUS_TALENTS: &[(i32, &[i32], &str)] = &[
-    (50, &[248], "Lift"),
-    (49, &[247], "Throw"),
-    (56, &[249], "Warp"),
-    (57, &[250], "Singularity"),
-    (63, &[251], "Barrier"),
-    (64, &[252], "Stasis"),
-    (86, &[254], "Damping"),
-    (91, &[256], "Hacking"),
-    (84, &[253], "Electronics"),
-    (93, &[255], "Decryption"),
-    (98, &[257], "First Aid"),
-    (99, &[257, 258], "Medicine"),
-    (15, &[244], "Shotguns"),
-    (7, &[245], "Assault Rifles"),
-    (21, &[246], "Sniper Rifles"),
-];
-
 pub enum Msg {
     ToggleBonusTalent(usize),
 }
@@ -91,6 +75,7 @@ impl Component for BonusTalents {
                 };
 
                 onselect.emit(callback);
+                document::edited(ctx);
                 false
             }
         }
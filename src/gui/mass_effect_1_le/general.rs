@@ -11,6 +11,7 @@ use crate::{
         components::{CallbackType, Helper, InputNumber, InputText, NumberType, Select, Table},
         mass_effect_1_le::bonus_talents::BonusTalents,
         raw_ui::RawUi,
+        shared::{FirstName, SaveInfo},
     },
     save_data::{
         mass_effect_1_le::{
@@ -25,9 +26,18 @@ use crate::{
         },
         RcCell, RcRef,
     },
-    services::database::Databases,
+    services::{database::Databases, document},
 };
 
+const HENCHMEN: [(&str, &str); 6] = [
+    ("hench_humanfemale", "Ashley"),
+    ("hench_turian", "Garrus"),
+    ("hench_humanmale", "Kaidan"),
+    ("hench_asari", "Liara"),
+    ("hench_quarian", "Tali"),
+    ("hench_krogan", "Wrex"),
+];
+
 #[derive(Clone, RawUi)]
 enum SoldierSpec {
     None,
@@ -112,11 +122,13 @@ pub enum Msg {
     Origin(usize),
     Notoriety(usize),
     Difficulty(usize),
+    AutoLevelUpTemplate(usize),
     TalentPoints(CallbackType),
     ResetTalents(Option<&'static str>),
     PlayerClass(usize),
     PlayerSpecialization(usize),
     BonusTalent(Option<i32>),
+    SyncSquadLevel,
 }
 
 #[derive(Properties, PartialEq)]
@@ -137,6 +149,7 @@ impl Props {
 pub struct Me1LeGeneral {
     _db_handle: ContextHandle<Databases>,
     player_class_db: Option<Rc<Me1LePlayerClassDb>>,
+    synced_squad: Option<usize>,
 }
 
 impl Component for Me1LeGeneral {
@@ -149,17 +162,30 @@ impl Component for Me1LeGeneral {
             .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
             .expect("no database provider");
 
-        Me1LeGeneral { _db_handle, player_class_db: databases.get_me1_le_player_classes() }
+        Me1LeGeneral {
+            _db_handle,
+            player_class_db: databases.get_me1_le_player_classes(),
+            synced_squad: None,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
-        let Me1LeSaveData { player, squad, plot, .. } = &mut *ctx.props().save_game_mut();
-        let (mut player, mut plot) = (player.borrow_mut(), plot.borrow_mut());
         match msg {
             Msg::DatabaseLoaded(dbs) => {
                 self.player_class_db = dbs.get_me1_le_player_classes();
-                true
+                return true;
             }
+            Msg::SyncSquadLevel => {
+                self.synced_squad = Some(ctx.props().save_game().sync_squad_level());
+                document::edited(ctx);
+                return true;
+            }
+            _ => (),
+        }
+
+        let Me1LeSaveData { player, squad, plot, .. } = &mut *ctx.props().save_game_mut();
+        let (mut player, mut plot) = (player.borrow_mut(), plot.borrow_mut());
+        let render = match msg {
             Msg::Gender(gender) => {
                 let gender = gender != 0;
 
@@ -198,6 +224,16 @@ impl Component for Me1LeGeneral {
                 }
                 false
             }
+            Msg::AutoLevelUpTemplate(class_idx) => {
+                let player_class = Me1LeClass::from(class_idx);
+                let template = self.player_class_db.as_ref().and_then(|player_class_db| {
+                    player_class_db.iter().find(|class| class.player_class == player_class)
+                });
+                if let Some(template) = template {
+                    player.set_auto_levelup_template_id(template.auto_levelup_template_id);
+                }
+                true
+            }
             Msg::ResetTalents(tag) => {
                 let (talent_points, complex_talents) = if let Some(tag) = tag {
                     // Squad mate
@@ -406,7 +442,9 @@ impl Component for Me1LeGeneral {
                 true
             }
             _ => unreachable!(),
-        }
+        };
+        document::edited(ctx);
+        render
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
@@ -419,12 +457,18 @@ impl Component for Me1LeGeneral {
                         { Self::role_play(ctx, save_game.player()) }
                         { Self::gameplay(ctx, save_game.player()) }
                         { Self::bonus_talents(ctx, player_class_db, save_game.player()) }
+                        { Self::presentation(save_game.player(), save_game.squad()) }
                     </div>
                     <div class="flex-1 pl-1 flex flex-col gap-1">
-                        { Self::general(ctx, save_game.player().game_options()) }
+                        { Self::general(ctx, player_class_db, save_game.player()) }
+                        <SaveInfo
+                            timestamp={RcRef::clone(&save_game.timestamp)}
+                            created_date={RcRef::clone(&save_game.created_date)}
+                            seconds_played={NumberType::Int(RcCell::clone(&save_game.seconds_played))}
+                        />
                         { Self::morality(save_game.plot()) }
                         { Self::resources(save_game.player()) }
-                        { Self::squad(ctx, save_game.squad()) }
+                        { self.squad(ctx, save_game.squad()) }
                     </div>
                 </div>
             }
@@ -442,7 +486,7 @@ impl Me1LeGeneral {
         let genders: &'static [&'static str] = &["Male", "Female"];
         html! {
             <Table title="Role-Play">
-                { player.first_name.view("Name") }
+                <FirstName value={RcRef::clone(&player.first_name)} />
                 <div class="flex items-center gap-1 cursor-default">
                     <Select
                         options={genders}
@@ -573,10 +617,36 @@ impl Me1LeGeneral {
         }
     }
 
-    fn general(ctx: &Context<Self>, game_options: Ref<'_, Vec<RcCell<i32>>>) -> Html {
+    fn general(
+        ctx: &Context<Self>, player_class_db: &Me1LePlayerClassDb, player: Ref<'_, Player>,
+    ) -> Html {
         let difficulty: &'static [&'static str] =
             &["Casual", "Normal", "Veteran", "Hardcore", "Insanity"];
-        let current_difficulty = game_options.get(0).map(|d| d.get() as usize).unwrap_or_default();
+        let current_difficulty =
+            player.game_options().get(0).map(|d| d.get() as usize).unwrap_or_default();
+
+        // Each class has its own template, anything else is left to the raw data
+        let template_id = player.auto_levelup_template_id();
+        let auto_levelup = player_class_db
+            .iter()
+            .find(|class| class.auto_levelup_template_id == template_id)
+            .map(|class| {
+                html! {
+                    <div class="flex items-center gap-1 cursor-default">
+                        <Select
+                            options={Me1LeClass::variants()}
+                            current_idx={class.player_class.clone() as usize}
+                            onselect={ctx.link().callback(Msg::AutoLevelUpTemplate)}
+                        />
+                        { "Auto Level Up" }
+                        <Helper text=
+                            "How the game spends your talent points when the auto level up is enabled. \
+                            Changing the class resets it to the one of the new class."
+                        />
+                    </div>
+                }
+            });
+
         html! {
             <Table title="General">
                 <div class="flex items-center gap-1 cursor-default">
@@ -587,6 +657,32 @@ impl Me1LeGeneral {
                     />
                     { "Difficulty" }
                 </div>
+                { for auto_levelup }
+            </Table>
+        }
+    }
+
+    fn presentation(player: Ref<'_, Player>, squad: Ref<'_, Vec<RcRef<Henchman>>>) -> Html {
+        let helmets = squad.iter().filter_map(|henchman| {
+            let henchman = henchman.borrow();
+            HENCHMEN
+                .iter()
+                .find(|(tag, _)| *henchman.tag() == *tag)
+                .map(|(_, name)| henchman.helmet_shown.view(&format!("{}'s helmet shown", name)))
+        });
+
+        html! {
+            <Table title="Presentation">
+                <div class="flex items-center gap-1">
+                    { player.armor_overridden.view("Armor appearance overridden") }
+                    <Helper text=
+                        "Stored by the Legendary Edition when another outfit is shown instead of \
+                        Shepard's armor, like the casual clothes aboard the Normandy.\n\
+                        The helmets are shown or hidden with the option of the squad screen."
+                    />
+                </div>
+                { player.helmet_shown.view("Shepard's helmet shown") }
+                { for helmets }
             </Table>
         }
     }
@@ -604,7 +700,11 @@ impl Me1LeGeneral {
         let Player { credits, medigel, grenades, omnigel, .. } = &*player;
         html! {
             <Table title="Resources">
-                { credits.view("Credits") }
+                <InputNumber
+                    label="Credits"
+                    value={NumberType::Int(RcCell::clone(credits))}
+                    step={1000.0}
+                />
                 { medigel.view("Medigel") }
                 { grenades.view("Grenades") }
                 { omnigel.view("Omnigel") }
@@ -612,18 +712,9 @@ impl Me1LeGeneral {
         }
     }
 
-    fn squad(ctx: &Context<Self>, squad: Ref<'_, Vec<RcRef<Henchman>>>) -> Html {
-        let characters = [
-            ("hench_humanfemale", "Ashley"),
-            ("hench_turian", "Garrus"),
-            ("hench_humanmale", "Kaidan"),
-            ("hench_asari", "Liara"),
-            ("hench_quarian", "Tali"),
-            ("hench_krogan", "Wrex"),
-        ];
-
+    fn squad(&self, ctx: &Context<Self>, squad: Ref<'_, Vec<RcRef<Henchman>>>) -> Html {
         let characters = squad.iter().filter_map(|character| {
-            characters.iter().find_map(|&(tag, name)| {
+            HENCHMEN.iter().find_map(|&(tag, name)| {
                 (*character.borrow().tag() == tag).then(|| {
                     html! {
                         <button class="button" onclick={ctx.link().callback(move |_| Msg::ResetTalents(Some(tag)))}>
@@ -634,8 +725,25 @@ impl Me1LeGeneral {
             })
         });
 
+        let synced = self.synced_squad.map(|synced| match synced {
+            0 => "Everyone is up to level".to_owned(),
+            1 => "1 squad member leveled up".to_owned(),
+            _ => format!("{} squad members leveled up", synced),
+        });
+
         html! {
             <Table title="Squad">
+                <div class="flex items-center gap-1">
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::SyncSquadLevel)}>
+                        {"Sync squad level to Shepard"}
+                    </button>
+                    <Helper text=
+                        "Raise every squad member below Shepard's level to it, with 2 talent points \
+                        for each level gained to spend in game.\n\
+                        The experience is shared by the whole squad."
+                    />
+                    { for synced }
+                </div>
                 { for characters }
             </Table>
         }
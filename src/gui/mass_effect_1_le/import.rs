@@ -0,0 +1,91 @@
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{Helper, Table},
+    save_data::mass_effect_1_le::import::{Me1Import, NOT_IMPORTED},
+    services::save_handler::{Action, SaveHandler},
+};
+
+pub enum Msg {
+    SaveHandler(SaveHandler),
+    Open,
+    Imported(Me1Import),
+}
+
+pub struct Me1LeImport {
+    _save_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    import: Option<Me1Import>,
+}
+
+impl Component for Me1LeImport {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+
+        Me1LeImport { _save_handle, save_handler, import: None }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SaveHandler(save_handler) => {
+                self.save_handler = save_handler;
+                false
+            }
+            Msg::Open => {
+                let callback = ctx.link().callback(Msg::Imported);
+                self.save_handler.action(Action::ImportMe1Save(callback));
+                false
+            }
+            Msg::Imported(import) => {
+                self.import = Some(import);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+
+        let report = self.import.as_ref().map(|import| {
+            let Me1Import { imported, missing } = import;
+            let missing = (!missing.is_empty()).then(|| {
+                html! {
+                    <p class="text-title-bar-close">
+                        { format!("Not found in the original save: {}", missing.join(", ")) }
+                    </p>
+                }
+            });
+            html! {
+                <>
+                    <p>{ format!("Imported: {}", imported.join(", ")) }</p>
+                    { for missing }
+                </>
+            }
+        });
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                <Table title="Import a Mass Effect 1 save">
+                    <div class="flex items-center gap-2">
+                        <button class="button" onclick={link.callback(|_| Msg::Open)}>
+                            { "Open original save" }
+                        </button>
+                        <Helper text=
+                            "Carries a career of the original Mass Effect 1 over to this save: the plots, then Shepard and the resources.\n\
+                            Shepard keeps the class of this save, the talents are only imported from a save of the same class.\n\
+                            Save under a new name to keep this one."
+                        />
+                    </div>
+                    <p>{ format!("Left as they are in this save: {}", NOT_IMPORTED.join(", ")) }</p>
+                    { for report }
+                </Table>
+            </div>
+        }
+    }
+}
@@ -3,7 +3,7 @@ use std::{cell::Ref, rc::Rc};
 use yew::{context::ContextHandle, prelude::*};
 
 use crate::{
-    gui::components::{Select, Table},
+    gui::components::{Helper, Select, Table},
     save_data::{
         mass_effect_1_le::{
             item_db::{DbItem, Me1ItemDb},
@@ -12,18 +12,39 @@ use crate::{
         },
         RcRef,
     },
-    services::database::Databases,
+    services::{database::Databases, document},
 };
 
 mod item_select;
 pub use self::item_select::*;
 
+const ITEM_SETS: &[&str] = &[
+    "Spectre Master Gear",
+    "Aldrin Labs",
+    "Ariake Technologies",
+    "Armax Arsenal",
+    "Armali Council",
+    "Devlon Industries",
+    "Elanus Risk Control",
+    "Elkoss Combine",
+    "Geth Armory",
+    "Hahne-Kedar",
+    "Haliat Armory",
+    "Kassa Fabrication",
+    "Rosenkov Materials",
+    "Serrice Council",
+    "Sirta Foundation",
+];
+
 pub enum Msg {
     DatabaseLoaded(Databases),
     ChangeItem(RcRef<Item>, DbItem),
     ChangeItemLevel(RcRef<Item>, usize),
     RemoveItem(RcRef<Vec<RcRef<Item>>>, usize),
     AddItem(RcRef<Vec<RcRef<Item>>>),
+    ItemSet(usize),
+    ItemSetLevel(usize),
+    AddItemSet,
 }
 
 #[derive(Properties, PartialEq)]
@@ -45,6 +66,8 @@ impl Props {
 pub struct Me1LeInventory {
     _db_handle: ContextHandle<Databases>,
     item_db: Option<Rc<Me1ItemDb>>,
+    item_set: usize,
+    item_set_level: usize,
 }
 
 impl Component for Me1LeInventory {
@@ -57,10 +80,15 @@ impl Component for Me1LeInventory {
             .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
             .expect("no database provider");
 
-        Me1LeInventory { _db_handle, item_db: databases.get_me1_item_db() }
+        Me1LeInventory {
+            _db_handle,
+            item_db: databases.get_me1_item_db(),
+            item_set: 0,
+            item_set_level: ItemLevel::X as usize,
+        }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::DatabaseLoaded(dbs) => {
                 self.item_db = dbs.get_me1_item_db();
@@ -70,19 +98,66 @@ impl Component for Me1LeInventory {
                 let mut item = item.borrow_mut();
                 item.set_item_id(new_item.item_id);
                 item.set_manufacturer_id(new_item.manufacturer_id);
-                false
+                document::edited(ctx);
+                // Slot labels follow the item
+                true
             }
             Msg::ChangeItemLevel(item, item_level) => {
                 let mut item = item.borrow_mut();
                 *item.item_level_mut() = ItemLevel::from(item_level);
+                document::edited(ctx);
                 false
             }
             Msg::RemoveItem(item_list, idx) => {
                 item_list.borrow_mut().remove(idx);
+                document::edited(ctx);
                 true
             }
             Msg::AddItem(item_list) => {
                 item_list.borrow_mut().push(Default::default());
+                document::edited(ctx);
+                true
+            }
+            Msg::ItemSet(idx) => {
+                self.item_set = idx;
+                false
+            }
+            Msg::ItemSetLevel(idx) => {
+                self.item_set_level = idx;
+                false
+            }
+            Msg::AddItemSet => {
+                let item_db = match self.item_db {
+                    Some(ref item_db) => item_db,
+                    None => return false,
+                };
+
+                // Shepard is always human, asari wear human armors
+                let armor_types = Some("Human")
+                    .into_iter()
+                    .chain(ctx.props().squad().iter().map(|henchman| {
+                        match henchman.borrow().tag().as_str() {
+                            "hench_krogan" => "Krogan",
+                            "hench_quarian" => "Quarian",
+                            "hench_turian" => "Turian",
+                            _ => "Human",
+                        }
+                    }))
+                    .collect::<Vec<_>>();
+
+                let inventory = RcRef::clone(&ctx.props().player().inventory().inventory);
+                let mut inventory = inventory.borrow_mut();
+                for armor_type in armor_types {
+                    for db_item in item_db.item_set(ITEM_SETS[self.item_set], armor_type) {
+                        let mut item = Item::default();
+                        item.set_item_id(db_item.item_id);
+                        item.set_manufacturer_id(db_item.manufacturer_id);
+                        *item.item_level_mut() = ItemLevel::from(self.item_set_level);
+                        item.set_new_item(true);
+                        inventory.push(item.into());
+                    }
+                }
+                document::edited(ctx);
                 true
             }
         }
@@ -114,10 +189,19 @@ impl Component for Me1LeInventory {
 }
 
 impl Me1LeInventory {
-    fn item_view(&self, ctx: &Context<Self>, item: &RcRef<Item>) -> Html {
+    // Named after the item in it, the game doesn't tell which slot is for what
+    fn slot_view(&self, ctx: &Context<Self>, idx: usize, item: &RcRef<Item>) -> Html {
+        let current_item = DbItem {
+            item_id: item.borrow().item_id(),
+            manufacturer_id: item.borrow().manufacturer_id(),
+        };
+        let kind = self.item_db.as_ref().and_then(|item_db| item_db.item_kind(&current_item));
+        let label = kind.map(ToOwned::to_owned).unwrap_or_else(|| format!("Slot {}", idx + 1));
+
         html! {
             <div class="flex items-center gap-1 min-w-0">
                 {self.item_view_no_flex(ctx, item)}
+                <span class="whitespace-nowrap">{ label }</span>
             </div>
         }
     }
@@ -155,10 +239,12 @@ impl Me1LeInventory {
 
     fn player(&self, ctx: &Context<Self>, inventory: Ref<'_, Inventory>) -> Html {
         let equipment = inventory.equipment();
-        let equipment = equipment.iter().map(|item| self.item_view(ctx, item));
+        let equipment =
+            equipment.iter().enumerate().map(|(idx, item)| self.slot_view(ctx, idx, item));
 
         let quick_slots = inventory.quick_slots();
-        let quick_slots = quick_slots.iter().map(|item| self.item_view(ctx, item));
+        let quick_slots =
+            quick_slots.iter().enumerate().map(|(idx, item)| self.slot_view(ctx, idx, item));
         html! {
             <div class="flex flex-col gap-1">
                 <div>
@@ -190,10 +276,12 @@ impl Me1LeInventory {
             };
 
             let equipment = henchman.equipment();
-            let equipment = equipment.iter().map(|item| self.item_view(ctx, item));
+            let equipment =
+                equipment.iter().enumerate().map(|(idx, item)| self.slot_view(ctx, idx, item));
 
             let quick_slots = henchman.quick_slots();
-            let quick_slots = quick_slots.iter().map(|item| self.item_view(ctx, item));
+            let quick_slots =
+                quick_slots.iter().enumerate().map(|(idx, item)| self.slot_view(ctx, idx, item));
             html! {
                 <div class="flex flex-col gap-1 mt-1">
                     <div>
@@ -261,6 +349,32 @@ impl Me1LeInventory {
         });
         html! {
             <>
+                <Table title="Add item set">
+                    <div class="flex items-center gap-1">
+                        <Select
+                            options={ITEM_SETS}
+                            current_idx={self.item_set}
+                            onselect={link.callback(Msg::ItemSet)}
+                        />
+                        <Select
+                            options={ItemLevel::variants()}
+                            current_idx={self.item_set_level}
+                            onselect={link.callback(Msg::ItemSetLevel)}
+                            sized=false
+                        />
+                        <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
+                            onclick={link.callback(|_| Msg::AddItemSet)}
+                        >
+                            {"add"}
+                        </button>
+                        <Helper text=
+                            "Add the armor and weapons of this manufacturer for Shepard and every squad member \
+                            to your inventory.\n\
+                            Each of them gets the armors that fit them, so a set without krogan armor \
+                            only gives weapons to Wrex."
+                        />
+                    </div>
+                </Table>
                 <Table title="Inventory">
                     { for inventory }
                     <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
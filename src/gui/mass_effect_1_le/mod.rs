@@ -3,7 +3,7 @@ use yew::prelude::*;
 use crate::{
     gui::{
         components::{raw_ui::RawUiStruct, Table},
-        raw_ui::{RawUi, RawUiChildren},
+        raw_ui::{RawUi, RawUiChildren, RawUiRenderer},
     },
     save_data::{
         mass_effect_1_le::{
@@ -16,11 +16,22 @@ use crate::{
 
 mod bonus_talents;
 mod general;
+mod import;
 mod inventory;
 
-pub use self::{general::*, inventory::*};
+pub use self::{general::*, import::*, inventory::*};
 
 impl RawUi for RcRef<Me1LeSaveData> {
+    // The fields of the `NoExport` in place of it, the last field
+    fn render<R: RawUiRenderer>(&self, renderer: &mut R, _: &str, _: bool) -> R::Output {
+        let mut fields = self.render_children(renderer);
+        fields.pop();
+        if let Some(no_export) = self.borrow().no_export().as_ref() {
+            fields.extend(no_export.render_children(renderer));
+        }
+        renderer.root(fields)
+    }
+
     fn view(&self, _: &str) -> yew::Html {
         let no_export = self
             .borrow()
@@ -42,12 +53,20 @@ impl RawUi for RcRef<Me1LeSaveData> {
 }
 
 impl RawUi for RcRef<NoExport> {
+    fn render<R: RawUiRenderer>(&self, renderer: &mut R, label: &str, _: bool) -> R::Output {
+        renderer.opaque(label, Some(self))
+    }
+
     fn view(&self, _: &str) -> yew::Html {
         Default::default()
     }
 }
 
 impl RawUi for RcRef<BaseObject> {
+    fn render<R: RawUiRenderer>(&self, renderer: &mut R, label: &str, _: bool) -> R::Output {
+        renderer.opaque(label, Some(self))
+    }
+
     fn view(&self, label: &str) -> yew::Html {
         let BaseObject { _class_name, owner_name, owner_class, _object } = &*self.borrow();
 
@@ -84,6 +103,10 @@ impl RawUi for RcRef<BaseObject> {
 }
 
 impl RawUi for RcRef<OptionObjectProxy> {
+    fn render<R: RawUiRenderer>(&self, renderer: &mut R, label: &str, _: bool) -> R::Output {
+        renderer.opaque(label, Some(self))
+    }
+
     fn view(&self, label: &str) -> yew::Html {
         self.borrow().proxy.view(label)
     }
@@ -0,0 +1,102 @@
+use yew::prelude::*;
+
+use crate::{
+    gui::components::{CheckBox, Table},
+    save_data::{mass_effect_2::DependentDlc, RcCell, RcRef},
+    services::{document, logger},
+};
+
+pub enum Msg {
+    Installed(i32, bool),
+    Fix,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub dlcs: RcRef<Vec<RcRef<DependentDlc>>>,
+}
+
+pub struct Me2Dlc {
+    // Module ids of the DLC the user doesn't have
+    uninstalled: Vec<i32>,
+}
+
+impl Component for Me2Dlc {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Me2Dlc { uninstalled: Vec::new() }
+    }
+
+    fn changed(&mut self, _ctx: &Context<Self>) -> bool {
+        self.uninstalled.clear();
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Installed(id, installed) => {
+                self.uninstalled.retain(|&uninstalled| uninstalled != id);
+                if !installed {
+                    self.uninstalled.push(id);
+                }
+                true
+            }
+            Msg::Fix => {
+                let mut dlcs = ctx.props().dlcs.borrow_mut();
+                let before = dlcs.len();
+                dlcs.retain(|dlc| !self.uninstalled.contains(&dlc.borrow().id()));
+                logger::info(format!(
+                    "Removed {} DLC references, the save loads without them now",
+                    before - dlcs.len()
+                ));
+                self.uninstalled.clear();
+                document::edited(ctx);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let dlcs = ctx.props().dlcs.borrow();
+        let rows = dlcs.iter().map(|dlc| {
+            let dlc = dlc.borrow();
+            let id = dlc.id();
+            let title = format!("Module {}: {}", id, dlc.name());
+            let onchange = ctx.link().callback(move |installed| Msg::Installed(id, installed));
+            html! {
+                <div class="flex items-center gap-1" {title}>
+                    <CheckBox
+                        label={dlc.title()}
+                        value={RcCell::new(!self.uninstalled.contains(&id))}
+                        {onchange}
+                    />
+                </div>
+            }
+        });
+        let empty = dlcs.is_empty().then(|| {
+            html! { <p class="text-fg/50">{ "The save doesn't need any DLC." }</p> }
+        });
+        let fix = (!self.uninstalled.is_empty()).then(|| {
+            html! {
+                <button class="button" onclick={ctx.link().callback(|_| Msg::Fix)}>
+                    { format!("Fix DLC mismatch ({} to remove)", self.uninstalled.len()) }
+                </button>
+            }
+        });
+
+        html! {
+            <Table title="DLC" opened={false} helper=
+                "The DLC the save was made with, the game refuses to load it with a red error \
+                as soon as one of them is missing.\n\
+                Untick the DLC that aren't installed then `Fix DLC mismatch` to remove them from \
+                the save. Their missions and items are lost, the rest of the save is kept."
+            >
+                { for rows }
+                { for empty }
+                { for fix }
+            </Table>
+        }
+    }
+}
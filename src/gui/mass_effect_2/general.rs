@@ -4,22 +4,28 @@ use yew::prelude::*;
 
 use crate::{
     gui::{
-        components::{Helper, InputText, Select, Table},
+        components::{Helper, InputNumber, InputText, NumberType, Select, Table},
         raw_ui::RawUi,
-        shared::{BonusPowerType, BonusPowers},
+        shared::{BonusPowerType, BonusPowers, FirstName, PointsCheck, PowerRanks, SaveInfo},
     },
     save_data::{
-        mass_effect_2::{player::Player, Difficulty},
+        mass_effect_2::{
+            player::{Player, Power},
+            squad::Henchman,
+            Difficulty,
+        },
         shared::{
-            player::{Notoriety, Origin},
+            player::{Notoriety, Origin, WeaponLoadout},
             plot::PlotTable,
+            power_points::PointRules,
             EndGameState,
         },
-        RcRef,
+        RcCell, RcRef,
     },
+    services::document,
 };
 
-use super::Me2Type;
+use super::{Me2Dlc, Me2Type};
 
 #[derive(Clone, RawUi)]
 enum Me2Class {
@@ -44,11 +50,128 @@ impl Me2Class {
     }
 }
 
+#[derive(Clone, Copy)]
+pub enum WeaponSlot {
+    AssaultRifle,
+    Shotgun,
+    SniperRifle,
+    SubmachineGun,
+    Pistol,
+    HeavyWeapon,
+}
+
+impl WeaponSlot {
+    fn all() -> [WeaponSlot; 6] {
+        [
+            WeaponSlot::AssaultRifle,
+            WeaponSlot::Shotgun,
+            WeaponSlot::SniperRifle,
+            WeaponSlot::SubmachineGun,
+            WeaponSlot::Pistol,
+            WeaponSlot::HeavyWeapon,
+        ]
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            WeaponSlot::AssaultRifle => "Assault Rifle",
+            WeaponSlot::Shotgun => "Shotgun",
+            WeaponSlot::SniperRifle => "Sniper Rifle",
+            WeaponSlot::SubmachineGun => "Submachine Gun",
+            WeaponSlot::Pistol => "Pistol",
+            WeaponSlot::HeavyWeapon => "Heavy Weapon",
+        }
+    }
+
+    // Same order as `class_names`, an empty class name is an empty slot
+    fn variants(self) -> &'static [&'static str] {
+        match self {
+            WeaponSlot::AssaultRifle => &[
+                "None",
+                "Assault Rifle",
+                "Geth Pulse Rifle",
+                "Collector Assault Rifle",
+                "Desert Assault Rifle",
+            ],
+            WeaponSlot::Shotgun => &[
+                "None",
+                "Shotgun",
+                "Flak Gun",
+                "Heavy Shotgun",
+                "Geth Shotgun",
+                "Cerberus Shotgun",
+            ],
+            WeaponSlot::SniperRifle => {
+                &["None", "Sniper Rifle", "Mass Cannon", "Anti-Mat Rifle", "Incisor Sniper Rifle"]
+            }
+            WeaponSlot::SubmachineGun => &["None", "Auto Pistol", "SMG", "Tactical Machine Pistol"],
+            WeaponSlot::Pistol => &["None", "Heavy Pistol", "Hand Cannon", "Laser Pistol"],
+            WeaponSlot::HeavyWeapon => &["None", "Grenade Launcher", "Nuke Launcher"],
+        }
+    }
+
+    fn class_names(self) -> &'static [&'static str] {
+        match self {
+            WeaponSlot::AssaultRifle => &[
+                "",
+                "SFXGameContent_Inventory.SFXWeapon_AssaultRifle",
+                "SFXGameContent_Inventory.SFXWeapon_GethPulseRifle",
+                "SFXGameContentDLC_PRE_Collectors.SFXWeapon_CollectorAssaultRifle_Player",
+                "SFXGameContentDLC_Desert.SFXWeapon_DesertAssaultRifle",
+            ],
+            WeaponSlot::Shotgun => &[
+                "",
+                "SFXGameContent_Inventory.SFXWeapon_Shotgun",
+                "SFXGameContent_Inventory.SFXWeapon_FlakGun",
+                "SFXGameContent_Inventory.SFXWeapon_HeavyShotgun",
+                "SFXGameContentDLC_MCR_02.SFXWeapon_GethShotgun",
+                "SFXGameContentDLC_PRE_Cerberus.SFXWeapon_CerberusShotgun",
+            ],
+            WeaponSlot::SniperRifle => &[
+                "",
+                "SFXGameContent_Inventory.SFXWeapon_SniperRifle",
+                "SFXGameContent_Inventory.SFXWeapon_MassCannon",
+                "SFXGameContent_Inventory.SFXWeapon_AntiMatRifle",
+                "SFXGameContentDLC_CER_02.SFXWeapon_IncisorSniperRifle_CER_02",
+            ],
+            WeaponSlot::SubmachineGun => &[
+                "",
+                "SFXGameContent_Inventory.SFXWeapon_AutoPistol",
+                "SFXGameContent_Inventory.SFXWeapon_SMG",
+                "SFXGameContentDLC_HEN_MT.SFXWeapon_TacticalMachinePistol",
+            ],
+            WeaponSlot::Pistol => &[
+                "",
+                "SFXGameContent_Inventory.SFXWeapon_HeavyPistol",
+                "SFXGameContent_Inventory.SFXWeapon_HandCannon",
+                "SFXGameContentDLC_Pistol.SFXWeapon_LaserPistol",
+            ],
+            WeaponSlot::HeavyWeapon => &[
+                "",
+                "SFXGameContent_Inventory.SFXHeavyWeapon_GrenadeLauncher",
+                "SFXGameContent_Inventory.SFXHeavyWeapon_NukeLauncher",
+            ],
+        }
+    }
+
+    fn class_name(self, loadout: &WeaponLoadout) -> &RcRef<String> {
+        match self {
+            WeaponSlot::AssaultRifle => &loadout.assault_rifle,
+            WeaponSlot::Shotgun => &loadout.shotgun,
+            WeaponSlot::SniperRifle => &loadout.sniper_rifle,
+            WeaponSlot::SubmachineGun => &loadout.submachine_gun,
+            WeaponSlot::Pistol => &loadout.pistol,
+            WeaponSlot::HeavyWeapon => &loadout.heavy_weapon,
+        }
+    }
+}
+
 pub enum Msg {
     Gender(usize),
     Origin(usize),
     Notoriety(usize),
     PlayerClass(usize),
+    Weapon(RcRef<WeaponLoadout>, WeaponSlot, usize),
 }
 
 #[derive(Properties, PartialEq)]
@@ -80,7 +203,7 @@ impl Component for Me2General {
         let (mut player, mut me1_plot, mut plot) =
             (player.borrow_mut(), me1_plot.borrow_mut(), plot.borrow_mut());
 
-        match msg {
+        let render = match msg {
             Msg::Gender(gender) => {
                 let gender = gender != 0;
 
@@ -201,30 +324,46 @@ impl Component for Me2General {
                 *player.class_name_mut() = Me2Class::names()[class_idx].to_owned();
                 false
             }
-        }
+            Msg::Weapon(loadout, slot, weapon_idx) => {
+                let loadout = loadout.borrow();
+                *slot.class_name(&loadout).borrow_mut() = slot.class_names()[weapon_idx].to_owned();
+                false
+            }
+        };
+        document::edited(ctx);
+        render
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let (difficulty, end_game_state, player, plot) = match ctx.props().save_game {
-            Me2Type::Vanilla(ref me2) => {
-                let me2 = me2.borrow();
-                (
-                    RcRef::clone(&me2.difficulty),
-                    RcRef::clone(&me2.end_game_state),
-                    RcRef::clone(&me2.player),
-                    RcRef::clone(&me2.plot),
-                )
-            }
-            Me2Type::Legendary(ref me2) => {
-                let me2 = me2.borrow();
-                (
-                    RcRef::clone(&me2.difficulty),
-                    RcRef::clone(&me2.end_game_state),
-                    RcRef::clone(&me2.player),
-                    RcRef::clone(&me2.plot),
-                )
-            }
-        };
+        let (difficulty, end_game_state, player, squad, plot, timestamp, seconds_played, dlcs) =
+            match ctx.props().save_game {
+                Me2Type::Vanilla(ref me2) => {
+                    let me2 = me2.borrow();
+                    (
+                        RcRef::clone(&me2.difficulty),
+                        RcRef::clone(&me2.end_game_state),
+                        RcRef::clone(&me2.player),
+                        RcRef::clone(&me2.squad),
+                        RcRef::clone(&me2.plot),
+                        RcRef::clone(&me2.timestamp),
+                        RcCell::clone(&me2.seconds_played),
+                        RcRef::clone(&me2.dependant_dlcs),
+                    )
+                }
+                Me2Type::Legendary(ref me2) => {
+                    let me2 = me2.borrow();
+                    (
+                        RcRef::clone(&me2.difficulty),
+                        RcRef::clone(&me2.end_game_state),
+                        RcRef::clone(&me2.player),
+                        RcRef::clone(&me2.squad),
+                        RcRef::clone(&me2.plot),
+                        RcRef::clone(&me2.timestamp),
+                        RcCell::clone(&me2.seconds_played),
+                        RcRef::clone(&me2.dependant_dlcs),
+                    )
+                }
+            };
 
         html! {
             <div class="flex divide-solid divide-x divide-default-border">
@@ -233,10 +372,14 @@ impl Component for Me2General {
                     { Self::morality(plot.borrow()) }
                     { Self::gameplay(ctx, player.borrow()) }
                     { Self::resources(player.borrow()) }
+                    { Self::power_ranks(player.borrow(), squad.borrow()) }
                 </div>
                 <div class="flex-1 pl-1 flex flex-col gap-1">
                     { Self::general(difficulty, end_game_state) }
+                    <SaveInfo {timestamp} seconds_played={NumberType::Float(seconds_played)} />
                     { Self::bonus_powers(player.borrow()) }
+                    { Self::weapon_loadouts(ctx, squad.borrow()) }
+                    <Me2Dlc {dlcs} />
                 </div>
             </div>
         }
@@ -249,7 +392,7 @@ impl Me2General {
         let genders: &'static [&'static str] = &["Male", "Female"];
         html! {
             <Table title="Role-Play">
-                { player.first_name.view("Name") }
+                <FirstName value={RcRef::clone(&player.first_name)} />
                 <div class="flex items-center gap-1 cursor-default">
                     <Select
                         options={genders}
@@ -317,9 +460,17 @@ impl Me2General {
                     {"Class"}
                 </div>
                 { level.view("Level") }
-                { current_xp.view("Current XP") }
+                <InputNumber
+                    label="Current XP"
+                    value={NumberType::Float(RcCell::clone(current_xp))}
+                    min={0.0}
+                />
                 { talent_points.view("Talent Points") }
-                { credits.view("Credits") }
+                <InputNumber
+                    label="Credits"
+                    value={NumberType::Int(RcCell::clone(credits))}
+                    step={1000.0}
+                />
                 { medigel.view("Medigel") }
             </Table>
         }
@@ -335,11 +486,108 @@ impl Me2General {
                 { palladium.view("Palladium") }
                 { platinum.view("Platinum") }
                 { probes.view("Probes") }
-                { current_fuel.view("Current Fuel") }
+                <InputNumber
+                    label="Current Fuel"
+                    value={NumberType::Float(RcCell::clone(current_fuel))}
+                    min={0.0}
+                />
             </Table>
         }
     }
 
+    fn power_ranks(player: Ref<'_, Player>, squad: Ref<'_, Vec<RcRef<Henchman>>>) -> Html {
+        fn ranks(powers: Ref<'_, Vec<RcRef<Power>>>) -> Vec<(String, RcCell<f32>)> {
+            powers
+                .iter()
+                .map(|power| {
+                    let power = power.borrow();
+                    let name = power.name().to_owned();
+                    (name, RcCell::clone(&power.rank))
+                })
+                .collect()
+        }
+
+        let squad = squad.iter().map(|henchman| {
+            let henchman = henchman.borrow();
+            let points = PointsCheck {
+                character: henchman.tag().to_owned(),
+                rules: PointRules::ME2_HENCHMAN,
+                level: RcCell::clone(&henchman.character_level),
+                unspent: RcCell::clone(&henchman.talent_points),
+            };
+            html! {
+                <PowerRanks
+                    title={henchman.tag().to_owned()}
+                    powers={ranks(henchman.powers())}
+                    {points}
+                    max_rank={4.0}
+                    opened={false}
+                />
+            }
+        });
+
+        let points = PointsCheck {
+            character: String::from("Shepard"),
+            rules: PointRules::ME2_SHEPARD,
+            level: RcCell::clone(&player.level),
+            unspent: RcCell::clone(&player.talent_points),
+        };
+        html! {
+            <>
+                <PowerRanks title="Power Ranks" powers={ranks(player.powers())} max_rank={4.0} {points} />
+                { for squad }
+            </>
+        }
+    }
+
+    fn weapon_loadouts(ctx: &Context<Self>, squad: Ref<'_, Vec<RcRef<Henchman>>>) -> Html {
+        let squad = squad.iter().map(|henchman| {
+            let henchman = henchman.borrow();
+            let loadout = RcRef::clone(&henchman.weapon_loadout);
+
+            let slots = WeaponSlot::all().into_iter().map(|slot| {
+                let class_name = RcRef::clone(slot.class_name(&loadout.borrow()));
+                let weapon_idx = slot
+                    .class_names()
+                    .iter()
+                    .position(|name| class_name.borrow().eq_ignore_ascii_case(name));
+
+                match weapon_idx {
+                    Some(weapon_idx) => {
+                        let loadout = RcRef::clone(&loadout);
+                        html! {
+                            <div class="flex items-center gap-1 cursor-default">
+                                <Select
+                                    options={slot.variants()}
+                                    current_idx={weapon_idx}
+                                    onselect={ctx.link().callback(move |idx| {
+                                        Msg::Weapon(RcRef::clone(&loadout), slot, idx)
+                                    })}
+                                />
+                                { slot.label() }
+                            </div>
+                        }
+                    }
+                    // Unknown weapon, most likely from a mod
+                    None => html! {
+                        <InputText label={slot.label()} value={class_name} />
+                    },
+                }
+            });
+
+            html! {
+                <Table title={format!("{} Weapons", henchman.tag())} opened={false} helper=
+                    "The weapon carried by this squad member for each weapon type, `None` leaves the slot empty. \
+                    A squad member can only use the weapon types they are trained with."
+                >
+                    { for slots }
+                </Table>
+            }
+        });
+
+        squad.collect()
+    }
+
     fn general(difficulty: RcRef<Difficulty>, end_game_state: RcRef<EndGameState>) -> Html {
         html! {
             <Table title="General">
@@ -1,8 +1,9 @@
+mod dlc;
 mod general;
 mod plot;
 mod raw_plot;
 
-pub use self::{general::*, plot::*, raw_plot::*};
+pub use self::{dlc::*, general::*, plot::*, raw_plot::*};
 
 use crate::save_data::{
     mass_effect_2::{Me2LeSaveGame, Me2SaveGame},
@@ -6,7 +6,7 @@ use crate::{
         components::{Helper, Tab, TabBar},
         format_code,
         mass_effect_1::Me1Plot,
-        shared::{IntPlotType, PlotCategory},
+        shared::{IntPlotType, PlotCategory, SquadFlags},
         Theme,
     },
     save_data::{
@@ -33,12 +33,15 @@ pub fn me2_plot(props: &Props) -> Html {
         let Me2PlotDb {
             player,
             crew,
+            squad,
             romance,
             missions,
             loyalty_missions,
+            dlc,
             research_upgrades,
             rewards,
             captains_cabin,
+            wardrobe,
             imported_me1,
         } = &*plot_db;
 
@@ -63,6 +66,7 @@ pub fn me2_plot(props: &Props) -> Html {
             ("Romance", romance),
             ("Missions", missions),
             ("Loyalty missions", loyalty_missions),
+            ("DLC", dlc),
             ("Research / Upgrades", research_upgrades),
         ];
 
@@ -112,6 +116,9 @@ pub fn me2_plot(props: &Props) -> Html {
                         category={player.clone()}
                     />
                 </Tab>
+                <Tab title="Squad">
+                    <SquadFlags booleans={RcRef::clone(booleans)} squad={squad.clone()} />
+                </Tab>
                 { for categories }
                 <Tab title="Captain's cabin">
                     <PlotCategory
@@ -120,6 +127,15 @@ pub fn me2_plot(props: &Props) -> Html {
                         category={captains_cabin.clone()}
                     />
                 </Tab>
+                <Tab title="Wardrobe">
+                    <div class="flex-auto flex flex-col gap-1">
+                        <div>
+                            <p>{ format_code("Entries marked `[DLC]` need their content to be installed, the game ignores them otherwise.") }</p>
+                            <hr class="border-t border-default-border" />
+                        </div>
+                        { for view_categories(wardrobe) }
+                    </div>
+                </Tab>
                 <Tab title="Rewards">
                     <PlotCategory
                         booleans={RcRef::clone(booleans)}
@@ -0,0 +1,152 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{Helper, Table},
+    save_data::{
+        mass_effect_3::continuity_db::{ContinuityFix, Me3ContinuityDb},
+        shared::plot::BitVec,
+        RcRef,
+    },
+    services::{database::Databases, document},
+};
+
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    Apply(usize),
+    ApplyAll,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub booleans: RcRef<BitVec>,
+}
+
+pub struct ContinuityFixes {
+    _databases_handle: ContextHandle<Databases>,
+    continuity_db: Option<Rc<Me3ContinuityDb>>,
+    // How many fixes were last applied, with how many plots they changed
+    applied: Option<(usize, usize)>,
+}
+
+impl Component for ContinuityFixes {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _databases_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        ContinuityFixes {
+            _databases_handle,
+            continuity_db: databases.get_me3_continuity(),
+            applied: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.continuity_db = dbs.get_me3_continuity();
+                true
+            }
+            Msg::Apply(idx) => {
+                if let Some((_, fix)) =
+                    self.continuity_db.as_ref().and_then(|db| db.fixes.get_index(idx))
+                {
+                    let changed = fix.apply(&mut ctx.props().booleans.borrow_mut());
+                    self.applied = Some((1, changed));
+                    document::edited(ctx);
+                }
+                true
+            }
+            Msg::ApplyAll => {
+                if let Some(ref continuity_db) = self.continuity_db {
+                    let mut booleans = ctx.props().booleans.borrow_mut();
+                    let needed: Vec<&ContinuityFix> = continuity_db
+                        .fixes
+                        .values()
+                        .filter(|fix| fix.is_needed(&booleans))
+                        .collect();
+                    let changed = needed.iter().map(|fix| fix.apply(&mut booleans)).sum();
+                    self.applied = Some((needed.len(), changed));
+                    document::edited(ctx);
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let continuity_db = match self.continuity_db {
+            Some(ref continuity_db) => continuity_db,
+            None => {
+                return html! {
+                    <>
+                        <p>{ "Loading database..." }</p>
+                        <hr class="border-t border-default-border" />
+                    </>
+                }
+            }
+        };
+
+        let booleans = ctx.props().booleans.borrow();
+        let needed = continuity_db.fixes.values().filter(|fix| fix.is_needed(&booleans)).count();
+        let fixes = continuity_db.fixes.iter().enumerate().map(|(idx, (name, fix))| {
+            let is_needed = fix.is_needed(&booleans);
+            let status = if is_needed { "Needed" } else { "Fine" };
+            html! {
+                <div class="flex items-center gap-2">
+                    <button class="button w-48" disabled={!is_needed}
+                        onclick={ctx.link().callback(move |_| Msg::Apply(idx))}
+                    >
+                        { name }
+                    </button>
+                    <span class="flex-auto">{ &fix.explanation }</span>
+                    <span class={classes![
+                        "whitespace-nowrap",
+                        (!is_needed).then(|| "text-fg/60"),
+                    ]}>
+                        { status }
+                    </span>
+                </div>
+            }
+        });
+
+        let applied = self.applied.map(|(fixes, changed)| {
+            html! {
+                <p class="text-fg/50">
+                    { format!("{} fixes applied, {} plots changed.", fixes, changed) }
+                </p>
+            }
+        });
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                <Table title="Continuity fixes">
+                    <div class="flex items-center gap-1">
+                        <p>{ "Set right the decisions of the previous games that came over wrong." }</p>
+                        <Helper text=
+                            "Each fix looks for a known mixup of the plots imported from Mass Effect 1 and 2, \
+                            only what it needs is changed.\n\
+                            The plots stay editable in the `Mass Effect 1` and `Mass Effect 2` tabs."
+                        />
+                    </div>
+                    { for fixes }
+                    <div class="flex items-center gap-2">
+                        <button class="button w-48" disabled={needed == 0}
+                            onclick={ctx.link().callback(|_| Msg::ApplyAll)}
+                        >
+                            { "Fix all" }
+                        </button>
+                        <span class="text-fg/60">{ format!("{} needed", needed) }</span>
+                    </div>
+                    { for applied }
+                </Table>
+            </div>
+        }
+    }
+}
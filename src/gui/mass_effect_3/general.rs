@@ -4,15 +4,24 @@ use yew::prelude::*;
 
 use crate::{
     gui::{
-        components::{Helper, InputText, Select, Table},
+        components::{Helper, InputNumber, InputText, NumberType, Select, Table},
+        mass_effect_3::Me3WarAssets,
         raw_ui::RawUi,
-        shared::{BonusPowerType, BonusPowers},
+        shared::{BonusPowerType, BonusPowers, FirstName, PointsCheck, PowerRanks, SaveInfo},
     },
     save_data::{
-        mass_effect_3::{player::Player, plot::PlotTable, Me3SaveGame},
-        shared::player::{Notoriety, Origin},
-        RcRef,
+        mass_effect_3::{
+            player::{Player, Power},
+            plot::PlotTable,
+            Me3SaveGame,
+        },
+        shared::{
+            player::{Notoriety, Origin},
+            power_points::PointRules,
+        },
+        RcCell, RcRef,
     },
+    services::document,
 };
 
 #[derive(Clone, RawUi)]
@@ -85,7 +94,7 @@ impl Component for Me3General {
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         let Me3SaveGame { player, plot, .. } = &mut *ctx.props().save_game_mut();
         let (mut player, mut plot) = (player.borrow_mut(), plot.borrow_mut());
-        match msg {
+        let render = match msg {
             Msg::Gender(gender) => {
                 let gender = gender != 0;
 
@@ -220,7 +229,9 @@ impl Component for Me3General {
                 *player.class_name_mut() = Me3Class::names()[class_idx].to_owned();
                 false
             }
-        }
+        };
+        document::edited(ctx);
+        render
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
@@ -232,10 +243,16 @@ impl Component for Me3General {
                     { Self::role_play(ctx, save_game.player()) }
                     { Self::morality(save_game.plot()) }
                     { Self::gameplay(ctx, save_game.player()) }
+                    { Self::power_ranks(&save_game) }
                 </div>
                 <div class="flex-1 pl-1 flex flex-col gap-1">
                     { Self::general(&save_game) }
+                    <SaveInfo
+                        timestamp={RcRef::clone(&save_game.timestamp)}
+                        seconds_played={NumberType::Float(RcCell::clone(&save_game.seconds_played))}
+                    />
                     { Self::bonus_powers(save_game.player()) }
+                    <Me3WarAssets player={RcRef::clone(&save_game.player)} />
                 </div>
             </div>
         }
@@ -248,7 +265,7 @@ impl Me3General {
         let genders: &'static [&'static str] = &["Male", "Female"];
         html! {
             <Table title="Role-Play">
-                { player.first_name.view("Name") }
+                <FirstName value={RcRef::clone(&player.first_name)} />
                 <div class="flex items-center gap-1 cursor-default">
                     <Select
                         options={genders}
@@ -307,6 +324,7 @@ impl Me3General {
             medigel,
             grenades,
             current_fuel,
+            health,
             ..
         } = &*player;
 
@@ -327,16 +345,81 @@ impl Me3General {
                     {"Class"}
                 </div>
                 { level.view("Level") }
-                { current_xp.view("Current XP") }
+                <InputNumber
+                    label="Current XP"
+                    value={NumberType::Float(RcCell::clone(current_xp))}
+                    min={0.0}
+                />
                 { talent_points.view("Talent Points") }
-                { credits.view("Credits") }
+                <InputNumber
+                    label="Credits"
+                    value={NumberType::Int(RcCell::clone(credits))}
+                    step={1000.0}
+                />
                 { medigel.view("Medigel") }
                 { grenades.view("Grenades") }
-                { current_fuel.view("Current Fuel") }
+                <InputNumber
+                    label="Current Fuel"
+                    value={NumberType::Float(RcCell::clone(current_fuel))}
+                    min={0.0}
+                />
+                <InputNumber
+                    label="Health"
+                    value={NumberType::Float(RcCell::clone(health))}
+                    min={0.0}
+                    helper="Current health of Shepard, the game will cap it to the maximum health."
+                />
             </Table>
         }
     }
 
+    fn power_ranks(save_game: &Ref<Me3SaveGame>) -> Html {
+        fn ranks(powers: Ref<'_, Vec<RcRef<Power>>>) -> Vec<(String, RcCell<f32>)> {
+            powers
+                .iter()
+                .map(|power| {
+                    let power = power.borrow();
+                    let name = power.name().to_owned();
+                    (name, RcCell::clone(&power.rank))
+                })
+                .collect()
+        }
+
+        let henchmen = save_game.squad();
+        let squad = henchmen.iter().map(|henchman| {
+            let henchman = henchman.borrow();
+            let points = PointsCheck {
+                character: henchman.tag().to_owned(),
+                rules: PointRules::ME3,
+                level: RcCell::clone(&henchman.character_level),
+                unspent: RcCell::clone(&henchman.talent_points),
+            };
+            html! {
+                <PowerRanks
+                    title={henchman.tag().to_owned()}
+                    powers={ranks(henchman.powers())}
+                    {points}
+                    max_rank={6.0}
+                    opened={false}
+                />
+            }
+        });
+
+        let player = save_game.player();
+        let points = PointsCheck {
+            character: String::from("Shepard"),
+            rules: PointRules::ME3,
+            level: RcCell::clone(&player.level),
+            unspent: RcCell::clone(&player.talent_points),
+        };
+        html! {
+            <>
+                <PowerRanks title="Power Ranks" powers={ranks(player.powers())} max_rank={6.0} {points} />
+                { for squad }
+            </>
+        }
+    }
+
     fn general(save_game: &Ref<Me3SaveGame>) -> Html {
         let Me3SaveGame { difficulty, end_game_state, conversation_mode, .. } = &**save_game;
         html! {
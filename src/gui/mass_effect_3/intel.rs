@@ -0,0 +1,116 @@
+use yew::prelude::*;
+
+use crate::{
+    gui::components::{CheckBox, Helper, Table},
+    save_data::{
+        mass_effect_3::plot_db::{IntelDb, IntelProblem},
+        shared::plot::BitVec,
+        RcCell, RcRef,
+    },
+    services::document,
+};
+
+pub enum Msg {
+    Change(usize, bool),
+    UnlockMissed,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub booleans: RcRef<BitVec>,
+    pub intel: IntelDb,
+}
+
+pub struct Intel {
+    // How many plots the last `Unlock missed` changed
+    unlocked: Option<usize>,
+}
+
+impl Component for Intel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Intel { unlocked: None }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let Props { booleans, intel } = ctx.props();
+        match msg {
+            Msg::Change(id, value) => {
+                booleans.borrow_mut().set_many([id], value);
+                self.unlocked = None;
+            }
+            Msg::UnlockMissed => {
+                let ids =
+                    intel.requisitions.values().filter_map(|requisition| requisition.unlocked);
+                self.unlocked = Some(booleans.borrow_mut().set_many(ids, true));
+            }
+        }
+        document::edited(ctx);
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let Props { booleans, intel } = ctx.props();
+        let is_set = |id: usize| booleans.borrow().get(id).map(|value| *value).unwrap_or(false);
+
+        let checkbox = |label: &str, id: usize| {
+            let onchange = ctx.link().callback(move |value| Msg::Change(id, value));
+            html! {
+                <CheckBox label={label.to_owned()} value={RcCell::new(is_set(id))} {onchange} />
+            }
+        };
+
+        let requisitions = intel.requisitions.iter().map(|(name, requisition)| {
+            let unlocked = match requisition.unlocked {
+                Some(id) => checkbox("Unlocked", id),
+                None => html! { <span class="text-fg/50">{ "Always available" }</span> },
+            };
+            html! {
+                <div class="flex items-center gap-2">
+                    <span class="w-48 truncate" title={name.clone()}>{ name }</span>
+                    <div class="w-36">{ unlocked }</div>
+                    { checkbox("Purchased", requisition.purchased) }
+                </div>
+            }
+        });
+
+        let problems = intel.check(&booleans.borrow()).into_iter().map(|problem| {
+            let text = match problem {
+                IntelProblem::NotUnlocked(name) => {
+                    format!("`{}` is purchased but was never unlocked.", name)
+                }
+            };
+            html! {
+                <p>
+                    <span class="text-title-bar-close select-none">{ "⚠ " }</span>
+                    { text }
+                </p>
+            }
+        });
+
+        let unlocked = self.unlocked.map(|changed| {
+            html! { <p class="text-fg/50">{ format!("{} requisitions unlocked.", changed) }</p> }
+        });
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                <Table title="Spectre requisitions">
+                    { for requisitions }
+                </Table>
+                <div class="flex items-center gap-1">
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::UnlockMissed)}>
+                        { "Unlock missed requisitions" }
+                    </button>
+                    <Helper text=
+                        "Makes every requisition of the Spectre terminal available for purchase, \
+                        as if all the intel had been turned in. Nothing gets purchased."
+                    />
+                </div>
+                { for unlocked }
+                { for problems }
+            </div>
+        }
+    }
+}
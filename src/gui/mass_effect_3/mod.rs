@@ -1,18 +1,34 @@
+mod continuity_fixes;
 mod general;
+mod intel;
 mod plot;
 mod plot_variable;
 mod raw_plot;
+mod squad_outfits;
+mod war_assets;
+mod weapon_mods;
+mod world_states;
 
-pub use self::{general::*, plot::*, plot_variable::*, raw_plot::*};
+pub use self::{
+    continuity_fixes::*, general::*, intel::*, plot::*, plot_variable::*, raw_plot::*,
+    squad_outfits::*, war_assets::*, weapon_mods::*, world_states::*,
+};
 
 use yew::prelude::*;
 
 use crate::{
-    gui::{raw_ui::RawUi, shared::Link},
+    gui::{
+        raw_ui::{RawUi, RawUiRenderer},
+        shared::Link,
+    },
     save_data::{mass_effect_3::plot::PlotTable, RcRef},
 };
 
 impl RawUi for RcRef<PlotTable> {
+    fn render<R: RawUiRenderer>(&self, renderer: &mut R, label: &str, _: bool) -> R::Output {
+        renderer.opaque(label, Some(self))
+    }
+
     fn view(&self, _: &str) -> yew::Html {
         html! {
             <Link tab="Raw Plot">{ "Raw Plot" }</Link>
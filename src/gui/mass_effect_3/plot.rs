@@ -6,8 +6,10 @@ use crate::{
         components::{Tab, TabBar},
         mass_effect_1::Me1Plot,
         mass_effect_2::Me2Plot,
-        mass_effect_3::PlotVariable,
-        shared::{IntPlotType, PlotCategory},
+        mass_effect_3::{
+            ContinuityFixes, Intel, PlotVariable, SquadOutfits, WeaponMods, WorldStates,
+        },
+        shared::{IntPlotType, PlotCategory, SquadFlags},
         Theme,
     },
     save_data::{
@@ -33,12 +35,14 @@ pub fn me3_plot(props: &Props) -> Html {
         let Me3PlotDb {
             general,
             crew,
+            squad,
             romance,
             missions,
             citadel_dlc,
             normandy,
             appearances,
             weapons_powers,
+            weapon_mods,
             intel,
         } = &*plot_db;
 
@@ -97,18 +101,32 @@ pub fn me3_plot(props: &Props) -> Html {
                         category={general.clone()}
                     />
                 </Tab>
+                <Tab title="World state">
+                    <WorldStates
+                        booleans={RcRef::clone(booleans)}
+                        integers={IntPlotType::clone(integers)}
+                    />
+                </Tab>
+                <Tab title="Continuity">
+                    <ContinuityFixes booleans={RcRef::clone(booleans)} />
+                </Tab>
+                <Tab title="Squad">
+                    <div class="flex-auto flex flex-col gap-1">
+                        <SquadFlags booleans={RcRef::clone(booleans)} squad={squad.clone()} />
+                        <SquadOutfits integers={IntPlotType::clone(integers)} />
+                    </div>
+                </Tab>
                 { for categories }
                 <Tab title="Weapons / Powers">
                     <div class="flex-auto flex flex-col gap-1">
                         { for weapons_powers }
                     </div>
                 </Tab>
+                <Tab title="Weapon mods">
+                    <WeaponMods variables={RcRef::clone(variables)} weapon_mods={weapon_mods.clone()} />
+                </Tab>
                 <Tab title="Intel">
-                    <PlotCategory
-                        booleans={RcRef::clone(booleans)}
-                        integers={IntPlotType::clone(integers)}
-                        category={intel.clone()}
-                    />
+                    <Intel booleans={RcRef::clone(booleans)} intel={intel.clone()} />
                 </Tab>
                 <Tab title="Mass Effect 2" theme={Theme::MassEffect2}>
                     <Me2Plot
@@ -5,7 +5,7 @@ use yew::prelude::*;
 use crate::{
     gui::{
         components::{Tab, TabBar},
-        shared::{FloatPlotType, IntPlotType, PlotType, RawPlot},
+        shared::{BookmarkPack, FloatPlotType, IntPlotType, PlotCoverageExport, PlotType, RawPlot},
     },
     save_data::{shared::plot::BitVec, RcRef},
     services::database::Databases,
@@ -21,20 +21,35 @@ pub struct Props {
 #[function_component(Me3RawPlot)]
 pub fn me3_raw_plot(props: &Props) -> Html {
     let dbs = use_context::<Databases>().expect("no database provider");
+    // Remounts the tabs so they read the imported bookmarks
+    let revision = use_state(|| 0_usize);
     if let Some(ref plot_db) = dbs.get_me3_raw_plot() {
         let (booleans, integers, floats) = (&props.booleans, &props.integers, &props.floats);
         html! {
-            <TabBar>
+            <>
+            <PlotCoverageExport
+                game="Mass Effect 3"
+                booleans={RcRef::clone(booleans)}
+                integers={integers.clone()}
+                floats={floats.clone()}
+                plot_db={Rc::clone(plot_db)}
+            />
+            <BookmarkPack onimported={
+                let revision = revision.clone();
+                Callback::from(move |_| revision.set(*revision + 1))
+            } />
+            <TabBar key={*revision}>
                 <Tab title="Booleans">
-                    <RawPlot plots={PlotType::Boolean(RcRef::clone(booleans))} plot_db={Rc::clone(plot_db)} />
+                    <RawPlot plots={PlotType::Boolean(RcRef::clone(booleans))} plot_db={Rc::clone(plot_db)} bookmarks_key="me3_raw_plot_bookmarks_booleans" />
                 </Tab>
                 <Tab title="Integers">
-                    <RawPlot plots={PlotType::Int(integers.clone())} plot_db={Rc::clone(plot_db)} />
+                    <RawPlot plots={PlotType::Int(integers.clone())} plot_db={Rc::clone(plot_db)} bookmarks_key="me3_raw_plot_bookmarks_integers" />
                 </Tab>
                 <Tab title="Floats">
-                    <RawPlot plots={PlotType::Float(floats.clone())} plot_db={Rc::clone(plot_db)} />
+                    <RawPlot plots={PlotType::Float(floats.clone())} plot_db={Rc::clone(plot_db)} bookmarks_key="me3_raw_plot_bookmarks_floats" />
                 </Tab>
             </TabBar>
+            </>
         }
     } else {
         html! {
@@ -0,0 +1,133 @@
+use yew::prelude::*;
+
+use crate::{
+    gui::{
+        components::{Select, Table},
+        shared::IntPlotType,
+    },
+    save_data::RcCell,
+    services::document,
+};
+
+// Name, plot integer of the outfit, outfits in the game order
+const SQUAD_OUTFITS: &[(&str, usize, &[&str])] = &[
+    ("Ashley", 10154, &["Default", "Alternate", "N7 Collector", "From Ashes"]),
+    ("EDI", 10156, &["Default", "Alternate", "N7 Collector", "Alternate Appearance Pack 1"]),
+    ("Garrus", 10155, &["Default", "Alternate", "N7 Collector", "Alternate Appearance Pack 1"]),
+    ("James", 10158, &["Default", "Alternate", "N7 Collector", "From Ashes"]),
+    ("Javik", 10157, &["Default", "Alternate"]),
+    ("Kaidan", 10153, &["Default", "Alternate", "N7 Collector", "From Ashes"]),
+    (
+        "Liara",
+        10152,
+        &["Default", "Alternate", "N7 Collector", "From Ashes", "Alternate Appearance Pack 1"],
+    ),
+    ("Tali", 10214, &["Default", "Alternate", "From Ashes"]),
+];
+
+pub enum Msg {
+    Change(usize, usize),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub integers: IntPlotType,
+}
+
+impl Props {
+    fn outfit(&self, idx: usize) -> Option<RcCell<i32>> {
+        match self.integers {
+            IntPlotType::Vec(ref vec) => vec.borrow().get(idx).map(RcCell::clone),
+            IntPlotType::IndexMap(ref index_map) => {
+                index_map.borrow().get(&(idx as i32)).map(RcCell::clone)
+            }
+        }
+    }
+}
+
+pub struct SquadOutfits;
+
+impl Component for SquadOutfits {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut this = SquadOutfits;
+        this.add_missing_plots(ctx);
+        this
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Change(plot, outfit) => {
+                if let Some(value) = ctx.props().outfit(plot) {
+                    value.set(outfit as i32);
+                }
+                document::edited(ctx);
+                true
+            }
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        self.add_missing_plots(ctx);
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let outfits = SQUAD_OUTFITS.iter().map(|&(name, plot, options)| {
+            let value = match ctx.props().outfit(plot) {
+                Some(value) => value.get(),
+                None => return Html::default(),
+            };
+            let select = match usize::try_from(value).ok().filter(|&idx| idx < options.len()) {
+                Some(current_idx) => html! {
+                    <Select
+                        {options}
+                        {current_idx}
+                        onselect={ctx.link().callback(move |idx| Msg::Change(plot, idx))}
+                    />
+                },
+                None => html! {
+                    <span class="w-2/3 text-fg/50">{ format!("Unknown outfit ({})", value) }</span>
+                },
+            };
+            html! {
+                <div class="flex items-center gap-1">
+                    { select }
+                    { name }
+                </div>
+            }
+        });
+
+        html! {
+            <Table title="Outfits">
+                { for outfits }
+                <p class="text-fg/50">
+                    { "The N7 Collector and DLC outfits need their content to be installed." }
+                </p>
+            </Table>
+        }
+    }
+}
+
+impl SquadOutfits {
+    // Never set until the outfit is changed in game
+    fn add_missing_plots(&mut self, ctx: &Context<Self>) {
+        let max = SQUAD_OUTFITS.iter().map(|&(_, plot, _)| plot).max().unwrap_or_default();
+        match ctx.props().integers {
+            IntPlotType::Vec(ref vec) => {
+                let mut vec = vec.borrow_mut();
+                if max >= vec.len() {
+                    vec.resize_with(max + 1, Default::default);
+                }
+            }
+            IntPlotType::IndexMap(ref index_map) => {
+                let mut index_map = index_map.borrow_mut();
+                for &(_, plot, _) in SQUAD_OUTFITS {
+                    index_map.entry(plot as i32).or_default();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{Helper, Table},
+    save_data::{
+        mass_effect_3::player::{Player, WarAssetBundle},
+        RcRef,
+    },
+    services::{
+        document,
+        save_handler::{Action, SaveHandler},
+    },
+};
+
+pub enum Msg {
+    Import,
+    Imported(WarAssetBundle),
+    Export,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub player: RcRef<Player>,
+}
+
+pub struct Me3WarAssets {
+    _db_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+}
+
+impl Component for Me3WarAssets {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _db_handle) =
+            ctx.link().context::<SaveHandler>(Callback::noop()).expect("no save handler provider");
+
+        Me3WarAssets { _db_handle, save_handler }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Import => {
+                let callback = ctx.link().callback(Msg::Imported);
+                self.save_handler.action(Action::ImportWarAssets(callback));
+                false
+            }
+            Msg::Imported(bundle) => {
+                ctx.props().player.borrow_mut().import_war_assets(bundle);
+                document::edited(ctx);
+                true
+            }
+            Msg::Export => {
+                let bundle = ctx.props().player.borrow().export_war_assets();
+                self.save_handler.action(Action::ExportWarAssets(bundle));
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let len = ctx.props().player.borrow().war_assets().len();
+
+        html! {
+            <Table title="Galaxy at War">
+                <div class="flex items-center gap-2">
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::Import)}>
+                        {"Import"}
+                    </button>
+                    <span>{"-"}</span>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::Export)}>
+                        {"Export"}
+                    </button>
+                    <span>{ format!("{} war assets", len) }</span>
+                    <Helper text=
+                        "Export only the war assets of this save to import them into another career, \
+                        without having to scan the galaxy again.\n\
+                        Importing replaces all the war assets of this save."
+                    />
+                </div>
+            </Table>
+        }
+    }
+}
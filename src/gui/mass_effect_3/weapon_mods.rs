@@ -0,0 +1,145 @@
+use indexmap::IndexMap;
+use yew::prelude::*;
+
+use crate::{
+    gui::components::{Helper, Select, Table},
+    save_data::{RcCell, RcRef},
+    services::document,
+};
+
+// A mod is found at level I, the bench upgrades it up to level V
+const LEVELS: &[&str] = &["Not found", "I", "II", "III", "IV", "V"];
+
+pub enum Msg {
+    ChangeLevel(String, usize),
+    UnlockAll(usize),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub variables: RcRef<IndexMap<String, RcCell<i32>>>,
+    pub weapon_mods: IndexMap<String, IndexMap<String, String>>,
+}
+
+impl Props {
+    // The game doesn't keep the case of the class names
+    fn level(&self, class_name: &str) -> usize {
+        let variables = self.variables.borrow();
+        let level = variables
+            .iter()
+            .find_map(|(key, value)| class_name.eq_ignore_ascii_case(key).then(|| value.get()))
+            .unwrap_or_default();
+        (level.max(0) as usize).min(LEVELS.len() - 1)
+    }
+
+    fn set_level(&self, class_name: &str, level: usize) {
+        let mut variables = self.variables.borrow_mut();
+        let value = variables.iter().find_map(|(key, value)| {
+            class_name.eq_ignore_ascii_case(key).then(|| RcCell::clone(value))
+        });
+        match value {
+            Some(value) => value.set(level as i32),
+            None => {
+                variables.insert(class_name.to_owned(), RcCell::new(level as i32));
+            }
+        }
+    }
+}
+
+pub struct WeaponMods {
+    // How many mods the last bulk action changed
+    unlocked: Option<usize>,
+}
+
+impl Component for WeaponMods {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        WeaponMods { unlocked: None }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            Msg::ChangeLevel(class_name, level) => {
+                props.set_level(&class_name, level);
+                document::edited(ctx);
+                self.unlocked = None;
+                true
+            }
+            Msg::UnlockAll(level) => {
+                // Never downgrades a mod
+                let mut changed = 0;
+                for class_name in props.weapon_mods.values().flat_map(|mods| mods.keys()) {
+                    if props.level(class_name) < level {
+                        props.set_level(class_name, level);
+                        changed += 1;
+                    }
+                }
+                document::edited(ctx);
+                self.unlocked = Some(changed);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let link = ctx.link();
+
+        let weapon_types = props.weapon_mods.iter().map(|(weapon_type, mods)| {
+            let levels: Vec<_> = mods
+                .iter()
+                .map(|(class_name, label)| (class_name, label, props.level(class_name)))
+                .collect();
+            let found = levels.iter().filter(|(_, _, level)| *level > 0).count();
+            let title = format!("{} ({}/{} found)", weapon_type, found, mods.len());
+
+            let mods = levels.into_iter().map(|(class_name, label, level)| {
+                let class_name = class_name.clone();
+                let onselect =
+                    link.callback(move |level| Msg::ChangeLevel(class_name.clone(), level));
+                html! {
+                    <div class="flex items-center gap-1 cursor-default">
+                        <Select options={LEVELS} current_idx={level} {onselect} />
+                        { label }
+                    </div>
+                }
+            });
+
+            html! {
+                <Table {title}>
+                    { for mods }
+                </Table>
+            }
+        });
+
+        let unlocked = self.unlocked.map(|changed| {
+            html! {
+                <span class="text-fg/50">{ format!("{} mods changed", changed) }</span>
+            }
+        });
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                <div class="flex items-center gap-1">
+                    <button class="button" onclick={link.callback(|_| Msg::UnlockAll(1))}>
+                        { "Unlock all" }
+                    </button>
+                    <button class="button" onclick={link.callback(|_| Msg::UnlockAll(LEVELS.len() - 1))}>
+                        { "Max all" }
+                    </button>
+                    <Helper text=
+                        "The mods found so far and their level on the weapon bench, whichever weapon \
+                        they are equipped on.\n\
+                        `Unlock all` finds the missing mods at level I, `Max all` upgrades every mod \
+                        to level V, neither of them lowers a mod."
+                    />
+                    { for unlocked }
+                </div>
+                { for weapon_types }
+            </div>
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{
+        components::{Helper, Table},
+        shared::IntPlotType,
+    },
+    save_data::{
+        mass_effect_3::world_state_db::{Me3WorldStateDb, WorldState},
+        shared::plot::BitVec,
+        RcCell, RcRef,
+    },
+    services::{database::Databases, document},
+};
+
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    Apply(usize),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub booleans: RcRef<BitVec>,
+    pub integers: IntPlotType,
+}
+
+pub struct WorldStates {
+    _databases_handle: ContextHandle<Databases>,
+    world_state_db: Option<Rc<Me3WorldStateDb>>,
+    // Name of the last applied preset, with how many plots it changed
+    applied: Option<(String, usize)>,
+}
+
+impl Component for WorldStates {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _databases_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        WorldStates {
+            _databases_handle,
+            world_state_db: databases.get_me3_world_states(),
+            applied: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.world_state_db = dbs.get_me3_world_states();
+                true
+            }
+            Msg::Apply(idx) => {
+                if let Some((name, world_state)) =
+                    self.world_state_db.as_ref().and_then(|db| db.presets.get_index(idx))
+                {
+                    let changed = Self::apply(ctx, world_state);
+                    document::edited(ctx);
+                    self.applied = Some((name.clone(), changed));
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let world_state_db = match self.world_state_db {
+            Some(ref world_state_db) => world_state_db,
+            None => {
+                return html! {
+                    <>
+                        <p>{ "Loading database..." }</p>
+                        <hr class="border-t border-default-border" />
+                    </>
+                }
+            }
+        };
+
+        let presets = world_state_db.presets.iter().enumerate().map(|(idx, (name, world_state))| {
+            let count = world_state.booleans().count() + world_state.integers().count();
+            html! {
+                <div class="flex items-center gap-2">
+                    <button class="button w-36" onclick={ctx.link().callback(move |_| Msg::Apply(idx))}>
+                        { name }
+                    </button>
+                    <span class="flex-auto">{ &world_state.description }</span>
+                    <span class="text-fg/60 whitespace-nowrap">{ format!("{} plots", count) }</span>
+                </div>
+            }
+        });
+
+        let applied = self.applied.as_ref().map(|(name, changed)| {
+            html! {
+                <p class="text-fg/50">{ format!("`{}` applied, {} plots changed.", name, changed) }</p>
+            }
+        });
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                <Table title="World state">
+                    <div class="flex items-center gap-1">
+                        <p>{ "Set every decision of the previous games at once." }</p>
+                        <Helper text=
+                            "For a game started without an imported save.\n\
+                            The ME1 and ME2 decisions are overwritten, what the preset doesn't cover \
+                            is left as it is. Fine tune it afterwards in the `Mass Effect 1` and `Mass Effect 2` tabs."
+                        />
+                    </div>
+                    { for presets }
+                    { for applied }
+                </Table>
+            </div>
+        }
+    }
+}
+
+impl WorldStates {
+    // Returns how many plots had another value
+    fn apply(ctx: &Context<Self>, world_state: &WorldState) -> usize {
+        let Props { booleans, integers } = ctx.props();
+        let mut changed = 0;
+
+        let mut booleans = booleans.borrow_mut();
+        for (id, value) in world_state.booleans() {
+            if id >= booleans.len() {
+                booleans.resize(id + 1, false);
+            }
+            if let Some(mut plot) = booleans.get_mut(id) {
+                if *plot != value {
+                    *plot = value;
+                    changed += 1;
+                }
+            }
+        }
+
+        for (id, value) in world_state.integers() {
+            let plot = match integers {
+                IntPlotType::Vec(vec) => {
+                    let mut vec = vec.borrow_mut();
+                    if id >= vec.len() {
+                        vec.resize_with(id + 1, Default::default);
+                    }
+                    RcCell::clone(&vec[id])
+                }
+                IntPlotType::IndexMap(index_map) => {
+                    RcCell::clone(index_map.borrow_mut().entry(id as i32).or_default())
+                }
+            };
+            if plot.get() != value {
+                plot.set(value);
+                changed += 1;
+            }
+        }
+        changed
+    }
+}
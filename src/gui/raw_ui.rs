@@ -1,12 +1,14 @@
 use std::{any::Any, fmt::Display};
 
 use indexmap::IndexMap;
+use serde::{de::DeserializeOwned, Serialize};
 use yew::prelude::*;
 
 use crate::{
     gui::components::{raw_ui::*, *},
     save_data::{
-        mass_effect_1_le::legacy::BaseObject, shared::appearance::LinearColor, Guid, RcCell, RcRef,
+        mass_effect_1_le::legacy::BaseObject, shared::appearance::LinearColor, Dummy, Guid, RcCell,
+        RcRef,
     },
 };
 
@@ -14,7 +16,13 @@ pub trait RawUi
 where
     Self: Clone + PartialEq + 'static,
 {
-    fn view(&self, label: &str) -> yew::Html;
+    // Types with a dedicated component render as `opaque` and implement `view` themselves,
+    // `opaque` of the HTML renderer goes to their `view`
+    fn render<R: RawUiRenderer>(&self, renderer: &mut R, label: &str, opened: bool) -> R::Output;
+
+    fn view(&self, label: &str) -> yew::Html {
+        self.render(&mut HtmlRenderer, label, false)
+    }
     fn view_opened(&self, label: &str, _opened: bool) -> yew::Html {
         self.view(label)
     }
@@ -24,100 +32,433 @@ pub trait RawUiChildren
 where
     Self: Clone + PartialEq + 'static,
 {
-    fn children(&self) -> Vec<yew::Html>;
+    fn render_children<R: RawUiRenderer>(&self, renderer: &mut R) -> Vec<R::Output>;
+
+    fn children(&self) -> Vec<yew::Html> {
+        self.render_children(&mut HtmlRenderer)
+    }
 }
 
-// Implémentation des types std
-impl RawUi for RcCell<u8> {
-    fn view(&self, label: &str) -> yew::Html {
+// What the derived raw UI is made of, the browser gets `HtmlRenderer`
+pub trait RawUiRenderer {
+    type Output;
+
+    fn number(&mut self, label: &str, value: NumberType) -> Self::Output;
+    // A number with its `#[raw_ui(step = .., precision = ..)]`, only the browser cares
+    fn formatted_number(
+        &mut self, label: &str, value: NumberType, _format: NumberFormat,
+    ) -> Self::Output {
+        self.number(label, value)
+    }
+    fn check_box(&mut self, label: &str, value: &RcCell<bool>) -> Self::Output;
+    fn text(&mut self, label: &str, value: &RcRef<String>) -> Self::Output;
+    fn string_ref(&mut self, label: &str, value: &RcCell<i32>) -> Self::Output;
+    fn guid(&mut self, label: &str, value: &RcRef<Guid>) -> Self::Output;
+    fn color(&mut self, label: &str, value: &RcRef<LinearColor>) -> Self::Output;
+    // Bytes nobody knows the meaning of, `key` names the region across saves
+    fn bytes<const LEN: usize>(
+        &mut self, label: &str, key: &'static str, value: &RcRef<Dummy<LEN>>,
+    ) -> Self::Output;
+    fn enumeration<E>(
+        &mut self, label: &str, variants: &'static [&'static str], value: &RcRef<E>,
+    ) -> Self::Output
+    where
+        E: From<usize> + Into<usize> + Clone + 'static;
+    fn option<T>(&mut self, label: &str, option: &RcRef<Option<T>>) -> Self::Output
+    where
+        T: RawUi;
+    fn vec<T>(&mut self, label: &str, vec: &RcRef<Vec<T>>, is_editable: bool) -> Self::Output
+    where
+        T: RawUi + Default + Display + Serialize + DeserializeOwned;
+    fn index_map<V>(&mut self, label: &str, index_map: IndexMapKeyType<V>) -> Self::Output
+    where
+        V: RawUi + Default + Serialize + DeserializeOwned;
+    fn structure(&mut self, label: &str, opened: bool, fields: Vec<Self::Output>) -> Self::Output;
+    fn root(&mut self, fields: Vec<Self::Output>) -> Self::Output;
+    // Rendered by the `view` of its own, `None` when there is nothing to render
+    fn opaque<T: RawUi>(&mut self, label: &str, value: Option<&T>) -> Self::Output;
+}
+
+pub struct HtmlRenderer;
+
+impl RawUiRenderer for HtmlRenderer {
+    type Output = yew::Html;
+
+    fn number(&mut self, label: &str, value: NumberType) -> yew::Html {
         html! {
-            <InputNumber label={label.to_owned()} value={NumberType::Byte(RcCell::clone(self))} />
+            <InputNumber label={label.to_owned()} {value} />
         }
     }
-}
 
-impl RawUi for RcCell<i32> {
-    fn view(&self, label: &str) -> yew::Html {
+    fn formatted_number(
+        &mut self, label: &str, value: NumberType, format: NumberFormat,
+    ) -> yew::Html {
+        let NumberFormat { step, precision } = format;
         html! {
-            <InputNumber label={label.to_owned()} value={NumberType::Int(RcCell::clone(self))} />
+            <InputNumber label={label.to_owned()} {value} {step} {precision} />
         }
     }
-}
 
-impl RawUi for RcCell<f32> {
-    fn view(&self, label: &str) -> yew::Html {
+    fn check_box(&mut self, label: &str, value: &RcCell<bool>) -> yew::Html {
         html! {
-            <InputNumber label={label.to_owned()} value={NumberType::Float(RcCell::clone(self))} />
+            <CheckBox label={label.to_owned()} value={RcCell::clone(value)} />
         }
     }
-}
 
-impl RawUi for RcCell<bool> {
-    fn view(&self, label: &str) -> yew::Html {
+    fn text(&mut self, label: &str, value: &RcRef<String>) -> yew::Html {
         html! {
-            <CheckBox label={label.to_owned()} value={RcCell::clone(self)} />
+            <InputText label={label.to_owned()} value={RcRef::clone(value)} />
         }
     }
-}
 
-impl RawUi for RcRef<String> {
-    fn view(&self, label: &str) -> yew::Html {
+    fn string_ref(&mut self, label: &str, value: &RcCell<i32>) -> yew::Html {
+        html! {
+            <RawUiStringRef label={label.to_owned()} value={RcCell::clone(value)} />
+        }
+    }
+
+    fn guid(&mut self, label: &str, value: &RcRef<Guid>) -> yew::Html {
         html! {
-            <InputText label={label.to_owned()} value={RcRef::clone(self)} />
+            <RawUiGuid label={label.to_owned()} guid={RcRef::clone(value)} />
         }
     }
+
+    fn color(&mut self, label: &str, value: &RcRef<LinearColor>) -> yew::Html {
+        html! {
+            <ColorPicker label={label.to_owned()} color={RcRef::clone(value)} />
+        }
+    }
+
+    fn bytes<const LEN: usize>(
+        &mut self, label: &str, key: &'static str, value: &RcRef<Dummy<LEN>>,
+    ) -> yew::Html {
+        let bytes = value.borrow().bytes().to_vec();
+        let onchange = {
+            let value = RcRef::clone(value);
+            Callback::from(move |(idx, byte): (usize, u8)| {
+                value.borrow_mut().bytes_mut()[idx] = byte;
+            })
+        };
+        html! {
+            <RawUiBytes label={label.to_owned()} region={key} {bytes} {onchange} />
+        }
+    }
+
+    fn enumeration<E>(
+        &mut self, label: &str, variants: &'static [&'static str], value: &RcRef<E>,
+    ) -> yew::Html
+    where
+        E: From<usize> + Into<usize> + Clone + 'static,
+    {
+        html! {
+            <RawUiEnum<E> label={label.to_owned()} items={variants} value={RcRef::clone(value)} />
+        }
+    }
+
+    fn option<T>(&mut self, label: &str, option: &RcRef<Option<T>>) -> yew::Html
+    where
+        T: RawUi,
+    {
+        html! {
+            <RawUiOption<T> label={label.to_owned()} option={RcRef::clone(option)} />
+        }
+    }
+
+    fn vec<T>(&mut self, label: &str, vec: &RcRef<Vec<T>>, is_editable: bool) -> yew::Html
+    where
+        T: RawUi + Default + Display + Serialize + DeserializeOwned,
+    {
+        html! {
+            <RawUiVec<T> label={label.to_owned()} vec={RcRef::clone(vec)} {is_editable} />
+        }
+    }
+
+    fn index_map<V>(&mut self, label: &str, index_map: IndexMapKeyType<V>) -> yew::Html
+    where
+        V: RawUi + Default + Serialize + DeserializeOwned,
+    {
+        html! {
+            <RawUiIndexMap<V> label={label.to_owned()} {index_map} />
+        }
+    }
+
+    fn structure(&mut self, label: &str, opened: bool, fields: Vec<yew::Html>) -> yew::Html {
+        html! {
+            <RawUiStruct label={label.to_owned()} {opened}>
+                { for fields }
+            </RawUiStruct>
+        }
+    }
+
+    fn root(&mut self, fields: Vec<yew::Html>) -> yew::Html {
+        html! {
+            <Table>
+                { for fields }
+            </Table>
+        }
+    }
+
+    fn opaque<T: RawUi>(&mut self, label: &str, value: Option<&T>) -> yew::Html {
+        value.map(|value| value.view(label)).unwrap_or_default()
+    }
+}
+
+// Implémentation des types std
+impl RawUi for RcCell<u8> {
+    fn render<R: RawUiRenderer>(&self, renderer: &mut R, label: &str, _: bool) -> R::Output {
+        renderer.number(label, NumberType::Byte(RcCell::clone(self)))
+    }
+}
+
+impl RawUi for RcCell<i32> {
+    fn render<R: RawUiRenderer>(&self, renderer: &mut R, label: &str, _: bool) -> R::Output {
+        renderer.number(label, NumberType::Int(RcCell::clone(self)))
+    }
+}
+
+impl RawUi for RcCell<f32> {
+    fn render<R: RawUiRenderer>(&self, renderer: &mut R, label: &str, _: bool) -> R::Output {
+        renderer.number(label, NumberType::Float(RcCell::clone(self)))
+    }
+}
+
+impl RawUi for RcCell<bool> {
+    fn render<R: RawUiRenderer>(&self, renderer: &mut R, label: &str, _: bool) -> R::Output {
+        renderer.check_box(label, self)
+    }
+}
+
+impl RawUi for RcRef<String> {
+    fn render<R: RawUiRenderer>(&self, renderer: &mut R, label: &str, _: bool) -> R::Output {
+        renderer.text(label, self)
+    }
 }
 
 impl<T> RawUi for RcRef<Option<T>>
 where
     T: RawUi,
 {
-    fn view(&self, label: &str) -> yew::Html {
-        html! {
-            <RawUiOption<T> label={label.to_owned()} option={RcRef::clone(self)} />
-        }
+    fn render<R: RawUiRenderer>(&self, renderer: &mut R, label: &str, _: bool) -> R::Output {
+        renderer.option(label, self)
     }
 }
 
 impl<T> RawUi for RcRef<Vec<T>>
 where
-    T: RawUi + Default + Display,
+    T: RawUi + Default + Display + Serialize + DeserializeOwned,
 {
-    fn view(&self, label: &str) -> yew::Html {
+    fn render<R: RawUiRenderer>(&self, renderer: &mut R, label: &str, _: bool) -> R::Output {
         // Make Vec of BaseObject not editable
         let is_editable = !(self as &dyn Any).is::<RcRef<Vec<RcRef<BaseObject>>>>();
-        html! {
-            <RawUiVec<T> label={label.to_owned()} vec={RcRef::clone(self)} {is_editable} />
-        }
+        renderer.vec(label, self, is_editable)
     }
 }
 
 impl<K, V> RawUi for RcRef<IndexMap<K, V>>
 where
     K: Clone + 'static,
-    V: RawUi + Default,
+    V: RawUi + Default + Serialize + DeserializeOwned,
     RcRef<IndexMap<K, V>>: Into<IndexMapKeyType<V>>,
 {
-    fn view(&self, label: &str) -> yew::Html {
-        html! {
-            <RawUiIndexMap<V> label={label.to_owned()} index_map={RcRef::clone(self).into()} />
-        }
+    fn render<R: RawUiRenderer>(&self, renderer: &mut R, label: &str, _: bool) -> R::Output {
+        renderer.index_map(label, RcRef::clone(self).into())
     }
 }
 
 // Shared
 impl RawUi for RcRef<Guid> {
-    fn view(&self, label: &str) -> yew::Html {
-        html! {
-            <RawUiGuid label={label.to_owned()} guid={RcRef::clone(self)} />
-        }
+    fn render<R: RawUiRenderer>(&self, renderer: &mut R, label: &str, _: bool) -> R::Output {
+        renderer.guid(label, self)
     }
 }
 
 impl RawUi for RcRef<LinearColor> {
-    fn view(&self, label: &str) -> yew::Html {
-        html! {
-            <ColorPicker label={label.to_owned()} color={RcRef::clone(self)} />
+    fn render<R: RawUiRenderer>(&self, renderer: &mut R, label: &str, _: bool) -> R::Output {
+        renderer.color(label, self)
+    }
+}
+
+// String refs, resolved against the loaded TLK
+pub fn view_string_ref(value: &RcCell<i32>, label: &str) -> yew::Html {
+    HtmlRenderer.string_ref(label, value)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::{mass_effect_1_le::Me1LeSaveGame, mass_effect_2::Me2SaveGame},
+        unreal,
+    };
+
+    // Only the shape of the tree, no browser needed
+    #[derive(Debug, PartialEq)]
+    struct Node {
+        label: String,
+        kind: &'static str,
+        children: Vec<Node>,
+    }
+
+    impl Node {
+        fn leaf(label: &str, kind: &'static str) -> Self {
+            Node { label: label.to_owned(), kind, children: Vec::new() }
+        }
+
+        fn child(&self, label: &str) -> &Node {
+            self.children
+                .iter()
+                .find(|child| child.label == label)
+                .unwrap_or_else(|| panic!("no `{}` in `{}`", label, self.label))
+        }
+    }
+
+    struct Outline;
+
+    impl RawUiRenderer for Outline {
+        type Output = Node;
+
+        fn number(&mut self, label: &str, value: NumberType) -> Node {
+            let kind = match value {
+                NumberType::Byte(_) => "byte",
+                NumberType::Int(_) => "int",
+                NumberType::Float(_) => "float",
+            };
+            Node::leaf(label, kind)
         }
+
+        fn check_box(&mut self, label: &str, _: &RcCell<bool>) -> Node {
+            Node::leaf(label, "bool")
+        }
+
+        fn text(&mut self, label: &str, _: &RcRef<String>) -> Node {
+            Node::leaf(label, "text")
+        }
+
+        fn string_ref(&mut self, label: &str, _: &RcCell<i32>) -> Node {
+            Node::leaf(label, "string ref")
+        }
+
+        fn guid(&mut self, label: &str, _: &RcRef<Guid>) -> Node {
+            Node::leaf(label, "guid")
+        }
+
+        fn color(&mut self, label: &str, _: &RcRef<LinearColor>) -> Node {
+            Node::leaf(label, "color")
+        }
+
+        fn bytes<const LEN: usize>(
+            &mut self, label: &str, _: &'static str, _: &RcRef<Dummy<LEN>>,
+        ) -> Node {
+            Node::leaf(label, "bytes")
+        }
+
+        fn enumeration<E>(&mut self, label: &str, _: &'static [&'static str], _: &RcRef<E>) -> Node
+        where
+            E: From<usize> + Into<usize> + Clone + 'static,
+        {
+            Node::leaf(label, "enum")
+        }
+
+        fn option<T>(&mut self, label: &str, option: &RcRef<Option<T>>) -> Node
+        where
+            T: RawUi,
+        {
+            let children =
+                option.borrow().iter().map(|some| some.render(self, label, false)).collect();
+            Node { label: label.to_owned(), kind: "option", children }
+        }
+
+        fn vec<T>(&mut self, label: &str, vec: &RcRef<Vec<T>>, _: bool) -> Node
+        where
+            T: RawUi + Default + Display + Serialize + DeserializeOwned,
+        {
+            let children = vec
+                .borrow()
+                .iter()
+                .map(|item| item.render(self, &item.to_string(), false))
+                .collect();
+            Node { label: label.to_owned(), kind: "vec", children }
+        }
+
+        fn index_map<V>(&mut self, label: &str, index_map: IndexMapKeyType<V>) -> Node
+        where
+            V: RawUi + Default + Serialize + DeserializeOwned,
+        {
+            let children = match index_map {
+                IndexMapKeyType::I32(index_map) => index_map
+                    .borrow()
+                    .iter()
+                    .map(|(key, value)| value.render(self, &key.to_string(), false))
+                    .collect(),
+                IndexMapKeyType::String(index_map) => index_map
+                    .borrow()
+                    .iter()
+                    .map(|(key, value)| value.render(self, key, false))
+                    .collect(),
+            };
+            Node { label: label.to_owned(), kind: "index map", children }
+        }
+
+        fn structure(&mut self, label: &str, _: bool, children: Vec<Node>) -> Node {
+            Node { label: label.to_owned(), kind: "struct", children }
+        }
+
+        fn root(&mut self, children: Vec<Node>) -> Node {
+            Node { label: String::new(), kind: "root", children }
+        }
+
+        fn opaque<T: RawUi>(&mut self, label: &str, _: Option<&T>) -> Node {
+            Node::leaf(label, "opaque")
+        }
+    }
+
+    #[test]
+    fn outline_me2() -> Result<()> {
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let me2_save_game: RcRef<Me2SaveGame> = unreal::Deserializer::from_bytes(&input)?;
+
+        let root = me2_save_game.render(&mut Outline, "Mass Effect 2", true);
+        assert_eq!(root.kind, "root");
+
+        // Fields in declaration order, private ones hidden
+        let labels: Vec<_> =
+            root.children.iter().take(3).map(|child| child.label.as_str()).collect();
+        assert_eq!(labels, ["Debug Name", "Seconds Played", "Disc"]);
+
+        assert_eq!(root.child("Debug Name").kind, "text");
+        assert_eq!(root.child("Seconds Played").kind, "float");
+        assert_eq!(root.child("Difficulty").kind, "enum");
+        assert_eq!(root.child("Plot").kind, "opaque");
+        // Shown by its own view, a link to the Raw Plot tab
+        let plot = RcRef::clone(&me2_save_game.borrow().plot);
+        assert_ne!(plot.render(&mut HtmlRenderer, "Plot", false), yew::Html::default());
+
+        let player = root.child("Player");
+        assert_eq!(player.kind, "struct");
+        assert_eq!(player.child("Is Female").kind, "bool");
+        assert_eq!(player.child("Current XP").kind, "float");
+        assert_eq!(player.child("Localized Last Name").kind, "string ref");
+        assert_eq!(player.child("Resources").kind, "struct");
+
+        let squad = root.child("Squad");
+        assert_eq!(squad.kind, "vec");
+        assert!(squad.children.iter().all(|henchman| henchman.kind == "struct"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn outline_unknown_bytes() -> Result<()> {
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        let player = me1_save_game.save_data.borrow().player.render(&mut Outline, "Player", true);
+        assert_eq!(player.child("Unknown").kind, "bytes");
+
+        Ok(())
     }
 }
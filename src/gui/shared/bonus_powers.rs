@@ -5,6 +5,7 @@ use crate::{
     save_data::{
         mass_effect_2::player::Power as Me2Power, mass_effect_3::player::Power as Me3Power, RcRef,
     },
+    services::document,
 };
 
 #[derive(Clone)]
@@ -85,7 +86,7 @@ impl Component for BonusPowers {
                         }
                     }
                 }
-
+                document::edited(ctx);
                 true
             }
         }
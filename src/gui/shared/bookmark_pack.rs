@@ -0,0 +1,139 @@
+use anyhow::{Context as ErrorContext, Error};
+use yew::prelude::*;
+
+use crate::{
+    gui::components::Helper,
+    services::{
+        bookmarks::BookmarkPack as Pack,
+        logger,
+        rpc::{self, Base64File, RpcFile},
+    },
+};
+
+pub enum Msg {
+    Export,
+    Exported,
+    Import,
+    Imported(Pack),
+    Cancelled,
+    Error(Error),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    // The pinned and bookmarked nodes are only read from the local storage when created
+    pub onimported: Callback<()>,
+}
+
+pub struct BookmarkPack {
+    // What the last import or export did
+    status: Option<String>,
+}
+
+impl Component for BookmarkPack {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        BookmarkPack { status: None }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Export => {
+                let pack = Pack::collect();
+                ctx.link().send_future(async move {
+                    let handle_export = async {
+                        let path = match rpc::export_bookmarks_dialog().await? {
+                            Some(path) => path,
+                            None => return Ok(None),
+                        };
+                        let output = serde_json::to_string_pretty(&pack)?;
+                        let rpc_file = RpcFile {
+                            path,
+                            file: Base64File {
+                                unencoded_size: output.len(),
+                                base64: base64::encode(output),
+                            },
+                        };
+                        rpc::save_file(rpc_file).await?;
+                        Ok::<_, Error>(Some(()))
+                    };
+                    match handle_export.await.context("Failed to export the bookmarks") {
+                        Ok(Some(())) => Msg::Exported,
+                        Ok(None) => Msg::Cancelled,
+                        Err(err) => Msg::Error(err),
+                    }
+                });
+                false
+            }
+            Msg::Exported => {
+                self.status = Some(String::from("Bookmarks exported"));
+                true
+            }
+            Msg::Import => {
+                ctx.link().send_future(async {
+                    let handle_import = async {
+                        let rpc_file = match rpc::import_bookmarks().await? {
+                            Some(rpc_file) => rpc_file,
+                            None => return Ok(None),
+                        };
+                        let file = rpc_file.file.decode()?;
+                        let pack: Pack = serde_json::from_slice(&file)?;
+                        Ok::<_, Error>(Some(pack))
+                    };
+                    match handle_import.await.context("Failed to import the bookmarks") {
+                        Ok(Some(pack)) => Msg::Imported(pack),
+                        Ok(None) => Msg::Cancelled,
+                        Err(err) => Msg::Error(err),
+                    }
+                });
+                false
+            }
+            Msg::Imported(pack) => {
+                let added = pack.merge();
+                let status = match added {
+                    0 => String::from("Nothing new in the pack"),
+                    1 => String::from("1 bookmark added"),
+                    _ => format!("{} bookmarks added", added),
+                };
+                self.status = Some(if pack.notes.is_empty() {
+                    status
+                } else {
+                    format!("{}: {}", status, pack.notes)
+                });
+                ctx.props().onimported.emit(());
+                true
+            }
+            Msg::Cancelled => false,
+            Msg::Error(err) => {
+                logger::error(format!("{:#}", err));
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        html! {
+            <div class="flex items-center gap-1 mb-1">
+                <button class="button" onclick={link.callback(|_| Msg::Import)}>
+                    { "Import bookmarks" }
+                </button>
+                <button class="button" onclick={link.callback(|_| Msg::Export)}>
+                    { "Export bookmarks" }
+                </button>
+                <Helper text=
+                    "A bookmark pack holds the pinned raw data nodes and the bookmarked raw plots \
+                    of every game, with a name for each plot.\n\
+                    Importing one adds its bookmarks to yours, for a guide to point at the values \
+                    it talks about. Fill in `notes` and the plot names of an exported pack \
+                    before sharing it."
+                />
+                { for self.status.as_ref().map(|status| html! {
+                    <span class="text-fg/50">{ status }</span>
+                }) }
+            </div>
+        }
+    }
+}
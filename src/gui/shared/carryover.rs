@@ -0,0 +1,379 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use super::IntPlotType;
+use crate::{
+    gui::components::{Helper, Table},
+    save_data::{
+        mass_effect_1::plot_db::Me1PlotDb,
+        mass_effect_2::plot_db::Me2PlotDb,
+        shared::plot::{BitVec, PlotCategory, PlotTable},
+        RcCell, RcRef,
+    },
+    services::{
+        database::Databases,
+        document,
+        save_game::SaveGame,
+        save_handler::{Action, SaveHandler},
+    },
+};
+
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    Open,
+    Opened(SaveGame),
+    Fix(usize),
+    FixAll,
+}
+
+// Where this save keeps the decisions of the previous games
+#[derive(Clone, PartialEq)]
+pub enum Downstream {
+    // ME2 keeps a copy of the imported ME1 plot
+    Me2 { me1_plot: RcRef<PlotTable> },
+    // ME3 has the ME2 plot with the same ids and the ME1 plot shifted by 10 000
+    Me3 { booleans: RcRef<BitVec>, integers: IntPlotType },
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub downstream: Downstream,
+}
+
+enum Upstream {
+    Me1(RcRef<PlotTable>),
+    Me2(RcRef<PlotTable>),
+    Me3,
+}
+
+impl Upstream {
+    fn name(&self) -> &'static str {
+        match self {
+            Upstream::Me1(_) => "Mass Effect 1",
+            Upstream::Me2(_) => "Mass Effect 2",
+            Upstream::Me3 => "Mass Effect 3",
+        }
+    }
+}
+
+impl From<SaveGame> for Upstream {
+    fn from(save_game: SaveGame) -> Self {
+        match save_game {
+            SaveGame::MassEffect1 { save_game, .. } => {
+                Upstream::Me1(RcRef::clone(&save_game.borrow().state().plot))
+            }
+            SaveGame::MassEffect1Le { save_game, .. } => {
+                Upstream::Me1(RcRef::clone(&save_game.borrow().save_data.borrow().plot))
+            }
+            SaveGame::MassEffect1LePs4 { save_game, .. } => {
+                Upstream::Me1(RcRef::clone(&save_game.borrow().plot))
+            }
+            SaveGame::MassEffect2 { save_game, .. } => {
+                Upstream::Me2(RcRef::clone(&save_game.borrow().plot))
+            }
+            SaveGame::MassEffect2Le { save_game, .. } => {
+                Upstream::Me2(RcRef::clone(&save_game.borrow().plot))
+            }
+            SaveGame::MassEffect2Quick { save_game, .. } => {
+                Upstream::Me2(RcRef::clone(&save_game.borrow().plot))
+            }
+            SaveGame::MassEffect3 { .. } | SaveGame::MassEffect3Quick { .. } => Upstream::Me3,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Plot {
+    Boolean(usize),
+    Integer(usize),
+}
+
+struct Mismatch {
+    label: String,
+    // Id in this save
+    plot: Plot,
+    upstream: i32,
+    downstream: i32,
+}
+
+pub struct Carryover {
+    _save_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    _databases_handle: ContextHandle<Databases>,
+    me1_plot_db: Option<Rc<Me1PlotDb>>,
+    me2_plot_db: Option<Rc<Me2PlotDb>>,
+    upstream: Option<Upstream>,
+}
+
+impl Component for Carryover {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) =
+            ctx.link().context::<SaveHandler>(Callback::noop()).expect("no save handler provider");
+        let (databases, _databases_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        Carryover {
+            _save_handle,
+            save_handler,
+            _databases_handle,
+            me1_plot_db: databases.clone().get_me1_plot(),
+            me2_plot_db: databases.get_me2_plot(),
+            upstream: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.me1_plot_db = dbs.clone().get_me1_plot();
+                self.me2_plot_db = dbs.get_me2_plot();
+                true
+            }
+            Msg::Open => {
+                let callback = ctx.link().callback(Msg::Opened);
+                self.save_handler.action(Action::OpenUpstreamSave(callback));
+                false
+            }
+            Msg::Opened(save_game) => {
+                self.upstream = Some(save_game.into());
+                true
+            }
+            Msg::Fix(idx) => {
+                if let Ok(mismatches) = self.mismatches(ctx) {
+                    if let Some(mismatch) = mismatches.get(idx) {
+                        Self::fix(ctx, mismatch);
+                    }
+                }
+                true
+            }
+            Msg::FixAll => {
+                if let Ok(mismatches) = self.mismatches(ctx) {
+                    for mismatch in mismatches.iter() {
+                        Self::fix(ctx, mismatch);
+                    }
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let upstream_name = self.upstream.as_ref().map(Upstream::name).unwrap_or("No save opened");
+
+        let content = match self.mismatches(ctx) {
+            Err(text) => html! { <p>{ text }</p> },
+            Ok(mismatches) if mismatches.is_empty() => {
+                html! { <p>{ "Every imported decision matches the previous save." }</p> }
+            }
+            Ok(mismatches) => {
+                let rows = mismatches.iter().enumerate().map(|(idx, mismatch)| {
+                    let Mismatch { label, plot, upstream, downstream } = mismatch;
+                    let (upstream, downstream) = match plot {
+                        Plot::Boolean(_) => {
+                            ((*upstream != 0).to_string(), (*downstream != 0).to_string())
+                        }
+                        Plot::Integer(_) => (upstream.to_string(), downstream.to_string()),
+                    };
+                    html! {
+                        <div class="flex items-center gap-2">
+                            <button class="button" onclick={link.callback(move |_| Msg::Fix(idx))}>
+                                { "Fix" }
+                            </button>
+                            <span class="flex-auto">{ label }</span>
+                            <span class="text-fg/60">
+                                { format!("previous: {} / this save: {}", upstream, downstream) }
+                            </span>
+                        </div>
+                    }
+                });
+
+                html! {
+                    <>
+                        <div class="flex items-center gap-2">
+                            <button class="button" onclick={link.callback(|_| Msg::FixAll)}>
+                                { "Fix all" }
+                            </button>
+                            <span>{ format!("{} mismatches", mismatches.len()) }</span>
+                        </div>
+                        { for rows }
+                    </>
+                }
+            }
+        };
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                <Table title="Carryover">
+                    <div class="flex items-center gap-2">
+                        <button class="button" onclick={link.callback(|_| Msg::Open)}>
+                            { "Open previous save" }
+                        </button>
+                        <span>{ upstream_name }</span>
+                        <Helper text=
+                            "Open a save of the previous game to check that its decisions were imported into this save.\n\
+                            Fixing a decision sets it in this save to what it is in the previous save."
+                        />
+                    </div>
+                    { content }
+                </Table>
+            </div>
+        }
+    }
+}
+
+impl Carryover {
+    fn mismatches(&self, ctx: &Context<Self>) -> Result<Vec<Mismatch>, &'static str> {
+        let upstream =
+            self.upstream.as_ref().ok_or("Open a save of the previous game to check it.")?;
+
+        // (Categories, previous save, this save, id offset in this save)
+        let (categories, (up_booleans, up_integers), (down_booleans, down_integers), offset) =
+            match (upstream, &ctx.props().downstream) {
+                (Upstream::Me1(plot), Downstream::Me2 { me1_plot }) => {
+                    if me1_plot.borrow().booleans().is_empty() {
+                        return Err(
+                            "This save has no imported ME1 save, there is nothing to check.",
+                        );
+                    }
+                    let plot_db = self.me1_plot_db.as_ref().ok_or("Loading database...")?;
+                    (Self::me1_categories(plot_db), Self::plot(plot), Self::plot(me1_plot), 0)
+                }
+                (Upstream::Me1(plot), Downstream::Me3 { booleans, integers }) => {
+                    let plot_db = self.me1_plot_db.as_ref().ok_or("Loading database...")?;
+                    let downstream = (RcRef::clone(booleans), IntPlotType::clone(integers));
+                    (Self::me1_categories(plot_db), Self::plot(plot), downstream, 10_000)
+                }
+                (Upstream::Me2(plot), Downstream::Me3 { booleans, integers }) => {
+                    let plot_db = self.me2_plot_db.as_ref().ok_or("Loading database...")?;
+                    let downstream = (RcRef::clone(booleans), IntPlotType::clone(integers));
+                    (Self::me2_categories(plot_db), Self::plot(plot), downstream, 0)
+                }
+                _ => return Err("This is not a save of a previous game."),
+            };
+
+        let mut mismatches = Vec::new();
+        for (title, category) in categories {
+            for (&id, label) in &category.booleans {
+                let upstream = get_boolean(&up_booleans, id);
+                let downstream = get_boolean(&down_booleans, id + offset);
+                if upstream != downstream {
+                    mismatches.push(Mismatch {
+                        label: format!("{} - {}", title, label),
+                        plot: Plot::Boolean(id + offset),
+                        upstream: upstream as i32,
+                        downstream: downstream as i32,
+                    });
+                }
+            }
+            for (&id, label) in &category.integers {
+                let upstream = get_integer(&up_integers, id);
+                let downstream = get_integer(&down_integers, id + offset);
+                if upstream != downstream {
+                    mismatches.push(Mismatch {
+                        label: format!("{} - {}", title, label),
+                        plot: Plot::Integer(id + offset),
+                        upstream,
+                        downstream,
+                    });
+                }
+            }
+        }
+        Ok(mismatches)
+    }
+
+    fn fix(ctx: &Context<Self>, mismatch: &Mismatch) {
+        let (booleans, integers) = match ctx.props().downstream {
+            Downstream::Me2 { ref me1_plot } => Self::plot(me1_plot),
+            Downstream::Me3 { ref booleans, ref integers } => {
+                (RcRef::clone(booleans), IntPlotType::clone(integers))
+            }
+        };
+
+        match mismatch.plot {
+            Plot::Boolean(id) => {
+                let mut bits = booleans.borrow_mut();
+                if id >= bits.len() {
+                    bits.resize(id + 1, false);
+                }
+                bits.set(id, mismatch.upstream != 0);
+            }
+            Plot::Integer(id) => match integers {
+                IntPlotType::Vec(vec) => {
+                    let mut vec = vec.borrow_mut();
+                    if id >= vec.len() {
+                        vec.resize_with(id + 1, Default::default);
+                    }
+                    vec[id].set(mismatch.upstream);
+                }
+                IntPlotType::IndexMap(index_map) => {
+                    index_map.borrow_mut().entry(id as i32).or_default().set(mismatch.upstream);
+                }
+            },
+        }
+        document::edited(ctx);
+    }
+
+    fn plot(plot: &RcRef<PlotTable>) -> (RcRef<BitVec>, IntPlotType) {
+        let plot = plot.borrow();
+        (RcRef::clone(&plot.booleans), IntPlotType::Vec(RcRef::clone(&plot.integers)))
+    }
+
+    fn me1_categories(plot_db: &Me1PlotDb) -> Vec<(String, PlotCategory)> {
+        let Me1PlotDb { player_crew, missions } = plot_db;
+        player_crew
+            .iter()
+            .chain(missions)
+            .map(|(title, category)| (title.clone(), category.clone()))
+            .collect()
+    }
+
+    fn me2_categories(plot_db: &Me2PlotDb) -> Vec<(String, PlotCategory)> {
+        let Me2PlotDb { player, crew, squad, romance, missions, loyalty_missions, dlc, .. } =
+            plot_db;
+
+        // Only the deaths of the squad carry over
+        let deaths = PlotCategory {
+            booleans: squad
+                .iter()
+                .filter_map(|(name, flags)| {
+                    flags.dead.map(|dead| (dead, format!("{} is dead", name)))
+                })
+                .collect(),
+            integers: Default::default(),
+            clusters: Default::default(),
+            dangerous: Default::default(),
+            as_bool: Default::default(),
+        };
+
+        [(String::from("Player"), player.clone()), (String::from("Squad"), deaths)]
+            .into_iter()
+            .chain(
+                crew.iter()
+                    .chain(romance)
+                    .chain(missions)
+                    .chain(loyalty_missions)
+                    .chain(dlc)
+                    .map(|(title, category)| (title.clone(), category.clone())),
+            )
+            .collect()
+    }
+}
+
+fn get_boolean(booleans: &RcRef<BitVec>, id: usize) -> bool {
+    booleans.borrow().get(id).map(|value| *value).unwrap_or_default()
+}
+
+fn get_integer(integers: &IntPlotType, id: usize) -> i32 {
+    match integers {
+        IntPlotType::Vec(vec) => vec.borrow().get(id).map(RcCell::get),
+        IntPlotType::IndexMap(index_map) => index_map.borrow().get(&(id as i32)).map(RcCell::get),
+    }
+    .unwrap_or_default()
+}
@@ -0,0 +1,130 @@
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{Helper, Table},
+    save_data::diff::Change,
+    services::save_handler::{Action, SaveHandler},
+};
+
+pub enum Msg {
+    Refresh,
+    Diffed(Vec<Change>),
+    SaveHandler(SaveHandler),
+}
+
+pub struct Changes {
+    _db_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    changes: Option<Vec<Change>>,
+}
+
+impl Component for Changes {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _db_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+        ctx.link().send_message(Msg::Refresh);
+
+        Changes { _db_handle, save_handler, changes: None }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Refresh => {
+                let callback = ctx.link().callback(Msg::Diffed);
+                self.save_handler.action(Action::DiffSave(callback));
+                false
+            }
+            Msg::Diffed(changes) => {
+                self.changes = Some(changes);
+                true
+            }
+            Msg::SaveHandler(save_handler) => {
+                self.save_handler = save_handler;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let changes = match self.changes {
+            Some(ref changes) if changes.is_empty() => html! { <p>{ "No changes" }</p> },
+            Some(ref changes) => Self::table(
+                format!("{} changes", changes.len()),
+                ["Field", "Original", "New"],
+                changes,
+                None,
+            ),
+            None => html! { <p>{ "Comparing..." }</p> },
+        };
+        let changed_by_game = self.save_handler.changed_by_game.as_ref().map(|changes| {
+            let helper =
+                "The plots the game has set since the save was last opened or saved here, \
+                found on reload. Play a mission, save over this file and reload it \
+                to see what the mission did.";
+            html! {
+                <>
+                    { Self::table(
+                        format!("{} plots changed by the game", changes.len()),
+                        ["Plot", "Last seen", "Reloaded"],
+                        changes,
+                        Some(helper),
+                    ) }
+                    <hr class="border-t border-default-border" />
+                </>
+            }
+        });
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                <div class="flex items-center gap-1">
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::Refresh)}>
+                        { "Refresh" }
+                    </button>
+                    <Helper text=
+                        "Compares what will be written with the file as it was opened.\n\
+                        This includes indirect changes like recomputed sizes and checksums."
+                    />
+                </div>
+                <hr class="border-t border-default-border" />
+                <div class="flex-auto flex flex-col gap-1 h-0 overflow-y-auto">
+                    { for changed_by_game }
+                    { changes }
+                </div>
+            </div>
+        }
+    }
+}
+
+impl Changes {
+    fn table(
+        title: String, [field, old_label, new_label]: [&str; 3], changes: &[Change],
+        helper: Option<&'static str>,
+    ) -> Html {
+        let rows = changes.iter().map(|Change { path, old, new }| {
+            let value =
+                |value: &Option<String>| value.clone().unwrap_or_else(|| String::from("<none>"));
+            html! {
+                <div class="flex gap-2 min-w-0">
+                    <span class="w-1/2 truncate" title={path.clone()}>{ path }</span>
+                    <span class="w-1/4 truncate">{ value(old) }</span>
+                    <span class="w-1/4 truncate">{ value(new) }</span>
+                </div>
+            }
+        });
+        html! {
+            <Table {title} {helper}>
+                <div class="flex gap-2 select-none">
+                    <span class="w-1/2">{ field }</span>
+                    <span class="w-1/4">{ old_label }</span>
+                    <span class="w-1/4">{ new_label }</span>
+                </div>
+                { for rows }
+            </Table>
+        }
+    }
+}
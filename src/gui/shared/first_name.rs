@@ -0,0 +1,89 @@
+use yew::prelude::*;
+
+use crate::{
+    gui::components::InputText,
+    save_data::{
+        shared::player::{self, NameProblem},
+        RcRef,
+    },
+    services::document,
+};
+
+pub enum Msg {
+    Changed,
+    Fix,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub value: RcRef<String>,
+}
+
+pub struct FirstName {
+    // The input only shows its value when created, a fix has to remount it
+    revision: usize,
+}
+
+impl Component for FirstName {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        FirstName { revision: 0 }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Changed => true,
+            Msg::Fix => {
+                let value = &ctx.props().value;
+                let fixed = player::fix_first_name(&value.borrow());
+                *value.borrow_mut() = fixed;
+                document::edited(ctx);
+                self.revision += 1;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let problems = NameProblem::check(&ctx.props().value.borrow());
+
+        let warnings = (!problems.is_empty()).then(|| {
+            let messages = problems.iter().map(|problem| {
+                html! { <li>{ problem.message() }</li> }
+            });
+            let fix = (problems != [NameProblem::Empty]).then(|| {
+                html! {
+                    <button class="button" onclick={link.callback(|_| Msg::Fix)}>
+                        { "Fix" }
+                    </button>
+                }
+            });
+            html! {
+                <div class="flex items-start gap-1 text-fg/50">
+                    <ul class="list-disc list-inside">
+                        { for messages }
+                    </ul>
+                    { for fix }
+                </div>
+            }
+        });
+
+        html! {
+            <>
+                <InputText key={self.revision}
+                    label="Name"
+                    value={RcRef::clone(&ctx.props().value)}
+                    oninput={link.callback(|_| Msg::Changed)}
+                    helper="The game only knows the characters of Western European languages \
+                        and names the career folder after the first name.\n\
+                        `Fix` removes what it can't handle and cuts the name to the length \
+                        the character creator allows."
+                />
+                { for warnings }
+            </>
+        }
+    }
+}
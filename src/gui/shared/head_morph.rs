@@ -1,23 +1,54 @@
-use std::cell::{Ref, RefMut};
+use std::{
+    cell::{Ref, RefMut},
+    rc::Rc,
+};
+
+use indexmap::IndexMap;
 
 use yew::{context::ContextHandle, prelude::*};
 
 use crate::{
-    gui::{components::Table, raw_ui::RawUiChildren},
-    save_data::{shared::appearance::HeadMorph as DataHeadMorph, RcRef},
-    services::save_handler::{Action, SaveHandler},
+    gui::{
+        components::{Helper, InputNumber, NumberType, Table},
+        raw_ui::RawUiChildren,
+    },
+    save_data::{
+        shared::appearance::{
+            GibbedGame, HeadMorph as DataHeadMorph, HeadMorphConversion, HeadMorphSliderDb,
+            OutOfRange, SliderRange,
+        },
+        RcCell, RcRef,
+    },
+    services::{
+        database::Databases,
+        document, logger,
+        save_handler::{Action, SaveHandler},
+    },
 };
 
+#[derive(Clone, Copy, PartialEq)]
+pub enum HeadMorphGame {
+    MassEffect1,
+    MassEffect2,
+    MassEffect3,
+}
+
 pub enum Msg {
+    DatabaseLoaded(Databases),
     Import,
     HeadMorphImported(DataHeadMorph),
+    ImportConverted,
+    HeadMorphConverted(DataHeadMorph),
     Export,
+    ExportGibbed(GibbedGame),
     RemoveHeadMorph,
+    Normalize,
 }
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
     pub head_morph: RcRef<Option<RcRef<DataHeadMorph>>>,
+    pub game: HeadMorphGame,
 }
 
 impl Props {
@@ -33,6 +64,8 @@ impl Props {
 pub struct HeadMorph {
     _db_handle: ContextHandle<SaveHandler>,
     save_handler: SaveHandler,
+    _databases_handle: ContextHandle<Databases>,
+    slider_db: Option<Rc<HeadMorphSliderDb>>,
 }
 
 impl Component for HeadMorph {
@@ -42,12 +75,25 @@ impl Component for HeadMorph {
     fn create(ctx: &Context<Self>) -> Self {
         let (save_handler, _db_handle) =
             ctx.link().context::<SaveHandler>(Callback::noop()).expect("no save handler provider");
+        let (databases, _databases_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
 
-        HeadMorph { _db_handle, save_handler }
+        HeadMorph {
+            _db_handle,
+            save_handler,
+            _databases_handle,
+            slider_db: databases.get_head_morph_sliders(),
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.slider_db = dbs.get_head_morph_sliders();
+                true
+            }
             Msg::Import => {
                 let callback = ctx.link().callback(Msg::HeadMorphImported);
                 self.save_handler.action(Action::ImportHeadMorph(callback));
@@ -55,6 +101,28 @@ impl Component for HeadMorph {
             }
             Msg::HeadMorphImported(head_morph) => {
                 *ctx.props().head_morph_mut() = Some(head_morph.into());
+                document::edited(ctx);
+                true
+            }
+            Msg::ImportConverted => {
+                let callback = ctx.link().callback(Msg::HeadMorphConverted);
+                self.save_handler.action(Action::ImportHeadMorph(callback));
+                false
+            }
+            Msg::HeadMorphConverted(head_morph) => {
+                if let (Some((previous, conversion)), Some(ranges)) =
+                    (self.conversion(ctx), self.ranges(ctx))
+                {
+                    let renamed = head_morph.convert(conversion);
+                    let normalized = head_morph.normalize(ranges);
+                    logger::info(format!(
+                        "Head morph converted from {}: {} entries renamed or dropped, {} sliders \
+                        brought within the creator limits",
+                        previous, renamed, normalized
+                    ));
+                    *ctx.props().head_morph_mut() = Some(head_morph.into());
+                    document::edited(ctx);
+                }
                 true
             }
             Msg::Export => {
@@ -63,8 +131,25 @@ impl Component for HeadMorph {
                 }
                 false
             }
+            Msg::ExportGibbed(game) => {
+                if let Some(ref head_morph) = *ctx.props().head_morph() {
+                    let head_morph = RcRef::clone(head_morph);
+                    self.save_handler.action(Action::ExportGibbedHeadMorph(head_morph, game));
+                }
+                false
+            }
             Msg::RemoveHeadMorph => {
                 ctx.props().head_morph_mut().take();
+                document::edited(ctx);
+                true
+            }
+            Msg::Normalize => {
+                if let (Some(head_morph), Some(ranges)) =
+                    (&*ctx.props().head_morph(), self.ranges(ctx))
+                {
+                    head_morph.borrow().normalize(ranges);
+                    document::edited(ctx);
+                }
                 true
             }
         }
@@ -73,11 +158,29 @@ impl Component for HeadMorph {
     fn view(&self, ctx: &Context<Self>) -> Html {
         let head_morph = ctx.props().head_morph();
         let export_remove = head_morph.is_some().then(|| {
+            // What Gibbed's editors and most shared head morphs use
+            let gibbed = match ctx.props().game {
+                HeadMorphGame::MassEffect1 => None,
+                HeadMorphGame::MassEffect2 => Some(GibbedGame::MassEffect2),
+                HeadMorphGame::MassEffect3 => Some(GibbedGame::MassEffect3),
+            };
+            let export_gibbed = gibbed.map(|game| {
+                let title =
+                    format!("Export as a `.{}` file for Gibbed's editors", game.extension());
+                html! {
+                    <button class="button" {title}
+                        onclick={ctx.link().callback(move |_| Msg::ExportGibbed(game))}
+                    >
+                        {"Export Gibbed"}
+                    </button>
+                }
+            });
             html! {
                 <>
                     <button class="button" onclick={ctx.link().callback(|_| Msg::Export)}>
                         {"Export"}
                     </button>
+                    { for export_gibbed }
                     <span>{"-"}</span>
                     <button class="button" onclick={ctx.link().callback(|_| Msg::RemoveHeadMorph)}>
                         {"Remove head morph"}
@@ -85,6 +188,11 @@ impl Component for HeadMorph {
                 </>
             }
         });
+        let out_of_range = head_morph.as_ref().and_then(|head_morph| {
+            let ranges = self.ranges(ctx)?;
+            Self::out_of_range(ctx, head_morph.borrow().out_of_range(ranges))
+        });
+        let sliders = head_morph.as_ref().map(|head_morph| self.sliders(ctx, head_morph));
         let raw = head_morph.as_ref().map(|head_morph| {
             html! {
                 <Table title="Raw">
@@ -92,17 +200,129 @@ impl Component for HeadMorph {
                 </Table>
             }
         });
+        let convert = self.conversion(ctx).map(|(previous, _)| {
+            html! {
+                <>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::ImportConverted)}>
+                        { format!("Convert & apply from {}", previous) }
+                    </button>
+                    <Helper text=
+                        "Imports a head morph exported from the previous game, with the names \
+                        that changed between the games mapped and the sliders brought within \
+                        the creator limits. For when the import of the game mangles the face."
+                    />
+                </>
+            }
+        });
         html! {
             <div class="flex-auto flex flex-col gap-1">
                 <div class="flex items-center gap-2">
                     <button class="button" onclick={ctx.link().callback(|_| Msg::Import)}>
                         {"Import"}
                     </button>
+                    { for convert }
                     { for export_remove }
                 </div>
                 <hr class="border-t border-default-border" />
+                { for out_of_range }
+                { for sliders }
                 { for raw }
             </div>
         }
     }
 }
+
+impl HeadMorph {
+    // Only ME2 and ME3 sliders have known ranges
+    fn ranges(&self, ctx: &Context<Self>) -> Option<&IndexMap<String, SliderRange>> {
+        let slider_db = self.slider_db.as_ref()?;
+        match ctx.props().game {
+            HeadMorphGame::MassEffect1 => None,
+            HeadMorphGame::MassEffect2 => Some(&slider_db.me2_ranges),
+            HeadMorphGame::MassEffect3 => Some(&slider_db.me3_ranges),
+        }
+    }
+
+    // From the previous game, ME1 having none
+    fn conversion(&self, ctx: &Context<Self>) -> Option<(&'static str, &HeadMorphConversion)> {
+        let slider_db = self.slider_db.as_ref()?;
+        match ctx.props().game {
+            HeadMorphGame::MassEffect1 => None,
+            HeadMorphGame::MassEffect2 => Some(("ME1", &slider_db.me1_to_me2)),
+            HeadMorphGame::MassEffect3 => Some(("ME2", &slider_db.me2_to_me3)),
+        }
+    }
+
+    fn out_of_range(ctx: &Context<Self>, out_of_range: Vec<OutOfRange>) -> Option<Html> {
+        if out_of_range.is_empty() {
+            return None;
+        }
+
+        let sliders = out_of_range.iter().map(|OutOfRange { parameter, value, range }| {
+            html! {
+                <p class="text-fg/50">
+                    { format!("{}: {} (from {} to {})", parameter, value, range.min, range.max) }
+                </p>
+            }
+        });
+        Some(html! {
+            <div class="flex flex-col gap-1 p-1 border border-default-border">
+                <div class="flex items-center gap-1">
+                    <span class="text-title-bar-close">
+                        { format!("⚠ {} sliders beyond the creator limits", out_of_range.len()) }
+                    </span>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::Normalize)}>
+                        { "Normalize to creator-compatible" }
+                    </button>
+                    <Helper text=
+                        "Head morphs shared online are often made with tools that go past the \
+                        creator limits, which can stretch the face in game. Normalizing brings \
+                        every slider back to its closest valid value."
+                    />
+                </div>
+                { for sliders }
+            </div>
+        })
+    }
+
+    fn sliders(&self, ctx: &Context<Self>, head_morph: &RcRef<DataHeadMorph>) -> Html {
+        let slider_db = match self.slider_db {
+            Some(ref slider_db) => slider_db,
+            None => return html! { <p>{ "Loading database..." }</p> },
+        };
+        let groups = match ctx.props().game {
+            HeadMorphGame::MassEffect1 => &slider_db.me1,
+            HeadMorphGame::MassEffect2 => &slider_db.me2,
+            HeadMorphGame::MassEffect3 => &slider_db.me3,
+        };
+
+        let head_morph = head_morph.borrow();
+        let (scalar_parameters, morph_features) =
+            (head_morph.scalar_parameters(), head_morph.morph_features());
+
+        let groups = groups.iter().map(|(title, sliders)| {
+            // Sliders the head morph doesn't have are not shown, the game uses its default
+            let sliders = sliders.iter().filter_map(|(parameter, label)| {
+                scalar_parameters.get(parameter).or_else(|| morph_features.get(parameter)).map(
+                    |value| {
+                        html! {
+                            <InputNumber
+                                label={label.clone()}
+                                value={NumberType::Float(RcCell::clone(value))}
+                                step={0.01}
+                                precision={3}
+                            />
+                        }
+                    },
+                )
+            });
+            html! {
+                <Table title={title.clone()}>
+                    { for sliders }
+                </Table>
+            }
+        });
+
+        html! { for groups }
+    }
+}
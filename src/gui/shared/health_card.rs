@@ -0,0 +1,279 @@
+use yew::{context::ContextHandle, prelude::*};
+
+use super::Link;
+use crate::{
+    gui::components::Helper,
+    save_data::{shared::plot::BitVec, RcRef},
+    services::{
+        database::Databases, document, health::FileHealth, save_game::SaveGame,
+        save_handler::SaveHandler,
+    },
+};
+
+pub enum Msg {
+    SaveHandler(SaveHandler),
+    DatabaseLoaded(Databases),
+    SyncGender,
+    Toggle,
+}
+
+// A plot that has to agree with the gender of the player
+struct GenderPlot {
+    booleans: RcRef<BitVec>,
+    id: usize,
+}
+
+struct Check {
+    name: &'static str,
+    problem: Option<Problem>,
+}
+
+struct Problem {
+    text: String,
+    help: Option<&'static str>,
+    fix: Option<Html>,
+}
+
+pub struct HealthCard {
+    _db_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    _databases_handle: ContextHandle<Databases>,
+    databases: Databases,
+    is_opened: bool,
+}
+
+impl Component for HealthCard {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _db_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+        let (databases, _databases_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        HealthCard { _db_handle, save_handler, _databases_handle, databases, is_opened: false }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SaveHandler(save_handler) => {
+                self.save_handler = save_handler;
+                true
+            }
+            Msg::DatabaseLoaded(databases) => {
+                self.databases = databases;
+                true
+            }
+            Msg::SyncGender => {
+                if let Some((is_female, plots)) = self.gender_plots() {
+                    for GenderPlot { booleans, id } in plots {
+                        booleans.borrow_mut().set_many([id], is_female);
+                    }
+                }
+                document::edited(ctx);
+                true
+            }
+            Msg::Toggle => {
+                self.is_opened = !self.is_opened;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let checks = self.checks(ctx);
+        let (passed, problems): (Vec<_>, Vec<_>) =
+            checks.into_iter().partition(|check| check.problem.is_none());
+
+        let summary = {
+            let text = if problems.is_empty() {
+                format!("✓ All {} checks passed", passed.len())
+            } else {
+                format!("{} of {} checks passed", passed.len(), passed.len() + problems.len())
+            };
+            let names = passed.iter().map(|check| check.name).collect::<Vec<_>>().join(", ");
+            html! {
+                <a class="text-fg/50 cursor-pointer select-none hover:underline"
+                    title={names}
+                    onclick={ctx.link().callback(|_| Msg::Toggle)}
+                >
+                    { text }
+                </a>
+            }
+        };
+
+        let passed = self.is_opened.then(|| {
+            let passed = passed.iter().map(|check| {
+                html! { <p class="text-fg/50">{ format!("✓ {}", check.name) }</p> }
+            });
+            html! { <div class="pl-2">{ for passed }</div> }
+        });
+
+        let problems = problems.into_iter().filter_map(|check| check.problem).map(|problem| {
+            let Problem { text, help, fix } = problem;
+            html! {
+                <div class="flex items-center gap-1">
+                    <span class="text-title-bar-close">{ format!("⚠ {}", text) }</span>
+                    { for help.map(|help| html! { <Helper text={help} /> }) }
+                    { for fix }
+                </div>
+            }
+        });
+
+        html! {
+            <div class="flex flex-col border border-default-border p-1 mb-1">
+                <div class="flex items-center gap-1">
+                    <span>{ "Health" }</span>
+                    { summary }
+                </div>
+                { for problems }
+                { for passed }
+            </div>
+        }
+    }
+}
+
+impl HealthCard {
+    fn checks(&self, ctx: &Context<Self>) -> Vec<Check> {
+        let save_game = match self.save_handler.save_game {
+            Some(ref save_game) => save_game,
+            None => return Vec::new(),
+        };
+
+        let mut checks = Vec::new();
+        if let Some(ref health) = self.save_handler.health {
+            checks.extend(Self::file_checks(health));
+        }
+
+        // Plot duplicates
+        if let Some((is_female, plots)) = self.gender_plots() {
+            let disagree = plots
+                .iter()
+                .filter(|GenderPlot { booleans, id }| {
+                    booleans.borrow().get(*id).map(|value| *value != is_female).unwrap_or(false)
+                })
+                .count();
+            checks.push(Check {
+                name: "Gender plots agree with the player",
+                problem: (disagree > 0).then(|| Problem {
+                    text: format!("{} gender plots disagree with the player", disagree),
+                    help: Some(
+                        "The game reads the gender from the plots in some conversations, \
+                        a mismatch mixes male and female lines.",
+                    ),
+                    fix: Some(html! {
+                        <button class="button" onclick={ctx.link().callback(|_| Msg::SyncGender)}>
+                            { "Sync" }
+                        </button>
+                    }),
+                }),
+            });
+        }
+
+        // Known dangerous combinations
+        let me3_booleans = match **save_game {
+            SaveGame::MassEffect3 { ref save_game, .. } => {
+                Some(RcRef::clone(&save_game.borrow().plot().booleans))
+            }
+            SaveGame::MassEffect3Quick { ref save_game, .. } => {
+                Some(RcRef::clone(&save_game.borrow().plot.borrow().booleans))
+            }
+            _ => None,
+        };
+        if let Some(booleans) = me3_booleans {
+            if let Some(plot_db) = self.databases.clone().get_me3_plot() {
+                let problems = plot_db.intel.check(&booleans.borrow()).len();
+                checks.push(Check {
+                    name: "Intel purchases possible in game",
+                    problem: (problems > 0).then(|| Problem {
+                        text: format!("{} intel purchases the game can't make", problems),
+                        help: None,
+                        fix: Some(html! { <Link tab="Plot">{ "Plot" }</Link> }),
+                    }),
+                });
+            }
+        }
+
+        checks
+    }
+
+    fn file_checks(health: &FileHealth) -> Vec<Check> {
+        let FileHealth { checksum, file_size, written_size } = *health;
+
+        let checksum = checksum.map(|is_valid| Check {
+            name: "Checksum",
+            problem: (!is_valid).then(|| Problem {
+                text: String::from("Checksum doesn't match"),
+                help: Some(
+                    "The file was edited by another tool or damaged on the way. \
+                    The game refuses it as is, saving writes a correct checksum.",
+                ),
+                fix: None,
+            }),
+        });
+
+        let problem = match written_size {
+            None => Some(Problem {
+                text: String::from("Can't be written back"),
+                help: Some("See the logs for what failed."),
+                fix: None,
+            }),
+            Some(written_size) if written_size < file_size => Some(Problem {
+                text: format!("{} bytes left unread", file_size - written_size),
+                help: Some(
+                    "The end of the file isn't part of any known section, saving drops it. \
+                    Keep a backup of the original.",
+                ),
+                fix: None,
+            }),
+            Some(written_size) if written_size > file_size => Some(Problem {
+                text: format!("{} bytes missing", written_size - file_size),
+                help: Some(
+                    "A section is shorter than what it says, the file was probably cut short. \
+                    Saving fills it back with default values.",
+                ),
+                fix: None,
+            }),
+            Some(_) => None,
+        };
+        let sections = Check { name: "Every section read", problem };
+
+        checksum.into_iter().chain(Some(sections)).collect()
+    }
+
+    // The gender of the player and its copies in the plots
+    fn gender_plots(&self) -> Option<(bool, Vec<GenderPlot>)> {
+        let save_game = self.save_handler.save_game.as_deref()?;
+        let plot =
+            |booleans: &RcRef<BitVec>, id| GenderPlot { booleans: RcRef::clone(booleans), id };
+        match save_game {
+            SaveGame::MassEffect2 { save_game, .. } => {
+                let me2 = save_game.borrow();
+                let plots =
+                    vec![plot(&me2.me1_plot().booleans, 4639), plot(&me2.plot().booleans, 66)];
+                let is_female = me2.player().is_female();
+                Some((is_female, plots))
+            }
+            SaveGame::MassEffect2Le { save_game, .. } => {
+                let me2 = save_game.borrow();
+                let plots =
+                    vec![plot(&me2.me1_plot().booleans, 4639), plot(&me2.plot().booleans, 66)];
+                let is_female = me2.player().is_female();
+                Some((is_female, plots))
+            }
+            SaveGame::MassEffect3 { save_game, .. } => {
+                let me3 = save_game.borrow();
+                let booleans = &me3.plot().booleans;
+                let plots = vec![plot(booleans, 14639), plot(booleans, 66), plot(booleans, 17662)];
+                let is_female = me3.player().is_female();
+                Some((is_female, plots))
+            }
+            _ => None,
+        }
+    }
+}
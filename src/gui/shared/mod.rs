@@ -1,10 +1,24 @@
 mod bonus_powers;
+mod bookmark_pack;
+mod carryover;
+mod changes;
+mod first_name;
 mod head_morph;
+mod health_card;
 mod link;
+mod notes;
 mod plot_category;
+mod plot_coverage;
+mod power_ranks;
 mod raw_plot;
+mod save_info;
+mod squad_flags;
 
-pub use self::{bonus_powers::*, head_morph::*, link::*, plot_category::*, raw_plot::*};
+pub use self::{
+    bonus_powers::*, bookmark_pack::*, carryover::*, changes::*, first_name::*, head_morph::*,
+    health_card::*, link::*, notes::*, plot_category::*, plot_coverage::*, power_ranks::*,
+    raw_plot::*, save_info::*, squad_flags::*,
+};
 
 use indexmap::IndexMap;
 use yew::prelude::*;
@@ -14,7 +28,7 @@ use crate::save_data::{
     RcCell, RcRef,
 };
 
-use super::raw_ui::RawUi;
+use super::raw_ui::{RawUi, RawUiRenderer};
 
 #[derive(Clone)]
 pub enum IntPlotType {
@@ -69,6 +83,10 @@ impl PartialEq for PlotType {
 }
 
 impl RawUi for RcRef<PlotTable> {
+    fn render<R: RawUiRenderer>(&self, renderer: &mut R, label: &str, _: bool) -> R::Output {
+        renderer.opaque(label, Some(self))
+    }
+
     fn view(&self, _: &str) -> yew::Html {
         html! {
             <Link tab="Raw Plot">{ "Raw Plot" }</Link>
@@ -0,0 +1,109 @@
+use std::rc::Rc;
+
+use web_sys::HtmlTextAreaElement;
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::Helper,
+    services::save_handler::{Action, SaveHandler},
+};
+
+pub enum Msg {
+    SaveHandlerChanged(SaveHandler),
+    Loaded(String),
+    Input(InputEvent),
+    Save,
+}
+
+pub struct Notes {
+    _db_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    notes: Option<String>,
+    is_dirty: bool,
+}
+
+impl Component for Notes {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _db_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandlerChanged))
+            .expect("no save handler provider");
+        save_handler.action(Action::LoadNotes(ctx.link().callback(Msg::Loaded)));
+
+        Notes { _db_handle, save_handler, notes: None, is_dirty: false }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SaveHandlerChanged(save_handler) => {
+                let is_same_save = match (&self.save_handler.save_game, &save_handler.save_game) {
+                    (Some(this), Some(other)) => Rc::ptr_eq(this, other),
+                    _ => false,
+                };
+                self.save_handler = save_handler;
+
+                // Opened or reloaded
+                if !is_same_save {
+                    self.notes = None;
+                    self.save_handler.action(Action::LoadNotes(ctx.link().callback(Msg::Loaded)));
+                    return true;
+                }
+                false
+            }
+            Msg::Loaded(notes) => {
+                self.notes = Some(notes);
+                self.is_dirty = false;
+                true
+            }
+            Msg::Input(event) => {
+                if let Some(textarea) = event.target_dyn_into::<HtmlTextAreaElement>() {
+                    self.notes = Some(textarea.value());
+                    let was_dirty = self.is_dirty;
+                    self.is_dirty = true;
+                    !was_dirty
+                } else {
+                    false
+                }
+            }
+            Msg::Save => {
+                if let Some(ref notes) = self.notes {
+                    self.save_handler.action(Action::SaveNotes(notes.clone()));
+                    self.is_dirty = false;
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let notes = match self.notes {
+            Some(ref notes) => notes.clone(),
+            None => return html! { <p>{ "Loading notes..." }</p> },
+        };
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                <div class="flex items-center gap-1">
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::Save)}>
+                        { "Save notes" }
+                    </button>
+                    { for self.is_dirty.then(|| html! { <span>{ "*" }</span> }) }
+                    <Helper text=
+                        "Free-form notes about this save.\n\
+                        They are stored next to the save in a `.notes.txt` file, the save itself is not modified."
+                    />
+                </div>
+                <hr class="border-t border-default-border" />
+                <textarea
+                    class="input flex-auto resize-none p-1"
+                    placeholder="e.g. This is the pre-Tuchanka decision point"
+                    value={notes}
+                    oninput={ctx.link().callback(Msg::Input)}
+                />
+            </div>
+        }
+    }
+}
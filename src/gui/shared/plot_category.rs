@@ -1,22 +1,32 @@
-use std::cell::RefMut;
+use std::{cell::RefMut, collections::HashSet};
 
 use yew::prelude::*;
 
 use crate::{
     gui::{
-        components::{CheckBox, Table},
+        components::{CheckBox, Select, Table},
         raw_ui::RawUi,
     },
     save_data::{
-        shared::plot::{BitVec, PlotCategory as PlotCategoryDb},
+        shared::plot::{BitVec, IntAsBool, PlotCategory as PlotCategoryDb},
         RcCell, RcRef,
     },
+    services::document,
 };
 
 use super::IntPlotType;
 
+// In the order of `IntAsBool` states: undecided, unchecked, checked
+const TRI_STATE: &[&str] = &["Undecided", "No", "Yes"];
+
 pub enum Msg {
     ChangeBool(usize, bool),
+    ApplyCluster,
+    DismissCluster,
+    ConfirmDangerous,
+    CancelDangerous,
+    ChangeInt(RcCell<i32>, i32),
+    ToggleAsNumber(usize),
 }
 
 #[derive(Properties, PartialEq)]
@@ -33,16 +43,32 @@ impl Props {
     fn booleans_mut(&self) -> RefMut<'_, BitVec> {
         self.booleans.borrow_mut()
     }
+
+    fn offset(&self, idx: usize) -> usize {
+        if self.me3_imported_me1 {
+            idx + 10_000
+        } else {
+            idx
+        }
+    }
 }
 
-pub struct PlotCategory;
+pub struct PlotCategory {
+    // The choice that was just made, while its cluster is being previewed
+    cluster_of: Option<usize>,
+    // A dangerous plot waiting for a confirmation, with the value it would get
+    confirming: Option<(usize, bool)>,
+    // Integers with a boolean mapping the user wants to edit as numbers anyway
+    as_number: HashSet<usize>,
+}
 
 impl Component for PlotCategory {
     type Message = Msg;
     type Properties = Props;
 
     fn create(ctx: &Context<Self>) -> Self {
-        let mut this = PlotCategory {};
+        let mut this =
+            PlotCategory { cluster_of: None, confirming: None, as_number: HashSet::new() };
         this.add_missing_plots(ctx);
         this
     }
@@ -50,10 +76,48 @@ impl Component for PlotCategory {
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::ChangeBool(idx, value) => {
-                if let Some(mut plot) = ctx.props().booleans_mut().get_mut(idx) {
-                    *plot = value;
+                if ctx.props().category.dangerous(idx).is_some() {
+                    self.confirming = Some((idx, value));
+                    return true;
                 }
-                false
+                self.change_bool(ctx, idx, value)
+            }
+            Msg::ConfirmDangerous => {
+                if let Some((idx, value)) = self.confirming.take() {
+                    self.change_bool(ctx, idx, value);
+                }
+                true
+            }
+            Msg::CancelDangerous => {
+                self.confirming = None;
+                true
+            }
+            Msg::ApplyCluster => {
+                if let Some(cluster_of) = self.cluster_of.take() {
+                    let props = ctx.props();
+                    for (idx, _, value) in self.pending_cluster(ctx, cluster_of) {
+                        if let Some(mut plot) = props.booleans_mut().get_mut(props.offset(idx)) {
+                            *plot = value;
+                        }
+                    }
+                    document::edited(ctx);
+                }
+                true
+            }
+            Msg::DismissCluster => {
+                self.cluster_of = None;
+                true
+            }
+            Msg::ChangeInt(plot, value) => {
+                plot.set(value);
+                document::edited(ctx);
+                true
+            }
+            Msg::ToggleAsNumber(idx) => {
+                if !self.as_number.remove(&idx) {
+                    self.as_number.insert(idx);
+                }
+                true
             }
         }
     }
@@ -64,39 +128,55 @@ impl Component for PlotCategory {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let Props { title, booleans, integers, category, me3_imported_me1 } = &ctx.props();
-        let PlotCategoryDb { booleans: bool_db, integers: int_db } = category;
+        let Props { title, booleans, integers, category, .. } = &ctx.props();
+        let PlotCategoryDb { booleans: bool_db, integers: int_db, .. } = category;
 
-        let booleans = bool_db.iter().map(|(idx, label)| {
-            let mut idx = *idx;
-            if *me3_imported_me1 {
-                idx += 10_000;
-            }
-            match booleans.borrow().get(idx) {
-                Some(value) => html! {
-                    <CheckBox
-                        label={label.clone()}
-                        value={RcCell::new(*value)}
-                        onchange={ctx.link().callback(move |value| Msg::ChangeBool(idx, value))}
-                    />
-                },
+        let booleans = bool_db.iter().map(|(&idx, label)| {
+            let value = booleans.borrow().get(ctx.props().offset(idx)).map(|value| *value);
+            match value {
+                Some(value) => {
+                    let cluster =
+                        (self.cluster_of == Some(idx)).then(|| self.view_cluster(ctx, idx));
+                    let confirming = self
+                        .confirming
+                        .filter(|&(confirming, _)| confirming == idx)
+                        .map(|(_, value)| self.view_confirm(ctx, idx, value));
+                    let onchange = ctx.link().callback(move |value| Msg::ChangeBool(idx, value));
+                    html! {
+                        <>
+                            <div class="flex items-center gap-1">
+                                <CheckBox
+                                    label={label.clone()}
+                                    value={RcCell::new(value)}
+                                    {onchange}
+                                />
+                                { Self::view_badge(category, idx) }
+                            </div>
+                            { for confirming }
+                            { for cluster }
+                        </>
+                    }
+                }
                 None => Html::default(),
             }
         });
 
-        let integers = int_db.iter().map(|(idx, label)| {
-            let mut idx = *idx;
-            if *me3_imported_me1 {
-                idx += 10_000;
-            }
+        let integers = int_db.iter().map(|(&idx, label)| {
+            let offset = ctx.props().offset(idx);
             let value = match integers {
-                IntPlotType::Vec(vec) => vec.borrow().get(idx).map(RcCell::clone),
+                IntPlotType::Vec(vec) => vec.borrow().get(offset).map(RcCell::clone),
                 IntPlotType::IndexMap(index_map) => {
-                    index_map.borrow().get(&(idx as i32)).map(RcCell::clone)
+                    index_map.borrow().get(&(offset as i32)).map(RcCell::clone)
                 }
             };
+            // Typed in directly, only a warning
             match value {
-                Some(value) => value.view(label),
+                Some(value) => html! {
+                    <div class="flex items-center gap-1">
+                        { self.view_int(ctx, idx, label, value) }
+                        { Self::view_badge(category, idx) }
+                    </div>
+                },
                 None => Html::default(),
             }
         });
@@ -111,9 +191,170 @@ impl Component for PlotCategory {
 }
 
 impl PlotCategory {
+    fn change_bool(&mut self, ctx: &Context<Self>, idx: usize, value: bool) -> bool {
+        let props = ctx.props();
+        if let Some(mut plot) = props.booleans_mut().get_mut(props.offset(idx)) {
+            *plot = value;
+        }
+        document::edited(ctx);
+
+        // Only a choice being made says something about the rest
+        let cluster_of = (value && !self.pending_cluster(ctx, idx).is_empty()).then(|| idx);
+        let changed =
+            cluster_of.is_some() || self.cluster_of.is_some() || self.confirming.is_some();
+        self.cluster_of = cluster_of;
+        changed
+    }
+
+    // As a number, or as a check box / tri-state if the database maps it to a boolean
+    fn view_int(&self, ctx: &Context<Self>, idx: usize, label: &str, value: RcCell<i32>) -> Html {
+        let as_bool = ctx.props().category.as_bool.get(&idx);
+        let state = as_bool.and_then(|as_bool| as_bool.state(value.get()));
+        let toggle = as_bool.map(|_| {
+            let (text, title) = if self.as_number.contains(&idx) {
+                ("☑", "Edit as a check box")
+            } else {
+                ("123", "Edit as a number")
+            };
+            html! {
+                <a class="rounded-none select-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1 py-0 cursor-pointer"
+                    {title}
+                    onclick={ctx.link().callback(move |_| Msg::ToggleAsNumber(idx))}
+                >
+                    { text }
+                </a>
+            }
+        });
+
+        let input = match (as_bool, state) {
+            (Some(as_bool), Some(state)) if !self.as_number.contains(&idx) => {
+                Self::view_as_bool(ctx, label, as_bool, state, value)
+            }
+            _ => value.view(label),
+        };
+        html! { <>
+            { input }
+            { for toggle }
+        </> }
+    }
+
+    fn view_as_bool(
+        ctx: &Context<Self>, label: &str, as_bool: &IntAsBool, state: Option<bool>,
+        value: RcCell<i32>,
+    ) -> Html {
+        let as_bool = as_bool.clone();
+        match as_bool.unset {
+            // Tri-state
+            Some(_) => {
+                let current_idx = match state {
+                    None => 0,
+                    Some(false) => 1,
+                    Some(true) => 2,
+                };
+                let onselect = ctx.link().callback(move |idx| {
+                    let state = match idx {
+                        0 => None,
+                        1 => Some(false),
+                        _ => Some(true),
+                    };
+                    Msg::ChangeInt(RcCell::clone(&value), as_bool.value(state))
+                });
+                html! {
+                    <div class="flex items-center gap-1">
+                        <Select options={TRI_STATE} {current_idx} {onselect} />
+                        { label }
+                    </div>
+                }
+            }
+            None => {
+                let onchange = ctx.link().callback(move |checked| {
+                    Msg::ChangeInt(RcCell::clone(&value), as_bool.value(Some(checked)))
+                });
+                html! {
+                    <CheckBox
+                        label={label.to_owned()}
+                        value={RcCell::new(state == Some(true))}
+                        {onchange}
+                    />
+                }
+            }
+        }
+    }
+
+    fn view_badge(category: &PlotCategoryDb, idx: usize) -> Html {
+        let reason = match category.dangerous(idx) {
+            Some(reason) => reason.to_owned(),
+            None => return Html::default(),
+        };
+        html! {
+            <span class="text-title-bar-close select-none" title={reason}>{ "⚠" }</span>
+        }
+    }
+
+    fn view_confirm(&self, ctx: &Context<Self>, idx: usize, value: bool) -> Html {
+        let link = ctx.link();
+        let reason = ctx.props().category.dangerous(idx).unwrap_or_default();
+        html! {
+            <div class="ml-5 mb-1 p-1 flex flex-col gap-1 border border-default-border">
+                <p>
+                    <span class="text-title-bar-close select-none">{ "⚠ " }</span>
+                    { reason }
+                </p>
+                <p class="text-fg/50">{ "Only change it if you know it's safe at this point." }</p>
+                <div class="flex gap-1">
+                    <button class="button" onclick={link.callback(|_| Msg::ConfirmDangerous)}>
+                        { if value { "Check it anyway" } else { "Uncheck it anyway" } }
+                    </button>
+                    <button class="button" onclick={link.callback(|_| Msg::CancelDangerous)}>
+                        { "Cancel" }
+                    </button>
+                </div>
+            </div>
+        }
+    }
+
+    // The plots of the cluster that don't have the value the game would have given them yet
+    fn pending_cluster<'a>(
+        &self, ctx: &'a Context<Self>, idx: usize,
+    ) -> Vec<(usize, &'a str, bool)> {
+        let props = ctx.props();
+        let booleans = props.booleans.borrow();
+        props
+            .category
+            .cluster(idx)
+            .filter(|&(idx, _, value)| {
+                booleans.get(props.offset(idx)).map(|current| *current != value).unwrap_or(false)
+            })
+            .collect()
+    }
+
+    fn view_cluster(&self, ctx: &Context<Self>, idx: usize) -> Html {
+        let link = ctx.link();
+        let plots = self.pending_cluster(ctx, idx).into_iter().map(|(_, label, value)| {
+            html! {
+                <p>{ format!("{} {}", if value { "check" } else { "uncheck" }, label) }</p>
+            }
+        });
+
+        html! {
+            <div class="ml-5 mb-1 p-1 flex flex-col gap-1 border border-default-border">
+                <p class="text-fg/50">{ "The game usually changes these along with it:" }</p>
+                { for plots }
+                <div class="flex gap-1">
+                    <button class="button" onclick={link.callback(|_| Msg::ApplyCluster)}>
+                        { "Change them too" }
+                    </button>
+                    <button class="button" onclick={link.callback(|_| Msg::DismissCluster)}>
+                        { "Only this one" }
+                    </button>
+                </div>
+            </div>
+        }
+    }
+
     fn add_missing_plots(&mut self, ctx: &Context<Self>) {
         let Props { booleans, integers, category, me3_imported_me1, .. } = &mut ctx.props();
-        let PlotCategoryDb { booleans: bool_db, integers: int_db } = &category;
+        let PlotCategoryDb { booleans: bool_db, integers: int_db, .. } = &category;
 
         // Booleans
         if let Some(&(mut max)) = bool_db.keys().max() {
@@ -0,0 +1,85 @@
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::{
+    gui::components::Helper,
+    save_data::{
+        shared::{
+            plot::{BitVec, RawPlotDb},
+            plot_coverage::PlotCoverage,
+        },
+        RcRef,
+    },
+    services::save_handler::{Action, SaveHandler},
+};
+
+use super::{FloatPlotType, IntPlotType};
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub game: &'static str,
+    pub booleans: RcRef<BitVec>,
+    pub integers: IntPlotType,
+    pub floats: FloatPlotType,
+    pub plot_db: Rc<RawPlotDb>,
+}
+
+impl Props {
+    fn coverage(&self) -> PlotCoverage {
+        // Unset plots say nothing about the game
+        let booleans: Vec<_> = self.booleans.borrow().iter_ones().collect();
+        let integers: Vec<_> = match self.integers {
+            IntPlotType::Vec(ref vec) => vec
+                .borrow()
+                .iter()
+                .enumerate()
+                .filter(|(_, value)| value.get() != 0)
+                .map(|(idx, _)| idx)
+                .collect(),
+            IntPlotType::IndexMap(ref index_map) => index_map
+                .borrow()
+                .iter()
+                .filter(|(_, value)| value.get() != 0)
+                .map(|(&idx, _)| idx as usize)
+                .collect(),
+        };
+        let floats: Vec<_> = match self.floats {
+            FloatPlotType::Vec(ref vec) => vec
+                .borrow()
+                .iter()
+                .enumerate()
+                .filter(|(_, value)| value.get() != 0.0)
+                .map(|(idx, _)| idx)
+                .collect(),
+            FloatPlotType::IndexMap(ref index_map) => index_map
+                .borrow()
+                .iter()
+                .filter(|(_, value)| value.get() != 0.0)
+                .map(|(&idx, _)| idx as usize)
+                .collect(),
+        };
+
+        PlotCoverage::new(self.game, &self.plot_db, booleans, integers, floats)
+    }
+}
+
+#[function_component(PlotCoverageExport)]
+pub fn plot_coverage_export(props: &Props) -> Html {
+    let save_handler = use_context::<SaveHandler>().expect("no save handler provider");
+    let coverage = props.coverage();
+    let unknown = coverage.booleans.unknown + coverage.integers.unknown + coverage.floats.unknown;
+    let onclick =
+        Callback::from(move |_| save_handler.action(Action::ExportPlotCoverage(coverage.clone())));
+
+    html! {
+        <div class="flex items-center gap-1 mb-1">
+            <button class="button" {onclick}>{ "Export coverage" }</button>
+            <span class="text-fg/50">{ format!("{} set plots are not in the database", unknown) }</span>
+            <Helper text=
+                "Lists the plots set in this save that the database doesn't know of yet, \
+                grouped by hundreds, in a JSON file sorted by id to compare it with other saves."
+            />
+        </div>
+    }
+}
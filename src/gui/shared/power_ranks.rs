@@ -0,0 +1,150 @@
+use yew::{prelude::*, ContextProvider};
+
+use crate::{
+    gui::components::{Helper, InputNumber, NumberType, Table},
+    save_data::{
+        shared::power_points::{self, PointRules, PowerPoints},
+        RcCell,
+    },
+    services::{derived_state::DerivedState, document::Document},
+};
+
+// What a character is allowed to have spent at its level
+#[derive(Clone, PartialEq)]
+pub struct PointsCheck {
+    // `Shepard` or the tag of a squad member
+    pub character: String,
+    pub rules: PointRules,
+    pub level: RcCell<i32>,
+    pub unspent: RcCell<i32>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub title: String,
+    // (Power name, rank)
+    pub powers: Vec<(String, RcCell<f32>)>,
+    pub max_rank: f64,
+    #[prop_or(true)]
+    pub opened: bool,
+    pub points: Option<PointsCheck>,
+}
+
+// Bumped by a refund, the ranks are shown again as lowered
+#[derive(Clone, PartialEq)]
+struct Refunds(usize);
+
+#[function_component(PowerRanks)]
+pub fn power_ranks(props: &Props) -> Html {
+    let Props { title, powers, max_rank, opened, points } = props;
+    let refunds = use_state(|| 0_usize);
+
+    let summary = points.as_ref().map(|check| {
+        let ranks: Vec<RcCell<f32>> = powers.iter().map(|(_, rank)| RcCell::clone(rank)).collect();
+        let onrefund = {
+            let refunds = refunds.clone();
+            Callback::from(move |_| refunds.set(*refunds + 1))
+        };
+        html! { <PointsSummary check={check.clone()} {ranks} {onrefund} /> }
+    });
+    let ranks = powers.iter().map(|(name, rank)| {
+        html! { <RankInput label={name.clone()} rank={RcCell::clone(rank)} max_rank={*max_rank} /> }
+    });
+    let empty = powers.is_empty().then(|| html! { <p>{ "<empty>" }</p> });
+
+    html! {
+        <ContextProvider<Refunds> context={Refunds(*refunds)}>
+            <Table title={title.clone()} opened={*opened} helper=
+                "The rank unlocked in each power tree, going above the last rank does nothing in game."
+            >
+                { for summary }
+                { for ranks }
+                { for empty }
+            </Table>
+        </ContextProvider<Refunds>>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct RankInputProps {
+    label: String,
+    rank: RcCell<f32>,
+    max_rank: f64,
+}
+
+#[function_component(RankInput)]
+fn rank_input(props: &RankInputProps) -> Html {
+    let RankInputProps { label, rank, max_rank } = props;
+    let refunds = use_context::<Refunds>().map(|refunds| refunds.0).unwrap_or_default();
+
+    html! {
+        <InputNumber key={refunds}
+            label={label.clone()}
+            value={NumberType::Float(RcCell::clone(rank))}
+            step={1.0}
+            min={0.0}
+            max={*max_rank}
+        />
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct PointsSummaryProps {
+    check: PointsCheck,
+    ranks: Vec<RcCell<f32>>,
+    onrefund: Callback<()>,
+}
+
+// Follows the edits on its own, the table stays as it is
+#[function_component(PointsSummary)]
+fn points_summary(props: &PointsSummaryProps) -> Html {
+    let PointsSummaryProps { check, ranks, onrefund } = props;
+    let derived_state = use_context::<DerivedState>().expect("no derived state provider");
+    let document = use_context::<Document>().expect("no document provider");
+
+    let counted =
+        match derived_state.power_points.as_ref().and_then(|points| points.get(&check.character)) {
+            Some(counted) => *counted,
+            None => return Html::default(),
+        };
+    let PowerPoints { spent, unspent, earned } = counted;
+    let summary = format!(
+        "{} points spent, {} unspent, {} earned at level {}",
+        spent,
+        unspent,
+        earned,
+        check.level.get()
+    );
+
+    let excess = (counted.excess() > 0).then(|| {
+        let onclick = {
+            let check = check.clone();
+            let ranks = ranks.clone();
+            let onrefund = onrefund.clone();
+            Callback::from(move |_| {
+                power_points::refund_excess(check.rules, check.level.get(), &ranks, &check.unspent);
+                document.edited();
+                onrefund.emit(());
+            })
+        };
+        html! { <>
+            <span class="text-title-bar-close">
+                { format!("⚠ {} points over", counted.excess()) }
+            </span>
+            <button class="button" {onclick}>{ "Refund excess" }</button>
+        </> }
+    });
+
+    html! {
+        <div class="flex items-center gap-1">
+            <span>{ summary }</span>
+            <Helper text=
+                "The game resets every power of a character with more points than its level \
+                gives, spent or not.\n\
+                Refunding takes the excess out of the unspent points first, then lowers the \
+                highest ranks."
+            />
+            { for excess }
+        </div>
+    }
+}
@@ -1,24 +1,92 @@
 use std::{
     cell::{Ref, RefMut},
+    collections::{BTreeMap, BTreeSet, HashSet},
+    ops::{Range, RangeInclusive},
     rc::Rc,
 };
 
-use gloo::{events::EventListener, timers::future::TimeoutFuture, utils};
+use gloo::{
+    events::EventListener,
+    storage::{LocalStorage, Storage},
+    timers::future::TimeoutFuture,
+    utils,
+};
 use indexmap::{map::Entry, IndexMap};
+use wasm_bindgen_futures as futures;
 use web_sys::{HtmlElement, HtmlInputElement};
 use yew::prelude::*;
 
 use crate::{
     gui::{
-        components::{CheckBox, Helper, InputNumber, NumberType},
+        components::{CheckBox, Helper, InputNumber, NumberType, Select},
         raw_ui::RawUi,
     },
     save_data::{shared::plot::RawPlotDb, RcCell, RcRef},
+    services::{bookmarks::names_key, document, logger, rpc},
 };
 
 use super::{FloatPlotType, IntPlotType, PlotType};
 
 const LABEL_LIST_MAX_LEN: usize = 10_000_000;
+const GROUPING_KEY: &str = "raw_plot_grouping";
+const DOUBLE_CLICK_KEY: &str = "raw_plot_double_click";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Grouping {
+    None,
+    Hundreds,
+    Thousands,
+}
+
+impl Grouping {
+    fn names() -> &'static [&'static str] {
+        &["No grouping", "By hundreds", "By thousands"]
+    }
+
+    fn from_idx(idx: usize) -> Self {
+        match idx {
+            1 => Grouping::Hundreds,
+            2 => Grouping::Thousands,
+            _ => Grouping::None,
+        }
+    }
+
+    fn size(self) -> Option<usize> {
+        match self {
+            Grouping::None => None,
+            Grouping::Hundreds => Some(100),
+            Grouping::Thousands => Some(1000),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum RowAction {
+    Toggle,
+    CopyId,
+    Bookmark,
+}
+
+impl RowAction {
+    fn names() -> &'static [&'static str] {
+        &["Double-click toggles", "Double-click copies the id", "Double-click bookmarks"]
+    }
+
+    fn from_idx(idx: usize) -> Self {
+        match idx {
+            1 => RowAction::CopyId,
+            2 => RowAction::Bookmark,
+            _ => RowAction::Toggle,
+        }
+    }
+}
+
+enum Row {
+    // First plot id of the range, positions of its plots in the label list
+    Group(usize, Range<usize>),
+    // Position in the label list
+    Plot(usize),
+}
 
 pub enum Msg {
     Scrolled,
@@ -26,12 +94,27 @@ pub enum Msg {
     Filter(InputEvent),
     Filtered,
     Add,
+    Grouping(usize),
+    ToggleGroup(usize),
+    DoubleClickAction(usize),
+    DoubleClick(usize),
+    ContextMenu(usize, i32, i32),
+    CloseContextMenu,
+    RowAction(RowAction, usize),
+    BookmarksOnly(bool),
+    Select(usize, bool),
+    Deselect,
+    SetSelection(bool),
+    RangeInput(InputEvent),
+    SetRange(bool),
 }
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
     pub plots: PlotType,
     pub plot_db: Rc<RawPlotDb>,
+    // Bookmarks are stored by game and plot type
+    pub bookmarks_key: &'static str,
     #[prop_or_default]
     filter: RcRef<String>,
     #[prop_or_default]
@@ -59,6 +142,19 @@ pub struct RawPlot {
     skip: usize,
     take: usize,
     label_list: Option<IndexMap<usize, Option<String>>>,
+    grouping: Grouping,
+    opened_groups: HashSet<usize>,
+    rows: Vec<Row>,
+    double_click: RowAction,
+    // Plot id, mouse position
+    context_menu: Option<(usize, i32, i32)>,
+    bookmarks: BTreeSet<usize>,
+    // Given by an imported bookmark pack
+    bookmark_names: BTreeMap<usize, String>,
+    bookmarks_only: bool,
+    // Plot ids where the shift-click selection starts and ends
+    selection: Option<(usize, usize)>,
+    range_input: String,
     is_filtering: bool,
     pending_filter: Option<InputEvent>,
 }
@@ -83,6 +179,19 @@ impl Component for RawPlot {
             skip: 0,
             take: 0,
             label_list: None,
+            grouping: Grouping::from_idx(LocalStorage::get(GROUPING_KEY).unwrap_or_default()),
+            opened_groups: HashSet::new(),
+            rows: Vec::new(),
+            double_click: RowAction::from_idx(
+                LocalStorage::get(DOUBLE_CLICK_KEY).unwrap_or_default(),
+            ),
+            context_menu: None,
+            bookmarks: LocalStorage::get(ctx.props().bookmarks_key).unwrap_or_default(),
+            bookmark_names: LocalStorage::get(&names_key(ctx.props().bookmarks_key))
+                .unwrap_or_default(),
+            bookmarks_only: false,
+            selection: None,
+            range_input: String::new(),
             is_filtering: false,
             pending_filter: None,
         };
@@ -99,7 +208,7 @@ impl Component for RawPlot {
                     let offset_height = scroll.offset_height();
                     let num_rows = offset_height / self.row_height + 2;
 
-                    let len = self.label_list.as_ref().map(|list| list.len()).unwrap_or_default();
+                    let len = self.rows.len();
                     let start = scroll_top / self.row_height;
                     self.skip = start.max(0) as usize;
                     self.take = num_rows.min(len as i32) as usize;
@@ -112,7 +221,8 @@ impl Component for RawPlot {
                         *plot = value;
                     }
                 }
-                false
+                // Refresh the set count of the group
+                self.grouping != Grouping::None
             }
             Msg::Filter(event) => {
                 if !self.is_filtering {
@@ -196,15 +306,107 @@ impl Component for RawPlot {
                     },
                 };
                 if added {
+                    document::edited(ctx);
                     self.update_label_list(ctx);
                     ctx.link().send_message(Msg::Scrolled);
                 }
                 false
             }
+            Msg::Grouping(idx) => {
+                let _ = LocalStorage::set(GROUPING_KEY, idx);
+                self.grouping = Grouping::from_idx(idx);
+                self.opened_groups.clear();
+                self.update_rows();
+                ctx.link().send_message(Msg::Scrolled);
+                false
+            }
+            Msg::ToggleGroup(start) => {
+                if !self.opened_groups.remove(&start) {
+                    self.opened_groups.insert(start);
+                }
+                self.update_rows();
+                ctx.link().send_message(Msg::Scrolled);
+                false
+            }
+            Msg::DoubleClickAction(idx) => {
+                let _ = LocalStorage::set(DOUBLE_CLICK_KEY, idx);
+                self.double_click = RowAction::from_idx(idx);
+                true
+            }
+            Msg::DoubleClick(idx) => {
+                self.row_action(ctx, self.double_click, idx);
+                true
+            }
+            Msg::ContextMenu(idx, x, y) => {
+                self.context_menu = Some((idx, x, y));
+                true
+            }
+            Msg::CloseContextMenu => {
+                self.context_menu = None;
+                true
+            }
+            Msg::RowAction(action, idx) => {
+                self.context_menu = None;
+                self.row_action(ctx, action, idx);
+                true
+            }
+            Msg::BookmarksOnly(bookmarks_only) => {
+                self.bookmarks_only = bookmarks_only;
+                self.update_label_list(ctx);
+                ctx.link().send_message(Msg::Scrolled);
+                false
+            }
+            Msg::Select(idx, extend) => {
+                self.selection = match self.selection {
+                    Some((anchor, _)) if extend => Some((anchor, idx)),
+                    _ => Some((idx, idx)),
+                };
+                true
+            }
+            Msg::Deselect => {
+                self.selection = None;
+                true
+            }
+            Msg::SetSelection(value) => {
+                let selected = self.selected();
+                self.set_booleans(ctx, selected, value);
+                true
+            }
+            Msg::RangeInput(event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    self.range_input = input.value();
+                }
+                false
+            }
+            Msg::SetRange(value) => match parse_range(&self.range_input) {
+                Some(range) if *range.end() >= LABEL_LIST_MAX_LEN => {
+                    logger::warn(format!("Plot ids stop at {}", LABEL_LIST_MAX_LEN - 1));
+                    false
+                }
+                Some(range) => {
+                    if self.set_booleans(ctx, range, value) {
+                        self.update_label_list(ctx);
+                        ctx.link().send_message(Msg::Scrolled);
+                    }
+                    true
+                }
+                None => {
+                    logger::warn(format!(
+                        "`{}` is not a range of plot ids, expected `start..end`",
+                        self.range_input
+                    ));
+                    false
+                }
+            },
         }
     }
 
     fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        self.context_menu = None;
+        self.selection = None;
+        self.bookmarks = LocalStorage::get(ctx.props().bookmarks_key).unwrap_or_default();
+        self.bookmark_names =
+            LocalStorage::get(&names_key(ctx.props().bookmarks_key)).unwrap_or_default();
         self.add_missing_plots(ctx);
         self.update_label_list(ctx);
 
@@ -220,47 +422,76 @@ impl Component for RawPlot {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let label_list = self.label_list.as_ref();
-        let rows =
-            label_list.unwrap().iter().skip(self.skip).take(self.take).map(|(&idx, label)| {
-                let label = label
-                    .as_ref()
-                    .map(|label| format!("{} - {}", idx, label))
-                    .unwrap_or_else(|| idx.to_string());
-
-                let row = match ctx.props().plots {
-                    PlotType::Boolean(ref booleans) => booleans.borrow().get(idx).map(|plot| {
-                        html! {
-                            <CheckBox
-                                {label}
-                                value={RcCell::new(*plot)}
-                                onchange={ctx.link().callback(move |value| Msg::ChangeBool(idx, value))}
-                            />
-                        }
-                    }),
-                    PlotType::Int(ref integers) => match integers {
-                        IntPlotType::Vec(ref vec) => {
-                            vec.borrow().get(idx).map(|plot| plot.view(&label))
-                        }
-                        IntPlotType::IndexMap(ref index_map) => {
-                            index_map.borrow().get(&(idx as i32)).map(|plot| plot.view(&label))
-                        }
-                    },
-                    PlotType::Float(ref floats) => match floats {
-                        FloatPlotType::Vec(ref vec) => {
-                            vec.borrow().get(idx).map(|plot| plot.view(&label))
-                        }
-                        FloatPlotType::IndexMap(ref index_map) => {
-                            index_map.borrow().get(&(idx as i32)).map(|plot| plot.view(&label))
-                        }
-                    },
-                };
-                html! {
-                    <div class="raw-plot-row">
-                        { for row }
-                    </div>
+        let label_list = self.label_list.as_ref().unwrap();
+        let rows = self.rows.iter().skip(self.skip).take(self.take).map(|row| {
+            let position = match *row {
+                Row::Group(start, ref positions) => {
+                    return self.view_group(ctx, label_list, start, positions.clone())
+                }
+                Row::Plot(position) => position,
+            };
+            let (&idx, label) = label_list.get_index(position).unwrap();
+
+            let label = label
+                .as_ref()
+                .map(|label| format!("{} - {}", idx, label))
+                .unwrap_or_else(|| idx.to_string());
+            let label = match self.bookmark_names.get(&idx) {
+                Some(name) if self.bookmarks.contains(&idx) => format!("★ {} ({})", label, name),
+                _ if self.bookmarks.contains(&idx) => format!("★ {}", label),
+                _ => label,
+            };
+
+            let row = match ctx.props().plots {
+                PlotType::Boolean(ref booleans) => booleans.borrow().get(idx).map(|plot| {
+                    html! {
+                        <CheckBox
+                            {label}
+                            value={RcCell::new(*plot)}
+                            onchange={ctx.link().callback(move |value| Msg::ChangeBool(idx, value))}
+                        />
+                    }
+                }),
+                PlotType::Int(ref integers) => match integers {
+                    IntPlotType::Vec(ref vec) => {
+                        vec.borrow().get(idx).map(|plot| plot.view(&label))
+                    }
+                    IntPlotType::IndexMap(ref index_map) => {
+                        index_map.borrow().get(&(idx as i32)).map(|plot| plot.view(&label))
+                    }
+                },
+                PlotType::Float(ref floats) => match floats {
+                    FloatPlotType::Vec(ref vec) => {
+                        vec.borrow().get(idx).map(|plot| plot.view(&label))
+                    }
+                    FloatPlotType::IndexMap(ref index_map) => {
+                        index_map.borrow().get(&(idx as i32)).map(|plot| plot.view(&label))
+                    }
+                },
+            };
+            // Double-clicking an input is for editing it
+            let ondblclick = ctx.link().batch_callback(move |e: MouseEvent| {
+                e.target_dyn_into::<HtmlInputElement>().is_none().then(|| Msg::DoubleClick(idx))
+            });
+            let oncontextmenu = ctx.link().callback(move |e: MouseEvent| {
+                e.prevent_default();
+                Msg::ContextMenu(idx, e.client_x(), e.client_y())
+            });
+            // Shift-click extends the selection instead of toggling
+            let is_boolean = matches!(ctx.props().plots, PlotType::Boolean(_));
+            let onclick = ctx.link().batch_callback(move |e: MouseEvent| {
+                if is_boolean && e.shift_key() {
+                    e.prevent_default();
                 }
+                is_boolean.then(|| Msg::Select(idx, e.shift_key()))
             });
+            let selected = self.is_selected(idx).then(|| "bg-theme-active");
+            html! {
+                <div class={classes!["raw-plot-row", selected]} {onclick} {ondblclick} {oncontextmenu}>
+                    { for row }
+                </div>
+            }
+        });
 
         let add_helper = match ctx.props().plots {
             PlotType::Boolean(_)
@@ -275,7 +506,7 @@ impl Component for RawPlot {
             },
             _ => Html::default(),
         };
-        let len = label_list.map(|list| list.len()).unwrap_or_default();
+        let len = self.rows.len();
         html! {
             <div class="flex-auto flex flex-col gap-1">
                 <div class="flex gap-3 w-2/3">
@@ -295,7 +526,29 @@ impl Component for RawPlot {
                         <input type="submit" class="button" value="Add" />
                         { add_helper }
                     </form>
+                    <Select
+                        options={Grouping::names()}
+                        current_idx={self.grouping as usize}
+                        onselect={ctx.link().callback(Msg::Grouping)}
+                    />
+                    <Select
+                        options={RowAction::names()}
+                        current_idx={self.double_click as usize}
+                        onselect={ctx.link().callback(Msg::DoubleClickAction)}
+                    />
+                    <div class="flex items-center gap-1">
+                        <CheckBox
+                            label="Bookmarks"
+                            value={RcCell::new(self.bookmarks_only)}
+                            onchange={ctx.link().callback(Msg::BookmarksOnly)}
+                        />
+                        <Helper text=
+                            "Only show the bookmarked plots.\n\
+                            Right-click a plot to toggle, copy or bookmark it."
+                        />
+                    </div>
                 </div>
+                { self.view_bulk(ctx) }
                 <hr class="border-t border-default-border" />
                 <div class="flex-auto h-0 overflow-y-auto"
                     onscroll={ctx.link().callback(|_| {gloo::console::log!("Scrolled"); Msg::Scrolled})}
@@ -311,6 +564,7 @@ impl Component for RawPlot {
                         </div>
                     </div>
                 </div>
+                { for self.context_menu.map(|(idx, x, y)| self.view_context_menu(ctx, idx, x, y)) }
             </div>
         }
     }
@@ -417,7 +671,266 @@ impl RawPlot {
                     || (is_number && idx.to_string().contains(&*filter))
             });
         }
+        if self.bookmarks_only {
+            label_list.retain(|idx, _| self.bookmarks.contains(idx));
+        }
 
         self.label_list = Some(label_list);
+        self.update_rows();
+    }
+
+    fn update_rows(&mut self) {
+        let label_list = match self.label_list {
+            Some(ref label_list) => label_list,
+            None => return,
+        };
+        let size = match self.grouping.size() {
+            Some(size) => size,
+            None => {
+                self.rows = (0..label_list.len()).map(Row::Plot).collect();
+                return;
+            }
+        };
+
+        // The label list is sorted, each group is a contiguous slice of it
+        let mut rows = Vec::new();
+        let mut position = 0;
+        while let Some((&idx, _)) = label_list.get_index(position) {
+            let start = idx / size * size;
+            let end = label_list
+                .keys()
+                .skip(position)
+                .position(|&idx| idx >= start + size)
+                .map(|len| position + len)
+                .unwrap_or_else(|| label_list.len());
+
+            rows.push(Row::Group(start, position..end));
+            if self.opened_groups.contains(&start) {
+                rows.extend((position..end).map(Row::Plot));
+            }
+            position = end;
+        }
+        self.rows = rows;
+    }
+
+    fn view_context_menu(&self, ctx: &Context<Self>, idx: usize, x: i32, y: i32) -> Html {
+        let link = ctx.link();
+        let button = |text: &'static str, action: RowAction| {
+            html! {
+                <button class="rounded-none hover:bg-theme-hover active:bg-theme-active px-1 text-left"
+                    onclick={link.callback(move |_| Msg::RowAction(action, idx))}
+                >
+                    { text }
+                </button>
+            }
+        };
+
+        // Only booleans can be toggled
+        let toggle = matches!(ctx.props().plots, PlotType::Boolean(_))
+            .then(|| button("Toggle", RowAction::Toggle));
+        let bookmark = if self.bookmarks.contains(&idx) {
+            button("Remove bookmark", RowAction::Bookmark)
+        } else {
+            button("Add bookmark", RowAction::Bookmark)
+        };
+
+        html! {
+            <>
+                <div class="fixed inset-0 z-40"
+                    onclick={link.callback(|_| Msg::CloseContextMenu)}
+                    oncontextmenu={link.callback(|e: MouseEvent| {
+                        e.prevent_default();
+                        Msg::CloseContextMenu
+                    })}
+                />
+                <div class="fixed z-50 flex flex-col min-w-[120px] border border-default-border bg-default-bg"
+                    style={format!("left: {}px; top: {}px;", x, y)}
+                >
+                    <div class="px-1 bg-theme-tab">{ idx }</div>
+                    { for toggle }
+                    { button("Copy ID", RowAction::CopyId) }
+                    { bookmark }
+                </div>
+            </>
+        }
+    }
+
+    fn row_action(&mut self, ctx: &Context<Self>, action: RowAction, idx: usize) {
+        match action {
+            RowAction::Toggle => {
+                if let PlotType::Boolean(ref booleans) = ctx.props().plots {
+                    if let Some(mut plot) = booleans.borrow_mut().get_mut(idx) {
+                        *plot = !*plot;
+                    }
+                }
+                document::edited(ctx);
+            }
+            RowAction::CopyId => futures::spawn_local(async move {
+                if let Err(err) = rpc::copy_to_clipboard(&idx.to_string()).await {
+                    logger::error(format!("Failed to copy the plot id: {:#}", err));
+                }
+            }),
+            RowAction::Bookmark => {
+                if self.bookmarks.remove(&idx) {
+                    if self.bookmark_names.remove(&idx).is_some() {
+                        let key = names_key(ctx.props().bookmarks_key);
+                        let _ = LocalStorage::set(&key, &self.bookmark_names);
+                    }
+                } else {
+                    self.bookmarks.insert(idx);
+                }
+                let _ = LocalStorage::set(ctx.props().bookmarks_key, &self.bookmarks);
+                if self.bookmarks_only {
+                    self.update_label_list(ctx);
+                    ctx.link().send_message(Msg::Scrolled);
+                }
+            }
+        }
     }
+
+    fn view_group(
+        &self, ctx: &Context<Self>, label_list: &IndexMap<usize, Option<String>>, start: usize,
+        positions: Range<usize>,
+    ) -> Html {
+        let size = self.grouping.size().unwrap_or_default();
+        let chevron = if self.opened_groups.contains(&start) {
+            "table-chevron-down"
+        } else {
+            "table-chevron-right"
+        };
+
+        let len = positions.len();
+        let set_count = positions
+            .filter_map(|position| label_list.get_index(position))
+            .filter(|&(&idx, _)| self.is_set(ctx, idx))
+            .count();
+
+        html! {
+            <div class="raw-plot-row">
+                <button class={classes![
+                        "rounded-none",
+                        "hover:bg-theme-hover",
+                        "active:bg-theme-active",
+                        "px-1",
+                        "pl-6",
+                        "w-full",
+                        "text-left",
+                        chevron,
+                    ]}
+                    onclick={ctx.link().callback(move |_| Msg::ToggleGroup(start))}
+                >
+                    { format!("{} - {}", start, start + size - 1) }
+                    <span class="ml-2 px-1 bg-theme-bg">{ format!("{} / {} set", set_count, len) }</span>
+                </button>
+            </div>
+        }
+    }
+
+    // Booleans only, a block of plots at once
+    fn view_bulk(&self, ctx: &Context<Self>) -> Html {
+        if !matches!(ctx.props().plots, PlotType::Boolean(_)) {
+            return Html::default();
+        }
+        let link = ctx.link();
+
+        let selection = self.selection.map(|_| {
+            let len = self.selected().len();
+            html! {
+                <div class="flex items-center gap-1">
+                    { format!("{} selected", len) }
+                    <button class="button" onclick={link.callback(|_| Msg::SetSelection(true))}>{ "Set" }</button>
+                    <button class="button" onclick={link.callback(|_| Msg::SetSelection(false))}>{ "Clear" }</button>
+                    <button class="button" onclick={link.callback(|_| Msg::Deselect)}>{ "Deselect" }</button>
+                </div>
+            }
+        });
+
+        html! {
+            <div class="flex gap-3 w-2/3">
+                <form class="flex items-center gap-1"
+                    onsubmit={link.callback(|e: FocusEvent| {
+                        e.prevent_default();
+                        Msg::SetRange(true)
+                    })}
+                >
+                    <input type="text" class="input w-[140px]" placeholder="1000..1099"
+                        value={self.range_input.clone()}
+                        oninput={link.callback(Msg::RangeInput)}
+                    />
+                    <input type="submit" class="button" value="Set" />
+                    <button type="button" class="button" onclick={link.callback(|_| Msg::SetRange(false))}>{ "Clear" }</button>
+                    <Helper text=
+                        "Set or clear every plot from `start` to `end`, both included.\n\
+                        Click a plot then Shift-click another one to select the shown plots in between."
+                    />
+                </form>
+                { for selection }
+            </div>
+        }
+    }
+
+    // The shown plots between both ends of the selection
+    fn selected(&self) -> Vec<usize> {
+        match (self.selection, &self.label_list) {
+            (Some(_), Some(label_list)) => {
+                label_list.keys().copied().filter(|&idx| self.is_selected(idx)).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn is_selected(&self, idx: usize) -> bool {
+        self.selection
+            .map(|(anchor, end)| (anchor.min(end)..=anchor.max(end)).contains(&idx))
+            .unwrap_or(false)
+    }
+
+    // Tells if plots were added past the end
+    fn set_booleans(
+        &self, ctx: &Context<Self>, ids: impl IntoIterator<Item = usize>, value: bool,
+    ) -> bool {
+        match ctx.props().plots {
+            PlotType::Boolean(ref booleans) => {
+                let mut booleans = booleans.borrow_mut();
+                let len = booleans.len();
+                let changed = booleans.set_many(ids, value);
+                document::edited(ctx);
+                logger::info(format!(
+                    "{} plots {}",
+                    changed,
+                    if value { "set" } else { "cleared" }
+                ));
+                booleans.len() > len
+            }
+            _ => false,
+        }
+    }
+
+    // Not false / 0
+    fn is_set(&self, ctx: &Context<Self>, idx: usize) -> bool {
+        match ctx.props().plots {
+            PlotType::Boolean(ref booleans) => booleans.borrow().get(idx).map(|plot| *plot),
+            PlotType::Int(ref integers) => match integers {
+                IntPlotType::Vec(ref vec) => vec.borrow().get(idx).map(|plot| plot.get() != 0),
+                IntPlotType::IndexMap(ref index_map) => {
+                    index_map.borrow().get(&(idx as i32)).map(|plot| plot.get() != 0)
+                }
+            },
+            PlotType::Float(ref floats) => match floats {
+                FloatPlotType::Vec(ref vec) => vec.borrow().get(idx).map(|plot| plot.get() != 0.0),
+                FloatPlotType::IndexMap(ref index_map) => {
+                    index_map.borrow().get(&(idx as i32)).map(|plot| plot.get() != 0.0)
+                }
+            },
+        }
+        .unwrap_or(false)
+    }
+}
+
+// `start..end` or `start-end`, both included like the groups
+pub fn parse_range(text: &str) -> Option<RangeInclusive<usize>> {
+    let (start, end) = text.split_once("..").or_else(|| text.split_once('-'))?;
+    let end = end.trim_start_matches('=');
+    let (start, end) = (start.trim().parse().ok()?, end.trim().parse().ok()?);
+    (start <= end).then(|| start..=end)
 }
@@ -0,0 +1,149 @@
+use js_sys::Date;
+use yew::prelude::*;
+
+use super::HealthCard;
+use crate::{
+    gui::components::{Helper, InputNumber, NumberType, Table},
+    save_data::{shared::SaveTimeStamp, RcCell, RcRef},
+    services::document,
+};
+
+pub enum Msg {
+    Changed,
+    SetNow(RcRef<SaveTimeStamp>),
+    Repair,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub timestamp: RcRef<SaveTimeStamp>,
+    pub created_date: Option<RcRef<SaveTimeStamp>>,
+    pub seconds_played: NumberType,
+}
+
+impl Props {
+    fn timestamps(&self) -> impl Iterator<Item = &RcRef<SaveTimeStamp>> {
+        Some(&self.timestamp).into_iter().chain(self.created_date.as_ref())
+    }
+
+    fn is_playtime_valid(&self) -> bool {
+        let seconds_played = self.seconds_played.get();
+        seconds_played.is_finite() && seconds_played >= 0.0
+    }
+}
+
+pub struct SaveInfo;
+
+impl Component for SaveInfo {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        SaveInfo
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            // Refresh the validation
+            Msg::Changed => true,
+            Msg::SetNow(timestamp) => {
+                Self::set_now(&mut timestamp.borrow_mut());
+                document::edited(ctx);
+                true
+            }
+            Msg::Repair => {
+                for timestamp in ctx.props().timestamps() {
+                    if !timestamp.borrow().is_valid() {
+                        Self::set_now(&mut timestamp.borrow_mut());
+                    }
+                }
+                if !ctx.props().is_playtime_valid() {
+                    ctx.props().seconds_played.set(0.0);
+                }
+                document::edited(ctx);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let Props { timestamp, created_date, seconds_played } = ctx.props();
+
+        let is_valid = ctx.props().timestamps().all(|timestamp| timestamp.borrow().is_valid())
+            && ctx.props().is_playtime_valid();
+        let repair = (!is_valid).then(|| {
+            html! {
+                <div class="flex items-center gap-1">
+                    <span class="text-title-bar-close">{ "Some of these fields are invalid." }</span>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::Repair)}>
+                        { "Repair" }
+                    </button>
+                    <Helper text=
+                        "Invalid dates are set to now and a negative play time to 0.\n\
+                        You can also edit the values by hand instead."
+                    />
+                </div>
+            }
+        });
+
+        html! {
+            <Table title="Save Info">
+                <HealthCard />
+                { for repair }
+                { Self::view_timestamp(ctx, timestamp, "Timestamp") }
+                { for created_date.as_ref().map(|created_date| Self::view_timestamp(ctx, created_date, "Created Date")) }
+                <InputNumber
+                    label="Seconds Played"
+                    value={seconds_played.clone()}
+                    onchange={ctx.link().callback(|_| Msg::Changed)}
+                />
+            </Table>
+        }
+    }
+}
+
+impl SaveInfo {
+    fn view_timestamp(ctx: &Context<Self>, timestamp: &RcRef<SaveTimeStamp>, label: &str) -> Html {
+        let set_now = {
+            let timestamp = RcRef::clone(timestamp);
+            ctx.link().callback(move |_| Msg::SetNow(RcRef::clone(&timestamp)))
+        };
+        let SaveTimeStamp { seconds_since_midnight, day, month, year } = &*timestamp.borrow();
+        let view_field = |value: &RcCell<i32>, label: &'static str| {
+            html! {
+                <InputNumber
+                    label={label}
+                    value={NumberType::Int(RcCell::clone(value))}
+                    onchange={ctx.link().callback(|_| Msg::Changed)}
+                />
+            }
+        };
+
+        html! {
+            <>
+                <div class="flex items-center gap-1">
+                    <span>{ label }</span>
+                    <button class="button" onclick={set_now}>{ "Set to now" }</button>
+                </div>
+                <div class="pl-2">
+                    { view_field(year, "Year") }
+                    { view_field(month, "Month") }
+                    { view_field(day, "Day") }
+                    { view_field(seconds_since_midnight, "Seconds since midnight") }
+                </div>
+            </>
+        }
+    }
+
+    fn set_now(timestamp: &mut SaveTimeStamp) {
+        let now = Date::new_0();
+        let seconds_since_midnight =
+            now.get_hours() * 3600 + now.get_minutes() * 60 + now.get_seconds();
+        timestamp.set(
+            now.get_full_year() as i32,
+            now.get_month() as i32 + 1,
+            now.get_date() as i32,
+            seconds_since_midnight as i32,
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use indexmap::IndexMap;
+use yew::prelude::*;
+
+use crate::{
+    gui::components::{CheckBox, Helper, Table},
+    save_data::{
+        shared::plot::{BitVec, SquadMemberFlags},
+        RcCell, RcRef,
+    },
+    services::document,
+};
+
+const MAX_PARTY_SIZE: usize = 2;
+
+pub enum Msg {
+    ChangeBool(usize, bool),
+    MakeSelectable,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub booleans: RcRef<BitVec>,
+    pub squad: IndexMap<String, SquadMemberFlags>,
+}
+
+pub struct SquadFlags;
+
+impl Component for SquadFlags {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut this = SquadFlags;
+        this.add_missing_plots(ctx);
+        this
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::ChangeBool(idx, value) => {
+                if let Some(mut plot) = ctx.props().booleans.borrow_mut().get_mut(idx) {
+                    *plot = value;
+                }
+                true
+            }
+            Msg::MakeSelectable => {
+                let stuck = Self::stuck(ctx);
+                let mut booleans = ctx.props().booleans.borrow_mut();
+                for flags in stuck {
+                    if let Some(mut plot) = booleans.get_mut(flags.selectable) {
+                        *plot = true;
+                    }
+                }
+                document::edited(ctx);
+                true
+            }
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        self.add_missing_plots(ctx);
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let Props { booleans, squad } = ctx.props();
+        let booleans = booleans.borrow();
+        let has_dead = squad.values().any(|flags| flags.dead.is_some());
+
+        let view_flag = |idx: usize| match booleans.get(idx) {
+            Some(value) => html! {
+                <div class="w-24">
+                    <CheckBox
+                        label={String::new()}
+                        value={RcCell::new(*value)}
+                        onchange={ctx.link().callback(move |value| Msg::ChangeBool(idx, value))}
+                    />
+                </div>
+            },
+            None => html! { <div class="w-24" /> },
+        };
+
+        let rows = squad.iter().map(|(name, flags)| {
+            let SquadMemberFlags { in_party, in_squad, selectable, dead } = *flags;
+            let dead = has_dead.then(|| match dead {
+                Some(dead) => view_flag(dead),
+                None => html! { <div class="w-24" /> },
+            });
+            html! {
+                <div class="flex items-center">
+                    <span class="w-32">{ name }</span>
+                    { view_flag(in_party) }
+                    { view_flag(in_squad) }
+                    { view_flag(selectable) }
+                    { for dead }
+                </div>
+            }
+        });
+
+        let party_size = squad
+            .values()
+            .filter(|flags| booleans.get(flags.in_party).map(|value| *value).unwrap_or(false))
+            .count();
+        let party_warning = (party_size > MAX_PARTY_SIZE).then(|| {
+            html! {
+                <p class="text-title-bar-close">
+                    { format!("{} members are in the party, the game only expects {}.", party_size, MAX_PARTY_SIZE) }
+                </p>
+            }
+        });
+
+        let stuck = Self::stuck(ctx);
+        let stuck_warning = (!stuck.is_empty()).then(|| {
+            let names = squad
+                .iter()
+                .filter(|(_, flags)| stuck.contains(flags))
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            html! {
+                <div class="flex items-center gap-1">
+                    <p class="text-title-bar-close">
+                        { format!("Recruited but greyed out in the squad selection: {}.", names) }
+                    </p>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::MakeSelectable)}>
+                        { "Make them selectable" }
+                    </button>
+                    <Helper text=
+                        "Some missions lock members out of the squad and a bugged save can keep \
+                        them locked for good. This sets them `Selectable` again, wherever Shepard is.\n\
+                        Save before a mission that needs a specific squad, it may lock them again."
+                    />
+                </div>
+            }
+        });
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                <div class="flex items-center gap-1">
+                    <p>{ "If a squad member is missing from the squad selection screen, check these flags." }</p>
+                    <Helper text=
+                        "• In party : currently following Shepard\n\
+                        • In squad : recruited\n\
+                        • Selectable : shown in the squad selection screen"
+                    />
+                </div>
+                { for party_warning }
+                { for stuck_warning }
+                <hr class="border-t border-default-border" />
+                <Table>
+                    <div class="flex select-none">
+                        <span class="w-32">{ "Member" }</span>
+                        <span class="w-24">{ "In party" }</span>
+                        <span class="w-24">{ "In squad" }</span>
+                        <span class="w-24">{ "Selectable" }</span>
+                        { for has_dead.then(|| html! { <span class="w-24">{ "Dead" }</span> }) }
+                    </div>
+                    { for rows }
+                </Table>
+            </div>
+        }
+    }
+}
+
+impl SquadFlags {
+    // Recruited and alive, but left out of the squad selection
+    fn stuck(ctx: &Context<Self>) -> Vec<&SquadMemberFlags> {
+        let Props { booleans, squad } = ctx.props();
+        let booleans = booleans.borrow();
+        let is_set = |idx: usize| booleans.get(idx).map(|value| *value).unwrap_or(false);
+
+        squad
+            .values()
+            .filter(|flags| {
+                is_set(flags.in_squad)
+                    && !is_set(flags.selectable)
+                    && !flags.dead.map(is_set).unwrap_or(false)
+            })
+            .collect()
+    }
+
+    fn add_missing_plots(&mut self, ctx: &Context<Self>) {
+        let Props { booleans, squad } = ctx.props();
+
+        let max = squad
+            .values()
+            .flat_map(|flags| {
+                [Some(flags.in_party), Some(flags.in_squad), Some(flags.selectable), flags.dead]
+            })
+            .flatten()
+            .max();
+
+        if let Some(max) = max {
+            let mut booleans = booleans.borrow_mut();
+            if max >= booleans.len() {
+                booleans.resize(max + 1, false);
+            }
+        }
+    }
+}
@@ -6,14 +6,21 @@ extern crate derive_more;
 #[macro_use]
 extern crate macros;
 
+mod cli;
 mod gui;
 mod save_data;
 mod services;
+mod tlk;
 mod unreal;
 
 use gui::App;
 
 fn main() {
+    // Built natively, it only edits saves from the command line
+    if !cfg!(target_arch = "wasm32") {
+        std::process::exit(cli::run(std::env::args().skip(1).collect()));
+    }
+
     let document = gloo::utils::document();
     let body = document.body().unwrap();
     let mount_point = body.last_element_child().unwrap();
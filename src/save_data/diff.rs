@@ -0,0 +1,565 @@
+use std::{collections::BTreeMap, fmt::Display, path::PathBuf};
+
+use indexmap::IndexMap;
+use serde::{ser, Serialize};
+
+use crate::{
+    save_data::{mass_effect_3::plot::PlotTable as Me3PlotTable, shared::plot::PlotTable},
+    unreal::{Error, Result},
+};
+
+#[derive(Serialize, Clone, PartialEq)]
+pub struct Change {
+    pub path: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// The changes between two values flattened by [`flatten_at`]
+pub fn diff_flattened(
+    original: Vec<(String, String)>, current: Vec<(String, String)>,
+) -> Vec<Change> {
+    let original: IndexMap<String, String> = original.into_iter().collect();
+    let current: IndexMap<String, String> = current.into_iter().collect();
+
+    let changed = current.iter().filter_map(|(path, new)| match original.get(path) {
+        Some(old) if old == new => None,
+        old => Some(Change { path: path.clone(), old: old.cloned(), new: Some(new.clone()) }),
+    });
+    let removed = original
+        .iter()
+        .filter(|(path, _)| !current.contains_key(*path))
+        .map(|(path, old)| Change { path: path.clone(), old: Some(old.clone()), new: None });
+
+    changed.chain(removed).collect()
+}
+
+/// The values at `paths` among those flattened by [`flatten_at`], `None` where there is nothing
+pub fn pick(flattened: Vec<(String, String)>, paths: &[String]) -> Vec<Option<String>> {
+    let output: IndexMap<String, String> = flattened.into_iter().collect();
+    paths.iter().map(|path| output.get(path).cloned()).collect()
+}
+
+/// Every value of `value` with its path, named and formatted like the Changes tab shows them.
+/// Under `prefix`, e.g. `player.datas`, to flatten the parts of a save rather than the save
+/// itself. Only what leads to one of `only` if given.
+pub fn flatten_at<T: ?Sized + Serialize>(
+    prefix: &str, value: &T, only: Option<&[String]>,
+) -> Result<Vec<(String, String)>> {
+    let path = if prefix.is_empty() { Vec::new() } else { vec![format!(".{}", prefix)] };
+    let mut flattener = Flattener { path, output: Vec::new(), only: only.map(<[_]>::to_vec) };
+    if flattener.is_wanted() {
+        value.serialize(&mut flattener)?;
+    }
+    Ok(flattener.output)
+}
+
+/// The set plots of a plot table by id, whichever game it comes from.
+/// The games trim the unset plots at the end of the tables, so unset and missing are the same.
+#[derive(Default)]
+pub struct PlotValues {
+    booleans: BTreeMap<usize, bool>,
+    integers: BTreeMap<usize, i32>,
+    floats: BTreeMap<usize, f32>,
+}
+
+impl From<&PlotTable> for PlotValues {
+    fn from(plot: &PlotTable) -> Self {
+        PlotValues {
+            booleans: plot.booleans().iter_ones().map(|idx| (idx, true)).collect(),
+            integers: plot
+                .integers()
+                .iter()
+                .enumerate()
+                .map(|(idx, value)| (idx, value.get()))
+                .filter(|&(_, value)| value != 0)
+                .collect(),
+            floats: plot
+                .floats()
+                .iter()
+                .enumerate()
+                .map(|(idx, value)| (idx, value.get()))
+                .filter(|&(_, value)| value != 0.0)
+                .collect(),
+        }
+    }
+}
+
+impl From<&Me3PlotTable> for PlotValues {
+    fn from(plot: &Me3PlotTable) -> Self {
+        PlotValues {
+            booleans: plot.booleans().iter_ones().map(|idx| (idx, true)).collect(),
+            integers: plot
+                .integers()
+                .iter()
+                .map(|(&idx, value)| (idx as usize, value.get()))
+                .filter(|&(_, value)| value != 0)
+                .collect(),
+            floats: plot
+                .floats()
+                .iter()
+                .map(|(&idx, value)| (idx as usize, value.get()))
+                .filter(|&(_, value)| value != 0.0)
+                .collect(),
+        }
+    }
+}
+
+impl PlotValues {
+    /// A plot named like `diff_plots` does, whatever the case of its kind
+    pub fn value(&self, path: &str) -> Option<String> {
+        let (kind, idx) = path.trim().split_once(' ')?;
+        let idx: usize = idx.trim().parse().ok()?;
+        let value = match kind.to_ascii_lowercase().as_str() {
+            "boolean" => self.booleans.get(&idx).copied().unwrap_or_default().to_string(),
+            "integer" => self.integers.get(&idx).copied().unwrap_or_default().to_string(),
+            "float" => self.floats.get(&idx).copied().unwrap_or_default().to_string(),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+// Plots by kind then id, e.g. `Boolean 1234`
+pub fn diff_plots(original: &PlotValues, current: &PlotValues) -> Vec<Change> {
+    fn diff_kind<V: Copy + Default + PartialEq + Display>(
+        kind: &str, original: &BTreeMap<usize, V>, current: &BTreeMap<usize, V>,
+        changes: &mut Vec<Change>,
+    ) {
+        let ids: BTreeMap<usize, ()> =
+            original.keys().chain(current.keys()).map(|&idx| (idx, ())).collect();
+        for idx in ids.into_keys() {
+            let old = original.get(&idx).copied().unwrap_or_default();
+            let new = current.get(&idx).copied().unwrap_or_default();
+            if old != new {
+                changes.push(Change {
+                    path: format!("{} {}", kind, idx),
+                    old: Some(old.to_string()),
+                    new: Some(new.to_string()),
+                });
+            }
+        }
+    }
+
+    let mut changes = Vec::new();
+    diff_kind("Boolean", &original.booleans, &current.booleans, &mut changes);
+    diff_kind("Integer", &original.integers, &current.integers, &mut changes);
+    diff_kind("Float", &original.floats, &current.floats, &mut changes);
+    changes
+}
+
+/// The plots a play session changed, from the snapshot taken before it to the save the game
+/// wrote after it, for the database contributors.
+#[derive(Serialize)]
+pub struct PlotResearch {
+    pub game: &'static str,
+    pub before: PathBuf,
+    pub after: PathBuf,
+    pub changes: Vec<Change>,
+}
+
+// Serialize a value into a flat list of `(path, value)`
+#[derive(Default)]
+struct Flattener {
+    path: Vec<String>,
+    output: Vec<(String, String)>,
+    // The paths to look for, everything if `None`
+    only: Option<Vec<String>>,
+}
+
+impl Flattener {
+    fn flatten<T: ?Sized + Serialize>(value: &T) -> Result<Vec<(String, String)>> {
+        let mut flattener = Flattener::default();
+        value.serialize(&mut flattener)?;
+        Ok(flattener.output)
+    }
+
+    fn push_value<V: Display>(&mut self, value: V) -> Result<()> {
+        let path = self.path.concat();
+        let path = path.trim_start_matches('.').to_owned();
+        self.output.push((path, value.to_string()));
+        Ok(())
+    }
+
+    // Whether the current path leads to one of the paths looked for
+    fn is_wanted(&self) -> bool {
+        match self.only {
+            Some(ref only) => {
+                let path = self.path.concat();
+                let path = path.trim_start_matches('.');
+                only.iter().any(|wanted| wanted.starts_with(path))
+            }
+            None => true,
+        }
+    }
+}
+
+macro_rules! impl_serialize {
+    ($ser_method:ident($type:ty)) => {
+        fn $ser_method(self, value: $type) -> Result<()> {
+            self.push_value(value)
+        }
+    };
+}
+
+impl<'a> ser::Serializer for &'a mut Flattener {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    impl_serialize!(serialize_bool(bool));
+    impl_serialize!(serialize_i8(i8));
+    impl_serialize!(serialize_i16(i16));
+    impl_serialize!(serialize_i32(i32));
+    impl_serialize!(serialize_i64(i64));
+    impl_serialize!(serialize_u8(u8));
+    impl_serialize!(serialize_u16(u16));
+    impl_serialize!(serialize_u32(u32));
+    impl_serialize!(serialize_u64(u64));
+    impl_serialize!(serialize_f32(f32));
+    impl_serialize!(serialize_f64(f64));
+    impl_serialize!(serialize_char(char));
+
+    fn serialize_str(self, value: &str) -> Result<()> {
+        self.push_value(format!("{:?}", value))
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<()> {
+        let hex: String = value.iter().map(|byte| format!("{:02x}", byte)).collect();
+        self.push_value(hex)
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.push_value("None")
+    }
+
+    fn serialize_some<T: ?Sized>(self, value: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        self.push_value("()")
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<()> {
+        self.push_value(name)
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<()> {
+        self.push_value(variant)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized>(self, _: &'static str, value: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized>(
+        self, _: &'static str, _: u32, variant: &'static str, value: &T,
+    ) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.path.push(format!("::{}", variant));
+        let result = value.serialize(&mut *self);
+        self.path.pop();
+        result
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq> {
+        Ok(Compound::new(self, false))
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple> {
+        Ok(Compound::new(self, false))
+    }
+
+    fn serialize_tuple_struct(
+        self, _: &'static str, _: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Ok(Compound::new(self, false))
+    }
+
+    fn serialize_tuple_variant(
+        self, _: &'static str, _: u32, variant: &'static str, _: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        self.path.push(format!("::{}", variant));
+        Ok(Compound::new(self, true))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(Compound::new(self, false))
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct> {
+        Ok(Compound::new(self, false))
+    }
+
+    fn serialize_struct_variant(
+        self, _: &'static str, _: u32, variant: &'static str, _: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        self.path.push(format!("::{}", variant));
+        Ok(Compound::new(self, true))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+struct Compound<'a> {
+    flattener: &'a mut Flattener,
+    idx: usize,
+    key: Option<String>,
+    is_variant: bool,
+}
+
+impl<'a> Compound<'a> {
+    fn new(flattener: &'a mut Flattener, is_variant: bool) -> Self {
+        Compound { flattener, idx: 0, key: None, is_variant }
+    }
+
+    fn serialize_segment<T: ?Sized + Serialize>(
+        &mut self, segment: String, value: &T,
+    ) -> Result<()> {
+        self.flattener.path.push(segment);
+        let result =
+            if self.flattener.is_wanted() { value.serialize(&mut *self.flattener) } else { Ok(()) };
+        self.flattener.path.pop();
+        result
+    }
+
+    fn serialize_next<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        let segment = format!("[{}]", self.idx);
+        self.idx += 1;
+        self.serialize_segment(segment, value)
+    }
+
+    fn finish(self) -> Result<()> {
+        if self.is_variant {
+            self.flattener.path.pop();
+        }
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeSeq for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.serialize_next(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTuple for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.serialize_next(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.serialize_next(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTupleVariant for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.serialize_next(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeMap for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let key = Flattener::flatten(key)?;
+        let key: Vec<_> = key.into_iter().map(|(_, value)| value).collect();
+        self.key = Some(key.join(", "));
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let key = self.key.take().unwrap_or_default();
+        self.serialize_segment(format!("[{}]", key), value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeStruct for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized>(&mut self, name: &'static str, value: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.serialize_segment(format!(".{}", name), value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized>(&mut self, name: &'static str, value: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.serialize_segment(format!(".{}", name), value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_2::Me2LeSaveGame, unreal};
+
+    #[test]
+    fn diff_save_game() -> Result<()> {
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+
+        let original: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let current: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        let diff = |original: &Me2LeSaveGame, current: &Me2LeSaveGame| -> Result<Vec<Change>> {
+            Ok(diff_flattened(Flattener::flatten(original)?, Flattener::flatten(current)?))
+        };
+
+        // No change
+        assert!(diff(&original, &current)?.is_empty());
+
+        // One change
+        current.player.borrow_mut().set_credits(1_234_567);
+        let changes = diff(&original, &current)?;
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "player.credits");
+        assert_eq!(changes[0].new.as_deref(), Some("1234567"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn watched_values() -> Result<()> {
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        save_game.player.borrow_mut().set_credits(1_234_567);
+
+        // Only what leads to them is walked through
+        let paths = [String::from("player.credits"), String::from("player.nope")];
+        let fields = flatten_at("", &save_game, Some(&paths))?;
+        assert_eq!(fields.len(), 1);
+        let values = pick(fields, &paths);
+        assert_eq!(values, [Some(String::from("1234567")), None]);
+
+        let plot_values = PlotValues::from(&*save_game.plot());
+        let is_set = save_game.plot().booleans()[10];
+        assert_eq!(plot_values.value("boolean 10"), Some(is_set.to_string()));
+        assert_eq!(plot_values.value("Integer 999999"), Some(String::from("0")));
+        assert_eq!(plot_values.value("Nope 1"), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn diff_plot_table() -> Result<()> {
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+
+        let original: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let current: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        // No change
+        let plot_values = |save: &Me2LeSaveGame| PlotValues::from(&*save.plot());
+        assert!(diff_plots(&plot_values(&original), &plot_values(&current)).is_empty());
+
+        // Set by the game, past the end of the table included
+        {
+            let mut plot = current.plot.borrow_mut();
+            let was_set = plot.booleans()[10];
+            plot.booleans_mut().set(10, !was_set);
+            let len = plot.integers().len();
+            plot.integers_mut().resize_with(len + 2, Default::default);
+            plot.integers()[len + 1].set(42);
+        }
+        let changes = diff_plots(&plot_values(&original), &plot_values(&current));
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].path, "Boolean 10");
+        assert_eq!(changes[1].path, format!("Integer {}", current.plot().integers().len() - 1));
+        assert_eq!(changes[1].old.as_deref(), Some("0"));
+        assert_eq!(changes[1].new.as_deref(), Some("42"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use anyhow::Result;
+use crc::{Crc, CRC_32_BZIP2};
+use serde::Serialize;
+
+use crate::unreal;
+
+// A field that depends on the bytes written around it, computed again once the save is serialized
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fixup {
+    // CRC32 of the whole file, appended after it
+    AppendedChecksum,
+    // CRC32 of everything before it, written over the u32 this many bytes from the end
+    ChecksumFromEnd(usize),
+}
+
+// Declared by every save that can be written, even with no field to fix up, so a new format
+// has to say how its file ends before it can be saved
+pub trait Fixups {
+    const FIXUPS: &'static [Fixup];
+}
+
+// Serializes the save then fixes up what it declares
+pub fn to_bytes<T: Serialize + Fixups>(value: &T, is_be: bool) -> Result<Vec<u8>> {
+    let mut output = if is_be {
+        unreal::Serializer::to_be_vec(value)?
+    } else {
+        unreal::Serializer::to_vec(value)?
+    };
+    apply(T::FIXUPS, &mut output, is_be);
+    Ok(output)
+}
+
+pub fn apply(fixups: &[Fixup], output: &mut Vec<u8>, is_be: bool) {
+    for fixup in fixups {
+        match *fixup {
+            Fixup::AppendedChecksum => {
+                let checksum = checksum(output, is_be);
+                output.extend(checksum);
+            }
+            Fixup::ChecksumFromEnd(from_end) => {
+                if let Some(offset) = output.len().checked_sub(from_end).filter(|_| from_end >= 4) {
+                    let checksum = checksum(&output[..offset], is_be);
+                    output[offset..offset + 4].copy_from_slice(&checksum);
+                }
+            }
+        }
+    }
+}
+
+// Whether the fields of a file read as is match what would be written, `None` if it has none
+pub fn verify(fixups: &[Fixup], input: &[u8], is_be: bool) -> Option<bool> {
+    // Undone in reverse, the last one applied being the outermost
+    let mut input = input;
+    let mut is_valid = None;
+    for fixup in fixups.iter().rev() {
+        let valid = match *fixup {
+            Fixup::AppendedChecksum => match input.len().checked_sub(4) {
+                Some(offset) => {
+                    let (body, stored) = input.split_at(offset);
+                    input = body;
+                    stored == checksum(body, is_be)
+                }
+                None => false,
+            },
+            Fixup::ChecksumFromEnd(from_end) => match input.len().checked_sub(from_end) {
+                Some(offset) if from_end >= 4 => {
+                    input[offset..offset + 4] == checksum(&input[..offset], is_be)
+                }
+                _ => false,
+            },
+        };
+        is_valid = Some(is_valid.unwrap_or(true) && valid);
+    }
+    is_valid
+}
+
+fn checksum(bytes: &[u8], is_be: bool) -> [u8; 4] {
+    let crc = Crc::<u32>::new(&CRC_32_BZIP2);
+    let checksum = crc.checksum(bytes);
+    if is_be {
+        u32::to_be_bytes(checksum)
+    } else {
+        u32::to_le_bytes(checksum)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn apply_verify() {
+        let body: Vec<u8> = (0..64).collect();
+
+        // Appended
+        for is_be in [false, true] {
+            let mut output = body.clone();
+            apply(&[Fixup::AppendedChecksum], &mut output, is_be);
+            assert_eq!(output.len(), body.len() + 4);
+            assert_eq!(verify(&[Fixup::AppendedChecksum], &output, is_be), Some(true));
+            assert_eq!(verify(&[Fixup::AppendedChecksum], &output, !is_be), Some(false));
+
+            output[10] ^= 0xff;
+            assert_eq!(verify(&[Fixup::AppendedChecksum], &output, is_be), Some(false));
+        }
+
+        // In place
+        let mut output = body.clone();
+        apply(&[Fixup::ChecksumFromEnd(12)], &mut output, false);
+        assert_eq!(output.len(), body.len());
+        assert_eq!(output[..52], body[..52]);
+        assert_eq!(output[56..], body[56..]);
+        assert_eq!(verify(&[Fixup::ChecksumFromEnd(12)], &output, false), Some(true));
+
+        // Past the checksum, not covered
+        output[60] ^= 0xff;
+        assert_eq!(verify(&[Fixup::ChecksumFromEnd(12)], &output, false), Some(true));
+        output[0] ^= 0xff;
+        assert_eq!(verify(&[Fixup::ChecksumFromEnd(12)], &output, false), Some(false));
+
+        // Nothing declared, nothing to check
+        assert_eq!(verify(&[], &body, false), None);
+        assert_eq!(verify(&[Fixup::AppendedChecksum], &[0, 1], false), Some(false));
+    }
+}
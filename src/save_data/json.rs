@@ -0,0 +1,122 @@
+use anyhow::{Context, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// The games a whole save can be exported as JSON for. Mass Effect 1 and its Legendary Edition
+/// keep compressed blobs and objects told apart by their position, JSON can't rebuild them.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum JsonGame {
+    MassEffect2,
+    MassEffect2Le,
+    MassEffect3,
+}
+
+impl JsonGame {
+    /// The extensions of the saves of the game, the one of the PC first
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            JsonGame::MassEffect2 | JsonGame::MassEffect3 => &["pcsav", "xbsav"],
+            JsonGame::MassEffect2Le => &["pcsav"],
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Export<'a, T> {
+    game: JsonGame,
+    extension: &'a str,
+    save: &'a T,
+}
+
+#[derive(Deserialize)]
+struct Header {
+    game: JsonGame,
+    #[serde(default)]
+    extension: String,
+}
+
+#[derive(Deserialize)]
+struct Import<T> {
+    save: T,
+}
+
+/// The whole parsed save as human-readable JSON, tagged with its game and the extension of its
+/// file to be imported back for the same platform
+pub fn to_string<T: Serialize>(game: JsonGame, extension: &str, save_game: &T) -> Result<String> {
+    let json = serde_json::to_string_pretty(&Export { game, extension, save: save_game })?;
+    Ok(json)
+}
+
+/// The game of a save exported as JSON and the extension of the save it stands for, to know what
+/// to import it as. An unknown extension falls back to the one of the PC.
+pub fn header(json: &str) -> Result<(JsonGame, &'static str)> {
+    let Header { game, extension } =
+        serde_json::from_str(json).context("Not a save exported as JSON by the editor")?;
+    let extensions = game.extensions();
+    let extension = extensions
+        .iter()
+        .find(|known| known.eq_ignore_ascii_case(&extension))
+        .copied()
+        .unwrap_or(extensions[0]);
+    Ok((game, extension))
+}
+
+pub fn from_str<T: DeserializeOwned>(json: &str) -> Result<T> {
+    let Import { save } = serde_json::from_str(json)?;
+    Ok(save)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::{
+            fixup,
+            mass_effect_2::{Me2LeSaveGame, Me2SaveGame},
+            mass_effect_3::Me3SaveGame,
+            Dummy,
+        },
+        unreal,
+    };
+
+    fn round_trip<T>(path: &str, game: JsonGame) -> Result<()>
+    where
+        T: Serialize + DeserializeOwned + fixup::Fixups,
+    {
+        let input = fs::read(path)?;
+        let save_game: T = unreal::Deserializer::from_bytes(&input)?;
+
+        let json = to_string(game, "pcsav", &save_game)?;
+        assert_eq!(header(&json)?, (game, "pcsav"));
+
+        // Rebuilt from the JSON alone
+        let imported: T = from_str(&json)?;
+        assert!(fixup::to_bytes(&imported, false)? == input);
+
+        Ok(())
+    }
+
+    #[test]
+    fn export_import() -> Result<()> {
+        round_trip::<Me2SaveGame>("test/ME2Save.pcsav", JsonGame::MassEffect2)?;
+        round_trip::<Me2LeSaveGame>("test/ME2LeSave.pcsav", JsonGame::MassEffect2Le)?;
+        round_trip::<Me3SaveGame>("test/ME3Save.pcsav", JsonGame::MassEffect3)?;
+
+        assert!(header("{}").is_err());
+
+        // Imported for the platform it was exported from
+        let json = to_string(JsonGame::MassEffect3, "XBSAV", &())?;
+        assert_eq!(header(&json)?, (JsonGame::MassEffect3, "xbsav"));
+        let json = to_string(JsonGame::MassEffect2Le, "xbsav", &())?;
+        assert_eq!(header(&json)?, (JsonGame::MassEffect2Le, "pcsav"));
+        let json = r#"{ "game": "MassEffect2", "save": null }"#;
+        assert_eq!(header(json)?, (JsonGame::MassEffect2, "pcsav"));
+
+        // Hand edited with more bytes than a field holds
+        assert!(serde_json::from_str::<Dummy<2>>("[1, 2, 3]").is_err());
+        Ok(())
+    }
+}
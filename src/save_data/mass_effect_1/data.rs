@@ -36,8 +36,12 @@ impl Data {
     }
 }
 
-fn get_name(names: &[Name], id: u32) -> String {
-    names[id as usize].string.borrow().clone()
+// A corrupted id points past the names
+fn get_name<E: de::Error>(names: &[Name], id: u32) -> Result<String, E> {
+    let name = names.get(id as usize).ok_or_else(|| {
+        E::custom(format!("name id {} out of the {} names of the save", id, names.len()))
+    })?;
+    Ok(name.string.borrow().clone())
 }
 
 impl List<RcRef<Property>> {
@@ -172,7 +176,7 @@ impl Property {
         let name_id = seq.next_element()?.unwrap();
         let _osef1 = seq.next_element()?.unwrap();
 
-        let name = get_name(names, name_id);
+        let name = get_name::<A::Error>(names, name_id)?;
         if name == "None" {
             return Ok(Property::None { name_id, _osef: _osef1 });
         }
@@ -184,7 +188,7 @@ impl Property {
         let size = seq.next_element()?.unwrap();
         let _osef3 = seq.next_element()?.unwrap();
 
-        let type_name = get_name(names, type_id);
+        let type_name = get_name::<A::Error>(names, type_id)?;
         let property = match type_name.as_str() {
             "ArrayProperty" => {
                 let len: u32 = seq.next_element()?.unwrap();
@@ -291,7 +295,7 @@ impl Property {
                 let struct_name_id = seq.next_element()?.unwrap();
                 let _osef4 = seq.next_element()?.unwrap();
 
-                let struct_name = get_name(names, struct_name_id);
+                let struct_name = get_name::<A::Error>(names, struct_name_id)?;
                 let struct_type = match struct_name.as_str() {
                     "LinearColor" => StructType::LinearColor(seq.next_element()?.unwrap()),
                     "Vector" => StructType::Vector(seq.next_element()?.unwrap()),
@@ -310,7 +314,7 @@ impl Property {
                     struct_type,
                 }
             }
-            _ => unimplemented!(),
+            _ => return Err(de::Error::custom(format!("unknown property type: {}", type_name))),
         };
         Ok(property)
     }
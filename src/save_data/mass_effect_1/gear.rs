@@ -0,0 +1,195 @@
+use super::{
+    data::{ArrayType, Data, Property},
+    player::Player,
+    List,
+};
+use crate::save_data::{
+    mass_effect_1_le::item_db::{DbItem, Me1ItemDb},
+    RcCell, RcRef,
+};
+
+// Only sold at level X
+const BEST_MANUFACTURER: &str = "Spectre Master Gear";
+
+impl Player {
+    /// Upgrades the equipped armors, amps, omni-tools and weapons of Shepard and the squad
+    /// to the item level the game hands out at Shepard's level, and to the Spectre Master Gear
+    /// once it reaches X. Items the item DB doesn't know as gear (grenades, empty slots...)
+    /// are left alone, so are the ones already better than that.
+    /// Returns how many items have been upgraded.
+    pub fn upgrade_gear(&self, item_db: &Me1ItemDb) -> usize {
+        let level = match self.player_level() {
+            Some(level) => level,
+            None => return 0,
+        };
+        let item_level = Self::item_level_for(level);
+
+        let mut upgraded = 0;
+        for pawn in self.datas.iter() {
+            let equipped = self.find_property(&pawn.properties, "m_aEquipped");
+            let quick_slots = self.find_property(&pawn.properties, "m_QuickSlotArray");
+            let item_ids = equipped
+                .iter()
+                .chain(quick_slots.iter())
+                .flat_map(Self::object_ids)
+                .collect::<Vec<_>>();
+
+            for item in item_ids.into_iter().filter_map(|item_id| self.get_data(item_id)) {
+                if self.upgrade_item(item, item_db, item_level) {
+                    upgraded += 1;
+                }
+            }
+        }
+        upgraded
+    }
+
+    // Item levels go up by one every 6 character levels, X from level 55
+    fn item_level_for(level: i32) -> u8 {
+        ((level + 5) / 6).clamp(1, 10) as u8
+    }
+
+    fn upgrade_item(&self, item: &Data, item_db: &Me1ItemDb, item_level: u8) -> bool {
+        let id = self.find_int(&item.properties, "m_nID");
+        let manufacturer = self.find_int(&item.properties, "m_manufacturer");
+        let sophistication =
+            self.find_property(&item.properties, "m_eSophistication").and_then(|property| {
+                match *property.borrow() {
+                    Property::Byte { ref value, .. } => Some(RcCell::clone(value)),
+                    _ => None,
+                }
+            });
+        let (id, manufacturer, sophistication) = match (id, manufacturer, sophistication) {
+            (Some(id), Some(manufacturer), Some(sophistication)) => {
+                (id, manufacturer, sophistication)
+            }
+            _ => return false,
+        };
+
+        let db_item = DbItem { item_id: id.get(), manufacturer_id: manufacturer.get() };
+        if item_db.item_kind(&db_item).is_none() {
+            return false;
+        }
+
+        let mut is_upgraded = false;
+        if item_level == 10 {
+            if let Some(best) = item_db.manufacturer_item(BEST_MANUFACTURER, db_item.item_id) {
+                if best != db_item {
+                    manufacturer.set(best.manufacturer_id);
+                    is_upgraded = true;
+                }
+            }
+        }
+        if sophistication.get() < item_level {
+            sophistication.set(item_level);
+            is_upgraded = true;
+        }
+        is_upgraded
+    }
+
+    pub fn player_level(&self) -> Option<i32> {
+        let m_player = self.m_player()?;
+        self.find_int(&m_player.properties, "m_XPLevel").map(|level| level.get())
+    }
+
+    pub fn current_game(&self) -> Option<&Data> {
+        let idx = self.objects.iter().position(|object| {
+            self.get_name(object.object_name_id).as_deref() == Some("CurrentGame")
+        })?;
+        self.get_data(idx as i32 + 1)
+    }
+
+    pub fn m_player(&self) -> Option<&Data> {
+        self.find_object(self.current_game()?, "m_Player")
+    }
+
+    // The data an object property points to
+    pub fn find_object(&self, data: &Data, name: &str) -> Option<&Data> {
+        let property = self.find_property(&data.properties, name)?;
+        let object = match *property.borrow() {
+            Property::Object { object_id, .. } => self.get_data(object_id),
+            _ => None,
+        };
+        object
+    }
+
+    fn object_ids(property: &RcRef<Property>) -> Vec<i32> {
+        match *property.borrow() {
+            Property::Array { ref array, .. } => array
+                .iter()
+                .filter_map(|item| match *item {
+                    ArrayType::Object(object_id) if object_id > 0 => Some(object_id),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn find_int(&self, properties: &List<RcRef<Property>>, name: &str) -> Option<RcCell<i32>> {
+        let property = self.find_property(properties, name)?;
+        let value = match *property.borrow() {
+            Property::Int { ref value, .. } => Some(RcCell::clone(value)),
+            _ => None,
+        };
+        value
+    }
+
+    pub fn find_property(
+        &self, properties: &List<RcRef<Property>>, name: &str,
+    ) -> Option<RcRef<Property>> {
+        properties.iter().find_map(|property| match *property.borrow() {
+            Property::Array { name_id, .. }
+            | Property::Bool { name_id, .. }
+            | Property::Byte { name_id, .. }
+            | Property::Float { name_id, .. }
+            | Property::Int { name_id, .. }
+            | Property::Name { name_id, .. }
+            | Property::Object { name_id, .. }
+            | Property::Str { name_id, .. }
+            | Property::StringRef { name_id, .. }
+            | Property::Struct { name_id, .. }
+            | Property::None { name_id, .. } => {
+                (self.get_name(name_id).as_deref() == Some(name)).then(|| RcRef::clone(property))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_1::Me1SaveGame, unreal};
+
+    #[test]
+    fn upgrade_gear() -> Result<()> {
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let item_db: Me1ItemDb = ron::from_str(&fs::read_to_string("databases/me1_item_db.ron")?)?;
+
+        // Level 60, plenty of gear below X or not from the Spectres
+        let player = me1_save_game.player();
+        assert!(player.upgrade_gear(&item_db) > 0);
+
+        // Nothing left to upgrade
+        assert_eq!(player.upgrade_gear(&item_db), 0);
+
+        // Still a valid save
+        let output = unreal::Serializer::to_vec(&*me1_save_game.player())?;
+        let _: Player = unreal::Deserializer::from_bytes(&output)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn item_level_for() {
+        assert_eq!(Player::item_level_for(1), 1);
+        assert_eq!(Player::item_level_for(6), 1);
+        assert_eq!(Player::item_level_for(7), 2);
+        assert_eq!(Player::item_level_for(55), 10);
+        assert_eq!(Player::item_level_for(60), 10);
+    }
+}
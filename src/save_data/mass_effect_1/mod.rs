@@ -1,7 +1,10 @@
 pub mod data;
+mod gear;
 pub mod player;
 pub mod plot_db;
+mod squad;
 pub mod state;
+pub mod talents;
 
 use self::{player::*, state::*};
 
@@ -14,7 +17,10 @@ use serde::ser::SerializeTupleStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use zip::{write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
 
-use super::{Dummy, List};
+use super::{
+    fixup::{Fixup, Fixups},
+    Dummy, List,
+};
 use crate::unreal;
 
 #[rcize_fields]
@@ -88,6 +94,11 @@ impl Me1SaveGame {
     }
 }
 
+// Its files are zipped, the zip having its own checksums
+impl Fixups for Me1SaveGame {
+    const FIXUPS: &'static [Fixup] = &[];
+}
+
 impl<'de> Deserialize<'de> for Me1SaveGame {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -111,7 +122,9 @@ impl<'de> Deserialize<'de> for Me1SaveGame {
 
                 // No man's land
                 let mut no_mans_land = Vec::new();
-                for _ in 0..(zip_offset - 12) {
+                let no_mans_land_len = u32::checked_sub(zip_offset, 12)
+                    .ok_or_else(|| de::Error::custom("zip offset before the end of the header"))?;
+                for _ in 0..no_mans_land_len {
                     no_mans_land.push(seq.next_element()?.unwrap());
                 }
 
@@ -150,10 +163,14 @@ impl serde::Serialize for Me1SaveGame {
             _world_save_package,
         } = self;
 
+        // Right after the no man's land
+        let zip_offset = u32::try_from(12 + _no_mans_land.len() as u64)
+            .map_err(|_| Error::custom("no man's land past the 4 GB of a save"))?;
+
         let mut s = serializer.serialize_tuple_struct("Me1SaveGame", 4)?;
         s.serialize_field(magic_number)?;
         s.serialize_field(_begin)?;
-        s.serialize_field(_zip_offset)?;
+        s.serialize_field(&zip_offset)?;
         s.serialize_field(_no_mans_land)?;
         s.serialize_field(&self.zip().map_err(Error::custom)?)?;
         s.end()
@@ -241,4 +258,14 @@ mod test {
         }
         Ok(())
     }
+
+    #[test]
+    fn corrupted_zip_offset() {
+        // Pointing inside the header
+        let mut input = b"RGMH".to_vec();
+        input.extend([0; 4]);
+        input.extend(4u32.to_le_bytes());
+        input.extend([0; 64]);
+        assert!(unreal::Deserializer::from_bytes::<Me1SaveGame>(&input).is_err());
+    }
 }
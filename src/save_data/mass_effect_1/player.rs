@@ -20,24 +20,32 @@ pub struct Player {
     classes: List<Class>,
     pub objects: List<Object>,
     _no_mans_land2: List<u8>,
-    datas: List<Data>,
+    pub datas: List<Data>,
 }
 
+// The ids come from the save itself, `None` for one that points nowhere
 impl Player {
-    pub fn get_name(&self, id: u32) -> String {
-        self.names.borrow()[id as usize].string.borrow().clone()
+    pub fn get_name(&self, id: u32) -> Option<String> {
+        self.names.borrow().get(id as usize).map(|name| name.string.borrow().clone())
     }
 
-    pub fn get_class(&self, id: i32) -> &Class {
-        &self.classes[id.abs() as usize - 1]
+    // Classes by negative ids
+    pub fn get_class(&self, id: i32) -> Option<&Class> {
+        let idx = (id.unsigned_abs() as usize).checked_sub(1)?;
+        self.classes.get(idx)
     }
 
-    pub fn get_object(&self, id: i32) -> &Object {
-        &self.objects[id as usize - 1]
+    // Objects and their data by positive ids
+    pub fn get_object(&self, id: i32) -> Option<&Object> {
+        self.objects.get(Self::object_idx(id)?)
     }
 
-    pub fn get_data(&self, i: i32) -> &Data {
-        &self.datas[i as usize - 1]
+    pub fn get_data(&self, id: i32) -> Option<&Data> {
+        self.datas.get(Self::object_idx(id)?)
+    }
+
+    fn object_idx(id: i32) -> Option<usize> {
+        usize::try_from(id).ok()?.checked_sub(1)
     }
 }
 
@@ -63,7 +71,9 @@ impl<'de> Deserialize<'de> for Player {
 
                 // No man's land 1
                 let mut no_mans_land1 = Vec::new();
-                for _ in 0..(header_offset - 12) {
+                let no_mans_land1_len = u32::checked_sub(header_offset, 12)
+                    .ok_or_else(|| de::Error::custom("header offset before the header"))?;
+                for _ in 0..no_mans_land1_len {
                     no_mans_land1.push(seq.next_element()?.unwrap());
                 }
 
@@ -89,7 +99,11 @@ impl<'de> Deserialize<'de> for Player {
 
                 // No man's land 2
                 let mut no_mans_land2 = Vec::new();
-                for _ in 0..(header.data_offset - header.no_mans_land_offset) {
+                let no_mans_land2_len =
+                    header.data_offset.checked_sub(header.no_mans_land_offset).ok_or_else(
+                        || de::Error::custom("data offset before the end of the objects"),
+                    )?;
+                for _ in 0..no_mans_land2_len {
                     no_mans_land2.push(seq.next_element()?.unwrap());
                 }
 
@@ -136,26 +150,37 @@ impl serde::Serialize for Player {
         } = self;
         let names = names.borrow();
 
-        // Calculs d'offsets
+        // Calculs d'offsets, in 64 bits to tell when they don't fit in the header anymore
+        let to_u32 = |offset: u64| {
+            u32::try_from(offset).map_err(|_| {
+                S::Error::custom(format!("offset of {} past the 4 GB of a save", offset))
+            })
+        };
         let mut header = header.clone();
 
-        header.name_len = names.len() as u32;
-        header.classes_offset = header.name_offset;
+        header.name_len = to_u32(names.len() as u64)?;
+        header.classes_len = to_u32(classes.len() as u64)?;
+        header.objects_len = to_u32(objects.len() as u64)?;
+        let mut classes_offset = header.name_offset as u64;
         for name in names.iter() {
-            header.classes_offset += name.size().map_err(Error::custom)? as u32;
+            classes_offset += name.size().map_err(Error::custom)? as u64;
         }
 
-        header.objects_offset = header.classes_offset + (classes.len() * 28) as u32;
-        header.no_mans_land_offset = header.objects_offset + (objects.len() * 72) as u32;
-        header.data_offset = header.no_mans_land_offset + _no_mans_land2.0.len() as u32;
+        let objects_offset = classes_offset + classes.len() as u64 * 28;
+        let no_mans_land_offset = objects_offset + objects.len() as u64 * 72;
+        let data_offset = no_mans_land_offset + _no_mans_land2.0.len() as u64;
+        header.classes_offset = to_u32(classes_offset)?;
+        header.objects_offset = to_u32(objects_offset)?;
+        header.no_mans_land_offset = to_u32(no_mans_land_offset)?;
+        header.data_offset = to_u32(data_offset)?;
 
         let mut objects = objects.clone();
         {
-            let mut current_offset = header.data_offset;
+            let mut current_offset = data_offset;
             for (i, object) in objects.iter_mut().enumerate() {
-                object.data_offset = current_offset;
-                let data_size = datas[i].size().map_err(Error::custom)? as u32;
-                object.data_size = data_size;
+                object.data_offset = to_u32(current_offset)?;
+                let data_size = datas[i].size().map_err(Error::custom)? as u64;
+                object.data_size = to_u32(data_size)?;
                 current_offset += data_size;
             }
         }
@@ -245,20 +270,22 @@ mod test {
     use super::*;
     use crate::unreal;
 
-    #[test]
-    fn deserialize_serialize() -> Result<()> {
+    fn player_data() -> Result<Vec<u8>> {
         let input = fs::read("test/ME1Save.MassEffectSave")?;
 
-        let player_data = {
-            let mut offset_bytes = [0; 4];
-            offset_bytes.copy_from_slice(&input[8..12]);
-            let zip_offset = <u32>::from_le_bytes(offset_bytes);
-            let mut zip = ZipArchive::new(Cursor::new(&input[zip_offset as usize..]))?;
+        let mut offset_bytes = [0; 4];
+        offset_bytes.copy_from_slice(&input[8..12]);
+        let zip_offset = <u32>::from_le_bytes(offset_bytes);
+        let mut zip = ZipArchive::new(Cursor::new(&input[zip_offset as usize..]))?;
 
-            let mut bytes = Vec::new();
-            zip.by_name("player.sav")?.read_to_end(&mut bytes)?;
-            bytes
-        };
+        let mut bytes = Vec::new();
+        zip.by_name("player.sav")?.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    #[test]
+    fn deserialize_serialize() -> Result<()> {
+        let player_data = player_data()?;
 
         // Deserialize
         let player: Player = unreal::Deserializer::from_bytes(&player_data)?;
@@ -279,4 +306,35 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn ids_out_of_the_tables() -> Result<()> {
+        let player: Player = unreal::Deserializer::from_bytes(&player_data()?)?;
+
+        assert!(player.get_name(player.names.borrow().len() as u32).is_none());
+        assert!(player.get_class(0).is_none());
+        assert!(player.get_class(i32::MIN).is_none());
+        assert!(player.get_object(0).is_none());
+        assert!(player.get_object(-1).is_none());
+        assert!(player.get_object(player.objects.len() as i32 + 1).is_none());
+        assert!(player.get_data(0).is_none());
+        assert!(player.get_data(-1).is_none());
+
+        // In the tables
+        assert!(player.get_name(0).is_some());
+        assert!(player.get_class(-1).is_some());
+        assert!(player.get_object(player.objects.len() as i32).is_some());
+        assert!(player.get_data(1).is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn corrupted_offsets() {
+        // The header offset pointing before itself
+        let mut input = vec![0; 8];
+        input.extend(4u32.to_le_bytes());
+        input.extend([0; 256]);
+        assert!(unreal::Deserializer::from_bytes::<Player>(&input).is_err());
+    }
 }
@@ -14,14 +14,29 @@ mod test {
     use anyhow::Result;
     use std::fs;
 
-    use crate::save_data::shared::plot::RawPlotDb;
+    use crate::save_data::shared::{plot::RawPlotDb, plot_db_version};
 
     use super::*;
 
     #[test]
     fn deserialize_plot_db() -> Result<()> {
         let input = fs::read_to_string("databases/me1_plot_db.ron")?;
-        let _me1_plot_db: Me1PlotDb = ron::from_str(&input)?;
+        let _me1_plot_db: Me1PlotDb = plot_db_version::from_ron(&input)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn clusters_are_known_booleans() -> Result<()> {
+        let input = fs::read_to_string("databases/me1_plot_db.ron")?;
+        let me1_plot_db: Me1PlotDb = ron::from_str(&input)?;
+
+        for category in me1_plot_db.player_crew.values().chain(me1_plot_db.missions.values()) {
+            for (idx, cluster) in &category.clusters {
+                assert!(category.booleans.contains_key(idx));
+                assert_eq!(category.cluster(*idx).count(), cluster.len());
+            }
+        }
 
         Ok(())
     }
@@ -29,7 +44,7 @@ mod test {
     #[test]
     fn deserialize_raw_plot_db() -> Result<()> {
         let input = fs::read_to_string("databases/me1_raw_plot_db.ron")?;
-        let _me1_raw_plot_db: RawPlotDb = ron::from_str(&input)?;
+        let _me1_raw_plot_db: RawPlotDb = plot_db_version::from_ron(&input)?;
 
         Ok(())
     }
@@ -0,0 +1,69 @@
+use std::ptr;
+
+use super::{data::Data, player::Player};
+use crate::save_data::{mass_effect_1_le::squad::level_up, RcCell};
+
+impl Player {
+    /// Brings every squad member up to Shepard's level, the same way as in the Legendary Edition.
+    /// Returns how many of them were below.
+    pub fn sync_squad_level(&self) -> usize {
+        let (m_player, level) = match (self.m_player(), self.player_level()) {
+            (Some(m_player), Some(level)) => (m_player, level),
+            _ => return 0,
+        };
+
+        self.henchmen(m_player)
+            .into_iter()
+            .filter(|(current, talent_points)| level_up(current, talent_points, level))
+            .count()
+    }
+
+    // Level and talent points of every pawn but Shepard
+    fn henchmen(&self, m_player: &Data) -> Vec<(RcCell<i32>, RcCell<i32>)> {
+        self.datas
+            .iter()
+            .filter(|pawn| !ptr::eq(*pawn, m_player))
+            .filter_map(|pawn| {
+                let level = self.find_int(&pawn.properties, "m_XPLevel")?;
+                let talent_points = self.find_int(&pawn.properties, "m_TalentPoints")?;
+                Some((level, talent_points))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use crate::{save_data::mass_effect_1::Me1SaveGame, unreal};
+
+    #[test]
+    fn sync_squad_level() -> Result<()> {
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let player = me1_save_game.player();
+        let (m_player, level) = (player.m_player().unwrap(), player.player_level().unwrap());
+
+        player.sync_squad_level();
+        let henchmen = player.henchmen(m_player);
+        assert!(!henchmen.is_empty());
+        assert!(henchmen.iter().all(|(current, _)| current.get() >= level));
+
+        // One member left behind
+        let (current, talent_points) = &henchmen[0];
+        current.set(level - 5);
+        let before = talent_points.get();
+
+        assert_eq!(player.sync_squad_level(), 1);
+        assert_eq!(current.get(), level);
+        assert_eq!(talent_points.get(), before + 10);
+
+        // Shepard is not part of it
+        let shepard = player.find_int(&m_player.properties, "m_XPLevel").unwrap();
+        assert_eq!(shepard.get(), level);
+        Ok(())
+    }
+}
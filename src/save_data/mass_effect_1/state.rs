@@ -1,17 +1,50 @@
 use serde::{Deserialize, Serialize};
 
-use crate::save_data::{shared::plot::PlotTable, Dummy, List};
+use crate::save_data::{
+    shared::{plot::PlotTable, Rotator, Vector},
+    Dummy, List,
+};
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUi)]
 pub struct State {
     _begin: Dummy<12>,
-    base_level_name: String,
-    _osef1: Dummy<24>,
+    pub base_level_name: String,
+    pub location: Vector,
+    pub rotation: Rotator,
     pub plot: PlotTable,
     _osef2: List<u8>,
 }
 
+pub struct Area {
+    pub name: &'static str,
+    pub level: &'static str,
+}
+
+// The level each main area is loaded from
+pub const AREAS: &[Area] = &[
+    Area { name: "Normandy", level: "BIOA_NOR10" },
+    Area { name: "Citadel", level: "BIOA_STA00" },
+    Area { name: "Eden Prime", level: "BIOA_PRO00" },
+    Area { name: "Therum", level: "BIOA_LAV60" },
+    Area { name: "Feros", level: "BIOA_WAR00" },
+    Area { name: "Noveria", level: "BIOA_ICE00" },
+    Area { name: "Virmire", level: "BIOA_JUG00" },
+    Area { name: "Ilos", level: "BIOA_END00" },
+];
+
+impl State {
+    // `None` for an area out of the list, like an uncharted world
+    pub fn area(&self) -> Option<&'static Area> {
+        let level = self.base_level_name();
+        AREAS.iter().find(|area| area.level.eq_ignore_ascii_case(&level))
+    }
+
+    pub fn move_to(&self, area: &Area) {
+        *self.base_level_name.borrow_mut() = area.level.to_owned();
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::fs;
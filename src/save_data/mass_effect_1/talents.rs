@@ -0,0 +1,330 @@
+use anyhow::{bail, Result};
+
+use super::{
+    data::{ArrayType, Data, Property},
+    player::Player,
+    List,
+};
+use crate::save_data::{RcCell, RcRef};
+
+/// Complex talent of each bonus talent, with the simple talents it comes with.
+pub const BONUS_TALENTS: &[(i32, &[i32], &str)] = &[
+    (50, &[248], "Lift"),
+    (49, &[247], "Throw"),
+    (56, &[249], "Warp"),
+    (57, &[250], "Singularity"),
+    (63, &[251], "Barrier"),
+    (64, &[252], "Stasis"),
+    (86, &[254], "Damping"),
+    (91, &[256], "Hacking"),
+    (84, &[253], "Electronics"),
+    (93, &[255], "Decryption"),
+    (98, &[257], "First Aid"),
+    (99, &[257, 258], "Medicine"),
+    (15, &[244], "Shotguns"),
+    (7, &[245], "Assault Rifles"),
+    (21, &[246], "Sniper Rifles"),
+];
+
+impl Player {
+    /// Whether Shepard has this talent, from its class or as a bonus.
+    pub fn has_talent(&self, complex_id: i32) -> bool {
+        self.has_talent_in("m_aComplexTalents", complex_id)
+    }
+
+    /// Whether Shepard got this talent as a bonus, only those come with their simple talents.
+    pub fn has_bonus_talent(&self, talent_idx: usize) -> bool {
+        let (complex_id, simple_ids, _) = BONUS_TALENTS[talent_idx];
+        self.has_talent(complex_id)
+            && simple_ids.iter().all(|&simple_id| self.has_talent_in("m_aSimpleTalents", simple_id))
+    }
+
+    /// Gives Shepard a bonus talent, laid out like the ones the game grants at character creation:
+    /// the complex talent to spend points in and the simple talents unlocking it.
+    /// Returns `false` if Shepard already has it.
+    pub fn add_bonus_talent(&self, talent_idx: usize) -> Result<bool> {
+        let (complex_id, simple_ids, _) = BONUS_TALENTS[talent_idx];
+        if self.has_talent(complex_id) {
+            return Ok(false);
+        }
+        let m_player = match self.m_player() {
+            Some(m_player) => m_player,
+            None => bail!("no player pawn"),
+        };
+
+        let complex = [
+            ("m_TalentID", complex_id),
+            ("m_Ranks", 0),
+            ("m_MaxRank", 12),
+            ("m_LevelOffset", -1),
+            ("m_LevelsPerRank", 1),
+            ("m_VisualOrder", 85),
+        ];
+        self.push_talent(m_player, "m_aComplexTalents", &complex)?;
+
+        for &simple_id in simple_ids {
+            let simple = [("m_TalentID", simple_id), ("m_Ranks", 1)];
+            self.push_talent(m_player, "m_aSimpleTalents", &simple)?;
+        }
+        Ok(true)
+    }
+
+    /// Takes a bonus talent back from Shepard and refunds the talent points spent in it.
+    /// Returns how many points have been refunded.
+    /// A class talent is left alone.
+    pub fn remove_bonus_talent(&mut self, talent_idx: usize) -> Result<i32> {
+        let (complex_id, simple_ids, _) = BONUS_TALENTS[talent_idx];
+        if !self.has_bonus_talent(talent_idx) {
+            return Ok(0);
+        }
+        let m_player = match self.m_player() {
+            Some(m_player) => m_player,
+            None => bail!("no player pawn"),
+        };
+
+        let mut refunded = 0;
+        self.retain_talents(m_player, "m_aComplexTalents", |talent| {
+            if self.talent_id(talent) != Some(complex_id) {
+                return true;
+            }
+            refunded += self.find_int(talent, "m_Ranks").map(|ranks| ranks.get()).unwrap_or(0);
+            false
+        })?;
+        self.retain_talents(m_player, "m_aSimpleTalents", |talent| {
+            self.talent_id(talent).map(|id| !simple_ids.contains(&id)).unwrap_or(true)
+        })?;
+
+        self.refund_talent_points(refunded)?;
+        Ok(refunded)
+    }
+
+    /// The rank of each talent Shepard can spend points in, by talent id.
+    pub fn talent_ranks(&self) -> Vec<(i32, i32)> {
+        let m_player = match self.m_player() {
+            Some(m_player) => m_player,
+            None => return Vec::new(),
+        };
+        self.talents(m_player, "m_aComplexTalents")
+            .iter()
+            .filter_map(|talent| {
+                let ranks = self.find_int(talent, "m_Ranks")?;
+                Some((self.talent_id(talent)?, ranks.get()))
+            })
+            .collect()
+    }
+
+    // A property left at its default isn't written, a pawn with no unspent point has no
+    // `m_TalentPoints`. It's added with the layout of the level, the other int of the pawn.
+    fn refund_talent_points(&mut self, points: i32) -> Result<()> {
+        let m_player_id = match self.m_player_id() {
+            Some(m_player_id) => m_player_id,
+            None => bail!("no player pawn"),
+        };
+        let m_player = match self.get_data(m_player_id) {
+            Some(m_player) => m_player,
+            None => bail!("no player pawn"),
+        };
+        if let Some(talent_points) = self.find_int(&m_player.properties, "m_TalentPoints") {
+            talent_points.update(|unspent| unspent + points);
+            return Ok(());
+        }
+        if points == 0 {
+            return Ok(());
+        }
+
+        let name_id =
+            self.names.borrow().iter().position(|name| *name.string.borrow() == "m_TalentPoints");
+        let template = self.find_property(&m_player.properties, "m_XPLevel");
+        let mut talent_points = match (name_id, template) {
+            (Some(name_id), Some(template)) => {
+                let mut talent_points = Self::duplicate(&template.borrow());
+                if let Property::Int { name_id: ref mut id, .. } = talent_points {
+                    *id = name_id as u32;
+                }
+                talent_points
+            }
+            _ => bail!("no `m_TalentPoints` to refund the talent points into"),
+        };
+        match talent_points {
+            Property::Int { ref mut value, .. } => value.set(points),
+            _ => bail!("`m_XPLevel` isn't an int"),
+        }
+
+        // Before the `None` ending the properties
+        let properties = &mut self.datas[m_player_id as usize - 1].properties;
+        let end = properties
+            .iter()
+            .position(|property| matches!(*property.borrow(), Property::None { .. }))
+            .unwrap_or(properties.len());
+        properties.insert(end, talent_points.into());
+        Ok(())
+    }
+
+    fn m_player_id(&self) -> Option<i32> {
+        let current_game = self.current_game()?;
+        let property = self.find_property(&current_game.properties, "m_Player")?;
+        let m_player_id = match *property.borrow() {
+            Property::Object { object_id, .. } if object_id > 0 => Some(object_id),
+            _ => None,
+        };
+        m_player_id
+    }
+
+    fn has_talent_in(&self, array_name: &str, talent_id: i32) -> bool {
+        match self.m_player() {
+            Some(m_player) => self
+                .talents(m_player, array_name)
+                .iter()
+                .any(|talent| self.talent_id(talent) == Some(talent_id)),
+            None => false,
+        }
+    }
+
+    fn talent_id(&self, talent: &List<RcRef<Property>>) -> Option<i32> {
+        self.find_int(talent, "m_TalentID").map(|id| id.get())
+    }
+
+    fn talents(&self, m_player: &Data, array_name: &str) -> Vec<List<RcRef<Property>>> {
+        let property = match self.find_property(&m_player.properties, array_name) {
+            Some(property) => property,
+            None => return Vec::new(),
+        };
+        let talents = match *property.borrow() {
+            Property::Array { ref array, .. } => array
+                .iter()
+                .filter_map(|talent| match talent {
+                    ArrayType::Properties(properties) => Some(properties.clone()),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        talents
+    }
+
+    // A copy of the first talent of the array with fresh values, there is no other way to know
+    // the name ids and the layout the game expects
+    fn push_talent(&self, m_player: &Data, array_name: &str, values: &[(&str, i32)]) -> Result<()> {
+        let template = match self.talents(m_player, array_name).into_iter().next() {
+            Some(template) => template,
+            None => bail!("no talent to copy in `{}`", array_name),
+        };
+
+        let talent: List<RcRef<Property>> = template
+            .iter()
+            .map(|property| Self::duplicate(&property.borrow()).into())
+            .collect::<Vec<_>>()
+            .into();
+        for &(name, value) in values {
+            if let Some(property) = self.find_int(&talent, name) {
+                property.set(value);
+            }
+        }
+
+        self.edit_talents(m_player, array_name, |array| {
+            array.push(ArrayType::Properties(talent));
+        })
+    }
+
+    fn retain_talents(
+        &self, m_player: &Data, array_name: &str,
+        mut keep: impl FnMut(&List<RcRef<Property>>) -> bool,
+    ) -> Result<()> {
+        self.edit_talents(m_player, array_name, |array| {
+            array.retain(|talent| match talent {
+                ArrayType::Properties(properties) => keep(properties),
+                _ => true,
+            });
+        })
+    }
+
+    // The size of an array property is written as is, it has to follow its content
+    fn edit_talents(
+        &self, m_player: &Data, array_name: &str, edit: impl FnOnce(&mut Vec<ArrayType>),
+    ) -> Result<()> {
+        let property = match self.find_property(&m_player.properties, array_name) {
+            Some(property) => property,
+            None => bail!("no `{}` on the player pawn", array_name),
+        };
+
+        if let Property::Array { ref mut array, .. } = *property.borrow_mut() {
+            edit(array);
+        }
+        let new_size = property.borrow().size()? - 24;
+        if let Property::Array { ref mut size, .. } = *property.borrow_mut() {
+            *size = new_size as u32;
+        }
+        Ok(())
+    }
+
+    fn duplicate(property: &Property) -> Property {
+        let mut property = property.clone();
+        match property {
+            Property::Int { ref mut value, .. } => *value = RcCell::new(value.get()),
+            // Prerequisites, none for a bonus talent
+            Property::Array { ref mut array, ref mut size, .. } => {
+                array.clear();
+                *size = 4;
+            }
+            _ => (),
+        }
+        property
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_1::Me1SaveGame, unreal};
+
+    #[test]
+    fn add_remove_bonus_talent() -> Result<()> {
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let mut me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let mut player = me1_save_game.player_mut();
+
+        let bonus = |name| BONUS_TALENTS.iter().position(|&(_, _, label)| label == name).unwrap();
+        let (damping, stasis, shotguns) = (bonus("Damping"), bonus("Stasis"), bonus("Shotguns"));
+
+        // An adept with the shotguns as a bonus
+        assert!(player.has_talent(64) && !player.has_bonus_talent(stasis));
+        assert!(player.has_bonus_talent(shotguns));
+        assert!(!player.add_bonus_talent(stasis)?);
+        assert_eq!(player.remove_bonus_talent(stasis)?, 0);
+        assert!(player.has_talent(64));
+
+        assert!(!player.has_bonus_talent(damping));
+        assert!(player.add_bonus_talent(damping)?);
+        assert!(player.has_bonus_talent(damping));
+
+        // Still a valid save, with the talent in it
+        let output = unreal::Serializer::to_vec(&*player)?;
+        let reloaded: Player = unreal::Deserializer::from_bytes(&output)?;
+        assert!(reloaded.has_bonus_talent(damping));
+
+        // Points spent in it are given back, every point of this save is spent
+        let talent_points = |player: &Player| {
+            let m_player = player.m_player().unwrap();
+            player.find_int(&m_player.properties, "m_TalentPoints").map(|points| points.get())
+        };
+        assert_eq!(talent_points(&player), None);
+        let m_player = player.m_player().unwrap();
+        let complex = player.talents(m_player, "m_aComplexTalents");
+        let added = complex.iter().find(|talent| player.talent_id(talent) == Some(86)).unwrap();
+        player.find_int(added, "m_Ranks").unwrap().set(3);
+
+        assert_eq!(player.remove_bonus_talent(damping)?, 3);
+        assert!(!player.has_talent(86));
+        assert_eq!(talent_points(&player), Some(3));
+
+        let output = unreal::Serializer::to_vec(&*player)?;
+        let reloaded: Player = unreal::Deserializer::from_bytes(&output)?;
+        assert!(!reloaded.has_talent(86));
+        assert_eq!(talent_points(&reloaded), Some(3));
+        Ok(())
+    }
+}
@@ -0,0 +1,436 @@
+use super::{player::Me1LeClass, Me1LeSaveData};
+use crate::save_data::{
+    mass_effect_1::{
+        data::{Data, Property, StructType},
+        player::Player as Me1Player,
+        Me1SaveGame,
+    },
+    shared::{
+        player::{Notoriety, Origin},
+        plot::PlotTable,
+    },
+    RcCell,
+};
+
+// Plots the Legendary Edition reads its player from
+const IS_FEMALE: usize = 4639;
+const ORIGIN: usize = 1;
+const NOTORIETY: usize = 2;
+
+/// Left as they are in the Legendary save, the original game lays them out as objects of its own
+/// packages with no known counterpart in the Legendary Edition.
+pub const NOT_IMPORTED: &[&str] =
+    &["Squad", "Inventory", "Head morph", "Location", "Experience", "Journal and codex"];
+
+/// What an import took from the original save, and what it couldn't find there.
+#[derive(Default, Debug)]
+pub struct Me1Import {
+    pub imported: Vec<&'static str>,
+    pub missing: Vec<String>,
+}
+
+impl Me1Import {
+    fn field<T>(&mut self, label: &'static str, value: Option<T>, set: impl FnOnce(T)) {
+        match value {
+            Some(value) => {
+                set(value);
+                self.imported.push(label);
+            }
+            None => self.missing.push(label.to_owned()),
+        }
+    }
+}
+
+impl Me1LeSaveData {
+    /// Carries an original ME1 career over to this save: the plots, then Shepard as far as the
+    /// two games share it. This save is the base, what isn't imported stays as it is, see
+    /// [`NOT_IMPORTED`].
+    /// The class isn't changed, the talents only go to the ones of the same class.
+    pub fn import_me1(&self, me1: &Me1SaveGame) -> Me1Import {
+        let mut import = Me1Import::default();
+
+        let state = me1.state();
+        let me1_plot = state.plot();
+        self.import_plot(&me1_plot);
+        import.imported.push("Plots");
+
+        let me1_player = me1.player();
+        let player = self.player();
+        let m_player = me1_player.m_player();
+        let current_game = me1_player.current_game();
+        let m_inventory = m_player
+            .and_then(|m_player| me1_player.find_object(m_player, "m_Squad"))
+            .and_then(|m_squad| me1_player.find_object(m_squad, "m_Inventory"));
+
+        // Shepard
+        let is_female = me1_plot.booleans().get(IS_FEMALE).map(|is_female| *is_female);
+        import.field("Gender", is_female, |is_female| player.is_female.set(is_female));
+        let origin = plot_variant(&me1_plot, ORIGIN, Origin::variants());
+        import.field("Origin", origin, |origin| *player.origin.borrow_mut() = Origin::from(origin));
+        let notoriety = plot_variant(&me1_plot, NOTORIETY, Notoriety::variants());
+        import.field("Notoriety", notoriety, |notoriety| {
+            *player.notoriety.borrow_mut() = Notoriety::from(notoriety)
+        });
+        let first_name = str_value(&me1_player, m_player, "m_FirstName");
+        import.field("Name", first_name, |first_name| *player.first_name.borrow_mut() = first_name);
+
+        let class = me1_class(&me1_player, m_player);
+        let is_same_class = match class {
+            Some(class) if class == *player.player_class() => true,
+            Some(class) => {
+                let class = Me1LeClass::variants()[usize::from(class)];
+                import
+                    .missing
+                    .push(format!("Talents, the original save is of the {} class", class));
+                false
+            }
+            None => {
+                import.missing.push(String::from("Class"));
+                false
+            }
+        };
+
+        let level = int_value(&me1_player, m_player, "m_XPLevel");
+        import.field("Level", level, |level| player.level.set(level));
+        let talent_points = int_value(&me1_player, m_player, "m_TalentPoints");
+        import.field("Talent points", talent_points, |points| player.talent_points.set(points));
+        if is_same_class {
+            let ranks = me1_player.talent_ranks();
+            import.field("Talents", (!ranks.is_empty()).then(|| ranks), |ranks| {
+                for talent in player.complex_talents().iter() {
+                    let talent = talent.borrow();
+                    let rank = ranks.iter().find(|&&(id, _)| id == talent.talent_id());
+                    if let Some(&(_, rank)) = rank {
+                        talent.current_rank.set(rank);
+                    }
+                }
+            });
+        }
+
+        // Resources
+        let credits = int_value(&me1_player, m_inventory, "m_nResourceCredits");
+        import.field("Credits", credits, |credits| player.credits.set(credits));
+        let medigel = float_value(&me1_player, m_inventory, "m_fResourceMedigel");
+        import.field("Medigel", medigel, |medigel| player.medigel.set(medigel as i32));
+        let grenades = int_value(&me1_player, m_inventory, "m_nResourceGrenades");
+        import.field("Grenades", grenades, |grenades| player.grenades.set(grenades as f32));
+        let omnigel = float_value(&me1_player, m_inventory, "m_fResourceSalvage");
+        import.field("Omnigel", omnigel, |omnigel| player.omnigel.set(omnigel));
+
+        // Career
+        let difficulty = game_option(&me1_player, current_game, "m_nCombatDifficulty");
+        let game_options = player.game_options();
+        let difficulty = difficulty.filter(|_| !game_options.is_empty());
+        import.field("Difficulty", difficulty, |difficulty| game_options[0].set(difficulty));
+        let seconds_played = float_value(&me1_player, current_game, "m_fPlaythroughTime");
+        import.field("Play time", seconds_played, |seconds_played| {
+            self.seconds_played.set(seconds_played as i32)
+        });
+
+        import
+    }
+
+    // Both games share the plot ids, the Legendary Edition only adds some past the end of the
+    // original tables. Those keep the values of this save.
+    fn import_plot(&self, me1: &PlotTable) {
+        let plot = self.plot();
+        {
+            let from = me1.booleans();
+            let mut booleans = plot.booleans.borrow_mut();
+            if booleans.len() < from.len() {
+                booleans.resize(from.len(), false);
+            }
+            for (id, value) in from.iter().enumerate() {
+                booleans.set(id, *value);
+            }
+        }
+        import_values(&me1.integers(), &mut plot.integers.borrow_mut());
+        import_values(&me1.floats(), &mut plot.floats.borrow_mut());
+    }
+}
+
+fn import_values<T: Copy>(from: &[RcCell<T>], to: &mut Vec<RcCell<T>>) {
+    for (id, value) in from.iter().enumerate() {
+        match to.get(id) {
+            Some(cell) => cell.set(value.get()),
+            None => to.push(RcCell::new(value.get())),
+        }
+    }
+}
+
+// `None` for a value out of the variants of the enum
+fn plot_variant(plot: &PlotTable, id: usize, variants: &[&str]) -> Option<usize> {
+    let value = plot.integers().get(id)?.get();
+    usize::try_from(value).ok().filter(|&value| value < variants.len())
+}
+
+// `BIO_PARTY_MEMBER_CLASS_BASE_ADEPT`
+fn me1_class(player: &Me1Player, m_player: Option<&Data>) -> Option<Me1LeClass> {
+    let property = player.find_property(&m_player?.properties, "m_ClassBase")?;
+    let class_name = match *property.borrow() {
+        Property::Name { ref value_name_id, .. } => player.get_name(value_name_id.get())?,
+        _ => return None,
+    };
+    let class = class_name.rsplit('_').next()?;
+    Me1LeClass::variants()
+        .iter()
+        .position(|variant| variant.eq_ignore_ascii_case(class))
+        .map(Me1LeClass::from)
+}
+
+// The properties left at their default aren't written, a missing number of an object is a zero:
+// no `m_TalentPoints` once they are all spent
+fn int_value(player: &Me1Player, data: Option<&Data>, name: &str) -> Option<i32> {
+    let value = player.find_int(&data?.properties, name).map(|value| value.get());
+    Some(value.unwrap_or_default())
+}
+
+fn float_value(player: &Me1Player, data: Option<&Data>, name: &str) -> Option<f32> {
+    let property = match player.find_property(&data?.properties, name) {
+        Some(property) => property,
+        None => return Some(0.0),
+    };
+    let value = match *property.borrow() {
+        Property::Float { ref value, .. } => Some(value.get()),
+        _ => None,
+    };
+    value
+}
+
+fn str_value(player: &Me1Player, data: Option<&Data>, name: &str) -> Option<String> {
+    let property = player.find_property(&data?.properties, name)?;
+    let value = match *property.borrow() {
+        Property::Str { ref string, .. } => Some(string.borrow().clone()),
+        _ => None,
+    };
+    value
+}
+
+// Looked up in the game options first, then in the current game itself
+fn game_option(player: &Me1Player, current_game: Option<&Data>, name: &str) -> Option<i32> {
+    let current_game = current_game?;
+    let in_game_options =
+        player.find_property(&current_game.properties, "m_GameOptions").and_then(|game_options| {
+            match *game_options.borrow() {
+                Property::Struct {
+                    struct_type: StructType::Properties(ref properties), ..
+                } => player.find_int(properties, name).map(|value| value.get()),
+                _ => None,
+            }
+        });
+    in_game_options.or_else(|| int_value(player, Some(current_game), name))
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::{
+            fixup::{self, Fixups},
+            mass_effect_1_le::Me1LeSaveGame,
+        },
+        unreal,
+    };
+
+    fn open(me1: &str, le: &str) -> Result<(Me1SaveGame, Me1LeSaveGame)> {
+        let me1 = unreal::Deserializer::from_bytes(&fs::read(me1)?)?;
+        let le = unreal::Deserializer::from_bytes(&fs::read(le)?)?;
+        Ok((me1, le))
+    }
+
+    #[test]
+    fn import_me1_save() -> Result<()> {
+        let (me1, le) = open("test/ME1Save.MassEffectSave", "test/ME1LeSave.pcsav")?;
+        let save_data = le.save_data.borrow();
+        let import = save_data.import_me1(&me1);
+
+        for label in ["Plots", "Gender", "Name", "Level", "Talent points", "Credits", "Play time"] {
+            assert!(import.imported.contains(&label), "{} not imported", label);
+        }
+
+        // Shepard
+        let me1_player = me1.player();
+        let player = save_data.player();
+        assert_eq!(Some(player.level()), me1_player.player_level());
+        // Every point spent, the original save doesn't write them
+        assert_eq!(player.talent_points(), 0);
+        let m_player = me1_player.m_player();
+        assert_eq!(
+            Some(player.first_name().clone()),
+            str_value(&me1_player, m_player, "m_FirstName")
+        );
+        let me1_state = me1.state();
+        let me1_plot = me1_state.plot();
+        assert_eq!(Some(player.is_female()), me1_plot.booleans().get(IS_FEMALE).map(|b| *b));
+
+        // Resources
+        let m_squad = m_player.and_then(|m_player| me1_player.find_object(m_player, "m_Squad"));
+        let m_inventory =
+            m_squad.and_then(|m_squad| me1_player.find_object(m_squad, "m_Inventory"));
+        assert_eq!(
+            Some(player.credits()),
+            int_value(&me1_player, m_inventory, "m_nResourceCredits")
+        );
+
+        // Plots
+        let plot = save_data.plot();
+        for (id, value) in me1_plot.booleans().iter().enumerate() {
+            assert_eq!(plot.booleans()[id], *value, "boolean {}", id);
+        }
+        for (id, value) in me1_plot.integers().iter().enumerate() {
+            assert_eq!(plot.integers()[id].get(), value.get(), "integer {}", id);
+        }
+        for (id, value) in me1_plot.floats().iter().enumerate() {
+            assert_eq!(plot.floats()[id].get(), value.get(), "float {}", id);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn imported_save_is_valid() -> Result<()> {
+        let (me1, le) = open("test/ME1Save.MassEffectSave", "test/ME1LeSave.pcsav")?;
+        let import = le.save_data.borrow().import_me1(&me1);
+        assert!(!import.imported.is_empty());
+
+        // Written as the game expects it
+        let output = fixup::to_bytes(&le, false)?;
+        assert_eq!(fixup::verify(Me1LeSaveGame::FIXUPS, &output, false), Some(true));
+        let reloaded: Me1LeSaveGame = unreal::Deserializer::from_bytes(&output)?;
+
+        let (save_data, reloaded) = (le.save_data.borrow(), reloaded.save_data.borrow());
+        assert_eq!(reloaded.player().level(), save_data.player().level());
+        assert_eq!(*reloaded.player().first_name(), *save_data.player().first_name());
+        assert!(**reloaded.plot().booleans() == **save_data.plot().booleans());
+
+        // Imported twice, nothing moves
+        let again = reloaded.import_me1(&me1);
+        assert_eq!(again.imported, import.imported);
+        assert!(fixup::to_bytes(&*reloaded, false)? == fixup::to_bytes(&*save_data, false)?);
+        Ok(())
+    }
+
+    #[test]
+    fn import_me1_export() -> Result<()> {
+        let (me1, le) = open("test/ME1Export.MassEffectSave", "test/ME1LeExport.pcsav")?;
+        let save_data = le.save_data.borrow();
+        let import = save_data.import_me1(&me1);
+
+        assert!(import.imported.contains(&"Plots"));
+        assert_eq!(Some(save_data.player().level()), me1.player().player_level());
+        // Still an export, without the data of the world
+        assert!(save_data.no_export().as_ref().is_none());
+
+        let output = fixup::to_bytes(&le, false)?;
+        let _: Me1LeSaveGame = unreal::Deserializer::from_bytes(&output)?;
+        Ok(())
+    }
+
+    #[test]
+    fn keep_the_legendary_plots() -> Result<()> {
+        let (me1, le) = open("test/ME1Save.MassEffectSave", "test/ME1LeSave.pcsav")?;
+        let save_data = le.save_data.borrow();
+
+        // Past the end of the original tables
+        let (boolean, integer) = {
+            let me1_state = me1.state();
+            let me1_plot = me1_state.plot();
+            let (boolean, integer) =
+                (me1_plot.booleans().len() + 10, me1_plot.integers().len() + 2);
+            (boolean, integer)
+        };
+        {
+            let plot = save_data.plot();
+            plot.booleans.borrow_mut().set_many([boolean], true);
+            let mut integers = plot.integers.borrow_mut();
+            integers.resize_with(integer + 1, Default::default);
+            integers[integer].set(42);
+        }
+
+        save_data.import_me1(&me1);
+        let plot = save_data.plot();
+        assert!(plot.booleans()[boolean]);
+        assert_eq!(plot.integers()[integer].get(), 42);
+        Ok(())
+    }
+
+    #[test]
+    fn shorter_legendary_tables() -> Result<()> {
+        let (me1, le) = open("test/ME1Save.MassEffectSave", "test/ME1LeSave.pcsav")?;
+        let save_data = le.save_data.borrow();
+        {
+            let plot = save_data.plot();
+            plot.booleans.borrow_mut().clear();
+            plot.integers.borrow_mut().clear();
+            plot.floats.borrow_mut().clear();
+        }
+
+        save_data.import_me1(&me1);
+        let (plot, me1_state) = (save_data.plot(), me1.state());
+        let me1_plot = me1_state.plot();
+        assert_eq!(plot.booleans().len(), me1_plot.booleans().len());
+        assert_eq!(plot.integers().len(), me1_plot.integers().len());
+        assert_eq!(plot.floats().len(), me1_plot.floats().len());
+        Ok(())
+    }
+
+    #[test]
+    fn import_talents_of_the_same_class() -> Result<()> {
+        let (me1, le) = open("test/ME1Save.MassEffectSave", "test/ME1LeSave.pcsav")?;
+        let save_data = le.save_data.borrow();
+        let me1_class = me1_class(&me1.player(), me1.player().m_player());
+        let ranks = me1.player().talent_ranks();
+        assert!(!ranks.is_empty());
+
+        // Made the same class as the original save
+        let me1_class = me1_class.expect("no class in the original save");
+        *save_data.player().player_class.borrow_mut() = me1_class.clone();
+        for talent in save_data.player().complex_talents().iter() {
+            talent.borrow().current_rank.set(0);
+        }
+
+        let import = save_data.import_me1(&me1);
+        assert!(import.imported.contains(&"Talents"));
+        let player = save_data.player();
+        for talent in player.complex_talents().iter() {
+            let talent = talent.borrow();
+            if let Some(&(_, rank)) = ranks.iter().find(|&&(id, _)| id == talent.talent_id()) {
+                assert_eq!(talent.current_rank(), rank, "talent {}", talent.talent_id());
+            }
+        }
+
+        // Another class, the talents are left alone
+        let other = (usize::from(me1_class) + 1) % Me1LeClass::variants().len();
+        *player.player_class.borrow_mut() = Me1LeClass::from(other);
+        for talent in player.complex_talents().iter() {
+            talent.borrow().current_rank.set(0);
+        }
+        let import = save_data.import_me1(&me1);
+        assert!(!import.imported.contains(&"Talents"));
+        assert!(import.missing.iter().any(|missing| missing.starts_with("Talents")));
+        assert!(player.complex_talents().iter().all(|talent| talent.borrow().current_rank() == 0));
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_plot_values() -> Result<()> {
+        let (me1, le) = open("test/ME1Save.MassEffectSave", "test/ME1LeSave.pcsav")?;
+        let save_data = le.save_data.borrow();
+        {
+            let me1_state = me1.state();
+            let me1_plot = me1_state.plot();
+            me1_plot.integers()[ORIGIN].set(9);
+            me1_plot.integers()[NOTORIETY].set(-1);
+        }
+        let origin = usize::from(save_data.player().origin().clone());
+
+        let import = save_data.import_me1(&me1);
+        assert!(import.missing.contains(&String::from("Origin")));
+        assert!(import.missing.contains(&String::from("Notoriety")));
+        assert_eq!(usize::from(save_data.player().origin().clone()), origin);
+        Ok(())
+    }
+}
@@ -7,9 +7,44 @@ pub struct DbItem {
     pub manufacturer_id: i32,
 }
 
+// How the item names end
+const ITEM_KINDS: &[&str] =
+    &["Assault Rifle", "Sniper Rifle", "Shotgun", "Pistol", "Armor", "Omni-Tool", "Biotic Amp"];
+
 #[derive(Deserialize, Deref, From, PartialEq, Eq)]
 pub struct Me1ItemDb(IndexMap<DbItem, String>);
 
+impl Me1ItemDb {
+    /// What kind of gear `item` is (`Pistol`, `Armor`, `Omni-Tool`...), read from its name.
+    pub fn item_kind(&self, item: &DbItem) -> Option<&'static str> {
+        let name = self.get(item)?.trim_end_matches(')');
+        ITEM_KINDS.iter().copied().find(|kind| name.ends_with(kind))
+    }
+
+    /// Every item sold by `manufacturer` that fits a squad member wearing `armor_type` armors
+    /// (`Human`, `Krogan`, `Quarian` or `Turian`).
+    /// That is all the weapons, amps and omni-tools, but only the armors of this type.
+    pub fn item_set(&self, manufacturer: &str, armor_type: &str) -> Vec<DbItem> {
+        let prefix = format!("[{}] ", manufacturer);
+        let armor = format!("({} ", armor_type);
+        self.iter()
+            .filter_map(|(item, name)| {
+                let name = name.strip_prefix(&prefix)?;
+                let is_armor = name.ends_with("Armor)");
+                (!is_armor || name.contains(&armor)).then(|| *item)
+            })
+            .collect()
+    }
+
+    /// The item with the same `item_id` sold by `manufacturer`, if there is one.
+    pub fn manufacturer_item(&self, manufacturer: &str, item_id: i32) -> Option<DbItem> {
+        let prefix = format!("[{}] ", manufacturer);
+        self.iter().find_map(|(item, name)| {
+            (item.item_id == item_id && name.starts_with(&prefix)).then(|| *item)
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::fs;
@@ -25,4 +60,50 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn item_set() -> Result<()> {
+        let input = fs::read_to_string("databases/me1_item_db.ron")?;
+        let me1_item_db: Me1ItemDb = ron::from_str(&input)?;
+
+        // 3 armors + 4 weapons
+        let human = me1_item_db.item_set("Spectre Master Gear", "Human");
+        assert_eq!(human.len(), 7);
+
+        // No krogan armor
+        let krogan = me1_item_db.item_set("Spectre Master Gear", "Krogan");
+        assert_eq!(krogan.len(), 4);
+        assert!(krogan.iter().all(|item| human.contains(item)));
+
+        // Not a manufacturer
+        assert!(me1_item_db.item_set("Spectre", "Human").is_empty());
+
+        // Human light armor
+        let spectre = me1_item_db.manufacturer_item("Spectre Master Gear", 287);
+        assert!(spectre.map(|item| human.contains(&item)).unwrap_or(false));
+        assert!(me1_item_db.manufacturer_item("Spectre Master Gear", 285).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn item_kind() -> Result<()> {
+        let input = fs::read_to_string("databases/me1_item_db.ron")?;
+        let me1_item_db: Me1ItemDb = ron::from_str(&input)?;
+
+        let kinds = me1_item_db
+            .item_set("Spectre Master Gear", "Human")
+            .iter()
+            .filter_map(|item| me1_item_db.item_kind(item))
+            .collect::<Vec<_>>();
+        assert_eq!(kinds.len(), 7);
+        assert_eq!(kinds.iter().filter(|&&kind| kind == "Armor").count(), 3);
+        assert!(kinds.contains(&"Assault Rifle") && kinds.contains(&"Sniper Rifle"));
+
+        // Empty slot
+        let none = DbItem { item_id: 0, manufacturer_id: 0 };
+        assert_eq!(me1_item_db.item_kind(&none), None);
+
+        Ok(())
+    }
 }
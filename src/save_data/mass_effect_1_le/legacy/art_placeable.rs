@@ -29,5 +29,5 @@ pub struct ArtPlaceableBehavior {
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct ArtPlaceable {
-    _unknown: Dummy<60>,
+    unknown: Dummy<60>,
 }
@@ -80,7 +80,12 @@ impl<'de> Deserialize<'de> for BaseObject {
                     }
                     "BioVehicleSaveObject" => Object::Vehicle(seq.next_element()?.unwrap()),
                     "BioWorldInfoSaveObject" => Object::World(seq.next_element()?.unwrap()),
-                    _ => unreachable!(),
+                    _ => {
+                        return Err(de::Error::custom(format!(
+                            "unknown save object class: {}",
+                            class_name
+                        )))
+                    }
                 };
 
                 Ok(BaseObject { _class_name: class_name, owner_name, owner_class, _object: object })
@@ -143,7 +148,7 @@ pub struct Vehicle {
     shield: f32,
     first_name: String,
     localized_last_name: i32,
-    _unknown: Dummy<16>,
+    unknown: Dummy<16>,
 }
 
 #[rcize_fields]
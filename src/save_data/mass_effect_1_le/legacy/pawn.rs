@@ -33,7 +33,7 @@ pub struct PawnBehavior {
     quick_slot: u8,
     squad: Option<BaseObject>,
     inventory: Option<BaseObject>,
-    _unknown: Dummy<3>,
+    unknown: Dummy<3>,
     experience: i32,
     talent_points: i32,
     talent_pool_points: i32,
@@ -1,3 +1,4 @@
+pub mod import;
 pub mod item_db;
 pub mod legacy;
 pub mod player;
@@ -20,6 +21,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use crate::{save_data::RcRef, unreal};
 
 use super::{
+    fixup::{Fixup, Fixups},
     shared::{
         plot::{Codex, Journal, PlotTable},
         Rotator, SaveTimeStamp, Vector,
@@ -44,6 +46,32 @@ pub struct Me1LeSaveGame {
     _uncompressed_size: u32,
 }
 
+// What the PC release writes, the PS4 stores the save data uncompressed instead
+const MAGIC_NUMBER: [u8; 4] = [0xC1, 0x83, 0x2A, 0x9E];
+const BLOCK_SIZE: u32 = 0x40000;
+const ZLIB: u32 = 1;
+
+impl Me1LeSaveGame {
+    /// Wraps the save data of a PS4 save in the compressed file of the PC.
+    /// The headers, the checksum and the sizes are computed when serialized.
+    pub fn from_save_data(save_data: RcRef<Me1LeSaveData>) -> Self {
+        Me1LeSaveGame {
+            magic_number: Me1LeMagicNumber(u32::from_le_bytes(MAGIC_NUMBER)),
+            block_size: BLOCK_SIZE,
+            _headers: Vec::new().into(),
+            save_data,
+            checksum: 0,
+            compression_flag: ZLIB,
+            _uncompressed_size: 0,
+        }
+    }
+}
+
+// The checksum is followed by the compression flag and the uncompressed size
+impl Fixups for Me1LeSaveGame {
+    const FIXUPS: &'static [Fixup] = &[Fixup::ChecksumFromEnd(12)];
+}
+
 impl<'de> Deserialize<'de> for Me1LeSaveGame {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -62,7 +90,11 @@ impl<'de> Deserialize<'de> for Me1LeSaveGame {
                 A: de::SeqAccess<'de>,
             {
                 let magic_number = seq.next_element()?.unwrap();
-                let block_size = seq.next_element()?.unwrap();
+                let block_size: u32 = seq.next_element()?.unwrap();
+                // Can't be split in chunks again otherwise
+                if block_size == 0 {
+                    return Err(de::Error::custom("block size of 0"));
+                }
 
                 // Headers
                 let mut headers = Vec::new();
@@ -88,6 +120,9 @@ impl<'de> Deserialize<'de> for Me1LeSaveGame {
 
                 // Save data
                 let save_data: Me1LeSaveData = {
+                    // A few corrupted bytes can inflate to gigabytes, not past what the full
+                    // header declares though
+                    let max_len = headers[0].uncompressed_size as u64;
                     let mut uncompressed = Vec::new();
 
                     for header in &headers[1..] {
@@ -96,8 +131,15 @@ impl<'de> Deserialize<'de> for Me1LeSaveGame {
                             compressed.push(seq.next_element()?.unwrap());
                         }
 
-                        let mut z = ZlibDecoder::new(&compressed[..]);
+                        let limit = (max_len + 1).saturating_sub(uncompressed.len() as u64);
+                        let mut z = ZlibDecoder::new(&compressed[..]).take(limit);
                         z.read_to_end(&mut uncompressed).map_err(de::Error::custom)?;
+                        if uncompressed.len() as u64 > max_len {
+                            return Err(de::Error::custom(format!(
+                                "save data inflating past the {} bytes declared",
+                                max_len
+                            )));
+                        }
                     }
 
                     unreal::Deserializer::from_bytes(&uncompressed).map_err(de::Error::custom)?
@@ -140,13 +182,14 @@ impl serde::Serialize for Me1LeSaveGame {
         let mut headers = Vec::new();
 
         let uncompressed = unreal::Serializer::to_vec(save_data).map_err(ser::Error::custom)?;
+        let too_big = || ser::Error::custom("save data past the 4 GB of a save");
 
-        headers
-            .push(ChunkHeader { compressed_size: 0, uncompressed_size: uncompressed.len() as u32 });
+        let uncompressed_size = u32::try_from(uncompressed.len()).map_err(|_| too_big())?;
+        headers.push(ChunkHeader { compressed_size: 0, uncompressed_size });
 
         // Compresse chaque chunk
         let mut compressed = Vec::new();
-        for chunk in uncompressed.chunks(*block_size as usize) {
+        for chunk in uncompressed.chunks((*block_size).max(1) as usize) {
             let uncompressed_size = chunk.len() as u32;
 
             let mut compressed_chunk = Vec::new();
@@ -155,9 +198,10 @@ impl serde::Serialize for Me1LeSaveGame {
                 z.read_to_end(&mut compressed_chunk).map_err(ser::Error::custom)?;
             }
 
-            let compressed_size = compressed_chunk.len() as u32;
+            let compressed_size = u32::try_from(compressed_chunk.len()).map_err(|_| too_big())?;
 
-            headers[0].compressed_size += compressed_size;
+            headers[0].compressed_size =
+                headers[0].compressed_size.checked_add(compressed_size).ok_or_else(too_big)?;
             headers.push(ChunkHeader { compressed_size, uncompressed_size });
 
             compressed.extend(&compressed_chunk);
@@ -187,7 +231,7 @@ impl<'de> Deserialize<'de> for Me1LeMagicNumber {
     {
         let version: [u8; 4] = Deserialize::deserialize(deserializer)?;
 
-        if version != [0xC1, 0x83, 0x2A, 0x9E] {
+        if version != MAGIC_NUMBER {
             return Err(de::Error::custom("Wrong magic number"));
         }
 
@@ -200,12 +244,12 @@ impl<'de> Deserialize<'de> for Me1LeMagicNumber {
 pub struct Me1LeSaveData {
     _version: Me1LeVersion,
     character_id: String,
-    created_date: SaveTimeStamp,
+    pub created_date: SaveTimeStamp,
     pub plot: PlotTable,
     journal: Journal,
     codex: Codex,
-    timestamp: SaveTimeStamp,
-    seconds_played: i32,
+    pub timestamp: SaveTimeStamp,
+    pub seconds_played: i32,
     pub player: Player,
     base_level_name: String,
     map_name: String,
@@ -218,6 +262,11 @@ pub struct Me1LeSaveData {
     pub no_export: NoExport, // Only serialized for normal savegames, not for character export
 }
 
+// Written uncompressed as is on PS4
+impl Fixups for Me1LeSaveData {
+    const FIXUPS: &'static [Fixup] = &[];
+}
+
 #[derive(Serialize, Clone)]
 pub struct Me1LeVersion(i32);
 
@@ -313,10 +362,9 @@ mod test {
     use std::time::Instant;
 
     use anyhow::Result;
-    use crc::{Crc, CRC_32_BZIP2};
 
     use super::*;
-    use crate::unreal;
+    use crate::{save_data::fixup, unreal};
 
     #[test]
     fn deserialize_serialize() -> Result<()> {
@@ -332,23 +380,13 @@ mod test {
 
             // Deserialize
             let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+            assert_eq!(fixup::verify(Me1LeSaveGame::FIXUPS, &input, false), Some(true));
 
             println!("Deserialize 1 : {:?}", Instant::now() - now);
             let now = Instant::now();
 
             // Serialize
-            let mut output = unreal::Serializer::to_vec(&me1_save_game)?;
-
-            // Checksum
-            {
-                let checksum_offset = output.len() - 12;
-                let crc = Crc::<u32>::new(&CRC_32_BZIP2);
-                let checksum = crc.checksum(&output[..checksum_offset]);
-
-                // Update checksum
-                let end = checksum_offset + 4;
-                output[checksum_offset..end].swap_with_slice(&mut u32::to_le_bytes(checksum));
-            }
+            let output = fixup::to_bytes(&me1_save_game, false)?;
 
             println!("Serialize 1 : {:?}", Instant::now() - now);
             let now = Instant::now();
@@ -360,18 +398,7 @@ mod test {
             let now = Instant::now();
 
             // Serialize (again)
-            let mut output_2 = unreal::Serializer::to_vec(&me1_save_game)?;
-
-            // Checksum
-            {
-                let checksum_offset = output_2.len() - 12;
-                let crc = Crc::<u32>::new(&CRC_32_BZIP2);
-                let checksum = crc.checksum(&output_2[..checksum_offset]);
-
-                // Update checksum
-                let end = checksum_offset + 4;
-                output_2[checksum_offset..end].swap_with_slice(&mut u32::to_le_bytes(checksum));
-            }
+            let output_2 = fixup::to_bytes(&me1_save_game, false)?;
 
             println!("Serialize 2 : {:?}", Instant::now() - now);
 
@@ -389,6 +416,24 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn convert_ps4_to_pc() -> Result<()> {
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        // PS4: the save data alone
+        let ps4 = fixup::to_bytes(&*me1_save_game.save_data.borrow(), false)?;
+        let save_data: Me1LeSaveData = unreal::Deserializer::from_bytes(&ps4)?;
+
+        // Back to the PC, as if it was written by the game
+        let pc = Me1LeSaveGame::from_save_data(save_data.into());
+        let output = fixup::to_bytes(&pc, false)?;
+        assert_eq!(fixup::verify(Me1LeSaveGame::FIXUPS, &output, false), Some(true));
+        assert!(output == fixup::to_bytes(&me1_save_game, false)?);
+
+        Ok(())
+    }
+
     // #[test]
     // fn uncompress() -> Result<()> {
     //     let input = fs::read("test/ME1Le_Export.pcsav")?;
@@ -399,4 +444,22 @@ mod test {
 
     //     Ok(())
     // }
+
+    #[test]
+    fn corrupted_sizes() -> Result<()> {
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+
+        // Magic number, block size, then the full header
+        let mut no_block_size = input.clone();
+        no_block_size[4..8].copy_from_slice(&0u32.to_le_bytes());
+        assert!(unreal::Deserializer::from_bytes::<Me1LeSaveGame>(&no_block_size).is_err());
+
+        // Inflating past the declared size
+        let mut too_small = input;
+        too_small[12..16].copy_from_slice(&16u32.to_le_bytes());
+        let err = unreal::Deserializer::from_bytes::<Me1LeSaveGame>(&too_small).err();
+        assert!(err.map_or(false, |err| err.to_string().contains("inflating past")));
+
+        Ok(())
+    }
 }
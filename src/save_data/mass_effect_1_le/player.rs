@@ -15,6 +15,7 @@ pub struct Player {
     pub localized_class_name: i32,
     pub player_class: Me1LeClass,
     pub level: i32,
+    #[raw_ui(label = "Current XP")]
     pub current_xp: f32,
     pub first_name: String,
     localized_last_name: i32,
@@ -29,12 +30,13 @@ pub struct Player {
     pub simple_talents: Vec<SimpleTalent>,
     pub complex_talents: Vec<ComplexTalent>,
     pub inventory: Inventory,
+    #[raw_ui(step = 1000)]
     pub credits: i32,
     pub medigel: i32,
     pub grenades: f32,
     pub omnigel: f32,
     pub face_code: String,
-    armor_overridden: bool,
+    pub armor_overridden: bool,
     pub auto_levelup_template_id: i32,
     health_per_level: f32,
     stability: f32,
@@ -54,8 +56,8 @@ pub struct Player {
     xp_level: i32,
     is_driving: bool,
     pub game_options: Vec<i32>,
-    helmet_shown: bool,
-    _unknown: Dummy<5>,
+    pub helmet_shown: bool,
+    unknown: Dummy<5>,
     last_power: String,
     health_max: f32,
     hotkeys: Vec<Hotkey>,
@@ -77,6 +79,7 @@ pub enum Me1LeClass {
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", talent_id)]
 pub struct SimpleTalent {
+    #[raw_ui(label = "Talent ID")]
     pub talent_id: i32,
     pub current_rank: i32,
 }
@@ -85,6 +88,7 @@ pub struct SimpleTalent {
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", talent_id)]
 pub struct ComplexTalent {
+    #[raw_ui(label = "Talent ID")]
     pub talent_id: i32,
     pub current_rank: i32,
     pub max_rank: i32,
@@ -130,9 +134,12 @@ impl Default for ItemLevel {
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
 pub struct Item {
+    #[raw_ui(label = "Item ID")]
     pub item_id: i32,
     pub item_level: ItemLevel,
+    #[raw_ui(label = "Manufacturer ID")]
     pub manufacturer_id: i32,
+    #[raw_ui(label = "Plot Conditional ID")]
     pub plot_conditional_id: i32,
     pub new_item: bool,
     junk: bool,
@@ -143,9 +150,12 @@ pub struct Item {
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
 pub struct ItemMod {
+    #[raw_ui(label = "Item ID")]
     pub item_id: i32,
     pub item_level: ItemLevel,
+    #[raw_ui(label = "Manufacturer ID")]
     pub manufacturer_id: i32,
+    #[raw_ui(label = "Plot Conditional ID")]
     pub plot_conditional_id: i32,
 }
 
@@ -1,6 +1,26 @@
 use serde::{Deserialize, Serialize};
 
-use super::player::{ComplexTalent, Item, SimpleTalent};
+use super::{
+    player::{ComplexTalent, Item, SimpleTalent},
+    Me1LeSaveData,
+};
+use crate::save_data::RcCell;
+
+// Earned by a squad member on each level up
+const TALENT_POINTS_PER_LEVEL: i32 = 2;
+
+/// Raises a squad member to `level` with the talent points of the levels gained.
+/// The experience is shared by the whole squad, so it is already right.
+/// Returns `false` if they were already there, a higher level is left as it is.
+pub fn level_up(current: &RcCell<i32>, talent_points: &RcCell<i32>, level: i32) -> bool {
+    let gained = level - current.get();
+    if gained <= 0 {
+        return false;
+    }
+    current.set(level);
+    talent_points.update(|talent_points| talent_points + gained * TALENT_POINTS_PER_LEVEL);
+    true
+}
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
@@ -31,7 +51,67 @@ pub struct Henchman {
     health: f32,
     shield: f32,
     level: i32,
-    helmet_shown: bool,
+    pub helmet_shown: bool,
     current_quick_slot: u8,
     health_max: f32,
 }
+
+impl Me1LeSaveData {
+    /// Brings every squad member up to Shepard's level, see [`level_up`].
+    /// Returns how many of them were below.
+    pub fn sync_squad_level(&self) -> usize {
+        let level = self.player().level();
+        let squad = self.squad();
+        squad
+            .iter()
+            .filter(|henchman| {
+                let henchman = henchman.borrow();
+                level_up(&henchman.level, &henchman.talent_points, level)
+            })
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_1_le::Me1LeSaveGame, unreal};
+
+    #[test]
+    fn level_up() {
+        let (level, talent_points) = (RcCell::new(10), RcCell::new(3));
+
+        assert!(super::level_up(&level, &talent_points, 15));
+        assert_eq!((level.get(), talent_points.get()), (15, 13));
+
+        // Never down
+        assert!(!super::level_up(&level, &talent_points, 12));
+        assert_eq!((level.get(), talent_points.get()), (15, 13));
+    }
+
+    #[test]
+    fn sync_squad_level() -> Result<()> {
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let save_data = me1_save_game.save_data.borrow();
+
+        save_data.sync_squad_level();
+        let level = save_data.player().level();
+        assert!(save_data.squad().iter().all(|henchman| henchman.borrow().level.get() >= level));
+
+        // One member left behind
+        let squad = save_data.squad();
+        let henchman = squad.first().unwrap().borrow();
+        henchman.level.set(level - 5);
+        let talent_points = henchman.talent_points.get();
+
+        assert_eq!(save_data.sync_squad_level(), 1);
+        assert_eq!(henchman.level.get(), level);
+        assert_eq!(henchman.talent_points.get(), talent_points + 5 * TALENT_POINTS_PER_LEVEL);
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 mod galaxy_map;
 pub mod player;
 pub mod plot_db;
-mod squad;
+pub mod squad;
 
 use self::{galaxy_map::*, player::*, squad::*};
 
@@ -12,19 +12,22 @@ use super::shared::{
     plot::{Codex, Journal, PlotTable},
     Door, EndGameState, Kismet, Level, Rotator, SaveTimeStamp, StreamingState, Vector,
 };
-use super::Guid;
+use super::{
+    fixup::{Fixup, Fixups},
+    Guid,
+};
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiRoot)]
 pub struct Me2SaveGame {
     _version: Me2Version,
     debug_name: String,
-    seconds_played: f32,
+    pub seconds_played: f32,
     disc: i32,
     base_level_name: String,
     pub difficulty: Difficulty,
     pub end_game_state: EndGameState,
-    timestamp: SaveTimeStamp,
+    pub timestamp: SaveTimeStamp,
     location: Vector,
     rotation: Rotator,
     current_loading_tip: i32,
@@ -34,13 +37,36 @@ pub struct Me2SaveGame {
     doors: Vec<Door>,
     pawns: Vec<Guid>,
     pub player: Player,
-    squad: Vec<Henchman>,
+    pub squad: Vec<Henchman>,
     pub plot: PlotTable,
     journal: Journal,
     codex: Codex,
     pub me1_plot: PlotTable,
     galaxy_map: GalaxyMap,
-    dependant_dlcs: Vec<DependentDlc>,
+    pub dependant_dlcs: Vec<DependentDlc>,
+}
+
+// Everything before the plot table, only parsed to be skipped by a quick plot edit
+#[derive(Deserialize)]
+pub struct Me2QuickHead {
+    _version: Me2Version,
+    _debug_name: String,
+    _seconds_played: f32,
+    _disc: i32,
+    _base_level_name: String,
+    _difficulty: Difficulty,
+    _end_game_state: EndGameState,
+    _timestamp: SaveTimeStamp,
+    _location: Vector,
+    _rotation: Rotator,
+    _current_loading_tip: i32,
+    _levels: Vec<Level>,
+    _streaming_states: Vec<StreamingState>,
+    _kismet_records: Vec<Kismet>,
+    _doors: Vec<Door>,
+    _pawns: Vec<Guid>,
+    _player: Player,
+    _squad: Vec<Henchman>,
 }
 
 #[derive(Serialize, Clone)]
@@ -50,6 +76,17 @@ pub struct Me2Version {
     pub is_xbox360: bool,
 }
 
+impl Me2SaveGame {
+    // Told apart by the byte order of the version
+    pub fn is_xbox360(&self) -> bool {
+        self._version.is_xbox360
+    }
+}
+
+impl Fixups for Me2SaveGame {
+    const FIXUPS: &'static [Fixup] = &[Fixup::AppendedChecksum];
+}
+
 impl<'de> Deserialize<'de> for Me2Version {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -57,6 +94,22 @@ impl<'de> Deserialize<'de> for Me2Version {
     {
         const GAME_VERSION: i32 = 29;
 
+        if deserializer.is_human_readable() {
+            // Exported as JSON, the platform is up to the file it's written to
+            #[derive(Deserialize)]
+            struct Me2Version {
+                version: i32,
+            }
+
+            let Me2Version { version } = Deserialize::deserialize(deserializer)?;
+            if version != GAME_VERSION {
+                return Err(de::Error::custom(
+                    "Wrong save version, please use a save from the latest version of the game",
+                ));
+            }
+            return Ok(Self { version, is_xbox360: false });
+        }
+
         let bytes: [u8; 4] = Deserialize::deserialize(deserializer)?;
         let version_le = i32::from_le_bytes(bytes);
         let version_be = i32::from_be_bytes(bytes);
@@ -78,12 +131,12 @@ impl<'de> Deserialize<'de> for Me2Version {
 pub struct Me2LeSaveGame {
     _version: Me2LeVersion,
     debug_name: String,
-    seconds_played: f32,
+    pub seconds_played: f32,
     disc: i32,
     base_level_name: String,
     pub difficulty: Difficulty,
     pub end_game_state: EndGameState,
-    timestamp: SaveTimeStamp,
+    pub timestamp: SaveTimeStamp,
     location: Vector,
     rotation: Rotator,
     current_loading_tip: i32,
@@ -94,13 +147,40 @@ pub struct Me2LeSaveGame {
     pawns: Vec<Guid>,
     pub player: Player,
     me1_import_bonus: Me1ImportBonus,
-    squad: Vec<Henchman>,
+    pub squad: Vec<Henchman>,
     pub plot: PlotTable,
     journal: Journal,
     codex: Codex,
     pub me1_plot: PlotTable,
     galaxy_map: GalaxyMap,
-    dependant_dlcs: Vec<DependentDlc>,
+    pub dependant_dlcs: Vec<DependentDlc>,
+}
+
+impl Fixups for Me2LeSaveGame {
+    const FIXUPS: &'static [Fixup] = &[Fixup::AppendedChecksum];
+}
+
+#[derive(Deserialize)]
+pub struct Me2LeQuickHead {
+    _version: Me2LeVersion,
+    _debug_name: String,
+    _seconds_played: f32,
+    _disc: i32,
+    _base_level_name: String,
+    _difficulty: Difficulty,
+    _end_game_state: EndGameState,
+    _timestamp: SaveTimeStamp,
+    _location: Vector,
+    _rotation: Rotator,
+    _current_loading_tip: i32,
+    _levels: Vec<Level>,
+    _streaming_states: Vec<StreamingState>,
+    _kismet_records: Vec<Kismet>,
+    _doors: Vec<Door>,
+    _pawns: Vec<Guid>,
+    _player: Player,
+    _me1_import_bonus: Me1ImportBonus,
+    _squad: Vec<Henchman>,
 }
 
 #[rcize_fields]
@@ -144,12 +224,35 @@ pub enum Difficulty {
     Insanity,
 }
 
+/// A DLC the save was made with, the game refuses to load it without that DLC
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", name)]
-struct DependentDlc {
-    id: i32,
-    name: String,
+pub struct DependentDlc {
+    pub id: i32,
+    pub name: String,
+}
+
+// By module id, the name saved is in the language of the game
+const DLC_TITLES: &[(i32, &str)] = &[
+    (95, "Normandy Crash Site"),
+    (100, "Zaeed - The Price of Revenge"),
+    (118, "Firewalker Pack"),
+    (119, "Kasumi - Stolen Memory"),
+    (132, "Overlord"),
+    (300, "Lair of the Shadow Broker"),
+    (400, "Arrival"),
+];
+
+impl DependentDlc {
+    /// The English name of the DLC, or the saved one for a DLC unknown to the editor
+    pub fn title(&self) -> String {
+        let id = self.id();
+        DLC_TITLES
+            .iter()
+            .find_map(|&(dlc_id, title)| (dlc_id == id).then(|| title.to_owned()))
+            .unwrap_or_else(|| self.name().clone())
+    }
 }
 
 #[cfg(test)]
@@ -157,10 +260,9 @@ mod test {
     use std::fs;
 
     use anyhow::Result;
-    use crc::{Crc, CRC_32_BZIP2};
 
     use super::*;
-    use crate::unreal;
+    use crate::{save_data::fixup, unreal};
 
     #[test]
     fn deserialize_serialize_vanilla() -> Result<()> {
@@ -170,12 +272,7 @@ mod test {
         let me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
 
         // Serialize
-        let mut output = unreal::Serializer::to_vec(&me2_save_game)?;
-
-        // Checksum
-        let crc = Crc::<u32>::new(&CRC_32_BZIP2);
-        let checksum = crc.checksum(&output);
-        output.extend(&u32::to_le_bytes(checksum));
+        let output = fixup::to_bytes(&me2_save_game, false)?;
 
         // // Check serialized = input
         // let cmp = input.chunks(4).zip(output.chunks(4));
@@ -191,6 +288,22 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn profile_sections() -> Result<()> {
+        let input = fs::read("test/ME2Save.pcsav")?;
+
+        let (_, sections): (Me2SaveGame, _) =
+            unreal::Deserializer::from_bytes_profiled(&input, true)?;
+
+        assert!(sections.iter().any(|section| section.name == "plot"));
+
+        // Everything but the trailing checksum
+        let bytes: usize = sections.iter().map(|section| section.bytes).sum();
+        assert_eq!(bytes, input.len() - 4);
+
+        Ok(())
+    }
+
     #[test]
     fn deserialize_serialize_vanilla_xbox360() -> Result<()> {
         let input_pc = fs::read("test/ME2Save.pcsav")?;
@@ -201,17 +314,8 @@ mod test {
         let me2_xb360: Me2SaveGame = unreal::Deserializer::from_be_bytes(&input_xb360)?;
 
         // Serialize
-        let mut output_xb360_to_pc = unreal::Serializer::to_vec(&me2_xb360)?;
-
-        let crc = Crc::<u32>::new(&CRC_32_BZIP2);
-        let checksum = crc.checksum(&output_xb360_to_pc);
-        output_xb360_to_pc.extend(&u32::to_le_bytes(checksum));
-
-        let mut output_pc_to_xb360 = unreal::Serializer::to_be_vec(&me2_pc)?;
-
-        let crc = Crc::<u32>::new(&CRC_32_BZIP2);
-        let checksum = crc.checksum(&output_pc_to_xb360);
-        output_pc_to_xb360.extend(&u32::to_be_bytes(checksum));
+        let output_xb360_to_pc = fixup::to_bytes(&me2_xb360, false)?;
+        let output_pc_to_xb360 = fixup::to_bytes(&me2_pc, true)?;
 
         // // Check serialized = input
         // let cmp = input.chunks(4).zip(output.chunks(4));
@@ -236,12 +340,7 @@ mod test {
         let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
 
         // Serialize
-        let mut output = unreal::Serializer::to_vec(&me2_save_game)?;
-
-        // Checksum
-        let crc = Crc::<u32>::new(&CRC_32_BZIP2);
-        let checksum = crc.checksum(&output);
-        output.extend(&u32::to_le_bytes(checksum));
+        let output = fixup::to_bytes(&me2_save_game, false)?;
 
         // // Check serialized = input
         // let cmp = input.chunks(4).zip(output.chunks(4));
@@ -256,4 +355,24 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn dependent_dlcs() -> Result<()> {
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let mut me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        // In English whatever the language of the game
+        let titles: Vec<_> =
+            me2_save_game.dependant_dlcs().iter().map(|dlc| dlc.borrow().title()).collect();
+        assert_eq!(titles.first().map(String::as_str), Some("Normandy Crash Site"));
+        assert!(titles.iter().any(|title| title == "Arrival"));
+
+        // Without the reference to Arrival
+        me2_save_game.dependant_dlcs_mut().retain(|dlc| dlc.borrow().id() != 400);
+        let output = fixup::to_bytes(&me2_save_game, false)?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&output)?;
+        assert_eq!(me2_save_game.dependant_dlcs().len(), titles.len() - 1);
+
+        Ok(())
+    }
 }
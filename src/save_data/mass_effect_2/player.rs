@@ -11,8 +11,10 @@ pub struct Player {
     pub is_female: bool,
     pub class_name: String,
     pub level: i32,
+    #[raw_ui(label = "Current XP")]
     pub current_xp: f32,
     pub first_name: String,
+    #[raw_ui(string_ref)]
     localized_last_name: i32,
     pub origin: Origin,
     pub notoriety: Notoriety,
@@ -25,15 +27,23 @@ pub struct Player {
     weapons: Vec<Weapon>,
     weapons_loadout: WeaponLoadout,
     hotkeys: Vec<Hotkey>,
+    #[raw_ui(step = 1000)]
     pub credits: i32,
     pub medigel: i32,
+    #[raw_ui(group = "Resources")]
     pub eezo: i32,
+    #[raw_ui(group = "Resources")]
     pub iridium: i32,
+    #[raw_ui(group = "Resources")]
     pub palladium: i32,
+    #[raw_ui(group = "Resources")]
     pub platinum: i32,
+    #[raw_ui(group = "Resources")]
     pub probes: i32,
+    #[raw_ui(group = "Resources")]
     pub current_fuel: f32,
     pub face_code: String,
+    #[raw_ui(string_ref)]
     localized_class_name: i32,
 }
 
@@ -42,7 +52,7 @@ pub struct Player {
 #[display(fmt = "{}", name)]
 pub struct Power {
     pub name: String,
-    rank: f32,
+    pub rank: f32,
     pub power_class_name: String,
     wheel_display_index: i32,
 }
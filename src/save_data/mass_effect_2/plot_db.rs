@@ -1,18 +1,21 @@
 use indexmap::IndexMap;
 use serde::Deserialize;
 
-use crate::save_data::shared::plot::PlotCategory;
+use crate::save_data::shared::plot::{PlotCategory, SquadMemberFlags};
 
 #[derive(Deserialize)]
 pub struct Me2PlotDb {
     pub player: PlotCategory,
     pub crew: IndexMap<String, PlotCategory>,
+    pub squad: IndexMap<String, SquadMemberFlags>,
     pub romance: IndexMap<String, PlotCategory>,
     pub missions: IndexMap<String, PlotCategory>,
     pub loyalty_missions: IndexMap<String, PlotCategory>,
+    pub dlc: IndexMap<String, PlotCategory>,
     pub research_upgrades: IndexMap<String, PlotCategory>,
     pub rewards: PlotCategory,
     pub captains_cabin: PlotCategory,
+    pub wardrobe: IndexMap<String, PlotCategory>,
     pub imported_me1: IndexMap<String, PlotCategory>,
 }
 
@@ -23,12 +26,30 @@ mod test {
     use anyhow::Result;
 
     use super::*;
-    use crate::save_data::shared::plot::RawPlotDb;
+    use crate::save_data::shared::{plot::RawPlotDb, plot_db_version};
 
     #[test]
     fn deserialize_plot_db() -> Result<()> {
         let input = fs::read_to_string("databases/me2_plot_db.ron")?;
-        let _me2_plot_db: Me2PlotDb = ron::from_str(&input)?;
+        let me2_plot_db: Me2PlotDb = plot_db_version::from_ron(&input)?;
+
+        // Every DLC mission has its own section
+        assert_eq!(me2_plot_db.dlc.len(), 5);
+
+        // A warning or a check box is shown in place of its plot, it has to be in the same category
+        let mut categories =
+            [&me2_plot_db.player, &me2_plot_db.rewards, &me2_plot_db.captains_cabin]
+                .into_iter()
+                .chain(me2_plot_db.crew.values())
+                .chain(me2_plot_db.romance.values())
+                .chain(me2_plot_db.missions.values())
+                .chain(me2_plot_db.loyalty_missions.values())
+                .chain(me2_plot_db.dlc.values())
+                .chain(me2_plot_db.research_upgrades.values())
+                .chain(me2_plot_db.wardrobe.values())
+                .chain(me2_plot_db.imported_me1.values());
+        assert!(categories
+            .all(|category| category.dangerous_are_known() && category.as_bool_are_known()));
 
         Ok(())
     }
@@ -36,7 +57,7 @@ mod test {
     #[test]
     fn deserialize_raw_plot_db() -> Result<()> {
         let input = fs::read_to_string("databases/me2_raw_plot_db.ron")?;
-        let _me2_raw_plot_db: RawPlotDb = ron::from_str(&input)?;
+        let _me2_raw_plot_db: RawPlotDb = plot_db_version::from_ron(&input)?;
 
         Ok(())
     }
@@ -0,0 +1,81 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::save_data::shared::plot::BitVec;
+
+// Known mixups of the plots carried over from the previous games, with how to set them right
+#[derive(Deserialize)]
+pub struct Me3ContinuityDb {
+    pub fixes: IndexMap<String, ContinuityFix>,
+}
+
+// Booleans of the ME3 table, ME3 keeps the ME1 ids shifted by 10 000
+#[derive(Deserialize, Clone, PartialEq)]
+pub struct ContinuityFix {
+    // What goes wrong in game, and what the fix does about it
+    pub explanation: String,
+    // All of them with these values for the fix to be needed
+    pub when: IndexMap<usize, bool>,
+    pub set: IndexMap<usize, bool>,
+}
+
+impl ContinuityFix {
+    pub fn is_needed(&self, booleans: &BitVec) -> bool {
+        self.when
+            .iter()
+            .all(|(&id, &value)| booleans.get(id).map(|plot| *plot).unwrap_or_default() == value)
+    }
+
+    // Returns how many plots had another value
+    pub fn apply(&self, booleans: &mut BitVec) -> usize {
+        let mut changed = 0;
+        for (&id, &value) in &self.set {
+            if id >= booleans.len() {
+                booleans.resize(id + 1, false);
+            }
+            if booleans.replace(id, value) != value {
+                changed += 1;
+            }
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::save_data::shared::{plot::RawPlotDb, plot_db_version};
+
+    #[test]
+    fn fixes_are_fixed_once_applied() -> Result<()> {
+        let input = fs::read_to_string("databases/me3_continuity_db.ron")?;
+        let continuity_db: Me3ContinuityDb = ron::from_str(&input)?;
+        let input = fs::read_to_string("databases/me3_raw_plot_db.ron")?;
+        let raw_plot_db: RawPlotDb = plot_db_version::from_ron(&input)?;
+
+        for (name, fix) in &continuity_db.fixes {
+            let ids: Vec<usize> = fix.when.keys().chain(fix.set.keys()).copied().collect();
+            assert!(
+                ids.iter().all(|id| raw_plot_db.booleans.contains_key(id)),
+                "{}: unknown plot",
+                name
+            );
+
+            let mut booleans = BitVec::default();
+            for (&id, &value) in &fix.when {
+                let len = booleans.len().max(id + 1);
+                booleans.resize(len, false);
+                booleans.set(id, value);
+            }
+            assert!(fix.is_needed(&booleans), "{}", name);
+            assert!(fix.apply(&mut booleans) > 0, "{}", name);
+            assert!(!fix.is_needed(&booleans), "{}: still needed once applied", name);
+            assert_eq!(fix.apply(&mut booleans), 0, "{}", name);
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,10 @@
+pub mod continuity_db;
 mod galaxy_map;
 pub mod player;
 pub mod plot;
 pub mod plot_db;
-mod squad;
+pub mod squad;
+pub mod world_state_db;
 
 use self::{galaxy_map::*, player::*, plot::*, squad::*};
 
@@ -14,20 +16,23 @@ use super::shared::{
     plot::PlotTable as Me1PlotTable, Door, EndGameState, Kismet, Level, Rotator, SaveTimeStamp,
     StreamingState, Vector,
 };
-use super::Guid;
+use super::{
+    fixup::{Fixup, Fixups},
+    Guid,
+};
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiRoot)]
 pub struct Me3SaveGame {
     _version: Me3Version,
     debug_name: String,
-    seconds_played: f32,
+    pub seconds_played: f32,
     disc: i32,
     base_level_name: String,
     base_level_name_display_override: String,
     pub difficulty: Difficulty,
     pub end_game_state: EndGameState,
-    timestamp: SaveTimeStamp,
+    pub timestamp: SaveTimeStamp,
     location: Vector,
     rotation: Rotator,
     current_loading_tip: i32,
@@ -38,7 +43,7 @@ pub struct Me3SaveGame {
     placeables: Vec<Placeable>,
     pawns: Vec<Guid>,
     pub player: Player,
-    squad: Vec<Henchman>,
+    pub squad: Vec<Henchman>,
     pub plot: PlotTable,
     journal: Journal,
     codex: Codex,
@@ -53,6 +58,31 @@ pub struct Me3SaveGame {
     saved_objective_text: i32,
 }
 
+// Everything before the plot table, only parsed to be skipped by a quick plot edit
+#[derive(Deserialize)]
+pub struct Me3QuickHead {
+    _version: Me3Version,
+    _debug_name: String,
+    _seconds_played: f32,
+    _disc: i32,
+    _base_level_name: String,
+    _base_level_name_display_override: String,
+    _difficulty: Difficulty,
+    _end_game_state: EndGameState,
+    _timestamp: SaveTimeStamp,
+    _location: Vector,
+    _rotation: Rotator,
+    _current_loading_tip: i32,
+    _levels: Vec<Level>,
+    _streaming_states: Vec<StreamingState>,
+    _kismet_records: Vec<Kismet>,
+    _doors: Vec<Door>,
+    _placeables: Vec<Placeable>,
+    _pawns: Vec<Guid>,
+    _player: Player,
+    _squad: Vec<Henchman>,
+}
+
 #[derive(Serialize, Clone)]
 pub struct Me3Version {
     version: i32,
@@ -60,6 +90,17 @@ pub struct Me3Version {
     pub is_xbox360: bool,
 }
 
+impl Me3SaveGame {
+    // Told apart by the byte order of the version
+    pub fn is_xbox360(&self) -> bool {
+        self._version.is_xbox360
+    }
+}
+
+impl Fixups for Me3SaveGame {
+    const FIXUPS: &'static [Fixup] = &[Fixup::AppendedChecksum];
+}
+
 impl<'de> Deserialize<'de> for Me3Version {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -67,18 +108,32 @@ impl<'de> Deserialize<'de> for Me3Version {
     {
         const GAME_VERSION: i32 = 59;
 
-        let bytes: [u8; 4] = Deserialize::deserialize(deserializer)?;
-        let version_le = i32::from_le_bytes(bytes);
-        let version_be = i32::from_be_bytes(bytes);
-
-        if version_le == GAME_VERSION {
-            Ok(Self { version: version_le, is_xbox360: false })
-        } else if version_be == GAME_VERSION {
-            Ok(Self { version: version_be, is_xbox360: true })
+        if deserializer.is_human_readable() {
+            // Exported as JSON, the platform is up to the file it's written to
+            #[derive(Deserialize)]
+            struct Me3Version {
+                version: i32,
+            }
+
+            let Me3Version { version } = Deserialize::deserialize(deserializer)?;
+            if version != GAME_VERSION {
+                return Err(de::Error::custom(format!("Unknown save version: {}", version)));
+            }
+            Ok(Self { version, is_xbox360: false })
         } else {
-            Err(de::Error::custom(
-                "Wrong save version, please use a save from the latest version of the game",
-            ))
+            let bytes: [u8; 4] = Deserialize::deserialize(deserializer)?;
+            let version_le = i32::from_le_bytes(bytes);
+            let version_be = i32::from_be_bytes(bytes);
+
+            if version_le == GAME_VERSION {
+                Ok(Self { version: version_le, is_xbox360: false })
+            } else if version_be == GAME_VERSION {
+                Ok(Self { version: version_be, is_xbox360: true })
+            } else {
+                Err(de::Error::custom(
+                    "Wrong save version, please use a save from the latest version of the game",
+                ))
+            }
         }
     }
 }
@@ -170,10 +225,9 @@ mod test {
     use std::fs;
 
     use anyhow::Result;
-    use crc::{Crc, CRC_32_BZIP2};
 
     use super::*;
-    use crate::unreal;
+    use crate::{save_data::fixup, unreal};
 
     #[test]
     fn deserialize_serialize() -> Result<()> {
@@ -183,12 +237,7 @@ mod test {
         let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
 
         // Serialize
-        let mut output = unreal::Serializer::to_vec(&me3_save_game)?;
-
-        // Checksum
-        let crc = Crc::<u32>::new(&CRC_32_BZIP2);
-        let checksum = crc.checksum(&output);
-        output.extend(&u32::to_le_bytes(checksum));
+        let output = fixup::to_bytes(&me3_save_game, false)?;
 
         // // Check serialized = input
         // let cmp = input.chunks(4).zip(output.chunks(4));
@@ -214,17 +263,8 @@ mod test {
         let me3_xb360: Me3SaveGame = unreal::Deserializer::from_be_bytes(&input_xb360)?;
 
         // Serialize
-        let mut output_xb360_to_pc = unreal::Serializer::to_vec(&me3_xb360)?;
-
-        let crc = Crc::<u32>::new(&CRC_32_BZIP2);
-        let checksum = crc.checksum(&output_xb360_to_pc);
-        output_xb360_to_pc.extend(&u32::to_le_bytes(checksum));
-
-        let mut output_pc_to_xb360 = unreal::Serializer::to_be_vec(&me3_pc)?;
-
-        let crc = Crc::<u32>::new(&CRC_32_BZIP2);
-        let checksum = crc.checksum(&output_pc_to_xb360);
-        output_pc_to_xb360.extend(&u32::to_be_bytes(checksum));
+        let output_xb360_to_pc = fixup::to_bytes(&me3_xb360, false)?;
+        let output_pc_to_xb360 = fixup::to_bytes(&me3_pc, true)?;
 
         // // Check serialized = input
         // let cmp = input.chunks(4).zip(output.chunks(4));
@@ -240,4 +280,24 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn export_import_war_assets() -> Result<()> {
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        // Export
+        let bundle = me3_save_game.player().export_war_assets();
+        let ron = ron::ser::to_string(&bundle)?;
+
+        // Import into a save without any asset
+        let mut other: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        other.player_mut().war_assets_mut().clear();
+        other.player_mut().import_war_assets(ron::from_str(&ron)?);
+
+        let imported = other.player().export_war_assets();
+        assert_eq!(imported.war_assets, bundle.war_assets);
+
+        Ok(())
+    }
 }
@@ -6,7 +6,7 @@ use crate::save_data::{
         appearance::Appearance,
         player::{Notoriety, Origin, WeaponLoadout},
     },
-    Guid,
+    Guid, RcCell,
 };
 
 #[rcize_fields]
@@ -18,8 +18,10 @@ pub struct Player {
     is_injured_pawn: bool,
     use_casual_appearance: bool,
     pub level: i32,
+    #[raw_ui(label = "Current XP")]
     pub current_xp: f32,
     pub first_name: String,
+    #[raw_ui(string_ref)]
     localized_last_name: i32,
     pub origin: Origin,
     pub notoriety: Notoriety,
@@ -30,7 +32,7 @@ pub struct Player {
     pub appearance: Appearance,
     emissive_id: i32,
     pub powers: Vec<Power>,
-    war_assets: IndexMap<i32, i32>,
+    pub war_assets: IndexMap<i32, i32>,
     weapons: Vec<Weapon>,
     weapons_mods: Vec<WeaponMod>,
     weapons_loadout: WeaponLoadout,
@@ -38,7 +40,8 @@ pub struct Player {
     secondary_weapon: String,
     loadout_weapon_group: Vec<i32>,
     hotkeys: Vec<Hotkey>,
-    health: f32,
+    pub health: f32,
+    #[raw_ui(step = 1000)]
     pub credits: i32,
     pub medigel: i32,
     eezo: i32,
@@ -49,16 +52,36 @@ pub struct Player {
     pub current_fuel: f32,
     pub grenades: i32,
     pub face_code: String,
+    #[raw_ui(string_ref)]
     localized_class_name: i32,
     character_guid: Guid,
 }
 
+impl Player {
+    pub fn export_war_assets(&self) -> WarAssetBundle {
+        let war_assets = self.war_assets().iter().map(|(&id, value)| (id, value.get())).collect();
+        WarAssetBundle { war_assets }
+    }
+
+    // Replaces all the assets, the ones missing from the bundle weren't acquired
+    pub fn import_war_assets(&mut self, bundle: WarAssetBundle) {
+        *self.war_assets_mut() =
+            bundle.war_assets.into_iter().map(|(id, value)| (id, RcCell::new(value))).collect();
+    }
+}
+
+// Galaxy at War assets alone, to carry them over to another ME3 career
+#[derive(Deserialize, Serialize, Clone, Default)]
+pub struct WarAssetBundle {
+    pub war_assets: IndexMap<i32, i32>,
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", name)]
 pub struct Power {
     pub name: String,
-    rank: f32,
+    pub rank: f32,
     evolved_choice_0: i32,
     evolved_choice_1: i32,
     evolved_choice_2: i32,
@@ -1,19 +1,22 @@
 use indexmap::IndexMap;
 use serde::Deserialize;
 
-use crate::save_data::shared::plot::PlotCategory;
+use crate::save_data::shared::plot::{BitVec, PlotCategory, SquadMemberFlags};
 
 #[derive(Deserialize)]
 pub struct Me3PlotDb {
     pub general: PlotCategory,
     pub crew: IndexMap<String, PlotCategory>,
+    pub squad: IndexMap<String, SquadMemberFlags>,
     pub romance: IndexMap<String, PlotCategory>,
     pub missions: IndexMap<String, PlotCategory>,
     pub citadel_dlc: IndexMap<String, PlotCategory>,
     pub normandy: IndexMap<String, PlotCategory>,
     pub appearances: IndexMap<String, PlotCategory>,
     pub weapons_powers: IndexMap<String, PlotVariable>,
-    pub intel: PlotCategory,
+    // Class name of each mod by weapon type, its level is a plot variable, 0 until found
+    pub weapon_mods: IndexMap<String, IndexMap<String, String>>,
+    pub intel: IntelDb,
 }
 
 #[derive(Deserialize, Clone, PartialEq, Eq)]
@@ -22,27 +25,114 @@ pub struct PlotVariable {
     pub variables: IndexMap<String, String>,
 }
 
+#[derive(Deserialize, Clone, PartialEq, Eq)]
+pub struct IntelDb {
+    pub requisitions: IndexMap<String, Requisition>,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Requisition {
+    // Not every purchase has to be unlocked first
+    #[serde(default)]
+    pub unlocked: Option<usize>,
+    pub purchased: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum IntelProblem {
+    // Purchased without having been unlocked
+    NotUnlocked(String),
+}
+
+impl IntelDb {
+    pub fn check(&self, booleans: &BitVec) -> Vec<IntelProblem> {
+        let is_set = |id: usize| booleans.get(id).map(|value| *value).unwrap_or(false);
+
+        self.requisitions
+            .iter()
+            .filter_map(|(name, requisition)| {
+                let unlocked = requisition.unlocked.map(is_set).unwrap_or(true);
+                (is_set(requisition.purchased) && !unlocked)
+                    .then(|| IntelProblem::NotUnlocked(name.clone()))
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use std::fs;
+    use std::{collections::HashSet, fs};
 
     use anyhow::Result;
 
     use super::*;
-    use crate::save_data::shared::plot::RawPlotDb;
+    use crate::save_data::shared::{plot::RawPlotDb, plot_db_version};
 
     #[test]
     fn deserialize_plot_db() -> Result<()> {
         let input = fs::read_to_string("databases/me3_plot_db.ron")?;
-        let _me3_plot_db: Me3PlotDb = ron::from_str(&input)?;
+        let me3_plot_db: Me3PlotDb = plot_db_version::from_ron(&input)?;
+
+        let mut categories = [&me3_plot_db.general]
+            .into_iter()
+            .chain(me3_plot_db.crew.values())
+            .chain(me3_plot_db.romance.values())
+            .chain(me3_plot_db.missions.values())
+            .chain(me3_plot_db.citadel_dlc.values())
+            .chain(me3_plot_db.normandy.values())
+            .chain(me3_plot_db.appearances.values());
+        assert!(categories
+            .all(|category| category.dangerous_are_known() && category.as_bool_are_known()));
+
+        // The mods are only edited on the weapon bench, once
+        let mut mods = me3_plot_db.weapon_mods.values().flat_map(|mods| mods.keys());
+        let mut seen = HashSet::new();
+        assert!(mods.all(|class| {
+            class.to_lowercase().contains(".sfxweaponmod_") && seen.insert(class.to_lowercase())
+        }));
+        let mut variables =
+            me3_plot_db.weapons_powers.values().flat_map(|var| var.variables.keys());
+        assert!(variables.all(|class| !seen.contains(&class.to_lowercase())));
 
         Ok(())
     }
 
+    #[test]
+    fn check_intel() {
+        let requisitions = [
+            ("Kit", Requisition { unlocked: Some(1), purchased: 2 }),
+            ("Amps", Requisition { unlocked: Some(3), purchased: 4 }),
+            ("Surgery", Requisition { unlocked: None, purchased: 5 }),
+        ];
+        let intel = IntelDb {
+            requisitions: requisitions
+                .map(|(name, req)| (name.to_owned(), req))
+                .into_iter()
+                .collect(),
+        };
+        let mut booleans = BitVec::default();
+
+        // Nothing, or only unlocks
+        assert!(intel.check(&booleans).is_empty());
+        booleans.set_many([1, 3], true);
+        assert!(intel.check(&booleans).is_empty());
+
+        // Surgery needs no unlock
+        booleans.set_many([2, 5], true);
+        assert!(intel.check(&booleans).is_empty());
+
+        booleans.set_many([4], true);
+        assert!(intel.check(&booleans).is_empty());
+
+        booleans.set_many([3], false);
+        let not_unlocked = IntelProblem::NotUnlocked("Amps".to_owned());
+        assert_eq!(intel.check(&booleans), vec![not_unlocked]);
+    }
+
     #[test]
     fn deserialize_raw_plot_db() -> Result<()> {
         let input = fs::read_to_string("databases/me3_raw_plot_db.ron")?;
-        let _me3_raw_plot_db: RawPlotDb = ron::from_str(&input)?;
+        let _me3_raw_plot_db: RawPlotDb = plot_db_version::from_ron(&input)?;
 
         Ok(())
     }
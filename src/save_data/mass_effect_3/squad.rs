@@ -7,10 +7,10 @@ use crate::save_data::shared::player::WeaponLoadout;
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", tag)]
 pub struct Henchman {
-    tag: String,
-    powers: Vec<Power>,
-    character_level: i32,
-    talent_points: i32,
+    pub tag: String,
+    pub powers: Vec<Power>,
+    pub character_level: i32,
+    pub talent_points: i32,
     weapon_loadout: WeaponLoadout,
     mapped_power: String,
     weapon_mods: Vec<WeaponMod>,
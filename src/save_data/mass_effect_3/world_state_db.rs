@@ -0,0 +1,151 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+// Whole sets of decisions of the trilogy, for a game started without an imported save
+#[derive(Deserialize)]
+pub struct Me3WorldStateDb {
+    pub presets: IndexMap<String, WorldState>,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+pub struct WorldState {
+    pub description: String,
+    // ME1 ids, ME3 keeps them shifted by 10 000
+    pub me1: PlotPreset,
+    // ME2 ids, the same in ME3
+    pub me2: PlotPreset,
+    pub me3: PlotPreset,
+}
+
+#[derive(Deserialize, Clone, PartialEq, Default)]
+pub struct PlotPreset {
+    #[serde(default)]
+    pub booleans: IndexMap<usize, bool>,
+    #[serde(default)]
+    pub integers: IndexMap<usize, i32>,
+}
+
+impl WorldState {
+    pub fn booleans(&self) -> impl Iterator<Item = (usize, bool)> + '_ {
+        self.presets().flat_map(|(offset, preset)| {
+            preset.booleans.iter().map(move |(&id, &value)| (id + offset, value))
+        })
+    }
+
+    pub fn integers(&self) -> impl Iterator<Item = (usize, i32)> + '_ {
+        self.presets().flat_map(|(offset, preset)| {
+            preset.integers.iter().map(move |(&id, &value)| (id + offset, value))
+        })
+    }
+
+    fn presets(&self) -> impl Iterator<Item = (usize, &PlotPreset)> {
+        [(10_000, &self.me1), (0, &self.me2), (0, &self.me3)].into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::save_data::{
+        mass_effect_1::plot_db::Me1PlotDb, mass_effect_2::plot_db::Me2PlotDb,
+        mass_effect_3::plot_db::Me3PlotDb, shared::plot::PlotCategory,
+    };
+
+    #[test]
+    fn presets_only_set_known_plots() -> Result<()> {
+        let input = fs::read_to_string("databases/me3_world_state_db.ron")?;
+        let world_state_db: Me3WorldStateDb = ron::from_str(&input)?;
+
+        let me1_plot_db: Me1PlotDb =
+            ron::from_str(&fs::read_to_string("databases/me1_plot_db.ron")?)?;
+        let me2_plot_db: Me2PlotDb =
+            ron::from_str(&fs::read_to_string("databases/me2_plot_db.ron")?)?;
+        let me3_plot_db: Me3PlotDb =
+            ron::from_str(&fs::read_to_string("databases/me3_plot_db.ron")?)?;
+
+        // Only the deaths of the squad carry over
+        let me2_deaths = PlotCategory {
+            booleans: me2_plot_db
+                .squad
+                .values()
+                .filter_map(|flags| flags.dead.map(|dead| (dead, String::new())))
+                .collect(),
+            integers: Default::default(),
+            clusters: Default::default(),
+            dangerous: Default::default(),
+            as_bool: Default::default(),
+        };
+
+        let me1_categories = me1_plot_db.player_crew.values().chain(me1_plot_db.missions.values());
+        let me1_categories = me1_categories.collect::<Vec<_>>();
+        let me2_categories = [&me2_plot_db.player, &me2_deaths]
+            .into_iter()
+            .chain(me2_plot_db.crew.values())
+            .chain(me2_plot_db.missions.values())
+            .chain(me2_plot_db.loyalty_missions.values())
+            .chain(me2_plot_db.dlc.values())
+            .chain(me2_plot_db.imported_me1.values())
+            .collect::<Vec<_>>();
+
+        let known = |categories: &[&PlotCategory], preset: &PlotPreset| {
+            preset
+                .booleans
+                .keys()
+                .all(|id| categories.iter().any(|category| category.booleans.contains_key(id)))
+                && preset
+                    .integers
+                    .keys()
+                    .all(|id| categories.iter().any(|category| category.integers.contains_key(id)))
+        };
+
+        for (name, world_state) in &world_state_db.presets {
+            assert!(known(&me1_categories, &world_state.me1), "{}: unknown ME1 plot", name);
+            assert!(known(&me2_categories, &world_state.me2), "{}: unknown ME2 plot", name);
+            let me3_categories = [&me3_plot_db.general];
+            assert!(known(&me3_categories, &world_state.me3), "{}: unknown ME3 plot", name);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn presets_follow_the_clusters() -> Result<()> {
+        let input = fs::read_to_string("databases/me3_world_state_db.ron")?;
+        let world_state_db: Me3WorldStateDb = ron::from_str(&input)?;
+        let me1_plot_db: Me1PlotDb =
+            ron::from_str(&fs::read_to_string("databases/me1_plot_db.ron")?)?;
+        let me2_plot_db: Me2PlotDb =
+            ron::from_str(&fs::read_to_string("databases/me2_plot_db.ron")?)?;
+
+        // A choice comes with what the game changes along with it, never against it
+        let follows = |categories: Vec<&PlotCategory>, preset: &PlotPreset| {
+            let chosen = categories
+                .into_iter()
+                .flat_map(|category| &category.clusters)
+                .filter(|(idx, _)| preset.booleans.get(*idx) == Some(&true));
+
+            chosen.flat_map(|(_, cluster)| cluster).all(|(other, value)| {
+                preset.booleans.get(other).map(|other| other == value).unwrap_or(true)
+            })
+        };
+
+        for (name, world_state) in &world_state_db.presets {
+            let me1_categories = me1_plot_db.missions.values().collect();
+            assert!(follows(me1_categories, &world_state.me1), "{}: ME1 cluster", name);
+
+            let me2_categories = me2_plot_db
+                .missions
+                .values()
+                .chain(me2_plot_db.loyalty_missions.values())
+                .chain(me2_plot_db.dlc.values())
+                .collect();
+            assert!(follows(me2_categories, &world_state.me2), "{}: ME2 cluster", name);
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
+pub mod diff;
+pub mod fixup;
+pub mod json;
 pub mod mass_effect_1;
 pub mod mass_effect_1_le;
 pub mod mass_effect_2;
 pub mod mass_effect_3;
+pub mod search;
 pub mod shared;
 
 use std::{
@@ -39,6 +43,11 @@ impl<T: Copy> RcCell<T> {
         let new = f(old);
         self.0.set(new);
     }
+
+    // The same for every clone, to find a cell in a map
+    pub fn address(&self) -> usize {
+        Rc::as_ptr(&self.0) as usize
+    }
 }
 
 impl<T: Copy> From<T> for RcCell<T> {
@@ -93,6 +102,11 @@ impl<T> RcRef<T> {
     pub fn borrow_mut(&self) -> RefMut<'_, T> {
         RefCell::borrow_mut(&self.0)
     }
+
+    // The same for every clone, to find a value in a map
+    pub fn address(&self) -> usize {
+        Rc::as_ptr(&self.0) as usize
+    }
 }
 
 impl<T> From<T> for RcRef<T> {
@@ -142,6 +156,16 @@ impl<const LEN: usize> Default for Dummy<LEN> {
     }
 }
 
+impl<const LEN: usize> Dummy<LEN> {
+    pub fn bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
 impl<'de, const LEN: usize> Deserialize<'de> for Dummy<LEN> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -162,7 +186,9 @@ impl<'de, const LEN: usize> Deserialize<'de> for Dummy<LEN> {
                 let mut result = [0u8; LEN];
                 let mut i = 0;
                 while let Some(element) = seq.next_element()? {
-                    result[i] = element;
+                    // More bytes than it holds, only from a hand edited JSON
+                    *result.get_mut(i).ok_or_else(|| de::Error::invalid_length(i + 1, &self))? =
+                        element;
                     i += 1;
                 }
                 Ok(Dummy(result))
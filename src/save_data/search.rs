@@ -0,0 +1,111 @@
+use crate::save_data::shared::plot::RawPlotDb;
+
+/// A field of the save by its path, or a plot known to the database by its kind and id, the
+/// same way the Changes tab and the Watch panel name them.
+pub struct Entry {
+    pub path: String,
+    pub label: Option<String>,
+    // Lowercased path, label and value
+    haystack: String,
+}
+
+/// Every field and known plot of a save, flattened once to be searched as the user types.
+/// The plots are added on their own once the plot database is loaded.
+#[derive(Default)]
+pub struct SearchIndex {
+    entries: Vec<Entry>,
+    has_plots: bool,
+}
+
+impl SearchIndex {
+    /// From the fields of a save as `SaveGame::flatten` gives them
+    pub fn new(fields: Vec<(String, String)>) -> Self {
+        let entries = fields
+            .into_iter()
+            .map(|(path, value)| Entry {
+                haystack: format!("{} {}", path, value).to_lowercase(),
+                path,
+                label: None,
+            })
+            .collect();
+        SearchIndex { entries, has_plots: false }
+    }
+
+    pub fn has_plots(&self) -> bool {
+        self.has_plots
+    }
+
+    pub fn add_plots(&mut self, raw_plot_db: &RawPlotDb) {
+        let RawPlotDb { booleans, integers, floats } = raw_plot_db;
+        let kinds = [("Boolean", booleans), ("Integer", integers), ("Float", floats)];
+
+        let plots = kinds.into_iter().flat_map(|(kind, plots)| {
+            plots.iter().map(move |(id, label)| {
+                let path = format!("{} {}", kind, id);
+                Entry {
+                    haystack: format!("{} {}", path, label).to_lowercase(),
+                    path,
+                    label: Some(label.clone()),
+                }
+            })
+        });
+        self.entries.extend(plots);
+        self.has_plots = true;
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The entries with every word of the query in their path, label or value
+    pub fn search(&self, query: &str) -> impl Iterator<Item = &Entry> + '_ {
+        let words: Vec<_> = query.split_whitespace().map(str::to_lowercase).collect();
+        self.entries.iter().filter(move |entry| {
+            !words.is_empty() && words.iter().all(|word| entry.haystack.contains(word))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::{diff, mass_effect_2::Me2LeSaveGame},
+        unreal,
+    };
+
+    #[test]
+    fn search_fields_and_plots() -> Result<()> {
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        save_game.player.borrow_mut().set_credits(1_234_567);
+
+        let mut index = SearchIndex::new(diff::flatten_at("", &save_game, None)?);
+        assert!(!index.is_empty() && !index.has_plots());
+
+        // By path and value, whatever the case
+        let found: Vec<_> = index.search("PLAYER.credits 1234567").map(|e| &e.path).collect();
+        assert_eq!(found, ["player.credits"]);
+        assert_eq!(index.search("   ").count(), 0);
+
+        let raw_plot_db: RawPlotDb =
+            ron::from_str(&fs::read_to_string("databases/me2_raw_plot_db.ron")?)?;
+        let (&id, label) = raw_plot_db.booleans.first().unwrap();
+        index.add_plots(&raw_plot_db);
+        assert!(index.has_plots());
+
+        let path = format!("Boolean {}", id);
+        let found = index.search(label).find(|entry| entry.path == path);
+        assert_eq!(found.and_then(|entry| entry.label.as_deref()), Some(label.as_str()));
+
+        Ok(())
+    }
+}
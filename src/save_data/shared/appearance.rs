@@ -1,8 +1,11 @@
-use anyhow::Result;
+use std::mem;
+
+use anyhow::{ensure, Context, Result};
 use indexmap::IndexMap;
 use serde::{ser::SerializeTupleStruct, Deserialize, Deserializer, Serialize, Serializer};
 
 use super::Vector;
+use crate::unreal;
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUi)]
@@ -46,6 +49,194 @@ pub struct HeadMorph {
     pub texture_parameters: IndexMap<String, String>,
 }
 
+const GIBBED_ME2_MAGIC: &[u8] = b"GIBBEDMASSEFFECT2HEADMORPH\0";
+const GIBBED_ME3_MAGIC: &[u8] = b"GIBBEDMASSEFFECT3HEADMORPH\0";
+
+/// The games Gibbed's editors exchange head morphs for
+#[derive(Clone, Copy, PartialEq)]
+pub enum GibbedGame {
+    MassEffect2,
+    MassEffect3,
+}
+
+impl GibbedGame {
+    pub fn extension(self) -> &'static str {
+        match self {
+            GibbedGame::MassEffect2 => "me2headmorph",
+            GibbedGame::MassEffect3 => "me3headmorph",
+        }
+    }
+
+    fn magic(self) -> &'static [u8] {
+        match self {
+            GibbedGame::MassEffect2 => GIBBED_ME2_MAGIC,
+            GibbedGame::MassEffect3 => GIBBED_ME3_MAGIC,
+        }
+    }
+
+    // The save version Gibbed's editors write
+    fn version(self) -> u32 {
+        match self {
+            GibbedGame::MassEffect2 => 29,
+            GibbedGame::MassEffect3 => 59,
+        }
+    }
+}
+
+impl HeadMorph {
+    pub fn is_gibbed(input: &[u8]) -> bool {
+        input.starts_with(GIBBED_ME2_MAGIC) || input.starts_with(GIBBED_ME3_MAGIC)
+    }
+
+    // `.me2headmorph` / `.me3headmorph` files: a null terminated magic, the save version of
+    // the game they were exported from (29 for ME2, 59 for ME3) then the head morph as saved
+    // by the game, which is the same in ME2 and ME3
+    pub fn from_gibbed(input: &[u8]) -> Result<Self> {
+        let input = [GIBBED_ME2_MAGIC, GIBBED_ME3_MAGIC]
+            .iter()
+            .find_map(|magic| input.strip_prefix(*magic))
+            .context("Not a Gibbed head morph")?;
+        ensure!(input.len() > 4, "Truncated Gibbed head morph");
+
+        let (_version, head_morph) = input.split_at(4);
+        let head_morph = unreal::Deserializer::from_bytes(head_morph)?;
+        Ok(head_morph)
+    }
+
+    /// The head morph as a `.me2headmorph` / `.me3headmorph` file Gibbed's editors can import
+    pub fn to_gibbed(&self, game: GibbedGame) -> Result<Vec<u8>> {
+        let mut output = game.magic().to_vec();
+        output.extend(game.version().to_le_bytes());
+        output.extend(unreal::Serializer::to_vec(self)?);
+        Ok(output)
+    }
+
+    // The sliders set to something the character creator can't produce, which a shared head
+    // morph often has and the game shows as a stretched face
+    pub fn out_of_range(&self, ranges: &IndexMap<String, SliderRange>) -> Vec<OutOfRange> {
+        let (scalar_parameters, morph_features) = (self.scalar_parameters(), self.morph_features());
+        scalar_parameters
+            .iter()
+            .chain(morph_features.iter())
+            .filter_map(|(parameter, value)| {
+                let range = *ranges.get(parameter)?;
+                let value = value.get();
+                (!range.contains(value)).then(|| OutOfRange {
+                    parameter: parameter.clone(),
+                    value,
+                    range,
+                })
+            })
+            .collect()
+    }
+
+    // Brings every slider back within what the creator can produce, returns how many changed
+    pub fn normalize(&self, ranges: &IndexMap<String, SliderRange>) -> usize {
+        let (scalar_parameters, morph_features) = (self.scalar_parameters(), self.morph_features());
+        let mut changed = 0;
+        for (parameter, value) in scalar_parameters.iter().chain(morph_features.iter()) {
+            if let Some(range) = ranges.get(parameter) {
+                if !range.contains(value.get()) {
+                    value.set(range.clamp(value.get()));
+                    changed += 1;
+                }
+            }
+        }
+        changed
+    }
+
+    // Carries a head morph over to the next game, returns how many entries were renamed or
+    // dropped
+    pub fn convert(&self, conversion: &HeadMorphConversion) -> usize {
+        let HeadMorphConversion {
+            bones,
+            morph_features,
+            scalar_parameters,
+            vector_parameters,
+            texture_parameters,
+        } = conversion;
+
+        rename(&mut self.offset_bones.borrow_mut(), bones)
+            + rename(&mut self.morph_features.borrow_mut(), morph_features)
+            + rename(&mut self.scalar_parameters.borrow_mut(), scalar_parameters)
+            + rename(&mut self.vector_parameters.borrow_mut(), vector_parameters)
+            + rename(&mut self.texture_parameters.borrow_mut(), texture_parameters)
+    }
+}
+
+fn rename<V>(entries: &mut IndexMap<String, V>, renames: &IndexMap<String, String>) -> usize {
+    let mut changed = 0;
+    *entries = mem::take(entries)
+        .into_iter()
+        .filter_map(|(name, value)| match renames.get(&name) {
+            Some(renamed) => {
+                changed += 1;
+                (!renamed.is_empty()).then(|| (renamed.clone(), value))
+            }
+            None => Some((name, value)),
+        })
+        .collect();
+    changed
+}
+
+/// How the names of a head morph change from a game to the next one
+#[derive(Deserialize, Default)]
+pub struct HeadMorphConversion {
+    #[serde(default)]
+    pub bones: IndexMap<String, String>,
+    #[serde(default)]
+    pub morph_features: IndexMap<String, String>,
+    #[serde(default)]
+    pub scalar_parameters: IndexMap<String, String>,
+    #[serde(default)]
+    pub vector_parameters: IndexMap<String, String>,
+    #[serde(default)]
+    pub texture_parameters: IndexMap<String, String>,
+}
+
+pub struct OutOfRange {
+    pub parameter: String,
+    pub value: f32,
+    pub range: SliderRange,
+}
+
+#[derive(Deserialize)]
+pub struct HeadMorphSliderDb {
+    pub me1: IndexMap<String, IndexMap<String, String>>,
+    pub me2: IndexMap<String, IndexMap<String, String>>,
+    pub me3: IndexMap<String, IndexMap<String, String>>,
+    // What the character creator can produce, the parameters not listed aren't checked
+    #[serde(default)]
+    pub me2_ranges: IndexMap<String, SliderRange>,
+    #[serde(default)]
+    pub me3_ranges: IndexMap<String, SliderRange>,
+    #[serde(default)]
+    pub me1_to_me2: HeadMorphConversion,
+    #[serde(default)]
+    pub me2_to_me3: HeadMorphConversion,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct SliderRange {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl SliderRange {
+    pub fn contains(self, value: f32) -> bool {
+        value >= self.min && value <= self.max
+    }
+
+    // NaN to the minimum
+    pub fn clamp(self, value: f32) -> f32 {
+        if value.is_nan() {
+            self.min
+        } else {
+            value.clamp(self.min, self.max)
+        }
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct LinearColor {
     pub r: f32,
@@ -88,7 +279,7 @@ mod test {
     use anyhow::Result;
 
     use super::*;
-    use crate::unreal;
+    use crate::save_data::RcCell;
 
     #[test]
     fn gibbed_head_morph() -> Result<()> {
@@ -101,4 +292,112 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn import_gibbed_head_morph() -> Result<()> {
+        let me2 = fs::read("test/GibbedME2.me2headmorph")?;
+        let me3 = fs::read("test/GibbedME3.me3headmorph")?;
+
+        assert!(HeadMorph::is_gibbed(&me2));
+        assert!(HeadMorph::is_gibbed(&me3));
+
+        let me2 = HeadMorph::from_gibbed(&me2)?;
+        let me3 = HeadMorph::from_gibbed(&me3)?;
+        assert!(me2.hair_mesh.borrow().starts_with("BIOG_HMF_HIR_PRO"));
+        assert!(me3.hair_mesh.borrow().starts_with("BIOG_HMF_HIR_PRO"));
+
+        // Wrong magic or truncated header
+        assert!(HeadMorph::from_gibbed(b"GIBBEDMASSEFFECT1HEADMORPH\0").is_err());
+        assert!(HeadMorph::from_gibbed(b"GIBBEDMASSEFFECT2HEADMORPH\0\x1d").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn export_gibbed_head_morph() -> Result<()> {
+        let me2 = fs::read("test/GibbedME2.me2headmorph")?;
+        let me3 = fs::read("test/GibbedME3.me3headmorph")?;
+
+        // Byte for byte what Gibbed's editors wrote
+        assert!(HeadMorph::from_gibbed(&me2)?.to_gibbed(GibbedGame::MassEffect2)? == me2);
+        assert!(HeadMorph::from_gibbed(&me3)?.to_gibbed(GibbedGame::MassEffect3)? == me3);
+
+        // From one game to the other
+        let me3_from_me2 = HeadMorph::from_gibbed(&me2)?.to_gibbed(GibbedGame::MassEffect3)?;
+        assert!(me3_from_me2.starts_with(GIBBED_ME3_MAGIC));
+        assert!(HeadMorph::from_gibbed(&me3_from_me2).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize_head_morph_slider_db() -> Result<()> {
+        let input = fs::read_to_string("databases/head_morph_slider_db.ron")?;
+        let head_morph_slider_db: HeadMorphSliderDb = ron::from_str(&input)?;
+
+        // Every ME2 / ME3 slider has a range
+        let HeadMorphSliderDb { me2, me3, me2_ranges, me3_ranges, .. } = &head_morph_slider_db;
+        assert!(me2
+            .values()
+            .flat_map(|sliders| sliders.keys())
+            .all(|p| me2_ranges.contains_key(p)));
+        assert!(me3
+            .values()
+            .flat_map(|sliders| sliders.keys())
+            .all(|p| me3_ranges.contains_key(p)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn normalize_head_morph() -> Result<()> {
+        let input = fs::read_to_string("databases/head_morph_slider_db.ron")?;
+        let HeadMorphSliderDb { me2_ranges, .. } = ron::from_str(&input)?;
+        let head_morph = HeadMorph::from_gibbed(&fs::read("test/GibbedME2.me2headmorph")?)?;
+
+        let before = head_morph.out_of_range(&me2_ranges).len();
+        let (stretched, nan) = {
+            let morph_features = head_morph.morph_features();
+            let mut features = morph_features.iter().filter(|(p, _)| me2_ranges.contains_key(*p));
+            let stretched = features.next().map(|(_, value)| RcCell::clone(value));
+            let nan = features.next().map(|(_, value)| RcCell::clone(value));
+            (stretched.context("no morph feature")?, nan.context("no morph feature")?)
+        };
+        stretched.set(3.5);
+        nan.set(f32::NAN);
+        assert_eq!(head_morph.out_of_range(&me2_ranges).len(), before + 2);
+
+        assert_eq!(head_morph.normalize(&me2_ranges), before + 2);
+        assert!(head_morph.out_of_range(&me2_ranges).is_empty());
+        assert_eq!(stretched.get(), 1.0);
+        assert_eq!(nan.get(), 0.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn convert_head_morph() -> Result<()> {
+        let input = fs::read_to_string("databases/head_morph_slider_db.ron")?;
+        let HeadMorphSliderDb { me2_to_me3, me3_ranges, .. } = ron::from_str(&input)?;
+        let head_morph = HeadMorph::from_gibbed(&fs::read("test/GibbedME2.me2headmorph")?)?;
+        let bones = head_morph.offset_bones().len();
+
+        assert!(head_morph.convert(&me2_to_me3) > 0);
+        assert!(!head_morph.morph_features().contains_key("None"));
+        assert_eq!(head_morph.offset_bones().len(), bones);
+
+        // Renamed in place
+        let conversion = HeadMorphConversion {
+            bones: [("headBase".to_owned(), "head".to_owned())].into_iter().collect(),
+            ..Default::default()
+        };
+        assert_eq!(head_morph.convert(&conversion), 1);
+        assert!(head_morph.offset_bones().contains_key("head"));
+
+        // Then brought within what the ME3 creator can produce
+        head_morph.normalize(&me3_ranges);
+        assert!(head_morph.out_of_range(&me3_ranges).is_empty());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,211 @@
+use super::{
+    character_sheet::CharacterSheet,
+    plot::{BitVec, PlotCategory},
+    statistics::Statistics,
+};
+use crate::save_data::{
+    mass_effect_1::{plot_db::Me1PlotDb, talents::BONUS_TALENTS, Me1SaveGame},
+    mass_effect_1_le::Me1LeSaveData,
+    mass_effect_2::{player::Player as Me2Player, plot_db::Me2PlotDb, Me2LeSaveGame, Me2SaveGame},
+    mass_effect_3::{plot_db::Me3PlotDb, Me3SaveGame},
+};
+
+// Everything the printed report shows, built from the same character sheet and statistics as
+// the header and the statistics export
+pub struct CharacterReport {
+    pub sheet: CharacterSheet,
+    pub statistics: Statistics,
+    // (Power name, rank)
+    pub powers: Vec<(String, f32)>,
+    // Labels of the major choices made, as known to the plot database
+    pub choices: Vec<String>,
+    pub war_assets: Option<WarAssetsSummary>,
+}
+
+pub struct WarAssetsSummary {
+    pub count: usize,
+    pub total_strength: i32,
+}
+
+impl CharacterReport {
+    pub fn me1(save_game: &Me1SaveGame, plot_db: &Me1PlotDb) -> Self {
+        let categories = plot_db.player_crew.values().chain(plot_db.missions.values());
+        CharacterReport {
+            sheet: CharacterSheet::me1(save_game),
+            statistics: Statistics::me1(save_game),
+            // Only the talent ids are in the save
+            powers: Vec::new(),
+            choices: Self::major_choices(categories, &save_game.state().plot().booleans()),
+            war_assets: None,
+        }
+    }
+
+    pub fn me1_le(save_data: &Me1LeSaveData, plot_db: &Me1PlotDb) -> Self {
+        let powers = save_data
+            .player()
+            .complex_talents()
+            .iter()
+            .map(|talent| {
+                let talent = talent.borrow();
+                let id = talent.talent_id();
+                let name = BONUS_TALENTS.iter().find(|(complex, _, _)| *complex == id);
+                let name = match name {
+                    Some((_, _, name)) => name.to_string(),
+                    None => format!("Talent {}", id),
+                };
+                (name, talent.current_rank() as f32)
+            })
+            .filter(|(_, rank)| *rank > 0.0)
+            .collect();
+
+        let categories = plot_db.player_crew.values().chain(plot_db.missions.values());
+        CharacterReport {
+            sheet: CharacterSheet::me1_le(save_data),
+            statistics: Statistics::me1_le(save_data),
+            powers,
+            choices: Self::major_choices(categories, &save_data.plot().booleans()),
+            war_assets: None,
+        }
+    }
+
+    pub fn me2(save_game: &Me2SaveGame, plot_db: &Me2PlotDb) -> Self {
+        let (powers, choices) =
+            Self::me2_player(&save_game.player(), &save_game.plot().booleans(), plot_db);
+        CharacterReport {
+            sheet: CharacterSheet::me2(save_game),
+            statistics: Statistics::me2(save_game),
+            powers,
+            choices,
+            war_assets: None,
+        }
+    }
+
+    pub fn me2_le(save_game: &Me2LeSaveGame, plot_db: &Me2PlotDb) -> Self {
+        let (powers, choices) =
+            Self::me2_player(&save_game.player(), &save_game.plot().booleans(), plot_db);
+        CharacterReport {
+            sheet: CharacterSheet::me2_le(save_game),
+            statistics: Statistics::me2_le(save_game),
+            powers,
+            choices,
+            war_assets: None,
+        }
+    }
+
+    pub fn me3(save_game: &Me3SaveGame, plot_db: &Me3PlotDb) -> Self {
+        let player = save_game.player();
+        let powers = player
+            .powers()
+            .iter()
+            .map(|power| {
+                let power = power.borrow();
+                let name = power.name().to_owned();
+                (name, power.rank())
+            })
+            .collect();
+
+        let war_assets = player.war_assets();
+        let war_assets = WarAssetsSummary {
+            count: war_assets.len(),
+            total_strength: war_assets.values().map(|strength| strength.get()).sum(),
+        };
+
+        let categories = [&plot_db.general]
+            .into_iter()
+            .chain(plot_db.crew.values())
+            .chain(plot_db.romance.values())
+            .chain(plot_db.missions.values())
+            .chain(plot_db.citadel_dlc.values());
+        CharacterReport {
+            sheet: CharacterSheet::me3(save_game),
+            statistics: Statistics::me3(save_game),
+            powers,
+            choices: Self::major_choices(categories, &save_game.plot().booleans()),
+            war_assets: Some(war_assets),
+        }
+    }
+
+    // Powers and choices
+    fn me2_player(
+        player: &Me2Player, booleans: &BitVec, plot_db: &Me2PlotDb,
+    ) -> (Vec<(String, f32)>, Vec<String>) {
+        let powers = player
+            .powers()
+            .iter()
+            .map(|power| {
+                let power = power.borrow();
+                let name = power.name().to_owned();
+                (name, power.rank())
+            })
+            .collect();
+
+        let categories = [&plot_db.player]
+            .into_iter()
+            .chain(plot_db.crew.values())
+            .chain(plot_db.romance.values())
+            .chain(plot_db.missions.values())
+            .chain(plot_db.loyalty_missions.values())
+            .chain(plot_db.dlc.values());
+        (powers, Self::major_choices(categories, booleans))
+    }
+
+    // A major choice is a boolean the database knows to come with a cluster of others,
+    // only the ones made are kept
+    fn major_choices<'a>(
+        categories: impl Iterator<Item = &'a PlotCategory>, booleans: &BitVec,
+    ) -> Vec<String> {
+        let mut choices = Vec::new();
+        for category in categories {
+            for idx in category.clusters.keys() {
+                let is_made = booleans.get(*idx).map(|plot| *plot).unwrap_or_default();
+                match category.booleans.get(idx) {
+                    Some(label) if is_made && !choices.contains(label) => {
+                        choices.push(label.clone())
+                    }
+                    _ => (),
+                }
+            }
+        }
+        choices
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::unreal;
+
+    #[test]
+    fn character_report() -> Result<()> {
+        let input = fs::read_to_string("databases/me2_plot_db.ron")?;
+        let plot_db: Me2PlotDb = ron::from_str(&input)?;
+
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let report = CharacterReport::me2(&save_game, &plot_db);
+        assert_eq!(report.sheet.level, Some(save_game.player().level()));
+        assert_eq!(report.powers.len(), save_game.player().powers().len());
+        assert!(report.war_assets.is_none());
+
+        // A choice once made is in the report
+        let collector_base = &plot_db.missions["Collector Base"];
+        assert!(collector_base.clusters.contains_key(&1831));
+        save_game.plot().booleans.borrow_mut().set(1831, true);
+        let report = CharacterReport::me2(&save_game, &plot_db);
+        assert!(report.choices.contains(&collector_base.booleans[&1831]));
+
+        let input = fs::read_to_string("databases/me3_plot_db.ron")?;
+        let plot_db: Me3PlotDb = ron::from_str(&input)?;
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let report = CharacterReport::me3(&save_game, &plot_db);
+        let war_assets = report.war_assets.unwrap();
+        assert_eq!(war_assets.count, save_game.player().war_assets().len());
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+use super::plot::PlotTable;
+use crate::save_data::{
+    mass_effect_1::{
+        data::{Data, Property},
+        player::Player as Me1Player,
+        Me1SaveGame,
+    },
+    mass_effect_1_le::{player::Me1LeClass, Me1LeSaveData},
+    mass_effect_2::{player::Player as Me2Player, Me2LeSaveGame, Me2SaveGame},
+    mass_effect_3::Me3SaveGame,
+};
+
+// What sums up a character at a glance, `None` when the save doesn't have it where expected
+#[derive(Clone, PartialEq, Default)]
+pub struct CharacterSheet {
+    pub name: Option<String>,
+    pub class: Option<String>,
+    pub level: Option<i32>,
+    pub credits: Option<i32>,
+    pub paragon: Option<i32>,
+    pub renegade: Option<i32>,
+    pub seconds_played: Option<f32>,
+}
+
+impl CharacterSheet {
+    pub fn me1(save_game: &Me1SaveGame) -> Self {
+        let player = save_game.player();
+        let m_player = player.m_player();
+        let first_name =
+            Self::me1_value(&player, m_player, "m_FirstName", |property| match property {
+                Property::Str { string, .. } => Some(string.borrow().clone()),
+                _ => None,
+            });
+        let class = Self::me1_value(&player, m_player, "m_ClassBase", |property| match property {
+            Property::Name { value_name_id, .. } => player.get_name(value_name_id.get()),
+            _ => None,
+        });
+        let current_game = player.current_game();
+        let seconds_played = Self::me1_value(
+            &player,
+            current_game,
+            "m_fPlaythroughTime",
+            |property| match property {
+                Property::Float { value, .. } => Some(value.get()),
+                _ => None,
+            },
+        );
+
+        let inventory = m_player
+            .and_then(|m_player| player.find_object(m_player, "m_Squad"))
+            .and_then(|m_squad| player.find_object(m_squad, "m_Inventory"));
+        let credits = inventory
+            .and_then(|inventory| player.find_int(&inventory.properties, "m_nResourceCredits"));
+
+        let morality = Self::morality(&save_game.state().plot(), 47, 46);
+        CharacterSheet {
+            name: first_name,
+            class: class.map(|class| Self::class_label(&class)),
+            level: player.player_level(),
+            credits: credits.map(|credits| credits.get()),
+            seconds_played,
+            ..morality
+        }
+    }
+
+    pub fn me1_le(save_data: &Me1LeSaveData) -> Self {
+        let player = save_data.player();
+        let class = player.player_class().clone() as usize;
+        let name = player.first_name().to_owned();
+        CharacterSheet {
+            name: Some(name),
+            class: Some(Me1LeClass::variants()[class].to_owned()),
+            level: Some(player.level()),
+            credits: Some(player.credits()),
+            seconds_played: Some(save_data.seconds_played() as f32),
+            ..Self::morality(&save_data.plot(), 47, 46)
+        }
+    }
+
+    pub fn me2(save_game: &Me2SaveGame) -> Self {
+        CharacterSheet {
+            seconds_played: Some(save_game.seconds_played()),
+            ..Self::me2_player(&save_game.player(), &save_game.plot())
+        }
+    }
+
+    pub fn me2_le(save_game: &Me2LeSaveGame) -> Self {
+        CharacterSheet {
+            seconds_played: Some(save_game.seconds_played()),
+            ..Self::me2_player(&save_game.player(), &save_game.plot())
+        }
+    }
+
+    pub fn me3(save_game: &Me3SaveGame) -> Self {
+        let player = save_game.player();
+        let plot = save_game.plot();
+        let integers = plot.integers();
+        let (name, class) = (player.first_name().to_owned(), player.class_name().to_owned());
+        CharacterSheet {
+            name: Some(name),
+            class: Some(Self::class_label(&class)),
+            level: Some(player.level()),
+            credits: Some(player.credits()),
+            paragon: integers.get(&10159).map(|paragon| paragon.get()),
+            renegade: integers.get(&10160).map(|renegade| renegade.get()),
+            seconds_played: Some(save_game.seconds_played()),
+        }
+    }
+
+    fn me2_player(player: &Me2Player, plot: &PlotTable) -> Self {
+        CharacterSheet {
+            name: Some(player.first_name().to_owned()),
+            class: Some(Self::class_label(&player.class_name())),
+            level: Some(player.level()),
+            credits: Some(player.credits()),
+            ..Self::morality(plot, 2, 3)
+        }
+    }
+
+    fn me1_value<T>(
+        player: &Me1Player, data: Option<&Data>, name: &str,
+        value: impl FnOnce(&Property) -> Option<T>,
+    ) -> Option<T> {
+        let property = player.find_property(&data?.properties, name)?;
+        let value = value(&property.borrow());
+        value
+    }
+
+    fn morality(plot: &PlotTable, paragon: usize, renegade: usize) -> Self {
+        let integers = plot.integers();
+        CharacterSheet {
+            paragon: integers.get(paragon).map(|paragon| paragon.get()),
+            renegade: integers.get(renegade).map(|renegade| renegade.get()),
+            ..Default::default()
+        }
+    }
+
+    // `SFXGame.SFXPawn_PlayerAdept` in ME2 and ME3, `BIO_PARTY_MEMBER_CLASS_BASE_ADEPT` in ME1
+    fn class_label(class_name: &str) -> String {
+        let class = class_name.rsplit('_').next().unwrap_or_default();
+        let class = class.strip_prefix("Player").unwrap_or(class);
+        let mut chars = class.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+            None => String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::unreal;
+
+    #[test]
+    fn character_sheets() -> Result<()> {
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let sheet = CharacterSheet::me1(&me1_save_game);
+        assert_eq!(sheet.name.as_deref(), Some("Clare"));
+        assert_eq!(sheet.class.as_deref(), Some("Adept"));
+        assert_eq!(sheet.level, Some(60));
+        assert!(sheet.credits.is_some() && sheet.seconds_played.is_some());
+
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let sheet = CharacterSheet::me2(&me2_save_game);
+        assert_eq!(sheet.level, Some(me2_save_game.player().level()));
+        assert!(sheet.class.map(|class| !class.contains('.')).unwrap_or(false));
+
+        // Follows the edits
+        me2_save_game.player().credits.set(123_456);
+        assert_eq!(CharacterSheet::me2(&me2_save_game).credits, Some(123_456));
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let sheet = CharacterSheet::me3(&me3_save_game);
+        assert_eq!(sheet.level, Some(me3_save_game.player().level()));
+        assert!(sheet.class.map(|class| !class.contains('.')).unwrap_or(false));
+        Ok(())
+    }
+
+    #[test]
+    fn class_label() {
+        assert_eq!(CharacterSheet::class_label("SFXGame.SFXPawn_PlayerVanguard"), "Vanguard");
+        assert_eq!(CharacterSheet::class_label("BIO_PARTY_MEMBER_CLASS_BASE_ADEPT"), "Adept");
+        assert_eq!(CharacterSheet::class_label(""), "");
+    }
+}
@@ -1,6 +1,13 @@
 pub mod appearance;
+pub mod character_report;
+pub mod character_sheet;
 pub mod player;
 pub mod plot;
+pub mod plot_coverage;
+pub mod plot_db_version;
+pub mod power_points;
+pub mod quick_plot;
+pub mod statistics;
 
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
@@ -43,10 +50,36 @@ impl serde::Serialize for EndGameState {
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUi)]
 pub struct SaveTimeStamp {
-    seconds_since_midnight: i32,
-    day: i32,
-    month: i32,
-    year: i32,
+    pub seconds_since_midnight: i32,
+    pub day: i32,
+    pub month: i32,
+    pub year: i32,
+}
+
+impl SaveTimeStamp {
+    // Crashes can leave garbage in there, which the games display as is or even refuse to load
+    pub fn is_valid(&self) -> bool {
+        let (year, month, day) = (self.year(), self.month(), self.day());
+
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+            2 => 28,
+            _ => return false,
+        };
+
+        (2007..=9999).contains(&year)
+            && (1..=days_in_month).contains(&day)
+            && (0..86400).contains(&self.seconds_since_midnight())
+    }
+
+    pub fn set(&mut self, year: i32, month: i32, day: i32, seconds_since_midnight: i32) {
+        self.set_year(year);
+        self.set_month(month);
+        self.set_day(day);
+        self.set_seconds_since_midnight(seconds_since_midnight);
+    }
 }
 
 #[rcize_fields]
@@ -107,3 +140,28 @@ pub struct Door {
     current_state: u8,
     old_state: u8,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::save_data::RcCell;
+
+    #[test]
+    fn validate_timestamp() {
+        let timestamp = |year, month, day, seconds_since_midnight| SaveTimeStamp {
+            seconds_since_midnight: RcCell::new(seconds_since_midnight),
+            day: RcCell::new(day),
+            month: RcCell::new(month),
+            year: RcCell::new(year),
+        };
+
+        assert!(timestamp(2021, 5, 14, 43200).is_valid());
+        assert!(timestamp(2020, 2, 29, 0).is_valid());
+        assert!(!timestamp(2021, 2, 29, 0).is_valid());
+        assert!(!timestamp(2021, 13, 1, 0).is_valid());
+        assert!(!timestamp(2021, 5, 0, 0).is_valid());
+        assert!(!timestamp(2021, 5, 14, -1).is_valid());
+        assert!(!timestamp(2021, 5, 14, 86400).is_valid());
+        assert!(!timestamp(0, 1, 1, 0).is_valid());
+    }
+}
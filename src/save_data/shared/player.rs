@@ -19,10 +19,108 @@ pub enum Notoriety {
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, RawUi)]
 pub struct WeaponLoadout {
-    assault_rifle: String,
-    shotgun: String,
-    sniper_rifle: String,
-    submachine_gun: String,
-    pistol: String,
-    heavy_weapon: String,
+    pub assault_rifle: String,
+    pub shotgun: String,
+    pub sniper_rifle: String,
+    pub submachine_gun: String,
+    pub pistol: String,
+    pub heavy_weapon: String,
+}
+
+// As long as the character creator lets a name be
+pub const MAX_FIRST_NAME_LEN: usize = 15;
+// Windows refuses them in the name of the career folder, made from the first name
+const FILE_NAME_FORBIDDEN: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+// What the game does with a first name it couldn't have been given
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NameProblem {
+    Empty,
+    TooLong(usize),
+    // The fonts of the game only cover Latin-1, the rest is drawn as boxes
+    Unsupported(char),
+    FileNameForbidden(char),
+    SurroundingSpaces,
+}
+
+impl NameProblem {
+    pub fn check(name: &str) -> Vec<NameProblem> {
+        let mut problems = Vec::new();
+        if name.trim().is_empty() {
+            problems.push(NameProblem::Empty);
+            return problems;
+        }
+
+        let len = name.chars().count();
+        if len > MAX_FIRST_NAME_LEN {
+            problems.push(NameProblem::TooLong(len));
+        }
+        if name.trim() != name {
+            problems.push(NameProblem::SurroundingSpaces);
+        }
+        for c in name.chars() {
+            let problem = if FILE_NAME_FORBIDDEN.contains(&c) {
+                NameProblem::FileNameForbidden(c)
+            } else if !is_supported(c) {
+                NameProblem::Unsupported(c)
+            } else {
+                continue;
+            };
+            if !problems.contains(&problem) {
+                problems.push(problem);
+            }
+        }
+        problems
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            NameProblem::Empty => String::from("The game expects a first name"),
+            NameProblem::TooLong(len) => format!(
+                "{} characters, the character creator stops at {}, the game UI may cut it",
+                len, MAX_FIRST_NAME_LEN
+            ),
+            NameProblem::Unsupported(c) => format!("`{}` is drawn as a box by the game", c),
+            NameProblem::FileNameForbidden(c) => {
+                format!("`{}` can't be in the name of the career folder", c)
+            }
+            NameProblem::SurroundingSpaces => String::from("Spaces around the name"),
+        }
+    }
+}
+
+fn is_supported(c: char) -> bool {
+    matches!(c, ' '..='~' | '\u{a0}'..='\u{ff}')
+}
+
+// The name without what the game can't handle, cut to the length of the character creator
+pub fn fix_first_name(name: &str) -> String {
+    let name: String =
+        name.chars().filter(|&c| is_supported(c) && !FILE_NAME_FORBIDDEN.contains(&c)).collect();
+    name.trim().chars().take(MAX_FIRST_NAME_LEN).collect::<String>().trim_end().to_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn first_name() {
+        assert!(NameProblem::check("John").is_empty());
+        assert!(NameProblem::check("Jöhn-Érik O'Neil").len() == 1);
+        assert_eq!(NameProblem::check("  "), [NameProblem::Empty]);
+        assert_eq!(
+            NameProblem::check(" Jo:hn✓ "),
+            [
+                NameProblem::SurroundingSpaces,
+                NameProblem::FileNameForbidden(':'),
+                NameProblem::Unsupported('✓'),
+            ]
+        );
+
+        assert_eq!(fix_first_name(" Jo:hn✓ "), "John");
+        assert_eq!(fix_first_name("Jöhn-Érik O'Neil"), "Jöhn-Érik O'Nei");
+        assert!(NameProblem::check(&fix_first_name("Jöhn-Érik O'Neil")).is_empty());
+        assert_eq!(fix_first_name("Abcdefghijklmn opq"), "Abcdefghijklmn");
+    }
 }
@@ -3,7 +3,7 @@ use bitvec::prelude::*;
 use indexmap::IndexMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Deref, DerefMut, Clone)]
+#[derive(Deref, DerefMut, Clone, Default)]
 pub struct BitVec(bitvec::vec::BitVec<u32, Lsb0>);
 
 impl<'de> Deserialize<'de> for BitVec {
@@ -27,6 +27,23 @@ impl serde::Serialize for BitVec {
     }
 }
 
+impl BitVec {
+    // Grows to the highest id, returns how many plots had another value
+    pub fn set_many(&mut self, ids: impl IntoIterator<Item = usize>, value: bool) -> usize {
+        let mut changed = 0;
+        for id in ids {
+            if id >= self.len() {
+                self.resize(id + 1, false);
+            }
+            if self[id] != value {
+                self.set(id, value);
+                changed += 1;
+            }
+        }
+        changed
+    }
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone)]
 pub struct PlotTable {
@@ -78,6 +95,74 @@ pub struct PlotCodexPage {
 pub struct PlotCategory {
     pub booleans: IndexMap<usize, String>,
     pub integers: IndexMap<usize, String>,
+    // Booleans the game sets along with a major choice, with the value they get
+    #[serde(default)]
+    pub clusters: IndexMap<usize, IndexMap<usize, bool>>,
+    // Plots known to crash or soft-lock the game when set out of context, with why
+    #[serde(default)]
+    pub dangerous: IndexMap<usize, String>,
+    // Integers that only ever take two or three values, edited as a check box or a tri-state
+    #[serde(default)]
+    pub as_bool: IndexMap<usize, IntAsBool>,
+}
+
+// The value an integer takes when unchecked, checked and, for a tri-state, undecided
+#[derive(Deserialize, Clone, PartialEq, Eq)]
+pub struct IntAsBool {
+    #[serde(default)]
+    pub off: i32,
+    #[serde(default = "IntAsBool::default_on")]
+    pub on: i32,
+    #[serde(default)]
+    pub unset: Option<i32>,
+}
+
+impl IntAsBool {
+    fn default_on() -> i32 {
+        1
+    }
+
+    // `None` for a value out of the mapping, `Some(None)` for undecided
+    pub fn state(&self, value: i32) -> Option<Option<bool>> {
+        match value {
+            value if value == self.on => Some(Some(true)),
+            value if value == self.off => Some(Some(false)),
+            value if Some(value) == self.unset => Some(None),
+            _ => None,
+        }
+    }
+
+    pub fn value(&self, state: Option<bool>) -> i32 {
+        match state {
+            Some(true) => self.on,
+            Some(false) => self.off,
+            None => self.unset.unwrap_or(self.off),
+        }
+    }
+}
+
+impl PlotCategory {
+    pub fn cluster(&self, idx: usize) -> impl Iterator<Item = (usize, &str, bool)> {
+        self.clusters.get(&idx).into_iter().flatten().filter_map(|(&idx, &value)| {
+            self.booleans.get(&idx).map(|label| (idx, label.as_str(), value))
+        })
+    }
+
+    pub fn dangerous(&self, idx: usize) -> Option<&str> {
+        self.dangerous.get(&idx).map(String::as_str)
+    }
+
+    // Every dangerous plot is one of the category
+    pub fn dangerous_are_known(&self) -> bool {
+        self.dangerous
+            .keys()
+            .all(|idx| self.booleans.contains_key(idx) || self.integers.contains_key(idx))
+    }
+
+    // Every integer edited as a boolean is one of the category
+    pub fn as_bool_are_known(&self) -> bool {
+        self.as_bool.keys().all(|idx| self.integers.contains_key(idx))
+    }
 }
 
 #[derive(Deserialize, Clone, PartialEq, Eq)]
@@ -86,3 +171,48 @@ pub struct RawPlotDb {
     pub integers: IndexMap<usize, String>,
     pub floats: IndexMap<usize, String>,
 }
+
+#[derive(Deserialize, Clone, PartialEq, Eq)]
+pub struct SquadMemberFlags {
+    pub in_party: usize,
+    pub in_squad: usize,
+    pub selectable: usize,
+    #[serde(default)]
+    pub dead: Option<usize>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn int_as_bool() {
+        let check_box: IntAsBool = ron::from_str("(on: 3)").unwrap();
+        assert_eq!(check_box.state(0), Some(Some(false)));
+        assert_eq!(check_box.state(3), Some(Some(true)));
+        assert_eq!(check_box.state(1), None);
+        assert_eq!(check_box.value(Some(true)), 3);
+
+        let tri_state: IntAsBool = ron::from_str("(unset: Some(0), off: 1, on: 2)").unwrap();
+        assert_eq!(tri_state.state(0), Some(None));
+        assert_eq!(tri_state.state(1), Some(Some(false)));
+        assert_eq!(tri_state.value(None), 0);
+        assert_eq!(tri_state.value(Some(true)), 2);
+    }
+
+    #[test]
+    fn set_many() {
+        let mut booleans = BitVec(bitvec::vec::BitVec::repeat(false, 64));
+        booleans.set(10, true);
+
+        assert_eq!(booleans.set_many(8..=12, true), 4);
+        assert_eq!(booleans.iter_ones().collect::<Vec<_>>(), [8, 9, 10, 11, 12]);
+
+        assert_eq!(booleans.set_many([9, 11, 40], false), 2);
+        assert_eq!(booleans.iter_ones().collect::<Vec<_>>(), [8, 10, 12]);
+
+        // Past the end
+        assert_eq!(booleans.set_many(100..=101, true), 2);
+        assert_eq!(booleans.len(), 102);
+    }
+}
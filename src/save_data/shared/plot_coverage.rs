@@ -0,0 +1,81 @@
+use std::collections::BTreeMap;
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use super::plot::RawPlotDb;
+
+const NEIGHBORHOOD_SIZE: usize = 100;
+
+// The plots set in a save that no database knows of yet, for the database contributors.
+// Everything is sorted by id so two reports can be diffed.
+#[derive(Serialize, Clone)]
+pub struct PlotCoverage {
+    pub game: &'static str,
+    pub booleans: Coverage,
+    pub integers: Coverage,
+    pub floats: Coverage,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct Coverage {
+    pub set: usize,
+    pub unknown: usize,
+    // First id of each hundred, unknown ids set in it
+    pub neighborhoods: BTreeMap<usize, Vec<usize>>,
+}
+
+impl Coverage {
+    fn new(set: impl IntoIterator<Item = usize>, known: &IndexMap<usize, String>) -> Self {
+        let mut coverage = Coverage::default();
+        for idx in set {
+            coverage.set += 1;
+            if !known.contains_key(&idx) {
+                coverage.unknown += 1;
+                let start = idx - idx % NEIGHBORHOOD_SIZE;
+                coverage.neighborhoods.entry(start).or_default().push(idx);
+            }
+        }
+        coverage.neighborhoods.values_mut().for_each(|ids| ids.sort_unstable());
+        coverage
+    }
+}
+
+impl PlotCoverage {
+    pub fn new(
+        game: &'static str, plot_db: &RawPlotDb, booleans: impl IntoIterator<Item = usize>,
+        integers: impl IntoIterator<Item = usize>, floats: impl IntoIterator<Item = usize>,
+    ) -> Self {
+        PlotCoverage {
+            game,
+            booleans: Coverage::new(booleans, &plot_db.booleans),
+            integers: Coverage::new(integers, &plot_db.integers),
+            floats: Coverage::new(floats, &plot_db.floats),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn group_unknown_plots() {
+        let plot_db = RawPlotDb {
+            booleans: [(12, String::from("Known"))].into_iter().collect(),
+            integers: IndexMap::new(),
+            floats: IndexMap::new(),
+        };
+
+        let coverage =
+            PlotCoverage::new("Mass Effect 2", &plot_db, [12, 199, 13, 250], [7], Vec::new());
+
+        assert_eq!(coverage.booleans.set, 4);
+        assert_eq!(coverage.booleans.unknown, 3);
+        let neighborhoods: Vec<_> = coverage.booleans.neighborhoods.into_iter().collect();
+        assert_eq!(neighborhoods, [(0, vec![13]), (100, vec![199]), (200, vec![250])]);
+
+        assert_eq!(coverage.integers.neighborhoods.get(&0), Some(&vec![7]));
+        assert_eq!(coverage.floats.set, 0);
+    }
+}
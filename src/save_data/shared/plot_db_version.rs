@@ -0,0 +1,93 @@
+use anyhow::{bail, Context, Result};
+use ron::{Map, Number, Value};
+use serde::de::DeserializeOwned;
+
+// What brings a plot database of version `idx + 1` to the next one, as parsed before it is
+// deserialized. New ones go at the end, the files written before them still load.
+type Migration = fn(&mut Map) -> Result<()>;
+const MIGRATIONS: &[Migration] = &[];
+
+/// Version of the plot databases this editor reads, given by the `version` field that opens
+/// them. A file without one is a version 1.
+pub const PLOT_DB_VERSION: usize = MIGRATIONS.len() + 1;
+
+/// Deserializes a plot database, known plots or raw plot labels, written for this version of
+/// the editor or an older one
+pub fn from_ron<T: DeserializeOwned>(input: &str) -> Result<T> {
+    migrate(ron::from_str(input)?, MIGRATIONS)
+}
+
+fn migrate<T: DeserializeOwned>(db: Value, migrations: &[Migration]) -> Result<T> {
+    let mut db = match db {
+        Value::Map(db) => db,
+        _ => bail!("Expected a database, `Me2PlotDb(...)`"),
+    };
+
+    let version = match db.remove(&Value::String("version".to_owned())) {
+        None => 1,
+        Some(Value::Number(Number::Integer(version))) if version >= 1 => version as usize,
+        Some(_) => bail!("Expected a version number starting at 1"),
+    };
+    let latest = migrations.len() + 1;
+    if version > latest {
+        bail!(
+            "This database is of version {}, newer than the version {} this editor reads",
+            version,
+            latest
+        );
+    }
+
+    for (idx, migration) in migrations.iter().enumerate().skip(version - 1) {
+        migration(&mut db)
+            .with_context(|| format!("Failed to migrate the database to version {}", idx + 2))?;
+    }
+    Ok(Value::Map(db).into_rust()?)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::save_data::shared::plot::RawPlotDb;
+
+    // Once named `labels`, say
+    fn rename_labels(db: &mut Map) -> Result<()> {
+        let labels = db.remove(&Value::String("labels".to_owned())).context("no labels")?;
+        db.insert(Value::String("booleans".to_owned()), labels);
+        Ok(())
+    }
+
+    #[test]
+    fn migrate_plot_db() -> Result<()> {
+        let migrations: &[Migration] = &[rename_labels];
+        let parse = |input: &str| ron::from_str::<Value>(input);
+
+        // Older files are brought to the latest version
+        let v1 = "RawPlotDb(labels: { 1: \"One\" }, integers: {}, floats: {})";
+        let db: RawPlotDb = migrate(parse(v1)?, migrations)?;
+        assert_eq!(db.booleans[&1], "One");
+
+        let v2 = "RawPlotDb(version: 2, booleans: { 1: \"One\" }, integers: {}, floats: {})";
+        let db: RawPlotDb = migrate(parse(v2)?, migrations)?;
+        assert_eq!(db.booleans[&1], "One");
+
+        // Newer ones can't be
+        let v3 = "RawPlotDb(version: 3, booleans: {}, integers: {}, floats: {})";
+        assert!(migrate::<RawPlotDb>(parse(v3)?, migrations).is_err());
+        assert!(migrate::<RawPlotDb>(parse(v2)?, &[]).is_err());
+
+        // The bundled databases are of this version
+        for entry in fs::read_dir("databases")? {
+            let path = entry?.path();
+            if path.to_string_lossy().ends_with("plot_db.ron") {
+                let input = fs::read_to_string(&path)?;
+                let version = input.lines().nth(1).unwrap_or_default().trim();
+                assert_eq!(version, format!("version: {},", PLOT_DB_VERSION), "{:?}", path);
+            }
+        }
+        Ok(())
+    }
+}
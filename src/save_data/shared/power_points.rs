@@ -0,0 +1,200 @@
+use indexmap::IndexMap;
+
+use crate::save_data::{
+    mass_effect_2::{
+        player::{Player as Me2Player, Power as Me2Power},
+        squad::Henchman as Me2Henchman,
+    },
+    mass_effect_3::{
+        player::{Player as Me3Player, Power as Me3Power},
+        squad::Henchman as Me3Henchman,
+    },
+    RcCell, RcRef,
+};
+
+// How many points a game hands out for the powers, a rank costs its number in both games: 1 for
+// the first one, 4 for the fourth
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PointRules {
+    // Had at level 1
+    start: i32,
+    // Earned by each level up to the first of each pair, from the end of the previous one
+    per_level: &'static [(i32, i32)],
+    max_rank: i32,
+}
+
+impl PointRules {
+    // `51` points at level 30
+    pub const ME2_SHEPARD: PointRules =
+        PointRules { start: 3, per_level: &[(20, 2), (30, 1)], max_rank: 4 };
+    pub const ME2_HENCHMAN: PointRules =
+        PointRules { start: 2, per_level: &[(30, 1)], max_rank: 4 };
+    // `181` points at level 60, the squad levels up along with Shepard
+    pub const ME3: PointRules =
+        PointRules { start: 3, per_level: &[(30, 2), (60, 4)], max_rank: 6 };
+
+    pub fn earned(&self, level: i32) -> i32 {
+        let mut earned = self.start;
+        let mut from = 1;
+        for &(to, points) in self.per_level {
+            let levels = level.min(to) - from;
+            if levels <= 0 {
+                break;
+            }
+            earned += levels * points;
+            from = to;
+        }
+        earned
+    }
+
+    pub fn spent(&self, rank: f32) -> i32 {
+        let rank = (rank.max(0.0) as i32).min(self.max_rank);
+        rank * (rank + 1) / 2
+    }
+}
+
+// The points of a character, the game resets all its powers on load if it has more than earned
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PowerPoints {
+    pub spent: i32,
+    pub unspent: i32,
+    pub earned: i32,
+}
+
+impl PowerPoints {
+    pub fn count(rules: PointRules, level: i32, ranks: &[f32], unspent: i32) -> Self {
+        let spent = ranks.iter().map(|&rank| rules.spent(rank)).sum();
+        PowerPoints { spent, unspent, earned: rules.earned(level) }
+    }
+
+    pub fn excess(&self) -> i32 {
+        (self.spent + self.unspent - self.earned).max(0)
+    }
+
+    // Shepard first, then the squad by tag
+    pub fn me2(player: &Me2Player, squad: &[RcRef<Me2Henchman>]) -> IndexMap<String, Self> {
+        fn ranks(powers: &[RcRef<Me2Power>]) -> Vec<f32> {
+            powers.iter().map(|power| power.borrow().rank()).collect()
+        }
+
+        let mut points = IndexMap::new();
+        let shepard = Self::count(
+            PointRules::ME2_SHEPARD,
+            player.level(),
+            &ranks(&player.powers()),
+            player.talent_points(),
+        );
+        points.insert(String::from("Shepard"), shepard);
+        for henchman in squad {
+            let henchman = henchman.borrow();
+            let henchman_points = Self::count(
+                PointRules::ME2_HENCHMAN,
+                henchman.character_level(),
+                &ranks(&henchman.powers()),
+                henchman.talent_points(),
+            );
+            points.insert(henchman.tag().to_owned(), henchman_points);
+        }
+        points
+    }
+
+    pub fn me3(player: &Me3Player, squad: &[RcRef<Me3Henchman>]) -> IndexMap<String, Self> {
+        fn ranks(powers: &[RcRef<Me3Power>]) -> Vec<f32> {
+            powers.iter().map(|power| power.borrow().rank()).collect()
+        }
+
+        let mut points = IndexMap::new();
+        let shepard = Self::count(
+            PointRules::ME3,
+            player.level(),
+            &ranks(&player.powers()),
+            player.talent_points(),
+        );
+        points.insert(String::from("Shepard"), shepard);
+        for henchman in squad {
+            let henchman = henchman.borrow();
+            let henchman_points = Self::count(
+                PointRules::ME3,
+                henchman.character_level(),
+                &ranks(&henchman.powers()),
+                henchman.talent_points(),
+            );
+            points.insert(henchman.tag().to_owned(), henchman_points);
+        }
+        points
+    }
+}
+
+// Takes the excess out of the unspent points, then out of the highest ranks if that isn't
+// enough. What a lowered rank gives back is left unspent.
+pub fn refund_excess(rules: PointRules, level: i32, ranks: &[RcCell<f32>], unspent: &RcCell<i32>) {
+    let earned = rules.earned(level);
+    let spent =
+        |ranks: &[RcCell<f32>]| -> i32 { ranks.iter().map(|rank| rules.spent(rank.get())).sum() };
+
+    if spent(ranks) <= earned {
+        unspent.set(unspent.get().min(earned - spent(ranks)));
+        return;
+    }
+
+    while spent(ranks) > earned {
+        let highest = ranks
+            .iter()
+            .map(|rank| (rank, (rank.get().max(0.0) as i32).min(rules.max_rank)))
+            .filter(|(_, rank)| *rank > 0)
+            .max_by_key(|(_, rank)| *rank);
+        match highest {
+            Some((cell, rank)) => cell.set((rank - 1) as f32),
+            None => break,
+        }
+    }
+    unspent.set((earned - spent(ranks)).max(0));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn refund_excess_points() {
+        assert_eq!(PointRules::ME2_SHEPARD.earned(1), 3);
+        assert_eq!(PointRules::ME2_SHEPARD.earned(30), 51);
+        assert_eq!(PointRules::ME2_HENCHMAN.earned(30), 31);
+        assert_eq!(PointRules::ME3.earned(60), 181);
+        // Above the level cap, nothing more
+        assert_eq!(PointRules::ME3.earned(99), 181);
+        assert_eq!(PointRules::ME3.spent(6.0), 21);
+        assert_eq!(PointRules::ME3.spent(9.0), 21);
+
+        let rules = PointRules::ME2_SHEPARD;
+        let ranks = |ranks: &[f32]| -> Vec<RcCell<f32>> {
+            ranks.iter().map(|&rank| RcCell::new(rank)).collect()
+        };
+        let count = |ranks: &[RcCell<f32>], unspent: &RcCell<i32>| {
+            let ranks: Vec<f32> = ranks.iter().map(RcCell::get).collect();
+            PowerPoints::count(rules, 10, &ranks, unspent.get())
+        };
+
+        // Level 10 gives 21 points
+        let fine = ranks(&[4.0, 2.0, 1.0]);
+        let unspent = RcCell::new(7);
+        assert_eq!(count(&fine, &unspent).excess(), 0);
+
+        // Too many unspent points, the ranks stay
+        let unspent = RcCell::new(12);
+        assert_eq!(count(&fine, &unspent).excess(), 5);
+        refund_excess(rules, 10, &fine, &unspent);
+        assert_eq!(unspent.get(), 7);
+        assert_eq!(count(&fine, &unspent).excess(), 0);
+
+        // Too many ranks, the highest go first
+        let maxed = ranks(&[4.0, 4.0, 3.0]);
+        let unspent = RcCell::new(2);
+        assert_eq!(count(&maxed, &unspent).excess(), 7);
+        refund_excess(rules, 10, &maxed, &unspent);
+        let lowered: Vec<f32> = maxed.iter().map(RcCell::get).collect();
+        assert_eq!(lowered, [3.0, 3.0, 3.0]);
+        assert_eq!(unspent.get(), 3);
+        assert_eq!(count(&maxed, &unspent).excess(), 0);
+    }
+}
@@ -0,0 +1,105 @@
+use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
+
+use crate::{
+    save_data::{
+        fixup::{Fixup, Fixups},
+        RcRef,
+    },
+    unreal,
+};
+
+// A save of which only the plot table is parsed, for a quick plot edit.
+// What comes before and after it is written back as it was read.
+#[derive(Clone)]
+pub struct QuickPlotSave<P> {
+    head: Vec<u8>,
+    pub plot: RcRef<P>,
+    tail: Vec<u8>,
+}
+
+impl<P> QuickPlotSave<P> {
+    // `H` being every field that comes before the plot table
+    pub fn from_bytes<'de, H>(input: &'de [u8], is_le: bool) -> unreal::Result<Self>
+    where
+        H: Deserialize<'de>,
+        P: Deserialize<'de>,
+    {
+        // The checksum is computed again on save
+        let input = &input[..input.len().saturating_sub(4)];
+
+        let (_, head_len) = unreal::Deserializer::from_bytes_prefix::<H>(input, is_le)?;
+        let (head, rest) = input.split_at(head_len);
+        let (plot, plot_len) = unreal::Deserializer::from_bytes_prefix(rest, is_le)?;
+        let tail = &rest[plot_len..];
+
+        Ok(QuickPlotSave { head: head.to_vec(), plot, tail: tail.to_vec() })
+    }
+}
+
+// Only ME2 and ME3 saves are quick edited, both end with a checksum
+impl<P> Fixups for QuickPlotSave<P> {
+    const FIXUPS: &'static [Fixup] = &[Fixup::AppendedChecksum];
+}
+
+impl<P: Serialize> Serialize for QuickPlotSave<P> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct Bytes<'a>(&'a [u8]);
+
+        impl Serialize for Bytes<'_> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_bytes(self.0)
+            }
+        }
+
+        let mut s = serializer.serialize_struct("QuickPlotSave", 3)?;
+        s.serialize_field("head", &Bytes(&self.head))?;
+        s.serialize_field("plot", &self.plot)?;
+        s.serialize_field("tail", &Bytes(&self.tail))?;
+        s.end()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::save_data::{
+        fixup,
+        mass_effect_2::Me2QuickHead,
+        mass_effect_3::{plot::PlotTable as Me3PlotTable, Me3QuickHead},
+        shared::plot::PlotTable,
+    };
+
+    #[test]
+    fn keep_everything_but_the_plot() -> Result<()> {
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let me2: QuickPlotSave<PlotTable> =
+            QuickPlotSave::from_bytes::<Me2QuickHead>(&input, true)?;
+        let output = unreal::Serializer::to_vec(&me2)?;
+        assert!(output == input[..input.len() - 4]);
+        assert!(fixup::to_bytes(&me2, false)? == input);
+
+        let input = fs::read("test/ME3Save360.xbsav")?;
+        let me3: QuickPlotSave<Me3PlotTable> =
+            QuickPlotSave::from_bytes::<Me3QuickHead>(&input, false)?;
+        me3.plot.borrow().booleans.borrow_mut().set(42, true);
+        let mut output = unreal::Serializer::to_be_vec(&me3)?;
+        assert_eq!(output.len(), input.len() - 4);
+
+        output.extend([0; 4]);
+        let reread: QuickPlotSave<Me3PlotTable> =
+            QuickPlotSave::from_bytes::<Me3QuickHead>(&output, false)?;
+        assert!(reread.plot.borrow().booleans.borrow()[42]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use super::{plot::PlotTable, EndGameState};
+use crate::save_data::{
+    mass_effect_1::Me1SaveGame,
+    mass_effect_1_le::{player::Me1LeClass, Me1LeSaveData},
+    mass_effect_2::{self, Me2LeSaveGame, Me2SaveGame},
+    mass_effect_3::{self, plot::PlotTable as Me3PlotTable, Me3SaveGame},
+    RcCell,
+};
+
+// Only what the community statistics need, nothing that could identify the player
+// (names, identity code, head morph, dates, file paths...)
+#[derive(Serialize)]
+pub struct Statistics {
+    pub game: &'static str,
+    pub class: Option<String>,
+    pub level: Option<i32>,
+    pub difficulty: Option<&'static str>,
+    pub end_game_state: Option<&'static str>,
+    pub plot: PlotStatistics,
+    // ME1 choices imported in ME2
+    pub me1_plot: Option<PlotStatistics>,
+}
+
+// Plot ids rather than labels so any plot database can read them, unset values are left out
+#[derive(Serialize)]
+pub struct PlotStatistics {
+    pub booleans: Vec<usize>,
+    pub integers: BTreeMap<usize, i32>,
+    pub floats: BTreeMap<usize, f32>,
+}
+
+impl From<&PlotTable> for PlotStatistics {
+    fn from(plot: &PlotTable) -> Self {
+        let booleans = plot.booleans().iter_ones().collect();
+        let integers = plot
+            .integers()
+            .iter()
+            .map(RcCell::get)
+            .enumerate()
+            .filter(|(_, value)| *value != 0)
+            .collect();
+        let floats = plot
+            .floats()
+            .iter()
+            .map(RcCell::get)
+            .enumerate()
+            .filter(|(_, value)| *value != 0.0)
+            .collect();
+        PlotStatistics { booleans, integers, floats }
+    }
+}
+
+impl From<&Me3PlotTable> for PlotStatistics {
+    fn from(plot: &Me3PlotTable) -> Self {
+        let booleans = plot.booleans().iter_ones().collect();
+        let integers = plot
+            .integers()
+            .iter()
+            .map(|(&id, value)| (id as usize, value.get()))
+            .filter(|(_, value)| *value != 0)
+            .collect();
+        let floats = plot
+            .floats()
+            .iter()
+            .map(|(&id, value)| (id as usize, value.get()))
+            .filter(|(_, value)| *value != 0.0)
+            .collect();
+        PlotStatistics { booleans, integers, floats }
+    }
+}
+
+impl Statistics {
+    pub fn me1(save_game: &Me1SaveGame) -> Self {
+        Statistics {
+            game: "Mass Effect 1",
+            class: None,
+            level: None,
+            difficulty: None,
+            end_game_state: None,
+            plot: (&*save_game.state().plot()).into(),
+            me1_plot: None,
+        }
+    }
+
+    pub fn me1_le(save_data: &Me1LeSaveData) -> Self {
+        let player = save_data.player();
+        let class = player.player_class().clone() as usize;
+        Statistics {
+            game: "Mass Effect 1 Legendary",
+            class: Some(Me1LeClass::variants()[class].to_owned()),
+            level: Some(player.level()),
+            difficulty: None,
+            end_game_state: None,
+            plot: (&*save_data.plot()).into(),
+            me1_plot: None,
+        }
+    }
+
+    pub fn me2(save_game: &Me2SaveGame) -> Self {
+        let player = save_game.player();
+        let class = player.class_name().to_owned();
+        Statistics {
+            game: "Mass Effect 2",
+            class: Some(class),
+            level: Some(player.level()),
+            difficulty: Some(Self::me2_difficulty(&save_game.difficulty())),
+            end_game_state: Some(Self::end_game_state(&save_game.end_game_state())),
+            plot: (&*save_game.plot()).into(),
+            me1_plot: Some((&*save_game.me1_plot()).into()),
+        }
+    }
+
+    pub fn me2_le(save_game: &Me2LeSaveGame) -> Self {
+        let player = save_game.player();
+        let class = player.class_name().to_owned();
+        Statistics {
+            game: "Mass Effect 2 Legendary",
+            class: Some(class),
+            level: Some(player.level()),
+            difficulty: Some(Self::me2_difficulty(&save_game.difficulty())),
+            end_game_state: Some(Self::end_game_state(&save_game.end_game_state())),
+            plot: (&*save_game.plot()).into(),
+            me1_plot: Some((&*save_game.me1_plot()).into()),
+        }
+    }
+
+    pub fn me3(save_game: &Me3SaveGame) -> Self {
+        let player = save_game.player();
+        let class = player.class_name().to_owned();
+        let difficulty = save_game.difficulty().clone() as usize;
+        Statistics {
+            game: "Mass Effect 3",
+            class: Some(class),
+            level: Some(player.level()),
+            difficulty: Some(mass_effect_3::Difficulty::variants()[difficulty]),
+            end_game_state: Some(Self::end_game_state(&save_game.end_game_state())),
+            plot: (&*save_game.plot()).into(),
+            me1_plot: None,
+        }
+    }
+
+    fn me2_difficulty(difficulty: &mass_effect_2::Difficulty) -> &'static str {
+        mass_effect_2::Difficulty::variants()[difficulty.clone() as usize]
+    }
+
+    fn end_game_state(end_game_state: &EndGameState) -> &'static str {
+        EndGameState::variants()[end_game_state.clone() as usize]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::unreal;
+
+    #[test]
+    fn anonymous_statistics() -> Result<()> {
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        let statistics = Statistics::me2(&me2_save_game);
+        assert!(!statistics.plot.booleans.is_empty());
+        assert!(statistics.me1_plot.is_some());
+
+        // Nothing identifying
+        let json = serde_json::to_string(&statistics)?;
+        let player = me2_save_game.player();
+        for identifying in [player.first_name(), player.face_code()] {
+            assert!(identifying.is_empty() || !json.contains(&*identifying));
+        }
+
+        Ok(())
+    }
+}
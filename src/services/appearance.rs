@@ -0,0 +1,226 @@
+use gloo::{
+    events::EventListener,
+    storage::{LocalStorage, Storage},
+    utils,
+};
+
+// Each preference follows the OS setting unless overridden
+#[derive(Clone, Copy, PartialEq)]
+pub enum Preference {
+    ColorScheme,
+    Motion,
+    Transparency,
+}
+
+impl Preference {
+    pub fn all() -> [Preference; 3] {
+        [Preference::ColorScheme, Preference::Motion, Preference::Transparency]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Preference::ColorScheme => "Theme",
+            Preference::Motion => "Animations",
+            Preference::Transparency => "Transparency",
+        }
+    }
+
+    // `System`, the override that enables the class, the one that disables it
+    pub fn options(self) -> &'static [&'static str] {
+        match self {
+            Preference::ColorScheme => &["System", "Light", "Dark"],
+            Preference::Motion | Preference::Transparency => &["System", "Reduced", "Full"],
+        }
+    }
+
+    pub fn get(self) -> usize {
+        LocalStorage::get(self.key()).unwrap_or_default()
+    }
+
+    pub fn set(self, idx: usize) {
+        let _ = LocalStorage::set(self.key(), idx);
+        self.apply();
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Preference::ColorScheme => "appearance_color_scheme",
+            Preference::Motion => "appearance_motion",
+            Preference::Transparency => "appearance_transparency",
+        }
+    }
+
+    fn media_query(self) -> &'static str {
+        match self {
+            Preference::ColorScheme => "(prefers-color-scheme: light)",
+            Preference::Motion => "(prefers-reduced-motion: reduce)",
+            Preference::Transparency => "(prefers-reduced-transparency: reduce)",
+        }
+    }
+
+    fn class(self) -> &'static str {
+        match self {
+            Preference::ColorScheme => "light",
+            Preference::Motion => "reduce-motion",
+            Preference::Transparency => "reduce-transparency",
+        }
+    }
+
+    fn is_enabled(self) -> bool {
+        match self.get() {
+            1 => true,
+            2 => false,
+            _ => utils::window()
+                .match_media(self.media_query())
+                .ok()
+                .flatten()
+                .map(|query| query.matches())
+                .unwrap_or(false),
+        }
+    }
+
+    fn apply(self) {
+        let body = utils::document().body().unwrap();
+        let _ = body.class_list().toggle_with_force(self.class(), self.is_enabled());
+    }
+}
+
+// Fonts aren't OS settings, the first option is the default
+#[derive(Clone, Copy, PartialEq)]
+pub enum Font {
+    Family,
+    Size,
+}
+
+impl Font {
+    pub fn all() -> [Font; 2] {
+        [Font::Family, Font::Size]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Font::Family => "Font",
+            Font::Size => "Font size",
+        }
+    }
+
+    pub fn options(self) -> &'static [&'static str] {
+        match self {
+            Font::Family => &["Monospace", "Sans-serif", "Serif"],
+            Font::Size => &["Normal", "Small", "Large"],
+        }
+    }
+
+    pub fn get(self) -> usize {
+        LocalStorage::get(self.key()).unwrap_or_default()
+    }
+
+    pub fn set(self, idx: usize) {
+        let _ = LocalStorage::set(self.key(), idx);
+        self.apply();
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Font::Family => "appearance_font_family",
+            Font::Size => "appearance_font_size",
+        }
+    }
+
+    // Same order as `options`
+    fn classes(self) -> &'static [&'static str] {
+        match self {
+            Font::Family => &["", "font-family-sans", "font-family-serif"],
+            Font::Size => &["", "font-size-small", "font-size-large"],
+        }
+    }
+
+    fn apply(self) {
+        let body = utils::document().body().unwrap();
+        let current = self.get();
+        // The default option has no class
+        for (idx, class) in self.classes().iter().enumerate().skip(1) {
+            let _ = body.class_list().toggle_with_force(class, idx == current);
+        }
+    }
+}
+
+// The theme accent follows the game, or the class of the opened character
+pub struct Accent;
+
+impl Accent {
+    const KEY: &'static str = "appearance_accent";
+    const CLASS: &'static str = "save-accent";
+    const PROPERTY: &'static str = "--accent";
+
+    pub fn options() -> &'static [&'static str] {
+        &["Game", "Character class"]
+    }
+
+    pub fn get() -> usize {
+        LocalStorage::get(Self::KEY).unwrap_or_default()
+    }
+
+    pub fn set(idx: usize) {
+        let _ = LocalStorage::set(Self::KEY, idx);
+        Self::apply();
+    }
+
+    // RGB channels like the neutral colors, `None` falls back to the game theme
+    pub fn set_color(color: Option<[u8; 3]>) {
+        let style = utils::document().body().unwrap().style();
+        let _ = match color {
+            Some([r, g, b]) => style.set_property(Self::PROPERTY, &format!("{} {} {}", r, g, b)),
+            None => style.remove_property(Self::PROPERTY).map(|_| ()),
+        };
+        Self::apply();
+    }
+
+    fn apply() {
+        let body = utils::document().body().unwrap();
+        let has_color = body
+            .style()
+            .get_property_value(Self::PROPERTY)
+            .map(|value| !value.is_empty())
+            .unwrap_or(false);
+        let _ = body.class_list().toggle_with_force(Self::CLASS, Self::get() == 1 && has_color);
+    }
+
+    // ME2 and ME3 class names are `SFXGame.SFXPawn_Player<Class>`, the non combat ones included
+    pub fn class_color(class_name: &str) -> Option<[u8; 3]> {
+        const COLORS: &[(&str, [u8; 3])] = &[
+            ("Adept", [125, 70, 190]),
+            ("Engineer", [35, 130, 175]),
+            ("Infiltrator", [45, 140, 85]),
+            ("Sentinel", [185, 140, 35]),
+            ("Soldier", [170, 50, 40]),
+            ("Vanguard", [205, 95, 30]),
+        ];
+        COLORS.iter().find(|(class, _)| class_name.contains(class)).map(|&(_, color)| color)
+    }
+}
+
+pub struct Appearance {
+    _listeners: Vec<EventListener>,
+}
+
+impl Appearance {
+    // Applies the preferences and keeps following the OS settings while alive
+    pub fn init() -> Self {
+        for font in Font::all() {
+            font.apply();
+        }
+        Accent::apply();
+
+        let _listeners = Preference::all()
+            .into_iter()
+            .filter_map(|preference| {
+                preference.apply();
+                let query = utils::window().match_media(preference.media_query()).ok()??;
+                Some(EventListener::new(&query, "change", move |_| preference.apply()))
+            })
+            .collect();
+
+        Appearance { _listeners }
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use gloo::storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+// Key of the game in a pack and in the local storage, then whether it has its own raw plot
+// bookmarks, the raw plot of ME1LE being the one of ME1
+const GAMES: &[(&str, bool)] = &[("me1", true), ("me1_le", false), ("me2", true), ("me3", true)];
+const PLOT_TYPES: &[&str] = &["booleans", "integers", "floats"];
+
+// The pins of the raw data and the bookmarks of the raw plots of every game, shared as a file
+// so that a guide can point at the values it talks about
+#[derive(Serialize, Deserialize, Default)]
+pub struct BookmarkPack {
+    // What the pack is for, shown once imported
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub games: BTreeMap<String, GameBookmarks>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct GameBookmarks {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub raw_data: Vec<String>,
+    // Plot id with its name, empty if it has none
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub booleans: BTreeMap<usize, String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub integers: BTreeMap<usize, String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub floats: BTreeMap<usize, String>,
+}
+
+impl GameBookmarks {
+    fn is_empty(&self) -> bool {
+        let GameBookmarks { raw_data, booleans, integers, floats } = self;
+        raw_data.is_empty() && booleans.is_empty() && integers.is_empty() && floats.is_empty()
+    }
+
+    fn plots(&self, plot_type: &str) -> &BTreeMap<usize, String> {
+        match plot_type {
+            "booleans" => &self.booleans,
+            "integers" => &self.integers,
+            _ => &self.floats,
+        }
+    }
+
+    fn plots_mut(&mut self, plot_type: &str) -> &mut BTreeMap<usize, String> {
+        match plot_type {
+            "booleans" => &mut self.booleans,
+            "integers" => &mut self.integers,
+            _ => &mut self.floats,
+        }
+    }
+}
+
+fn pins_key(game: &str) -> String {
+    format!("{}_raw_data_pins", game)
+}
+
+pub fn names_key(bookmarks_key: &str) -> String {
+    format!("{}_names", bookmarks_key)
+}
+
+fn bookmarks_key(game: &str, plot_type: &str) -> String {
+    format!("{}_raw_plot_bookmarks_{}", game, plot_type)
+}
+
+impl BookmarkPack {
+    // Everything pinned or bookmarked on this computer
+    pub fn collect() -> Self {
+        let games = GAMES
+            .iter()
+            .map(|&(game, has_plots)| {
+                let mut bookmarks = GameBookmarks {
+                    raw_data: LocalStorage::get(&pins_key(game)).unwrap_or_default(),
+                    ..Default::default()
+                };
+                if has_plots {
+                    for plot_type in PLOT_TYPES {
+                        let key = bookmarks_key(game, plot_type);
+                        let ids: BTreeSet<usize> = LocalStorage::get(&key).unwrap_or_default();
+                        let mut names: BTreeMap<usize, String> =
+                            LocalStorage::get(&names_key(&key)).unwrap_or_default();
+                        let plots = ids
+                            .into_iter()
+                            .map(|id| (id, names.remove(&id).unwrap_or_default()))
+                            .collect();
+                        *bookmarks.plots_mut(plot_type) = plots;
+                    }
+                }
+                (game.to_owned(), bookmarks)
+            })
+            .filter(|(_, bookmarks)| !bookmarks.is_empty())
+            .collect();
+
+        BookmarkPack { notes: String::new(), games }
+    }
+
+    // Added to the ones already there, a name of the pack replacing the one of a bookmark.
+    // Returns how many pins and bookmarks were new
+    pub fn merge(&self) -> usize {
+        let mut added = 0;
+        for &(game, has_plots) in GAMES {
+            let bookmarks = match self.games.get(game) {
+                Some(bookmarks) => bookmarks,
+                None => continue,
+            };
+
+            if !bookmarks.raw_data.is_empty() {
+                let key = pins_key(game);
+                let mut pins: Vec<String> = LocalStorage::get(&key).unwrap_or_default();
+                for pin in &bookmarks.raw_data {
+                    if !pins.contains(pin) {
+                        pins.push(pin.clone());
+                        added += 1;
+                    }
+                }
+                pins.sort();
+                let _ = LocalStorage::set(&key, pins);
+            }
+
+            if !has_plots {
+                continue;
+            }
+            for plot_type in PLOT_TYPES {
+                let plots = bookmarks.plots(plot_type);
+                if plots.is_empty() {
+                    continue;
+                }
+
+                let key = bookmarks_key(game, plot_type);
+                let mut ids: BTreeSet<usize> = LocalStorage::get(&key).unwrap_or_default();
+                let mut names: BTreeMap<usize, String> =
+                    LocalStorage::get(&names_key(&key)).unwrap_or_default();
+                for (&id, name) in plots {
+                    if ids.insert(id) {
+                        added += 1;
+                    }
+                    if !name.is_empty() {
+                        names.insert(id, name.clone());
+                    }
+                }
+                let _ = LocalStorage::set(&key, ids);
+                let _ = LocalStorage::set(&names_key(&key), names);
+            }
+        }
+        added
+    }
+}
@@ -1,17 +1,23 @@
-use std::rc::Rc;
+use std::{collections::HashSet, rc::Rc};
 
 use anyhow::{Context as ErrorContext, Error, Result};
 use yew::{prelude::*, ContextProvider};
 
 use crate::{
     save_data::{
-        mass_effect_1::plot_db::Me1PlotDb, mass_effect_1_le::item_db::Me1ItemDb,
-        mass_effect_1_le::player_class_db::Me1LePlayerClassDb, mass_effect_2::plot_db::Me2PlotDb,
-        mass_effect_3::plot_db::Me3PlotDb, shared::plot::RawPlotDb,
+        mass_effect_1::plot_db::Me1PlotDb,
+        mass_effect_1_le::item_db::Me1ItemDb,
+        mass_effect_1_le::player_class_db::Me1LePlayerClassDb,
+        mass_effect_2::plot_db::Me2PlotDb,
+        mass_effect_3::{
+            continuity_db::Me3ContinuityDb, plot_db::Me3PlotDb, world_state_db::Me3WorldStateDb,
+        },
+        shared::{appearance::HeadMorphSliderDb, plot::RawPlotDb, plot_db_version},
     },
-    services::rpc,
+    services::{logger, rpc},
 };
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Type {
     Me1LePlayerClasses,
     Me1Plot,
@@ -21,6 +27,9 @@ pub enum Type {
     Me2RawPlot,
     Me3Plot,
     Me3RawPlot,
+    Me3WorldStates,
+    Me3Continuity,
+    HeadMorphSliders,
 }
 
 pub enum Database {
@@ -32,6 +41,9 @@ pub enum Database {
     Me2RawPlot(RawPlotDb),
     Me3Plot(Me3PlotDb),
     Me3RawPlot(RawPlotDb),
+    Me3WorldStates(Me3WorldStateDb),
+    Me3Continuity(Me3ContinuityDb),
+    HeadMorphSliders(HeadMorphSliderDb),
 }
 
 pub enum Msg {
@@ -50,6 +62,9 @@ pub struct Databases {
     me2_raw_plot: Option<Rc<RawPlotDb>>,
     me3_plot: Option<Rc<Me3PlotDb>>,
     me3_raw_plot: Option<Rc<RawPlotDb>>,
+    me3_world_states: Option<Rc<Me3WorldStateDb>>,
+    me3_continuity: Option<Rc<Me3ContinuityDb>>,
+    head_morph_sliders: Option<Rc<HeadMorphSliderDb>>,
     load_callback: Callback<Type>,
 }
 
@@ -110,6 +125,27 @@ impl Databases {
         self.me3_raw_plot
     }
 
+    pub fn get_me3_world_states(self) -> Option<Rc<Me3WorldStateDb>> {
+        if self.me3_world_states.is_none() {
+            self.load_database(Type::Me3WorldStates);
+        }
+        self.me3_world_states
+    }
+
+    pub fn get_me3_continuity(self) -> Option<Rc<Me3ContinuityDb>> {
+        if self.me3_continuity.is_none() {
+            self.load_database(Type::Me3Continuity);
+        }
+        self.me3_continuity
+    }
+
+    pub fn get_head_morph_sliders(self) -> Option<Rc<HeadMorphSliderDb>> {
+        if self.head_morph_sliders.is_none() {
+            self.load_database(Type::HeadMorphSliders);
+        }
+        self.head_morph_sliders
+    }
+
     fn load_database(&self, db_type: Type) {
         self.load_callback.emit(db_type);
     }
@@ -126,6 +162,9 @@ impl PartialEq for Databases {
             me2_raw_plot,
             me3_plot,
             me3_raw_plot,
+            me3_world_states,
+            me3_continuity,
+            head_morph_sliders,
             load_callback: _,
         } = self;
         me1_le_player_classes.is_some() == other.me1_le_player_classes.is_some()
@@ -136,6 +175,9 @@ impl PartialEq for Databases {
             && me2_raw_plot.is_some() == other.me2_raw_plot.is_some()
             && me3_plot.is_some() == other.me3_plot.is_some()
             && me3_raw_plot.is_some() == other.me3_raw_plot.is_some()
+            && me3_world_states.is_some() == other.me3_world_states.is_some()
+            && me3_continuity.is_some() == other.me3_continuity.is_some()
+            && head_morph_sliders.is_some() == other.head_morph_sliders.is_some()
     }
 }
 
@@ -147,6 +189,9 @@ pub struct Props {
 
 pub struct DatabaseProvider {
     dbs: Databases,
+    // Asked for by every component showing it until it's there, loaded once.
+    // A failed one isn't tried again, it would fail the same way.
+    requested: HashSet<Type>,
 }
 
 impl Component for DatabaseProvider {
@@ -156,12 +201,15 @@ impl Component for DatabaseProvider {
     fn create(ctx: &Context<Self>) -> Self {
         let load_callback = ctx.link().callback(Msg::LoadDatabase);
         let dbs = Databases { load_callback, ..Default::default() };
-        Self { dbs }
+        Self { dbs, requested: HashSet::new() }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::LoadDatabase(db_type) => {
+                if !self.requested.insert(db_type) {
+                    return false;
+                }
                 match db_type {
                     Type::Me1LePlayerClasses => {
                         Self::load_db(ctx, "databases/me1_le_player_class_db.ron", |response| {
@@ -170,12 +218,12 @@ impl Component for DatabaseProvider {
                         })
                     }
                     Type::Me1Plot => Self::load_db(ctx, "databases/me1_plot_db.ron", |response| {
-                        let db = ron::from_str(&response)?;
+                        let db = plot_db_version::from_ron(&response)?;
                         Ok(Database::Me1Plot(db))
                     }),
                     Type::Me1RawPlot => {
                         Self::load_db(ctx, "databases/me1_raw_plot_db.ron", |response| {
-                            let db = ron::from_str(&response)?;
+                            let db = plot_db_version::from_ron(&response)?;
                             Ok(Database::Me1RawPlot(db))
                         })
                     }
@@ -184,25 +232,43 @@ impl Component for DatabaseProvider {
                         Ok(Database::Me1Items(db))
                     }),
                     Type::Me2Plot => Self::load_db(ctx, "databases/me2_plot_db.ron", |response| {
-                        let db = ron::from_str(&response)?;
+                        let db = plot_db_version::from_ron(&response)?;
                         Ok(Database::Me2Plot(db))
                     }),
                     Type::Me2RawPlot => {
                         Self::load_db(ctx, "databases/me2_raw_plot_db.ron", |response| {
-                            let db = ron::from_str(&response)?;
+                            let db = plot_db_version::from_ron(&response)?;
                             Ok(Database::Me2RawPlot(db))
                         })
                     }
                     Type::Me3Plot => Self::load_db(ctx, "databases/me3_plot_db.ron", |response| {
-                        let db = ron::from_str(&response)?;
+                        let db = plot_db_version::from_ron(&response)?;
                         Ok(Database::Me3Plot(db))
                     }),
                     Type::Me3RawPlot => {
                         Self::load_db(ctx, "databases/me3_raw_plot_db.ron", |response| {
-                            let db = ron::from_str(&response)?;
+                            let db = plot_db_version::from_ron(&response)?;
                             Ok(Database::Me3RawPlot(db))
                         })
                     }
+                    Type::Me3WorldStates => {
+                        Self::load_db(ctx, "databases/me3_world_state_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::Me3WorldStates(db))
+                        })
+                    }
+                    Type::Me3Continuity => {
+                        Self::load_db(ctx, "databases/me3_continuity_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::Me3Continuity(db))
+                        })
+                    }
+                    Type::HeadMorphSliders => {
+                        Self::load_db(ctx, "databases/head_morph_slider_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::HeadMorphSliders(db))
+                        })
+                    }
                 }
                 false
             }
@@ -232,10 +298,20 @@ impl Component for DatabaseProvider {
                     Database::Me3RawPlot(db) => {
                         self.dbs.me3_raw_plot = Some(db.into());
                     }
+                    Database::Me3WorldStates(db) => {
+                        self.dbs.me3_world_states = Some(db.into());
+                    }
+                    Database::Me3Continuity(db) => {
+                        self.dbs.me3_continuity = Some(db.into());
+                    }
+                    Database::HeadMorphSliders(db) => {
+                        self.dbs.head_morph_sliders = Some(db.into());
+                    }
                 }
                 true
             }
             Msg::Error(err) => {
+                logger::error(format!("{:#}", err));
                 ctx.props().onerror.emit(err);
                 false
             }
@@ -263,7 +339,10 @@ impl DatabaseProvider {
                 deserialize(file)
             };
             match handle_db.await.context(format!("Failed to parse `/{}`", path)) {
-                Ok(db) => Msg::DatabaseLoaded(Box::new(db)),
+                Ok(db) => {
+                    logger::debug(format!("Loaded database `/{}`", path));
+                    Msg::DatabaseLoaded(Box::new(db))
+                }
                 Err(err) => Msg::Error(err),
             }
         });
@@ -0,0 +1,135 @@
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+use yew::{context::ContextHandle, prelude::*, ContextProvider};
+
+use crate::{
+    save_data::shared::{character_sheet::CharacterSheet, power_points::PowerPoints},
+    services::{
+        document::{Document, Subscription},
+        save_game::SaveGame,
+        save_handler::SaveHandler,
+    },
+};
+
+pub enum Msg {
+    SaveHandler(SaveHandler),
+    Refresh,
+}
+
+// What the editor works out of the opened save, as it is after the last edit
+#[derive(Clone, PartialEq, Default)]
+pub struct DerivedState {
+    pub character_sheet: Option<Rc<CharacterSheet>>,
+    // By character, `Shepard` and the tags of the squad
+    pub power_points: Option<Rc<IndexMap<String, PowerPoints>>>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub children: Children,
+}
+
+pub struct DerivedStateProvider {
+    _save_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    // Every edit made in the tabs
+    _edits: Subscription,
+    state: DerivedState,
+}
+
+impl Component for DerivedStateProvider {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+
+        let (document, _) =
+            ctx.link().context::<Document>(Callback::noop()).expect("no document provider");
+        let _edits = document.listen(ctx.link().callback(|_| Msg::Refresh));
+
+        let state = Self::derive(&save_handler);
+        DerivedStateProvider { _save_handle, save_handler, _edits, state }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SaveHandler(save_handler) => {
+                self.save_handler = save_handler;
+                self.refresh()
+            }
+            Msg::Refresh => self.refresh(),
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <ContextProvider<DerivedState> context={self.state.clone()}>
+                { ctx.props().children.clone() }
+            </ContextProvider<DerivedState>>
+        }
+    }
+}
+
+impl DerivedStateProvider {
+    // Subscribers are only notified when something they show has changed
+    fn refresh(&mut self) -> bool {
+        let state = Self::derive(&self.save_handler);
+        let changed = state != self.state;
+        self.state = state;
+        changed
+    }
+
+    fn derive(save_handler: &SaveHandler) -> DerivedState {
+        let character_sheet = save_handler.save_game.as_deref().and_then(|save_game| {
+            let sheet = match save_game {
+                SaveGame::MassEffect1 { save_game, .. } => CharacterSheet::me1(&save_game.borrow()),
+                SaveGame::MassEffect1Le { save_game, .. } => {
+                    let save_game = save_game.borrow();
+                    let save_data = save_game.save_data.borrow();
+                    CharacterSheet::me1_le(&save_data)
+                }
+                SaveGame::MassEffect1LePs4 { save_game, .. } => {
+                    CharacterSheet::me1_le(&save_game.borrow())
+                }
+                SaveGame::MassEffect2 { save_game, .. } => CharacterSheet::me2(&save_game.borrow()),
+                SaveGame::MassEffect2Le { save_game, .. } => {
+                    CharacterSheet::me2_le(&save_game.borrow())
+                }
+                SaveGame::MassEffect3 { save_game, .. } => CharacterSheet::me3(&save_game.borrow()),
+                // No character in the plot table
+                SaveGame::MassEffect2Quick { .. } | SaveGame::MassEffect3Quick { .. } => {
+                    return None
+                }
+            };
+            Some(Rc::new(sheet))
+        });
+        let power_points = save_handler.save_game.as_deref().and_then(|save_game| {
+            let points = match save_game {
+                SaveGame::MassEffect2 { save_game, .. } => {
+                    let save_game = save_game.borrow();
+                    let points = PowerPoints::me2(&save_game.player(), &save_game.squad());
+                    points
+                }
+                SaveGame::MassEffect2Le { save_game, .. } => {
+                    let save_game = save_game.borrow();
+                    let points = PowerPoints::me2(&save_game.player(), &save_game.squad());
+                    points
+                }
+                SaveGame::MassEffect3 { save_game, .. } => {
+                    let save_game = save_game.borrow();
+                    let points = PowerPoints::me3(&save_game.player(), &save_game.squad());
+                    points
+                }
+                // ME1 has talents of its own
+                _ => return None,
+            };
+            Some(Rc::new(points))
+        });
+        DerivedState { character_sheet, power_points }
+    }
+}
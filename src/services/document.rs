@@ -0,0 +1,259 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::{Rc, Weak},
+};
+
+use gloo::timers::future::TimeoutFuture;
+use yew::{context::ContextHandle, prelude::*, ContextProvider};
+
+use crate::services::save_handler::{Action, SaveHandler};
+
+pub enum Msg {
+    SaveHandler(SaveHandler),
+    Edited,
+    Applied,
+    Refresh,
+    Evaluated(Vec<String>, Vec<Option<String>>),
+}
+
+struct Subscriber {
+    id: usize,
+    path: String,
+    callback: Callback<Option<String>>,
+}
+
+#[derive(Default)]
+struct Observers {
+    next_id: usize,
+    subscribers: Vec<Subscriber>,
+    // As last sent to the subscribers, by path
+    values: HashMap<String, Option<String>>,
+    // Told of every edit, by id
+    listeners: Vec<(usize, Callback<()>)>,
+}
+
+impl Observers {
+    fn next_id(&mut self) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+}
+
+/// The opened save seen value by value, by the paths of the Changes tab and the Watch panel:
+/// `player.credits`, `Boolean 1234`.
+/// A subscriber is only called when the value at its path changes, nothing else is rendered again.
+/// The components editing the save tell it with `edited`.
+#[derive(Clone)]
+pub struct Document {
+    observers: Rc<RefCell<Observers>>,
+    refresh: Callback<()>,
+    edited: Callback<()>,
+}
+
+impl Document {
+    /// `callback` gets the value at `path` once known, then every new one, `None` where there is
+    /// nothing. Called until the subscription is dropped.
+    pub fn subscribe(&self, path: String, callback: Callback<Option<String>>) -> Subscription {
+        let (id, known) = {
+            let mut observers = self.observers.borrow_mut();
+            let id = observers.next_id();
+            let known = observers.values.get(&path).cloned();
+            observers.subscribers.push(Subscriber { id, path, callback: callback.clone() });
+            (id, known)
+        };
+        // Known already, no need to wait for the next refresh
+        match known {
+            Some(value) => callback.emit(value),
+            None => self.refresh.emit(()),
+        }
+        Subscription { observers: Rc::downgrade(&self.observers), id }
+    }
+
+    /// `callback` is called once each edit has been applied, for what is worked out of the whole
+    /// save. Called until the subscription is dropped.
+    pub fn listen(&self, callback: Callback<()>) -> Subscription {
+        let mut observers = self.observers.borrow_mut();
+        let id = observers.next_id();
+        observers.listeners.push((id, callback));
+        Subscription { observers: Rc::downgrade(&self.observers), id }
+    }
+
+    /// To call once the save has been edited, the edit is recorded in the undo history and the
+    /// subscribed paths are read again.
+    pub fn edited(&self) {
+        self.edited.emit(());
+    }
+}
+
+/// `Document::edited` from a component, for the editing components which may be rendered outside
+/// of a document provider.
+pub fn edited<C: Component>(ctx: &Context<C>) {
+    if let Some((document, _)) = ctx.link().context::<Document>(Callback::noop()) {
+        document.edited();
+    }
+}
+
+impl PartialEq for Document {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.observers, &other.observers)
+    }
+}
+
+/// Unsubscribes when dropped
+pub struct Subscription {
+    observers: Weak<RefCell<Observers>>,
+    id: usize,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(observers) = self.observers.upgrade() {
+            let mut observers = observers.borrow_mut();
+            observers.subscribers.retain(|subscriber| subscriber.id != self.id);
+            observers.listeners.retain(|(id, _)| *id != self.id);
+            let Observers { subscribers, values, .. } = &mut *observers;
+            values.retain(|path, _| subscribers.iter().any(|subscriber| subscriber.path == *path));
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub children: Children,
+}
+
+pub struct DocumentProvider {
+    _save_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    document: Document,
+    applying: bool,
+}
+
+impl Component for DocumentProvider {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+
+        let document = Document {
+            observers: Default::default(),
+            refresh: ctx.link().callback(|_| Msg::Refresh),
+            edited: ctx.link().callback(|_| Msg::Edited),
+        };
+        DocumentProvider { _save_handle, save_handler, document, applying: false }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SaveHandler(save_handler) => {
+                self.save_handler = save_handler;
+                self.refresh(ctx);
+            }
+            Msg::Edited => {
+                // The other edits bump the revision of the save handler
+                self.save_handler.action(Action::Edited);
+
+                // Once the edit has been applied, the edits made in the same tick are read once
+                if !self.applying {
+                    self.applying = true;
+                    ctx.link().send_future(async {
+                        TimeoutFuture::new(0).await;
+                        Msg::Applied
+                    });
+                }
+            }
+            Msg::Applied => {
+                self.applying = false;
+                self.refresh(ctx);
+
+                // Collected first, a listener may listen or stop listening
+                let listeners: Vec<_> = self
+                    .document
+                    .observers
+                    .borrow()
+                    .listeners
+                    .iter()
+                    .map(|(_, callback)| callback.clone())
+                    .collect();
+                for listener in listeners {
+                    listener.emit(());
+                }
+            }
+            Msg::Refresh => self.refresh(ctx),
+            Msg::Evaluated(paths, values) => self.notify(paths, values),
+        }
+        // The document stays the same, only the subscribers are told
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <ContextProvider<Document> context={self.document.clone()}>
+                { ctx.props().children.clone() }
+            </ContextProvider<Document>>
+        }
+    }
+}
+
+impl DocumentProvider {
+    // Only the subscribed paths are walked through
+    fn refresh(&self, ctx: &Context<Self>) {
+        if self.save_handler.save_game.is_none() {
+            return;
+        }
+
+        let mut paths: Vec<String> = self
+            .document
+            .observers
+            .borrow()
+            .subscribers
+            .iter()
+            .map(|subscriber| subscriber.path.clone())
+            .collect();
+        paths.sort_unstable();
+        paths.dedup();
+
+        if !paths.is_empty() {
+            let callback = {
+                let paths = paths.clone();
+                ctx.link().callback(move |values| Msg::Evaluated(paths.clone(), values))
+            };
+            self.save_handler.action(Action::WatchValues(paths, callback));
+        }
+    }
+
+    fn notify(&self, paths: Vec<String>, values: Vec<Option<String>>) {
+        // Collected first, a callback may subscribe or unsubscribe
+        let mut notified = Vec::new();
+        {
+            let mut observers = self.document.observers.borrow_mut();
+            for (path, value) in paths.into_iter().zip(values) {
+                if observers.values.get(&path) == Some(&value) {
+                    continue;
+                }
+                let before = notified.len();
+                notified.extend(
+                    observers
+                        .subscribers
+                        .iter()
+                        .filter(|subscriber| subscriber.path == path)
+                        .map(|subscriber| (subscriber.callback.clone(), value.clone())),
+                );
+                // Not kept once nobody subscribes to it anymore
+                if notified.len() > before {
+                    observers.values.insert(path, value);
+                }
+            }
+        }
+
+        for (callback, value) in notified {
+            callback.emit(value);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::VecDeque;
+
+use anyhow::Result;
+
+use super::save_game::SaveGame;
+
+// Past that, the oldest edits can't be undone anymore
+const MAX_UNDO: usize = 50;
+
+/// The opened save as it was written after each edit, to go back and forth between them.
+/// An edit is only known once it has been written to a checkpoint.
+///
+/// Every step is a whole file, 30 to 150 KB for the test saves, so up to 51 of them are kept in
+/// memory: less than 8 MB for the biggest. A checkpoint serializes the whole save and an undo
+/// parses it again, both take the time of a save and an open, not of the edit.
+#[derive(Default)]
+pub struct EditHistory {
+    // Oldest first
+    undo: VecDeque<Vec<u8>>,
+    redo: Vec<Vec<u8>>,
+    current: Option<Vec<u8>>,
+}
+
+impl EditHistory {
+    // Forgets the previous save, this one is where undoing stops
+    pub fn reset(&mut self, opened: &SaveGame) -> Result<()> {
+        *self = EditHistory::default();
+        self.current = Some(opened.to_bytes(opened.file_path())?);
+        Ok(())
+    }
+
+    // Writes the save as it is now, nothing new if it hasn't changed since the last checkpoint
+    pub fn checkpoint(&mut self, save_game: &SaveGame) -> Result<()> {
+        let written = save_game.to_bytes(save_game.file_path())?;
+        if self.current.as_ref() == Some(&written) {
+            return Ok(());
+        }
+
+        if let Some(previous) = self.current.replace(written) {
+            self.undo.push_back(previous);
+            if self.undo.len() > MAX_UNDO {
+                self.undo.pop_front();
+            }
+        }
+        self.redo.clear();
+        Ok(())
+    }
+
+    // The save to go back to, parsed again as the same kind of save as `save_game`
+    pub fn undo(&mut self, save_game: &SaveGame) -> Option<Result<SaveGame>> {
+        let previous = self.undo.pop_back()?;
+        self.redo.extend(self.current.replace(previous));
+        self.current.as_deref().map(|previous| save_game.reparse(previous))
+    }
+
+    pub fn redo(&mut self, save_game: &SaveGame) -> Option<Result<SaveGame>> {
+        let next = self.redo.pop()?;
+        self.undo.extend(self.current.replace(next));
+        self.current.as_deref().map(|next| save_game.reparse(next))
+    }
+}
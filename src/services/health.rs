@@ -0,0 +1,39 @@
+use crate::{
+    save_data::fixup,
+    services::{
+        logger,
+        save_game::{is_xbox360_path, SaveGame},
+    },
+};
+
+// What the opened file says about itself, before any edit
+pub struct FileHealth {
+    // `None` for the formats without one
+    pub checksum: Option<bool>,
+    pub file_size: usize,
+    // Size of the save written back as it was read, `None` if it can't be
+    pub written_size: Option<usize>,
+}
+
+impl FileHealth {
+    pub fn check(save_game: &SaveGame, input: &[u8]) -> Self {
+        let file_path = save_game.file_path();
+        let is_xbox360 = match save_game {
+            SaveGame::MassEffect2Quick { is_xbox360, .. }
+            | SaveGame::MassEffect3Quick { is_xbox360, .. } => *is_xbox360,
+            _ => is_xbox360_path(file_path),
+        };
+        let checksum = fixup::verify(save_game.fixups(), input, is_xbox360);
+
+        // Bytes left unread or sections cut short don't make it back
+        let written_size = match save_game.to_bytes(file_path) {
+            Ok(output) => Some(output.len()),
+            Err(err) => {
+                logger::warn(format!("The save can't be written back: {:?}", err));
+                None
+            }
+        };
+
+        FileHealth { checksum, file_size: input.len(), written_size }
+    }
+}
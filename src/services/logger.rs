@@ -0,0 +1,32 @@
+use wasm_bindgen_futures as futures;
+
+use super::rpc::{self, LogLevel, LogParams};
+
+// Forwarded to the backend so everything ends up in the same logs
+pub fn log(level: LogLevel, message: String) {
+    #[cfg(debug_assertions)]
+    gloo::console::log!(&message);
+
+    futures::spawn_local(async move {
+        // Can't go through the backend, the console is the only place left
+        if let Err(err) = rpc::log(LogParams { level, message }).await {
+            gloo::console::error!(format!("Failed to forward a log to the backend: {:#}", err));
+        }
+    });
+}
+
+pub fn error(message: impl Into<String>) {
+    log(LogLevel::Error, message.into());
+}
+
+pub fn warn(message: impl Into<String>) {
+    log(LogLevel::Warn, message.into());
+}
+
+pub fn info(message: impl Into<String>) {
+    log(LogLevel::Info, message.into());
+}
+
+pub fn debug(message: impl Into<String>) {
+    log(LogLevel::Debug, message.into());
+}
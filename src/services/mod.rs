@@ -1,4 +1,18 @@
+pub mod appearance;
+pub mod bookmarks;
 pub mod database;
+pub mod derived_state;
+pub mod document;
 pub mod drop_handler;
+pub mod edit_history;
+pub mod health;
+pub mod logger;
+pub mod reveal;
 pub mod rpc;
+pub mod save_diff;
+pub mod save_game;
 pub mod save_handler;
+pub mod save_json;
+pub mod script;
+pub mod session;
+pub mod tlk;
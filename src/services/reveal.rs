@@ -0,0 +1,34 @@
+use gloo::timers::callback::Timeout;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement};
+use yew::NodeRef;
+
+// Long enough to be noticed, not to be in the way
+const HIGHLIGHT_MS: u32 = 1_000;
+const HIGHLIGHT_CLASS: &str = "bg-theme-active";
+
+/// Scrolls to an element just added to a list, highlights it for a moment and focuses its
+/// first input. Called from `rendered`, it waits for the children opened with the element to
+/// be rendered too.
+pub fn reveal(node_ref: &NodeRef) {
+    let node_ref = node_ref.clone();
+    Timeout::new(0, move || {
+        let element = match node_ref.cast::<Element>() {
+            Some(element) => element,
+            None => return,
+        };
+
+        element.scroll_into_view();
+        let input = element.query_selector("input, textarea").ok().flatten();
+        if let Some(input) = input.and_then(|input| input.dyn_into::<HtmlElement>().ok()) {
+            let _ = input.focus();
+        }
+
+        let _ = element.class_list().add_1(HIGHLIGHT_CLASS);
+        Timeout::new(HIGHLIGHT_MS, move || {
+            let _ = element.class_list().remove_1(HIGHLIGHT_CLASS);
+        })
+        .forget();
+    })
+    .forget();
+}
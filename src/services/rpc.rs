@@ -54,6 +54,10 @@ pub async fn download_and_install_update() -> Result<()> {
     call("download_and_install_update").await
 }
 
+pub async fn cancel_update() -> Result<()> {
+    call("cancel_update").await
+}
+
 pub async fn open_external_link(link: &str) -> Result<()> {
     call_with_params("open_external_link", link).await
 }
@@ -62,6 +66,10 @@ pub async fn save_file(rpc_file: RpcFile) -> Result<()> {
     call_with_params("save_file", rpc_file).await
 }
 
+pub async fn save_save_file(params: SaveFileParams) -> Result<()> {
+    call_with_params("save_save_file", params).await
+}
+
 pub async fn open_save(last_dir: bool) -> Result<Option<RpcFile>> {
     call_with_params("open_save", last_dir).await
 }
@@ -86,10 +94,118 @@ pub async fn export_head_morph_dialog() -> Result<Option<PathBuf>> {
     call("export_head_morph_dialog").await
 }
 
+pub async fn export_gibbed_head_morph_dialog(extension: &str) -> Result<Option<PathBuf>> {
+    call_with_params("export_gibbed_head_morph_dialog", extension).await
+}
+
+pub async fn import_war_assets() -> Result<Option<RpcFile>> {
+    call("import_war_assets").await
+}
+
+pub async fn export_war_assets_dialog() -> Result<Option<PathBuf>> {
+    call("export_war_assets_dialog").await
+}
+
+pub async fn export_statistics_dialog() -> Result<Option<PathBuf>> {
+    call("export_statistics_dialog").await
+}
+
+pub async fn export_plot_coverage_dialog() -> Result<Option<PathBuf>> {
+    call("export_plot_coverage_dialog").await
+}
+
+pub async fn export_plot_research_dialog() -> Result<Option<PathBuf>> {
+    call("export_plot_research_dialog").await
+}
+
+pub async fn import_bookmarks() -> Result<Option<RpcFile>> {
+    call("import_bookmarks").await
+}
+
+pub async fn export_bookmarks_dialog() -> Result<Option<PathBuf>> {
+    call("export_bookmarks_dialog").await
+}
+
+pub async fn import_save_json() -> Result<Option<RpcFile>> {
+    call("import_save_json").await
+}
+
+pub async fn export_save_json_dialog() -> Result<Option<PathBuf>> {
+    call("export_save_json_dialog").await
+}
+
 pub async fn load_database(path: &str) -> Result<RpcFile> {
     call_with_params("load_database", path).await
 }
 
+pub async fn import_tlk() -> Result<Option<RpcFile>> {
+    call("import_tlk").await
+}
+
+pub async fn load_tlk(path: PathBuf) -> Result<RpcFile> {
+    call_with_params("load_tlk", path).await
+}
+
+pub async fn find_cloud_save(path: PathBuf) -> Result<Option<CloudSave>> {
+    call_with_params("find_cloud_save", path).await
+}
+
+pub async fn list_careers() -> Result<Vec<Career>> {
+    call("list_careers").await
+}
+
+pub async fn delete_saves(saves: Vec<PathBuf>) -> Result<()> {
+    call_with_params("delete_saves", saves).await
+}
+
+pub async fn renumber_saves(saves: Vec<PathBuf>) -> Result<()> {
+    call_with_params("renumber_saves", saves).await
+}
+
+pub async fn list_backups(path: PathBuf) -> Result<Vec<Backup>> {
+    call_with_params("list_backups", path).await
+}
+
+pub async fn restore_backup(params: RestoreBackupParams) -> Result<RpcFile> {
+    call_with_params("restore_backup", params).await
+}
+
+pub async fn launch_game(target: String) -> Result<()> {
+    call_with_params("launch_game", target).await
+}
+
+pub async fn load_notes(path: PathBuf) -> Result<String> {
+    call_with_params("load_notes", path).await
+}
+
+pub async fn save_notes(params: NotesParams) -> Result<()> {
+    call_with_params("save_notes", params).await
+}
+
+pub async fn edit_externally(params: ExternalEditParams) -> Result<()> {
+    call_with_params("edit_externally", params).await
+}
+
+pub async fn fuzzing_enabled() -> Result<bool> {
+    call("fuzzing_enabled").await
+}
+
+pub async fn get_logs() -> Result<Vec<String>> {
+    call("get_logs").await
+}
+
+pub async fn copy_logs() -> Result<()> {
+    call("copy_logs").await
+}
+
+pub async fn log(params: LogParams) -> Result<()> {
+    call_with_params("log", params).await
+}
+
+pub async fn copy_to_clipboard(text: &str) -> Result<()> {
+    call_with_params("copy_to_clipboard", text).await
+}
+
 // Utils
 #[derive(Serialize)]
 pub struct DialogParams {
@@ -97,12 +213,115 @@ pub struct DialogParams {
     pub filters: Vec<(&'static str, Vec<&'static str>)>,
 }
 
+#[derive(Serialize)]
+pub struct NotesParams {
+    pub path: PathBuf,
+    pub notes: String,
+}
+
+#[derive(Serialize)]
+pub struct RestoreBackupParams {
+    pub path: PathBuf,
+    pub backup: PathBuf,
+}
+
+#[derive(Serialize)]
+pub struct ExternalEditParams {
+    pub id: u32,
+    pub text: String,
+}
+
+#[derive(Clone, Copy, Serialize)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+#[derive(Serialize)]
+pub struct LogParams {
+    pub level: LogLevel,
+    pub message: String,
+}
+
+#[derive(Serialize)]
+pub struct SaveFileParams {
+    pub rpc_file: RpcFile,
+    pub timestamp: FileTimestamp,
+}
+
+// Modified time given to a written save, `Set` in milliseconds since the Unix epoch
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub enum FileTimestamp {
+    Now,
+    Keep,
+    Set(f64),
+}
+
+impl Default for FileTimestamp {
+    fn default() -> Self {
+        FileTimestamp::Now
+    }
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct RpcFile {
     pub path: PathBuf,
     pub file: Base64File,
 }
 
+#[derive(Deserialize)]
+pub struct CloudSave {
+    pub path: PathBuf,
+    pub modified: f64,
+    pub opened_modified: f64,
+}
+
+impl CloudSave {
+    // A couple of seconds apart is still the same save
+    pub fn is_diverging(&self) -> bool {
+        (self.modified - self.opened_modified).abs() > 2_000.0
+    }
+
+    pub fn is_newer(&self) -> bool {
+        self.modified > self.opened_modified
+    }
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+pub struct Career {
+    pub game: String,
+    pub name: String,
+    pub path: PathBuf,
+    pub size: u64,
+    pub saves: Vec<SaveFile>,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+pub struct SaveFile {
+    pub path: PathBuf,
+    pub kind: SaveKind,
+    pub size: u64,
+    pub modified: f64,
+}
+
+// A copy of a save made before it was overwritten, `created` in milliseconds since the Unix epoch
+#[derive(Deserialize, Clone, PartialEq)]
+pub struct Backup {
+    pub path: PathBuf,
+    pub created: f64,
+    pub size: u64,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+pub enum SaveKind {
+    Auto,
+    Quick,
+    Manual(u32),
+    Other,
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct Base64File {
     pub unencoded_size: usize,
@@ -110,9 +329,9 @@ pub struct Base64File {
 }
 
 impl Base64File {
-    pub fn decode(self) -> Result<Vec<u8>> {
+    pub fn decode(&self) -> Result<Vec<u8>> {
         let mut vec = vec![0; self.unencoded_size];
-        base64::decode_config_slice(self.base64, base64::STANDARD, &mut vec)?;
+        base64::decode_config_slice(&self.base64, base64::STANDARD, &mut vec)?;
         Ok(vec)
     }
 }
@@ -0,0 +1,175 @@
+use std::mem;
+
+use anyhow::{bail, Result};
+
+use crate::{
+    gui::components::raw_ui::{self, Fields, Originals},
+    save_data::diff::{self, Change, PlotValues},
+    services::{
+        logger,
+        save_game::{match_raw_tree, Plots, SaveGame},
+    },
+};
+
+/// The fields of the save changed since the opened file, then what will be written differently
+pub fn diff_save(
+    save_game: &SaveGame, original: &SaveGame, original_file: &[u8],
+) -> Result<Vec<Change>> {
+    if mem::discriminant(save_game) != mem::discriminant(original) {
+        bail!("The original file is not the same game");
+    }
+    let mut changes = diff::diff_flattened(original.flatten(None)?, save_game.flatten(None)?);
+
+    // What will actually be written, checksums included
+    let output = save_game.to_bytes(save_game.file_path())?;
+    if output.len() != original_file.len() {
+        changes.push(Change {
+            path: String::from("File size"),
+            old: Some(original_file.len().to_string()),
+            new: Some(output.len().to_string()),
+        });
+    }
+    let changed_bytes = output.iter().zip(original_file).filter(|(new, old)| new != old).count();
+    if changed_bytes > 0 {
+        changes.push(Change {
+            path: String::from("Changed bytes"),
+            old: None,
+            new: Some(changed_bytes.to_string()),
+        });
+    }
+    Ok(changes)
+}
+
+/// Each field of the edited save with the same field of the opened file
+pub fn pair_originals(edited: &SaveGame, original: &SaveGame) -> Originals {
+    Originals::pair(fields(edited), fields(original))
+}
+
+// What the inputs can revert, the ME1 player has no raw tree of its own
+fn fields(save_game: &SaveGame) -> Fields {
+    match_raw_tree!(save_game, root => raw_ui::fields_of(root),
+        SaveGame::MassEffect1 { save_game, .. } => {
+            let save_game = save_game.borrow();
+            let mut fields = raw_ui::me1_player_fields(&save_game.player());
+            fields.extend(raw_ui::fields_of(&save_game.state));
+            fields
+        },
+        SaveGame::MassEffect2Quick { save_game, .. } => raw_ui::fields_of(&save_game.borrow().plot),
+        SaveGame::MassEffect3Quick { save_game, .. } => raw_ui::fields_of(&save_game.borrow().plot),
+    )
+}
+
+/// Plots first, then anything else by its path in the save
+pub fn watch_values(save_game: &SaveGame, paths: &[String]) -> Result<Vec<Option<String>>> {
+    let fields = diff::pick(save_game.flatten(Some(paths))?, paths);
+
+    let plot_values = plot_values(save_game);
+    let values =
+        paths.iter().zip(fields).map(|(path, field)| plot_values.value(path).or(field)).collect();
+    Ok(values)
+}
+
+/// The plots set by the game in `save_game` since it was `last_seen_file`. Not being able to
+/// tell is not worth an error.
+pub fn changed_by_game(last_seen_file: &[u8], save_game: &SaveGame) -> Vec<Change> {
+    match save_game.reparse(last_seen_file) {
+        Ok(last_seen) => diff::diff_plots(&plot_values(&last_seen), &plot_values(save_game)),
+        Err(err) => {
+            logger::warn(format!("Failed to read the last seen save: {:#}", err));
+            Vec::new()
+        }
+    }
+}
+
+pub fn plot_values(save_game: &SaveGame) -> PlotValues {
+    save_game.with_plots(|plots| match plots {
+        Plots::Shared(plot) => PlotValues::from(plot),
+        Plots::Me3(plot) => PlotValues::from(plot),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::PathBuf};
+
+    use anyhow::Result;
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{
+        save_data::{mass_effect_1::Me1SaveGame, mass_effect_1_le::Me1LeSaveGame, RcRef},
+        unreal,
+    };
+
+    fn open<T: for<'de> Deserialize<'de>>(path: &str) -> Result<RcRef<T>> {
+        let input = fs::read(path)?;
+        Ok(RcRef::new(unreal::Deserializer::from_bytes(&input)?))
+    }
+
+    #[test]
+    fn diff_me1_save_game() -> Result<()> {
+        let file_path = PathBuf::from("test/ME1Save.MassEffectSave");
+        let original = SaveGame::MassEffect1 {
+            file_path: file_path.clone(),
+            save_game: open("test/ME1Save.MassEffectSave")?,
+        };
+        let current = open::<Me1SaveGame>("test/ME1Save.MassEffectSave")?;
+
+        // The parsed player and state, not the bytes of the zip
+        let fields = original.flatten(None)?;
+        assert!(fields.iter().all(|(path, _)| {
+            path.starts_with("player.names")
+                || path.starts_with("player.datas")
+                || path.starts_with("state")
+        }));
+        let diff = |current: &RcRef<Me1SaveGame>| -> Result<Vec<Change>> {
+            let current = SaveGame::MassEffect1 {
+                file_path: file_path.clone(),
+                save_game: RcRef::clone(current),
+            };
+            Ok(diff::diff_flattened(original.flatten(None)?, current.flatten(None)?))
+        };
+        assert!(diff(&current)?.is_empty());
+
+        {
+            let current = current.borrow();
+            let player = current.player();
+            let m_player = player.m_player().unwrap();
+            let level = player.find_int(&m_player.properties, "m_XPLevel").unwrap();
+            level.set(level.get() + 1);
+        }
+        let changes = diff(&current)?;
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].path.starts_with("player.datas["));
+        Ok(())
+    }
+
+    #[test]
+    fn diff_and_watch_me1_le_save_game() -> Result<()> {
+        let file_path = PathBuf::from("test/ME1LeSave.pcsav");
+        let original = SaveGame::MassEffect1Le {
+            file_path: file_path.clone(),
+            save_game: open("test/ME1LeSave.pcsav")?,
+        };
+        let current: RcRef<Me1LeSaveGame> = open("test/ME1LeSave.pcsav")?;
+        let current_save = SaveGame::MassEffect1Le { file_path, save_game: RcRef::clone(&current) };
+
+        // The parsed save data, not the compressed chunks
+        assert!(
+            diff::diff_flattened(original.flatten(None)?, current_save.flatten(None)?).is_empty()
+        );
+        current.borrow().save_data.borrow().player.borrow_mut().set_credits(1_234_567);
+        let changes = diff::diff_flattened(original.flatten(None)?, current_save.flatten(None)?);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "player.credits");
+        assert_eq!(changes[0].new.as_deref(), Some("1234567"));
+
+        // Only what leads to the watched paths
+        let paths = [String::from("player.credits"), String::from("player.nope")];
+        let fields = current_save.flatten(Some(&paths))?;
+        assert_eq!(fields, [(String::from("player.credits"), String::from("1234567"))]);
+        let values = watch_values(&current_save, &paths)?;
+        assert_eq!(values, [Some(String::from("1234567")), None]);
+        Ok(())
+    }
+}
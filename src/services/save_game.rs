@@ -0,0 +1,493 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, ensure, Context, Result};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    gui::{components::raw_ui, raw_ui::RawUi},
+    save_data::{
+        diff,
+        fixup::{self, Fixups},
+        mass_effect_1::{Me1MagicNumber, Me1SaveGame},
+        mass_effect_1_le::{Me1LeMagicNumber, Me1LeSaveData, Me1LeSaveGame, Me1LeVersion},
+        mass_effect_2::{
+            Me2LeQuickHead, Me2LeSaveGame, Me2LeVersion, Me2QuickHead, Me2SaveGame, Me2Version,
+        },
+        mass_effect_3::{plot::PlotTable as Me3PlotTable, Me3QuickHead, Me3SaveGame, Me3Version},
+        shared::{
+            plot::{BitVec, PlotTable},
+            quick_plot::QuickPlotSave,
+        },
+        RcCell, RcRef,
+    },
+    services::session::LaunchGame,
+    unreal::{self, Section},
+};
+
+/// Matches a `&SaveGame` with `$root` bound to the `&RcRef` of the tree of fields its Raw Data
+/// tab walks, the save data of ME1LE. The arms of ME1 and the quick plot edits, which have none,
+/// follow.
+macro_rules! match_raw_tree {
+    ($save_game:expr, $root:ident => $body:expr, $($other:pat => $other_body:expr),+ $(,)?) => {
+        match $save_game {
+            $crate::services::save_game::SaveGame::MassEffect1Le { save_game, .. } => {
+                match &save_game.borrow().save_data {
+                    $root => $body,
+                }
+            }
+            $crate::services::save_game::SaveGame::MassEffect1LePs4 { save_game: $root, .. } => {
+                $body
+            }
+            $crate::services::save_game::SaveGame::MassEffect2 { save_game: $root, .. } => $body,
+            $crate::services::save_game::SaveGame::MassEffect2Le { save_game: $root, .. } => $body,
+            $crate::services::save_game::SaveGame::MassEffect3 { save_game: $root, .. } => $body,
+            $($other => $other_body),+
+        }
+    };
+}
+pub(crate) use match_raw_tree;
+
+#[derive(Clone)]
+pub enum SaveGame {
+    MassEffect1 {
+        file_path: PathBuf,
+        save_game: RcRef<Me1SaveGame>,
+    },
+    MassEffect1Le {
+        file_path: PathBuf,
+        save_game: RcRef<Me1LeSaveGame>,
+    },
+    MassEffect1LePs4 {
+        file_path: PathBuf,
+        save_game: RcRef<Me1LeSaveData>,
+    },
+    MassEffect2 {
+        file_path: PathBuf,
+        save_game: RcRef<Me2SaveGame>,
+    },
+    MassEffect2Le {
+        file_path: PathBuf,
+        save_game: RcRef<Me2LeSaveGame>,
+    },
+    MassEffect3 {
+        file_path: PathBuf,
+        save_game: RcRef<Me3SaveGame>,
+    },
+    // Only the plot table is parsed
+    MassEffect2Quick {
+        file_path: PathBuf,
+        save_game: RcRef<QuickPlotSave<PlotTable>>,
+        is_legendary: bool,
+        is_xbox360: bool,
+    },
+    MassEffect3Quick {
+        file_path: PathBuf,
+        save_game: RcRef<QuickPlotSave<Me3PlotTable>>,
+        is_xbox360: bool,
+    },
+}
+
+/// The plot table of a save, ME3 has its own
+pub enum Plots<'a> {
+    Shared(&'a PlotTable),
+    Me3(&'a Me3PlotTable),
+}
+
+impl SaveGame {
+    pub fn file_path(&self) -> &PathBuf {
+        match self {
+            SaveGame::MassEffect1 { file_path, .. }
+            | SaveGame::MassEffect1Le { file_path, .. }
+            | SaveGame::MassEffect1LePs4 { file_path, .. }
+            | SaveGame::MassEffect2 { file_path, .. }
+            | SaveGame::MassEffect2Le { file_path, .. }
+            | SaveGame::MassEffect3 { file_path, .. }
+            | SaveGame::MassEffect2Quick { file_path, .. }
+            | SaveGame::MassEffect3Quick { file_path, .. } => file_path,
+        }
+    }
+
+    pub fn is_quick(&self) -> bool {
+        matches!(self, SaveGame::MassEffect2Quick { .. } | SaveGame::MassEffect3Quick { .. })
+    }
+
+    pub fn game_name(&self) -> &'static str {
+        match self {
+            SaveGame::MassEffect1 { .. } => "Mass Effect 1",
+            SaveGame::MassEffect1Le { .. } => "Mass Effect 1 Legendary",
+            SaveGame::MassEffect1LePs4 { .. } => "Mass Effect 1 Legendary PS4",
+            SaveGame::MassEffect2 { .. } => "Mass Effect 2",
+            SaveGame::MassEffect2Le { .. } => "Mass Effect 2 Legendary",
+            SaveGame::MassEffect3 { .. } => "Mass Effect 3",
+            SaveGame::MassEffect2Quick { is_legendary: false, .. } => {
+                "Mass Effect 2 (quick plot edit)"
+            }
+            SaveGame::MassEffect2Quick { is_legendary: true, .. } => {
+                "Mass Effect 2 Legendary (quick plot edit)"
+            }
+            SaveGame::MassEffect3Quick { .. } => "Mass Effect 3 (quick plot edit)",
+        }
+    }
+
+    // Saves whose plots can be compared, whether opened whole or for a quick plot edit
+    pub fn plot_game(&self) -> &'static str {
+        match self {
+            SaveGame::MassEffect1 { .. } => "Mass Effect 1",
+            SaveGame::MassEffect1Le { .. } | SaveGame::MassEffect1LePs4 { .. } => {
+                "Mass Effect 1 Legendary"
+            }
+            SaveGame::MassEffect2 { .. }
+            | SaveGame::MassEffect2Quick { is_legendary: false, .. } => "Mass Effect 2",
+            SaveGame::MassEffect2Le { .. }
+            | SaveGame::MassEffect2Quick { is_legendary: true, .. } => "Mass Effect 2 Legendary",
+            SaveGame::MassEffect3 { .. } | SaveGame::MassEffect3Quick { .. } => "Mass Effect 3",
+        }
+    }
+
+    /// Calls `f` with the plot table of the save, the same one as the Raw Plot tab
+    pub fn with_plots<T>(&self, f: impl FnOnce(Plots) -> T) -> T {
+        match self {
+            SaveGame::MassEffect1 { save_game, .. } => {
+                f(Plots::Shared(&save_game.borrow().state().plot()))
+            }
+            SaveGame::MassEffect1Le { save_game, .. } => {
+                f(Plots::Shared(&save_game.borrow().save_data.borrow().plot()))
+            }
+            SaveGame::MassEffect1LePs4 { save_game, .. } => {
+                f(Plots::Shared(&save_game.borrow().plot()))
+            }
+            SaveGame::MassEffect2 { save_game, .. } => f(Plots::Shared(&save_game.borrow().plot())),
+            SaveGame::MassEffect2Le { save_game, .. } => {
+                f(Plots::Shared(&save_game.borrow().plot()))
+            }
+            SaveGame::MassEffect3 { save_game, .. } => f(Plots::Me3(&save_game.borrow().plot())),
+            SaveGame::MassEffect2Quick { save_game, .. } => {
+                f(Plots::Shared(&save_game.borrow().plot.borrow()))
+            }
+            SaveGame::MassEffect3Quick { save_game, .. } => {
+                f(Plots::Me3(&save_game.borrow().plot.borrow()))
+            }
+        }
+    }
+
+    /// Every value of the parsed save with its path, like the Changes tab names them, only what
+    /// leads to one of `only` if given. Not the save as it is written: the ME1 and ME1LE files are
+    /// compressed, the quick saves keep everything but the plot as raw bytes.
+    pub fn flatten(&self, only: Option<&[String]>) -> unreal::Result<Vec<(String, String)>> {
+        match_raw_tree!(self, root => diff::flatten_at("", root, only),
+            SaveGame::MassEffect1 { save_game, .. } => {
+                let save_game = save_game.borrow();
+                let player = save_game.player();
+                let mut fields = diff::flatten_at("player.names", &*player.names.borrow(), only)?;
+                fields.extend(diff::flatten_at("player.datas", &player.datas, only)?);
+                fields.extend(diff::flatten_at("state", &*save_game.state(), only)?);
+                Ok(fields)
+            },
+            SaveGame::MassEffect2Quick { save_game, .. } => {
+                diff::flatten_at("plot", &save_game.borrow().plot, only)
+            },
+            SaveGame::MassEffect3Quick { save_game, .. } => {
+                diff::flatten_at("plot", &save_game.borrow().plot, only)
+            },
+        )
+    }
+
+    /// Sets the value of the Raw Data tab at `path`, `Player > Credits`, to `value` and returns
+    /// the one it had and the one it has now. `None` only reads it.
+    pub fn raw_value(&self, path: &str, value: Option<&str>) -> Result<(String, String)> {
+        fn edit<T: RawUi>(root: &T, path: &str, value: Option<&str>) -> Result<(String, String)> {
+            match value {
+                Some(value) => raw_ui::set_raw_value(root, path, value),
+                None => raw_ui::get_raw_value(root, path).map(|value| (value.clone(), value)),
+            }
+        }
+
+        match_raw_tree!(self, root => edit(root, path, value),
+            // Its Raw Data tab walks the properties of the package, not a tree of fields
+            SaveGame::MassEffect1 { .. } => {
+                bail!(
+                    "Only the plots of an original Mass Effect 1 save can be edited, not `{}`",
+                    path
+                )
+            },
+            SaveGame::MassEffect2Quick { .. } | SaveGame::MassEffect3Quick { .. } => {
+                bail!("Only the plots of a quick plot edit can be edited, not `{}`", path)
+            },
+        )
+    }
+
+    // Consoles and XBOX 360 saves have nothing to launch
+    pub fn launch_game(&self) -> Option<LaunchGame> {
+        // ME3 and ME3 Legendary share the same format
+        let is_legendary = |file_path: &Path| {
+            file_path.components().any(|component| {
+                component.as_os_str().to_string_lossy().to_lowercase()
+                    == "mass effect legendary edition"
+            })
+        };
+        match self {
+            SaveGame::MassEffect1 { .. } => Some(LaunchGame::MassEffect1),
+            SaveGame::MassEffect1Le { .. }
+            | SaveGame::MassEffect2Le { .. }
+            | SaveGame::MassEffect2Quick { is_legendary: true, .. } => Some(LaunchGame::Legendary),
+            SaveGame::MassEffect1LePs4 { .. } => None,
+            SaveGame::MassEffect2 { save_game, .. } => {
+                (!save_game.borrow().is_xbox360()).then(|| LaunchGame::MassEffect2)
+            }
+            SaveGame::MassEffect2Quick { is_xbox360, .. } => {
+                (!is_xbox360).then(|| LaunchGame::MassEffect2)
+            }
+            SaveGame::MassEffect3 { file_path, save_game } => {
+                (!save_game.borrow().is_xbox360()).then(|| Self::me3_game(is_legendary(file_path)))
+            }
+            SaveGame::MassEffect3Quick { file_path, is_xbox360, .. } => {
+                (!is_xbox360).then(|| Self::me3_game(is_legendary(file_path)))
+            }
+        }
+    }
+
+    fn me3_game(is_legendary: bool) -> LaunchGame {
+        if is_legendary {
+            LaunchGame::Legendary
+        } else {
+            LaunchGame::MassEffect3
+        }
+    }
+
+    /// Tells the game from the header of `input`. `sections` is filled with the time each part of
+    /// the save took to parse.
+    pub fn parse(file_path: PathBuf, input: &[u8], sections: &mut Vec<Section>) -> Result<Self> {
+        fn header<'de, T>(header: &'de [u8]) -> Result<T, unreal::Error>
+        where
+            T: Deserialize<'de>,
+        {
+            unreal::Deserializer::from_bytes::<T>(header)
+        }
+
+        fn profiled<'de, T>(
+            input: &'de [u8], is_le: bool, sections: &mut Vec<Section>,
+        ) -> Result<T, unreal::Error>
+        where
+            T: Deserialize<'de>,
+        {
+            let (value, profile) = unreal::Deserializer::from_bytes_profiled(input, is_le)?;
+            *sections = profile;
+            Ok(value)
+        }
+
+        let save_game = if header::<Me1MagicNumber>(input).is_ok() {
+            // ME1
+            SaveGame::MassEffect1 { file_path, save_game: profiled(input, true, sections)? }
+        } else if header::<Me1LeMagicNumber>(input).is_ok() {
+            // ME1 Legendary
+            SaveGame::MassEffect1Le { file_path, save_game: profiled(input, true, sections)? }
+        } else if header::<Me1LeVersion>(input).is_ok() {
+            // ME1LE PS4
+            SaveGame::MassEffect1LePs4 { file_path, save_game: profiled(input, true, sections)? }
+        } else if let Ok(save) = header::<Me2Version>(input) {
+            // ME2
+            let save_game = profiled(input, !save.is_xbox360, sections)?;
+            SaveGame::MassEffect2 { file_path, save_game }
+        } else if header::<Me2LeVersion>(input).is_ok() {
+            // ME2 Legendary
+            SaveGame::MassEffect2Le { file_path, save_game: profiled(input, true, sections)? }
+        } else if let Ok(save) = header::<Me3Version>(input) {
+            // ME3
+            let save_game = profiled(input, !save.is_xbox360, sections)?;
+            SaveGame::MassEffect3 { file_path, save_game }
+        } else {
+            bail!("Unsupported file");
+        };
+        Ok(save_game)
+    }
+
+    /// Only the plot table of a Mass Effect 2 or 3 save, the rest is kept as bytes
+    pub fn parse_quick(file_path: PathBuf, input: &[u8]) -> Result<Self> {
+        let save_game = if let Ok(save) = unreal::Deserializer::from_bytes::<Me2Version>(input) {
+            let is_xbox360 = save.is_xbox360;
+            let save_game = QuickPlotSave::from_bytes::<Me2QuickHead>(input, !is_xbox360)?;
+            SaveGame::MassEffect2Quick {
+                file_path,
+                save_game: save_game.into(),
+                is_legendary: false,
+                is_xbox360,
+            }
+        } else if unreal::Deserializer::from_bytes::<Me2LeVersion>(input).is_ok() {
+            let save_game = QuickPlotSave::from_bytes::<Me2LeQuickHead>(input, true)?;
+            SaveGame::MassEffect2Quick {
+                file_path,
+                save_game: save_game.into(),
+                is_legendary: true,
+                is_xbox360: false,
+            }
+        } else if let Ok(save) = unreal::Deserializer::from_bytes::<Me3Version>(input) {
+            let is_xbox360 = save.is_xbox360;
+            let save_game = QuickPlotSave::from_bytes::<Me3QuickHead>(input, !is_xbox360)?;
+            SaveGame::MassEffect3Quick { file_path, save_game: save_game.into(), is_xbox360 }
+        } else {
+            bail!("The quick plot edit only supports Mass Effect 2 and 3 saves");
+        };
+        Ok(save_game)
+    }
+
+    /// The same kind of save parsed from other bytes, sharing nothing with it
+    pub fn reparse(&self, input: &[u8]) -> Result<Self> {
+        let file_path = self.file_path().clone();
+        if self.is_quick() {
+            Self::parse_quick(file_path, input)
+        } else {
+            Self::parse(file_path, input, &mut Vec::new())
+        }
+    }
+
+    /// The save as written to `path`, whose extension picks the platform
+    pub fn to_bytes(&self, path: &Path) -> Result<Vec<u8>> {
+        let to_xbox360 = is_xbox360_path(path);
+        let output = match self {
+            SaveGame::MassEffect1 { save_game, .. } => {
+                fixup::to_bytes(&*save_game.borrow(), false)?
+            }
+            SaveGame::MassEffect1Le { save_game, .. } if is_ps4_path(path) => {
+                fixup::to_bytes(&*save_game.borrow().save_data.borrow(), false)?
+            }
+            SaveGame::MassEffect1Le { save_game, .. } => {
+                fixup::to_bytes(&*save_game.borrow(), false)?
+            }
+            SaveGame::MassEffect1LePs4 { save_game, .. } if is_pc_path(path) => {
+                let save_game = Me1LeSaveGame::from_save_data(RcRef::clone(save_game));
+                fixup::to_bytes(&save_game, false)?
+            }
+            SaveGame::MassEffect1LePs4 { save_game, .. } => {
+                fixup::to_bytes(&*save_game.borrow(), false)?
+            }
+            SaveGame::MassEffect2 { save_game, .. } => {
+                fixup::to_bytes(&*save_game.borrow(), to_xbox360)?
+            }
+            SaveGame::MassEffect2Le { save_game, .. } => {
+                fixup::to_bytes(&*save_game.borrow(), false)?
+            }
+            SaveGame::MassEffect3 { save_game, .. } => {
+                fixup::to_bytes(&*save_game.borrow(), to_xbox360)?
+            }
+            SaveGame::MassEffect2Quick { save_game, is_xbox360, .. } => {
+                quick_to_bytes(path, save_game, *is_xbox360)?
+            }
+            SaveGame::MassEffect3Quick { save_game, is_xbox360, .. } => {
+                quick_to_bytes(path, save_game, *is_xbox360)?
+            }
+        };
+        Ok(output)
+    }
+
+    /// What the format declares to be computed again on save
+    pub fn fixups(&self) -> &'static [fixup::Fixup] {
+        match self {
+            SaveGame::MassEffect1 { .. } => Me1SaveGame::FIXUPS,
+            SaveGame::MassEffect1Le { .. } => Me1LeSaveGame::FIXUPS,
+            SaveGame::MassEffect1LePs4 { .. } => Me1LeSaveData::FIXUPS,
+            SaveGame::MassEffect2 { .. } => Me2SaveGame::FIXUPS,
+            SaveGame::MassEffect2Le { .. } => Me2LeSaveGame::FIXUPS,
+            SaveGame::MassEffect3 { .. } => Me3SaveGame::FIXUPS,
+            SaveGame::MassEffect2Quick { .. } => QuickPlotSave::<PlotTable>::FIXUPS,
+            SaveGame::MassEffect3Quick { .. } => QuickPlotSave::<Me3PlotTable>::FIXUPS,
+        }
+    }
+}
+
+// The tables of a save hold every plot of the game, an id past their end is a typo that would
+// grow them as much. The integers and floats of ME3 only hold the plots set, by id.
+impl Plots<'_> {
+    pub fn booleans(&self) -> &RcRef<BitVec> {
+        match self {
+            Plots::Shared(plot) => &plot.booleans,
+            Plots::Me3(plot) => &plot.booleans,
+        }
+    }
+
+    pub fn boolean(&self, id: usize) -> Result<bool> {
+        let booleans = self.booleans().borrow();
+        match booleans.get(id) {
+            Some(value) => Ok(*value),
+            None => bail!("No Boolean {}, the table stops at {}", id, booleans.len()),
+        }
+    }
+
+    // Returns the value it had
+    pub fn set_boolean(&self, id: usize, value: bool) -> Result<bool> {
+        let mut booleans = self.booleans().borrow_mut();
+        let len = booleans.len();
+        ensure!(id < len, "No Boolean {}, the table stops at {}", id, len);
+        Ok(booleans.replace(id, value))
+    }
+
+    // A plot of ME3 not set yet is added if `add`, read as 0 otherwise
+    pub fn integer(&self, id: usize, add: bool) -> Result<RcCell<i32>> {
+        match self {
+            Plots::Shared(plot) => listed(&plot.integers, "Integer", id),
+            Plots::Me3(plot) => by_id(&plot.integers, id, add),
+        }
+    }
+
+    pub fn float(&self, id: usize, add: bool) -> Result<RcCell<f32>> {
+        match self {
+            Plots::Shared(plot) => listed(&plot.floats, "Float", id),
+            Plots::Me3(plot) => by_id(&plot.floats, id, add),
+        }
+    }
+
+    // Returns the value it had, a plot of ME3 not set yet is added
+    pub fn set_integer(&self, id: usize, value: i32) -> Result<i32> {
+        self.integer(id, true).map(|integer| replace(&integer, value))
+    }
+
+    pub fn set_float(&self, id: usize, value: f32) -> Result<f32> {
+        self.float(id, true).map(|float| replace(&float, value))
+    }
+}
+
+fn replace<T: Copy>(cell: &RcCell<T>, value: T) -> T {
+    let before = cell.get();
+    cell.set(value);
+    before
+}
+
+fn listed<T: Copy>(table: &RcRef<Vec<RcCell<T>>>, kind: &str, id: usize) -> Result<RcCell<T>> {
+    let table = table.borrow();
+    match table.get(id) {
+        Some(value) => Ok(RcCell::clone(value)),
+        None => bail!("No {} {}, the table stops at {}", kind, id, table.len()),
+    }
+}
+
+fn by_id<T>(table: &RcRef<IndexMap<i32, RcCell<T>>>, id: usize, add: bool) -> Result<RcCell<T>>
+where
+    T: Copy + Default,
+{
+    let id = i32::try_from(id).with_context(|| format!("Invalid plot id {}", id))?;
+    if add {
+        return Ok(RcCell::clone(table.borrow_mut().entry(id).or_default()));
+    }
+    Ok(table.borrow().get(&id).map(RcCell::clone).unwrap_or_default())
+}
+
+fn quick_to_bytes<P: Serialize>(
+    path: &Path, save_game: &RcRef<QuickPlotSave<P>>, is_xbox360: bool,
+) -> Result<Vec<u8>> {
+    if is_xbox360_path(path) != is_xbox360 {
+        bail!("A quick plot edit can't change the platform of the save, open it normally");
+    }
+    fixup::to_bytes(&*save_game.borrow(), is_xbox360)
+}
+
+pub fn is_xbox360_path(path: &Path) -> bool {
+    path.extension().map(|ext| ext.eq_ignore_ascii_case("xbsav")).unwrap_or_default()
+}
+
+fn is_ps4_path(path: &Path) -> bool {
+    path.extension().map(|ext| ext.eq_ignore_ascii_case("ps4sav")).unwrap_or_default()
+}
+
+fn is_pc_path(path: &Path) -> bool {
+    path.extension().map(|ext| ext.eq_ignore_ascii_case("pcsav")).unwrap_or_default()
+}
@@ -1,54 +1,113 @@
-use std::{path::PathBuf, rc::Rc};
+use std::{cmp::Ordering, future::Future, path::PathBuf, rc::Rc};
 
-use anyhow::{bail, Context as ErrorContext, Error, Result};
-use crc::{Crc, CRC_32_BZIP2};
-use gloo::utils;
+use anyhow::{anyhow, bail, Context as ErrorContext, Error, Result};
+use gloo::{
+    events::{EventListener, EventListenerOptions},
+    timers::{callback::Timeout, future::TimeoutFuture},
+    utils,
+};
+use js_sys::Date;
 use ron::ser::PrettyConfig;
-use serde::Deserialize;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlInputElement, HtmlTextAreaElement, KeyboardEvent};
 use yew::{prelude::*, ContextProvider};
 
 use crate::{
-    gui::Theme,
-    save_data::mass_effect_1_le::Me1LeMagicNumber,
+    gui::{components::raw_ui::Originals, Theme},
     save_data::{
-        mass_effect_1::{Me1MagicNumber, Me1SaveGame},
-        mass_effect_1_le::{Me1LeSaveData, Me1LeSaveGame, Me1LeVersion},
-        mass_effect_2::{Me2LeSaveGame, Me2LeVersion, Me2SaveGame, Me2Version},
-        mass_effect_3::{Me3SaveGame, Me3Version},
-        shared::appearance::HeadMorph,
+        diff::{self, Change, PlotResearch, PlotValues},
+        mass_effect_1_le::{import::Me1Import, player::Me1LeClass, Me1LeSaveData},
+        mass_effect_3::player::WarAssetBundle,
+        shared::{
+            appearance::{GibbedGame, HeadMorph},
+            plot_coverage::PlotCoverage,
+            statistics::Statistics,
+        },
         RcRef,
     },
-    services::rpc::{self, Base64File, DialogParams, RpcFile},
-    unreal,
+    services::{
+        appearance::Accent,
+        health::FileHealth,
+        logger,
+        rpc::{
+            self, Backup, Base64File, CloudSave, DialogParams, NotesParams, RestoreBackupParams,
+            RpcFile, SaveFileParams,
+        },
+        save_diff,
+        save_game::SaveGame,
+        save_json,
+        script::{Script, ScriptEdit},
+        session,
+    },
+    unreal::{fuzz, Section},
 };
 
-use super::drop_handler::DropHandler;
+use super::{drop_handler::DropHandler, edit_history::EditHistory};
 
-#[derive(Clone)]
-pub enum SaveGame {
-    MassEffect1 { file_path: PathBuf, save_game: RcRef<Me1SaveGame> },
-    MassEffect1Le { file_path: PathBuf, save_game: RcRef<Me1LeSaveGame> },
-    MassEffect1LePs4 { file_path: PathBuf, save_game: RcRef<Me1LeSaveData> },
-    MassEffect2 { file_path: PathBuf, save_game: RcRef<Me2SaveGame> },
-    MassEffect2Le { file_path: PathBuf, save_game: RcRef<Me2LeSaveGame> },
-    MassEffect3 { file_path: PathBuf, save_game: RcRef<Me3SaveGame> },
-}
+// Edits closer than that are undone together, typing a number say
+const CHECKPOINT_MS: u32 = 500;
 
 pub enum Action {
     OpenSave,
+    OpenPath(PathBuf),
+    OpenQuickPlot,
     SaveSave,
+    SaveAndLaunch,
+    ConvertPlatform,
     ReloadSave,
     ImportHeadMorph(Callback<HeadMorph>),
     ExportHeadMorph(RcRef<HeadMorph>),
+    ExportGibbedHeadMorph(RcRef<HeadMorph>, GibbedGame),
+    ImportWarAssets(Callback<WarAssetBundle>),
+    ExportWarAssets(WarAssetBundle),
+    ExportStatistics,
+    ExportPlotCoverage(PlotCoverage),
+    ExportJson,
+    ImportJson,
+    OpenUpstreamSave(Callback<SaveGame>),
+    ImportMe1Save(Callback<Me1Import>),
+    DiffSave(Callback<Vec<Change>>),
+    WatchValues(Vec<String>, Callback<Vec<Option<String>>>),
+    FuzzSave,
+    SaveBothCopies(bool),
+    WriteProtection(bool),
+    AllowOverwrite,
+    LoadNotes(Callback<String>),
+    SaveNotes(String),
+    ListBackups(Callback<Vec<Backup>>),
+    RestoreBackup(PathBuf),
+    StartResearch,
+    StopResearch,
+    ExportResearch,
+    RunScript(String, Callback<Vec<ScriptEdit>>),
+    // Sent by the document for each edit made in the tabs
+    Edited,
+    Error(Error),
 }
 
 pub enum Msg {
     Action(Action),
-    SaveOpened(SaveGame),
+    SaveOpened(SaveGame, Vec<u8>),
+    SaveReloaded(SaveGame, Vec<u8>, Vec<Change>),
     SaveDropped(Result<(String, Vec<u8>)>),
-    SaveSaved,
+    SaveSaved(Option<Vec<u8>>),
+    CloudSaveFound(Option<CloudSave>),
+    NotesLoaded(String, Callback<String>),
+    BackupsListed(Vec<Backup>, Callback<Vec<Backup>>),
+    NotesSaved,
     HeadMorphImported(HeadMorph, Callback<HeadMorph>),
     HeadMorphExported,
+    WarAssetsImported(WarAssetBundle, Callback<WarAssetBundle>),
+    WarAssetsExported,
+    StatisticsExported,
+    PlotCoverageExported,
+    PlotResearchExported,
+    JsonExported,
+    UpstreamSaveOpened(SaveGame, Callback<SaveGame>),
+    Me1SaveOpened(SaveGame, Callback<Me1Import>),
+    Checkpoint,
+    Undo,
+    Redo,
     Error(Error),
     Noop,
 }
@@ -63,9 +122,27 @@ pub struct Props {
 #[derive(Clone)]
 pub struct SaveHandler {
     pub save_game: Option<Rc<SaveGame>>,
+    pub cloud_save: Option<Rc<CloudSave>>,
+    pub save_both_copies: bool,
+    // `Save` writes a copy next to the opened save instead
+    pub write_protected: bool,
+    // The plots the game changed since the save was last opened or saved, on reload
+    pub changed_by_game: Option<Rc<Vec<Change>>>,
+    pub research: Option<Rc<Research>>,
+    pub health: Option<Rc<FileHealth>>,
+    // Bumped by the edits made from outside the tabs, which are then rebuilt to show them
+    pub revision: usize,
     callback: Callback<Action>,
 }
 
+// Started from the opened save, the next save of the same game opened is compared with it
+#[derive(Clone)]
+pub struct Research {
+    pub game: &'static str,
+    pub snapshot_path: PathBuf,
+    pub result: Option<Rc<PlotResearch>>,
+}
+
 impl SaveHandler {
     pub fn action(&self, action: Action) {
         self.callback.emit(action);
@@ -74,17 +151,56 @@ impl SaveHandler {
 
 impl PartialEq for SaveHandler {
     fn eq(&self, other: &Self) -> bool {
-        match (&self.save_game, &other.save_game) {
+        let save_game = match (&self.save_game, &other.save_game) {
             (Some(this), Some(other)) => Rc::ptr_eq(this, other),
             (None, None) => true,
             _ => false,
-        }
+        };
+        let cloud_save = match (&self.cloud_save, &other.cloud_save) {
+            (Some(this), Some(other)) => Rc::ptr_eq(this, other),
+            (None, None) => true,
+            _ => false,
+        };
+        let changed_by_game = match (&self.changed_by_game, &other.changed_by_game) {
+            (Some(this), Some(other)) => Rc::ptr_eq(this, other),
+            (None, None) => true,
+            _ => false,
+        };
+        let research = match (&self.research, &other.research) {
+            (Some(this), Some(other)) => Rc::ptr_eq(this, other),
+            (None, None) => true,
+            _ => false,
+        };
+        let health = match (&self.health, &other.health) {
+            (Some(this), Some(other)) => Rc::ptr_eq(this, other),
+            (None, None) => true,
+            _ => false,
+        };
+        save_game
+            && cloud_save
+            && changed_by_game
+            && research
+            && health
+            && self.save_both_copies == other.save_both_copies
+            && self.write_protected == other.write_protected
+            && self.revision == other.revision
     }
 }
 
 pub struct SaveHandlerProvider {
     _drop_handler: DropHandler,
+    _shortcut_listener: EventListener,
+    history: EditHistory,
+    checkpoint_timeout: Option<Timeout>,
     save_handler: SaveHandler,
+    original_file: Option<Rc<Vec<u8>>>,
+    // The opened file parsed again, what the fields of the edited save are reverted to
+    original_save: Option<SaveGame>,
+    originals: Originals,
+    // The opened file as it was last read or written by us
+    last_seen_file: Option<Rc<Vec<u8>>>,
+    // The plots when the research was started
+    research_snapshot: Option<PlotValues>,
 }
 
 impl Component for SaveHandlerProvider {
@@ -93,11 +209,41 @@ impl Component for SaveHandlerProvider {
 
     fn create(ctx: &Context<Self>) -> Self {
         let _drop_handler = DropHandler::new(ctx.link().callback(Msg::SaveDropped));
-        let save_handler =
-            SaveHandler { save_game: None, callback: ctx.link().callback(Msg::Action) };
+        let _shortcut_listener = {
+            let link = ctx.link().clone();
+            let options = EventListenerOptions::enable_prevent_default();
+            EventListener::new_with_options(&utils::document(), "keydown", options, move |event| {
+                if let Some(msg) = Self::shortcut(event) {
+                    event.prevent_default();
+                    link.send_message(msg);
+                }
+            })
+        };
+        let save_handler = SaveHandler {
+            save_game: None,
+            cloud_save: None,
+            save_both_copies: false,
+            write_protected: session::is_write_protected(),
+            changed_by_game: None,
+            research: None,
+            health: None,
+            revision: 0,
+            callback: ctx.link().callback(Msg::Action),
+        };
         Self::open_command_line_save(ctx);
 
-        SaveHandlerProvider { _drop_handler, save_handler }
+        SaveHandlerProvider {
+            _drop_handler,
+            _shortcut_listener,
+            history: EditHistory::default(),
+            checkpoint_timeout: None,
+            save_handler,
+            original_file: None,
+            original_save: None,
+            originals: Originals::default(),
+            last_seen_file: None,
+            research_snapshot: None,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -107,50 +253,279 @@ impl Component for SaveHandlerProvider {
                 match action {
                     Action::OpenSave => {
                         let last_dir = self.save_handler.save_game.is_some();
-                        Self::open_save(ctx, last_dir);
+                        Self::open_save(ctx, last_dir, false);
+                    }
+                    Action::OpenPath(path) => Self::open_path(ctx, path),
+                    Action::OpenQuickPlot => {
+                        let last_dir = self.save_handler.save_game.is_some();
+                        Self::open_save(ctx, last_dir, true);
                     }
                     Action::SaveSave => {
                         if let Some(ref save_game) = self.save_handler.save_game {
-                            Self::save_save(ctx, save_game);
+                            Self::save_save(ctx, save_game, self.cloud_path(), None);
+                        }
+                    }
+                    Action::SaveAndLaunch => {
+                        if let Some(ref save_game) = self.save_handler.save_game {
+                            let launch = save_game.launch_game().map(session::launch_target);
+                            Self::save_save(ctx, save_game, self.cloud_path(), launch);
+                        }
+                    }
+                    Action::ConvertPlatform => {
+                        if let Some(ref save_game) = self.save_handler.save_game {
+                            Self::convert_platform(ctx, save_game);
                         }
                     }
                     Action::ReloadSave => {
                         if let Some(ref save_game) = self.save_handler.save_game {
-                            match save_game.as_ref() {
-                                SaveGame::MassEffect1 { file_path, .. }
-                                | SaveGame::MassEffect1Le { file_path, .. }
-                                | SaveGame::MassEffect1LePs4 { file_path, .. }
-                                | SaveGame::MassEffect2 { file_path, .. }
-                                | SaveGame::MassEffect2Le { file_path, .. }
-                                | SaveGame::MassEffect3 { file_path, .. } => {
-                                    Self::reload_save(ctx, file_path.clone())
-                                }
-                            }
+                            Self::reload_save(
+                                ctx,
+                                save_game.file_path().clone(),
+                                save_game.is_quick(),
+                                self.last_seen_file.clone(),
+                            );
                         }
                     }
                     Action::ImportHeadMorph(callback) => Self::import_head_morph(ctx, callback),
                     Action::ExportHeadMorph(head_morph) => Self::export_head_morph(ctx, head_morph),
+                    Action::ExportGibbedHeadMorph(head_morph, game) => {
+                        Self::export_gibbed_head_morph(ctx, head_morph, game)
+                    }
+                    Action::ImportWarAssets(callback) => Self::import_war_assets(ctx, callback),
+                    Action::ExportWarAssets(bundle) => Self::export_war_assets(ctx, bundle),
+                    Action::ExportStatistics => {
+                        if let Some(ref save_game) = self.save_handler.save_game {
+                            Self::export_statistics(ctx, save_game);
+                        }
+                    }
+                    Action::ExportPlotCoverage(coverage) => {
+                        Self::export_plot_coverage(ctx, coverage)
+                    }
+                    Action::ExportJson => {
+                        if let Some(ref save_game) = self.save_handler.save_game {
+                            Self::export_json(ctx, save_game);
+                        }
+                    }
+                    Action::ImportJson => Self::import_json(ctx),
+                    Action::OpenUpstreamSave(callback) => Self::open_upstream_save(ctx, callback),
+                    Action::ImportMe1Save(callback) => {
+                        let opened = ctx
+                            .link()
+                            .callback(move |me1| Msg::Me1SaveOpened(me1, callback.clone()));
+                        Self::open_upstream_save(ctx, opened);
+                    }
+                    Action::DiffSave(callback) => {
+                        if let (Some(save_game), Some(original_save), Some(original_file)) =
+                            (&self.save_handler.save_game, &self.original_save, &self.original_file)
+                        {
+                            match save_diff::diff_save(save_game, original_save, original_file)
+                                .context("Failed to compare the save with the original file")
+                            {
+                                Ok(changes) => callback.emit(changes),
+                                Err(err) => Self::emit_error(ctx, err),
+                            }
+                        }
+                    }
+                    Action::WatchValues(paths, callback) => {
+                        if let Some(ref save_game) = self.save_handler.save_game {
+                            match save_diff::watch_values(save_game, &paths)
+                                .context("Failed to read the watched values")
+                            {
+                                Ok(values) => callback.emit(values),
+                                Err(err) => Self::emit_error(ctx, err),
+                            }
+                        }
+                    }
+                    Action::LoadNotes(callback) => {
+                        if let Some(ref save_game) = self.save_handler.save_game {
+                            Self::load_notes(ctx, save_game.file_path().clone(), callback);
+                        }
+                    }
+                    Action::SaveNotes(notes) => {
+                        if let Some(ref save_game) = self.save_handler.save_game {
+                            Self::save_notes(ctx, save_game.file_path().clone(), notes);
+                        }
+                    }
+                    Action::ListBackups(callback) => {
+                        if let Some(ref save_game) = self.save_handler.save_game {
+                            Self::list_backups(ctx, save_game.file_path().clone(), callback);
+                        }
+                    }
+                    Action::RestoreBackup(backup) => {
+                        if let Some(ref save_game) = self.save_handler.save_game {
+                            let path = save_game.file_path().clone();
+                            Self::restore_backup(ctx, path, backup, save_game.is_quick());
+                        }
+                    }
+                    Action::FuzzSave => {
+                        if let (Some(save_game), Some(original_file)) =
+                            (&self.save_handler.save_game, &self.original_file)
+                        {
+                            Self::fuzz_save(ctx, save_game, Rc::clone(original_file));
+                        }
+                    }
+                    Action::SaveBothCopies(save_both_copies) => {
+                        self.save_handler.save_both_copies = save_both_copies;
+                        return true;
+                    }
+                    Action::WriteProtection(protect) => {
+                        session::set_write_protection(protect);
+                        self.save_handler.write_protected = session::is_write_protected();
+                        return true;
+                    }
+                    Action::AllowOverwrite => {
+                        session::allow_overwrite(true);
+                        self.save_handler.write_protected = session::is_write_protected();
+                        return true;
+                    }
+                    Action::StartResearch => {
+                        if let Some(ref save_game) = self.save_handler.save_game {
+                            self.research_snapshot = Some(save_diff::plot_values(save_game));
+                            self.save_handler.research = Some(Rc::new(Research {
+                                game: save_game.plot_game(),
+                                snapshot_path: save_game.file_path().clone(),
+                                result: None,
+                            }));
+                            return true;
+                        }
+                    }
+                    Action::StopResearch => {
+                        self.research_snapshot = None;
+                        self.save_handler.research = None;
+                        return true;
+                    }
+                    Action::Edited => {
+                        // Written once the edits have stopped, each edit resets the wait
+                        if self.save_handler.save_game.is_some() {
+                            let link = ctx.link().clone();
+                            let timeout = Timeout::new(CHECKPOINT_MS, move || {
+                                link.send_message(Msg::Checkpoint)
+                            });
+                            self.checkpoint_timeout = Some(timeout);
+                        }
+                    }
+                    Action::Error(err) => Self::emit_error(ctx, err),
+                    Action::RunScript(script, callback) => {
+                        if let Some(ref save_game) = self.save_handler.save_game {
+                            match Script::compile(&script)
+                                .and_then(|script| script.try_run(save_game))
+                                .context("Failed to run the script")
+                            {
+                                Ok(edits) => {
+                                    logger::info(format!("Script ran, {} edits", edits.len()));
+                                    callback.emit(edits);
+                                    // Undone at once
+                                    self.checkpoint();
+                                    // Back to the same tab once rebuilt
+                                    session::start_restoring();
+                                    self.save_handler.revision += 1;
+                                    ctx.props().onnotification.emit("Script ran");
+                                    return true;
+                                }
+                                Err(err) => Self::emit_error(ctx, err),
+                            }
+                        }
+                    }
+                    Action::ExportResearch => {
+                        let result = self
+                            .save_handler
+                            .research
+                            .as_ref()
+                            .and_then(|research| research.result.as_ref().map(Rc::clone));
+                        if let Some(result) = result {
+                            Self::export_plot_research(ctx, result);
+                        }
+                    }
                 }
                 false
             }
             // Messages
-            Msg::SaveOpened(save_game) => {
+            Msg::SaveOpened(save_game, original_file) => {
+                logger::info(format!(
+                    "Opened `{}` as {} ({} bytes)",
+                    save_game.file_path().display(),
+                    save_game.game_name(),
+                    original_file.len()
+                ));
+                Self::find_cloud_save(ctx, save_game.file_path().clone());
+                self.save_handler.cloud_save = None;
+                self.save_handler.save_both_copies = false;
+                self.save_handler.changed_by_game = None;
+                self.original_save = match save_game.reparse(&original_file) {
+                    Ok(original_save) => Some(original_save),
+                    Err(err) => {
+                        logger::warn(format!("Fields won't be revertable: {:?}", err));
+                        None
+                    }
+                };
+                self.save_handler.health =
+                    Some(Rc::new(FileHealth::check(&save_game, &original_file)));
                 self.save_handler.save_game = Some(save_game.into());
+                self.pair_originals();
+                self.reset_history();
+                let original_file = Rc::new(original_file);
+                self.last_seen_file = Some(Rc::clone(&original_file));
+                self.original_file = Some(original_file);
+                self.compare_research(ctx);
                 self.change_theme();
                 ctx.props().onnotification.emit("Opened");
                 true
             }
+            Msg::SaveReloaded(save_game, original_file, changed_by_game) => {
+                if !changed_by_game.is_empty() {
+                    logger::info(format!(
+                        "{} plots changed by the game since last seen",
+                        changed_by_game.len()
+                    ));
+                }
+                self.update(ctx, Msg::SaveOpened(save_game, original_file));
+                self.save_handler.changed_by_game =
+                    (!changed_by_game.is_empty()).then(|| Rc::new(changed_by_game));
+                true
+            }
             Msg::SaveDropped(result) => {
                 match result {
                     Ok((file_name, bytes)) => Self::open_dropped_file(ctx, file_name, bytes),
-                    Err(err) => ctx.props().onerror.emit(err),
+                    Err(err) => Self::emit_error(ctx, err),
                 }
                 false
             }
-            Msg::SaveSaved => {
+            Msg::SaveSaved(written_file) => {
+                logger::info("Save saved");
+                if let Some(written_file) = written_file {
+                    self.last_seen_file = Some(Rc::new(written_file));
+                }
+                // The timestamps have changed
+                if let Some(ref save_game) = self.save_handler.save_game {
+                    Self::find_cloud_save(ctx, save_game.file_path().clone());
+                }
                 ctx.props().onnotification.emit("Saved");
                 false
             }
+            Msg::CloudSaveFound(cloud_save) => {
+                if let Some(ref cloud_save) = cloud_save {
+                    if cloud_save.is_diverging() {
+                        logger::warn(format!(
+                            "The cloud copy `{}` differs from the opened save",
+                            cloud_save.path.display()
+                        ));
+                    }
+                }
+                self.save_handler.cloud_save = cloud_save.map(Rc::new);
+                true
+            }
+            Msg::BackupsListed(backups, callback) => {
+                callback.emit(backups);
+                false
+            }
+            Msg::NotesLoaded(notes, callback) => {
+                callback.emit(notes);
+                false
+            }
+            Msg::NotesSaved => {
+                ctx.props().onnotification.emit("Notes saved");
+                false
+            }
             Msg::HeadMorphImported(head_morph, callback) => {
                 callback.emit(head_morph);
                 ctx.props().onnotification.emit("Imported");
@@ -160,8 +535,44 @@ impl Component for SaveHandlerProvider {
                 ctx.props().onnotification.emit("Exported");
                 false
             }
+            Msg::WarAssetsImported(bundle, callback) => {
+                callback.emit(bundle);
+                ctx.props().onnotification.emit("Imported");
+                false
+            }
+            Msg::WarAssetsExported => {
+                ctx.props().onnotification.emit("Exported");
+                false
+            }
+            Msg::StatisticsExported => {
+                ctx.props().onnotification.emit("Statistics exported");
+                false
+            }
+            Msg::PlotCoverageExported => {
+                ctx.props().onnotification.emit("Plot coverage exported");
+                false
+            }
+            Msg::PlotResearchExported => {
+                ctx.props().onnotification.emit("Plot research exported");
+                false
+            }
+            Msg::JsonExported => {
+                ctx.props().onnotification.emit("Exported as JSON");
+                false
+            }
+            Msg::UpstreamSaveOpened(save_game, callback) => {
+                callback.emit(save_game);
+                false
+            }
+            Msg::Me1SaveOpened(me1, callback) => self.import_me1(ctx, me1, callback),
+            Msg::Checkpoint => {
+                self.checkpoint();
+                false
+            }
+            Msg::Undo => self.travel(ctx, true),
+            Msg::Redo => self.travel(ctx, false),
             Msg::Error(err) => {
-                ctx.props().onerror.emit(err);
+                Self::emit_error(ctx, err);
                 false
             }
             Msg::Noop => {
@@ -175,21 +586,30 @@ impl Component for SaveHandlerProvider {
     fn view(&self, ctx: &Context<Self>) -> Html {
         html! {
             <ContextProvider<SaveHandler> context={self.save_handler.clone()}>
-                { ctx.props().children.clone() }
+                <ContextProvider<Originals> context={self.originals.clone()}>
+                    { ctx.props().children.clone() }
+                </ContextProvider<Originals>>
             </ContextProvider<SaveHandler>>
         }
     }
 }
 
 impl SaveHandlerProvider {
-    fn open_save(ctx: &Context<Self>, last_dir: bool) {
+    fn open_save(ctx: &Context<Self>, last_dir: bool, quick: bool) {
         ctx.link().send_future(async move {
             let handle_save = async {
                 let has_rpc_file = rpc::open_save(last_dir).await?;
                 let result = match has_rpc_file {
                     Some(rpc_file) => {
                         let RpcFile { path, file } = rpc_file;
-                        Self::deserialize(path, file.decode()?).map(Some)?
+                        let input = file.decode()?;
+                        let save_game = if quick {
+                            Self::deserialize_quick(path, &input)?
+                        } else {
+                            session::set_last_save(&path);
+                            Self::deserialize(path, &input)?
+                        };
+                        Some((save_game, input))
                     }
                     None => None,
                 };
@@ -197,7 +617,56 @@ impl SaveHandlerProvider {
             };
 
             match handle_save.await.context("Failed to open the save") {
-                Ok(Some(save_game)) => Msg::SaveOpened(save_game),
+                Ok(Some((save_game, input))) => Msg::SaveOpened(save_game, input),
+                Ok(None) => Msg::Noop,
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    // A recent save or one found in the documents folder, no dialog needed
+    fn open_path(ctx: &Context<Self>, path: PathBuf) {
+        ctx.link().send_future(async move {
+            let handle_save = async {
+                let rpc_file = match rpc::reload_save(path.clone()).await {
+                    Ok(rpc_file) => rpc_file,
+                    Err(err) => {
+                        // Moved or deleted since
+                        session::forget_recent_save(&path);
+                        return Err(err);
+                    }
+                };
+                let RpcFile { path, file } = rpc_file;
+                let input = file.decode()?;
+                session::set_last_save(&path);
+                Self::deserialize(path, &input).map(|save_game| (save_game, input))
+            };
+
+            match handle_save.await.context("Failed to open the save") {
+                Ok((save_game, input)) => Msg::SaveOpened(save_game, input),
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    // Opened alongside the current save, to compare with it
+    fn open_upstream_save(ctx: &Context<Self>, callback: Callback<SaveGame>) {
+        ctx.link().send_future(async move {
+            let handle_save = async {
+                let has_rpc_file = rpc::open_save(true).await?;
+                let result = match has_rpc_file {
+                    Some(rpc_file) => {
+                        let RpcFile { path, file } = rpc_file;
+                        let input = file.decode()?;
+                        Self::deserialize(path, &input).map(Some)?
+                    }
+                    None => None,
+                };
+                Ok::<_, Error>(result)
+            };
+
+            match handle_save.await.context("Failed to open the previous save") {
+                Ok(Some(save_game)) => Msg::UpstreamSaveOpened(save_game, callback),
                 Ok(None) => Msg::Noop,
                 Err(err) => Msg::Error(err),
             }
@@ -211,7 +680,9 @@ impl SaveHandlerProvider {
                 let result = match has_rpc_file {
                     Some(rpc_file) => {
                         let RpcFile { path, file } = rpc_file;
-                        Self::deserialize(path, file.decode()?).map(Some)?
+                        let input = file.decode()?;
+                        session::set_last_save(&path);
+                        Self::deserialize(path, &input).map(|save_game| Some((save_game, input)))?
                     }
                     None => None,
                 };
@@ -219,25 +690,61 @@ impl SaveHandlerProvider {
             };
 
             match handle_save.await.context("Failed to open the save") {
-                Ok(Some(save_game)) => Msg::SaveOpened(save_game),
-                Ok(None) => Msg::Noop,
+                Ok(Some((save_game, input))) => Msg::SaveOpened(save_game, input),
+                Ok(None) => match session::save_to_reopen() {
+                    Some(path) => Self::reopen_last_save(path).await,
+                    None => Msg::Noop,
+                },
                 Err(err) => Msg::Error(err),
             }
         });
     }
 
+    // The save may have been moved or deleted since, which is not worth an error
+    async fn reopen_last_save(path: PathBuf) -> Msg {
+        let handle_save = async {
+            let RpcFile { path, file } = rpc::reload_save(path).await?;
+            let input = file.decode()?;
+            Self::deserialize(path, &input).map(|save_game| (save_game, input))
+        };
+
+        session::start_restoring();
+        match handle_save.await {
+            Ok((save_game, input)) => Msg::SaveOpened(save_game, input),
+            Err(err) => {
+                logger::warn(format!("Failed to reopen the last save: {:#}", err));
+                session::forget_last_save();
+                Msg::Noop
+            }
+        }
+    }
+
     fn open_dropped_file(ctx: &Context<Self>, file_name: String, bytes: Vec<u8>) {
         ctx.link().send_message({
-            let deserialize = || Self::deserialize(file_name.into(), bytes);
+            let deserialize = || Self::deserialize(file_name.into(), &bytes);
 
             match deserialize().context("Failed to open the save") {
-                Ok(save_game) => Msg::SaveOpened(save_game),
+                Ok(save_game) => Msg::SaveOpened(save_game, bytes),
                 Err(err) => Msg::Error(err),
             }
         });
     }
 
-    fn save_save(ctx: &Context<Self>, save_game: &Rc<SaveGame>) {
+    // The other copy of the save, written too if asked and allowed
+    fn cloud_path(&self) -> Option<PathBuf> {
+        self.save_handler
+            .cloud_save
+            .as_ref()
+            .filter(|_| self.save_handler.save_both_copies)
+            .filter(|_| !self.save_handler.write_protected)
+            .map(|cloud_save| cloud_save.path.clone())
+    }
+
+    // `launch` once written, if it was
+    fn save_save(
+        ctx: &Context<Self>, save_game: &Rc<SaveGame>, cloud_path: Option<PathBuf>,
+        launch: Option<String>,
+    ) {
         let (path, filters) = match save_game.as_ref() {
             SaveGame::MassEffect1 { ref file_path, .. } => {
                 (file_path.clone(), vec![("Mass Effect 1 save", vec!["MassEffectSave"])])
@@ -265,172 +772,401 @@ impl SaveHandlerProvider {
                     ("Mass Effect 3 XBOX 360 save", vec!["xbsav"]),
                 ],
             ),
+            // The rest of the save can't change platform
+            SaveGame::MassEffect2Quick { ref file_path, is_xbox360, .. } => {
+                if *is_xbox360 {
+                    (file_path.clone(), vec![("Mass Effect 2 XBOX 360 save", vec!["xbsav"])])
+                } else {
+                    (file_path.clone(), vec![("Mass Effect 2 PC save", vec!["pcsav"])])
+                }
+            }
+            SaveGame::MassEffect3Quick { ref file_path, is_xbox360, .. } => {
+                if *is_xbox360 {
+                    (file_path.clone(), vec![("Mass Effect 3 XBOX 360 save", vec!["xbsav"])])
+                } else {
+                    (file_path.clone(), vec![("Mass Effect 3 PC save", vec!["pcsav"])])
+                }
+            }
+        };
+
+        let path = session::save_target(&path);
+        Self::save_as(ctx, save_game, path, filters, cloud_path, launch);
+    }
+
+    // Between the PC and PS4 saves of ME1LE
+    fn convert_platform(ctx: &Context<Self>, save_game: &Rc<SaveGame>) {
+        let (file_path, filter, extension) = match save_game.as_ref() {
+            SaveGame::MassEffect1Le { file_path, .. } => {
+                (file_path, "Mass Effect 1 Legendary PS4 save", "ps4sav")
+            }
+            SaveGame::MassEffect1LePs4 { file_path, .. } => {
+                (file_path, "Mass Effect 1 Legendary PC save", "pcsav")
+            }
+            _ => {
+                logger::info(format!(
+                    "{} saves can't be converted to another platform",
+                    save_game.game_name()
+                ));
+                return;
+            }
         };
 
+        // The extension picks the format when serializing
+        let path = file_path.with_extension(extension);
+        let filters = vec![(filter, vec![extension])];
+
+        Self::save_as(ctx, save_game, path, filters, None, None);
+    }
+
+    fn save_as(
+        ctx: &Context<Self>, save_game: &Rc<SaveGame>, path: PathBuf,
+        filters: Vec<(&'static str, Vec<&'static str>)>, cloud_path: Option<PathBuf>,
+        launch: Option<String>,
+    ) {
+        let opened_path = save_game.file_path().clone();
         let save_game = Rc::clone(save_game);
         ctx.link().send_future(async move {
             let handle_save = async {
                 let has_path = rpc::save_save_dialog(DialogParams { path, filters }).await?;
-                let cancelled = match has_path {
+                let written_file = match has_path {
+                    Some(path) if path == opened_path && session::is_write_protected() => {
+                        bail!(
+                            "`{}` is write protected, save it under another name or allow \
+                            overwriting it from the menu bar",
+                            path.display()
+                        )
+                    }
                     Some(path) => {
-                        let rpc_file = Self::serialize(path, save_game)?;
-                        rpc::save_file(rpc_file).await?;
-                        false
+                        let timestamp = session::save_timestamp();
+                        let rpc_file = Self::serialize(path, Rc::clone(&save_game))?;
+                        // What the game overwrites next, unless saved as another file
+                        let written_file = if rpc_file.path == opened_path {
+                            Some(rpc_file.file.decode()?)
+                        } else {
+                            None
+                        };
+                        rpc::save_save_file(SaveFileParams { rpc_file, timestamp }).await?;
+
+                        // Keep the cloud copy in sync
+                        if let Some(cloud_path) = cloud_path {
+                            let rpc_file = Self::serialize(cloud_path, save_game)?;
+                            rpc::save_save_file(SaveFileParams { rpc_file, timestamp })
+                                .await
+                                .context("Failed to save the cloud copy")?;
+                        }
+
+                        if let Some(target) = launch {
+                            rpc::launch_game(target).await.context("Failed to launch the game")?;
+                        }
+                        Some(written_file)
                     }
-                    None => true,
+                    None => None,
                 };
-                Ok::<_, Error>(cancelled)
+                Ok::<_, Error>(written_file)
             };
 
             match handle_save.await.context("Failed to save the save") {
-                Ok(false) => Msg::SaveSaved,
-                Ok(true) => Msg::Noop,
+                Ok(Some(written_file)) => Msg::SaveSaved(written_file),
+                Ok(None) => Msg::Noop,
                 Err(err) => Msg::Error(err),
             }
         });
     }
 
-    fn reload_save(ctx: &Context<Self>, path: PathBuf) {
+    fn reload_save(
+        ctx: &Context<Self>, path: PathBuf, quick: bool, last_seen_file: Option<Rc<Vec<u8>>>,
+    ) {
         ctx.link().send_future(async move {
             let handle_save = async move {
                 let rpc_file = rpc::reload_save(path).await?;
                 let RpcFile { path, file } = rpc_file;
-                Self::deserialize(path, file.decode()?)
+                let input = file.decode()?;
+                let save_game = if quick {
+                    Self::deserialize_quick(path, &input)?
+                } else {
+                    Self::deserialize(path, &input)?
+                };
+                Ok::<_, Error>((save_game, input))
             };
 
             match handle_save.await.context("Failed to reload the save") {
-                Ok(save_game) => Msg::SaveOpened(save_game),
+                Ok((save_game, input)) => {
+                    let changed_by_game = match last_seen_file {
+                        // Overwritten by the game since, what did it set?
+                        Some(last_seen_file) if *last_seen_file != input => {
+                            save_diff::changed_by_game(&last_seen_file, &save_game)
+                        }
+                        _ => Vec::new(),
+                    };
+                    Msg::SaveReloaded(save_game, input, changed_by_game)
+                }
                 Err(err) => Msg::Error(err),
             }
         });
     }
 
-    fn deserialize(file_path: PathBuf, input: Vec<u8>) -> Result<SaveGame> {
-        fn header<'de, T>(header: &'de [u8]) -> Result<T, unreal::Error>
-        where
-            T: Deserialize<'de>,
-        {
-            unreal::Deserializer::from_bytes::<T>(header)
-        }
-
-        let save_game = if header::<Me1MagicNumber>(&input).is_ok() {
-            // ME1
-            SaveGame::MassEffect1 {
-                file_path,
-                save_game: unreal::Deserializer::from_bytes(&input)?,
-            }
-        } else if header::<Me1LeMagicNumber>(&input).is_ok() {
-            // ME1 Legendary
-            SaveGame::MassEffect1Le {
-                file_path,
-                save_game: unreal::Deserializer::from_bytes(&input)?,
-            }
-        } else if header::<Me1LeVersion>(&input).is_ok() {
-            // ME1LE PS4
-            SaveGame::MassEffect1LePs4 {
-                file_path,
-                save_game: unreal::Deserializer::from_bytes(&input)?,
-            }
-        } else if let Ok(save) = header::<Me2Version>(&input) {
-            // ME2
-            let save_game = if save.is_xbox360 {
-                unreal::Deserializer::from_be_bytes(&input)?
-            } else {
-                unreal::Deserializer::from_bytes(&input)?
-            };
-            SaveGame::MassEffect2 { file_path, save_game }
-        } else if header::<Me2LeVersion>(&input).is_ok() {
-            // ME2 Legendary
-            SaveGame::MassEffect2Le {
-                file_path,
-                save_game: unreal::Deserializer::from_bytes(&input)?,
-            }
-        } else if let Ok(save) = header::<Me3Version>(&input) {
-            // ME3
-            let save_game = if save.is_xbox360 {
-                unreal::Deserializer::from_be_bytes(&input)?
-            } else {
-                unreal::Deserializer::from_bytes(&input)?
-            };
-            SaveGame::MassEffect3 { file_path, save_game }
-        } else {
-            bail!("Unsupported file");
-        };
-        Ok(save_game)
+    fn find_cloud_save(ctx: &Context<Self>, path: PathBuf) {
+        ctx.link().send_future(async move {
+            // Not being able to tell is not worth an error
+            let cloud_save = rpc::find_cloud_save(path).await.ok().flatten();
+            Msg::CloudSaveFound(cloud_save)
+        });
     }
 
-    fn serialize(path: PathBuf, save_game: Rc<SaveGame>) -> Result<RpcFile> {
-        let output = match save_game.as_ref() {
-            SaveGame::MassEffect1 { save_game, .. } => unreal::Serializer::to_vec(&save_game)?,
-            SaveGame::MassEffect1Le { save_game, .. } => {
-                let mut output = unreal::Serializer::to_vec(&save_game)?;
+    fn load_notes(ctx: &Context<Self>, path: PathBuf, callback: Callback<String>) {
+        ctx.link().send_future(async move {
+            match rpc::load_notes(path).await.context("Failed to load the notes") {
+                Ok(notes) => Msg::NotesLoaded(notes, callback),
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
 
-                // Checksum
-                let checksum_offset = output.len() - 12;
-                let crc = Crc::<u32>::new(&CRC_32_BZIP2);
-                let checksum = crc.checksum(&output[..checksum_offset]);
+    fn save_notes(ctx: &Context<Self>, path: PathBuf, notes: String) {
+        ctx.link().send_future(async move {
+            match rpc::save_notes(NotesParams { path, notes })
+                .await
+                .context("Failed to save the notes")
+            {
+                Ok(()) => Msg::NotesSaved,
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
 
-                // Update checksum
-                let end = checksum_offset + 4;
-                output[checksum_offset..end].swap_with_slice(&mut u32::to_le_bytes(checksum));
-                output
+    fn list_backups(ctx: &Context<Self>, path: PathBuf, callback: Callback<Vec<Backup>>) {
+        ctx.link().send_future(async move {
+            match rpc::list_backups(path).await.context("Failed to list the backups") {
+                Ok(backups) => Msg::BackupsListed(backups, callback),
+                Err(err) => Msg::Error(err),
             }
-            SaveGame::MassEffect1LePs4 { save_game, .. } => unreal::Serializer::to_vec(&save_game)?,
-            SaveGame::MassEffect2 { save_game, .. } => {
-                let is_xbox360 = path
-                    .extension()
-                    .map(|ext| ext.eq_ignore_ascii_case("xbsav"))
-                    .unwrap_or_default();
+        });
+    }
 
-                let mut output = if is_xbox360 {
-                    unreal::Serializer::to_be_vec(&save_game)?
+    // Written over the save, which is backed up first, then opened again
+    fn restore_backup(ctx: &Context<Self>, path: PathBuf, backup: PathBuf, quick: bool) {
+        ctx.link().send_future(async move {
+            let handle_restore = async move {
+                let rpc_file = rpc::restore_backup(RestoreBackupParams { path, backup }).await?;
+                let RpcFile { path, file } = rpc_file;
+                let input = file.decode()?;
+                let save_game = if quick {
+                    Self::deserialize_quick(path, &input)?
                 } else {
-                    unreal::Serializer::to_vec(&save_game)?
+                    Self::deserialize(path, &input)?
                 };
+                Ok::<_, Error>((save_game, input))
+            };
+
+            match handle_restore.await.context("Failed to restore the backup") {
+                Ok((save_game, input)) => Msg::SaveOpened(save_game, input),
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    // Corrupts the original file over and over, the parser has to reject it without panicking
+    fn fuzz_save(ctx: &Context<Self>, save_game: &SaveGame, original_file: Rc<Vec<u8>>) {
+        const RUNS: usize = 500;
 
-                let crc = Crc::<u32>::new(&CRC_32_BZIP2);
-                let checksum = crc.checksum(&output);
+        let file_path = save_game.file_path().clone();
+        let quick = save_game.is_quick();
+        let seed = Date::now() as u64;
+        logger::info(format!(
+            "Fuzzing `{}` {} times with seed {}",
+            file_path.display(),
+            RUNS,
+            seed
+        ));
 
-                let extend = if is_xbox360 {
-                    u32::to_be_bytes(checksum)
+        ctx.link().send_future(async move {
+            // A panic aborts the editor, the seed has to reach the logs before
+            TimeoutFuture::new(0).await;
+
+            let mut sections = Vec::new();
+            if !quick {
+                let _ = SaveGame::parse(file_path.clone(), &original_file, &mut sections);
+            }
+            let report = fuzz::fuzz(&original_file, &sections, seed, RUNS, |input| {
+                if quick {
+                    SaveGame::parse_quick(file_path.clone(), input).is_ok()
                 } else {
-                    u32::to_le_bytes(checksum)
-                };
-                output.extend(extend);
-                output
+                    SaveGame::parse(file_path.clone(), input, &mut Vec::new()).is_ok()
+                }
+            });
+
+            for finding in &report.findings {
+                logger::warn(finding.to_string());
             }
-            SaveGame::MassEffect2Le { save_game, .. } => {
-                let mut output = unreal::Serializer::to_vec(&save_game)?;
+            logger::info(format!(
+                "Fuzzing done: {} rejected, {} accepted, {} findings",
+                report.rejected,
+                report.runs - report.rejected,
+                report.findings.len()
+            ));
+            Msg::Noop
+        });
+    }
+
+    fn emit_error(ctx: &Context<Self>, err: Error) {
+        logger::error(format!("{:#}", err));
+        ctx.props().onerror.emit(err);
+    }
+
+    // Ctrl+Z to undo, Ctrl+Y or Ctrl+Shift+Z to redo. Left to the text inputs being typed in,
+    // they undo their own typing.
+    fn shortcut(event: &Event) -> Option<Msg> {
+        let event = event.dyn_ref::<KeyboardEvent>()?;
+        if !(event.ctrl_key() || event.meta_key()) || event.alt_key() {
+            return None;
+        }
+
+        let target = event.target();
+        let is_typing = target.as_ref().map_or(false, |target| {
+            target.has_type::<HtmlTextAreaElement>()
+                || target
+                    .dyn_ref::<HtmlInputElement>()
+                    .map_or(false, |input| input.type_() != "checkbox")
+        });
+        if is_typing {
+            return None;
+        }
+
+        match event.key().to_lowercase().as_str() {
+            "z" if event.shift_key() => Some(Msg::Redo),
+            "z" => Some(Msg::Undo),
+            "y" => Some(Msg::Redo),
+            _ => None,
+        }
+    }
 
-                let crc = Crc::<u32>::new(&CRC_32_BZIP2);
-                let checksum = crc.checksum(&output);
-                output.extend(&u32::to_le_bytes(checksum));
-                output
+    fn reset_history(&mut self) {
+        self.checkpoint_timeout = None;
+        self.history = EditHistory::default();
+        if let Some(ref save_game) = self.save_handler.save_game {
+            if let Err(err) = self.history.reset(save_game) {
+                logger::warn(format!("Edits won't be undoable: {:?}", err));
             }
-            SaveGame::MassEffect3 { save_game, .. } => {
-                let is_xbox360 = path
-                    .extension()
-                    .map(|ext| ext.eq_ignore_ascii_case("xbsav"))
-                    .unwrap_or_default();
+        }
+    }
 
-                let mut output = if is_xbox360 {
-                    unreal::Serializer::to_be_vec(&save_game)?
-                } else {
-                    unreal::Serializer::to_vec(&save_game)?
-                };
+    // Into the opened Legendary save, then the tabs are rebuilt to show the imported career
+    fn import_me1(
+        &mut self, ctx: &Context<Self>, me1: SaveGame, callback: Callback<Me1Import>,
+    ) -> bool {
+        let me1 = match me1 {
+            SaveGame::MassEffect1 { save_game, .. } => save_game,
+            other => {
+                let err = anyhow!("Expected a Mass Effect 1 save, got {}", other.game_name());
+                Self::emit_error(ctx, err.context("Failed to import the save"));
+                return false;
+            }
+        };
+        let import = match self.save_handler.save_game.as_deref() {
+            Some(SaveGame::MassEffect1Le { save_game, .. }) => {
+                save_game.borrow().save_data.borrow().import_me1(&me1.borrow())
+            }
+            Some(SaveGame::MassEffect1LePs4 { save_game, .. }) => {
+                save_game.borrow().import_me1(&me1.borrow())
+            }
+            _ => return false,
+        };
 
-                let crc = Crc::<u32>::new(&CRC_32_BZIP2);
-                let checksum = crc.checksum(&output);
+        logger::info(format!("Imported from Mass Effect 1: {}", import.imported.join(", ")));
+        if !import.missing.is_empty() {
+            logger::warn(format!(
+                "Not found in the Mass Effect 1 save: {}",
+                import.missing.join(", ")
+            ));
+        }
+        callback.emit(import);
+        // Undone at once
+        self.checkpoint();
+        session::start_restoring();
+        self.save_handler.revision += 1;
+        ctx.props().onnotification.emit("Imported");
+        true
+    }
 
-                let extend = if is_xbox360 {
-                    u32::to_be_bytes(checksum)
-                } else {
-                    u32::to_le_bytes(checksum)
-                };
-                output.extend(extend);
-                output
+    // Writes the save as it is now, a step of the history if it has been edited since
+    fn checkpoint(&mut self) {
+        self.checkpoint_timeout = None;
+        if let Some(ref save_game) = self.save_handler.save_game {
+            if let Err(err) = self.history.checkpoint(save_game) {
+                logger::warn(format!("This edit won't be undoable: {:?}", err));
             }
+        }
+    }
+
+    // The save is parsed again as it was before or after the edit, then the tabs are rebuilt
+    fn travel(&mut self, ctx: &Context<Self>, back: bool) -> bool {
+        let save_game = match self.save_handler.save_game {
+            Some(ref save_game) => Rc::clone(save_game),
+            None => return false,
         };
+        // The last edit may still be waiting for its checkpoint
+        self.checkpoint();
+
+        let restored =
+            if back { self.history.undo(&save_game) } else { self.history.redo(&save_game) };
+        let restored = match restored {
+            Some(Ok(restored)) => restored,
+            None => {
+                let nothing = if back { "Nothing to undo" } else { "Nothing to redo" };
+                ctx.props().onnotification.emit(nothing);
+                return false;
+            }
+            Some(Err(err)) => {
+                let context = if back { "Failed to undo" } else { "Failed to redo" };
+                Self::emit_error(ctx, err.context(context));
+                return false;
+            }
+        };
+
+        self.save_handler.save_game = Some(Rc::new(restored));
+        self.pair_originals();
+        // Back to the same tab once rebuilt
+        session::start_restoring();
+        self.save_handler.revision += 1;
+        ctx.props().onnotification.emit(if back { "Undone" } else { "Redone" });
+        true
+    }
+
+    fn deserialize(file_path: PathBuf, input: &[u8]) -> Result<SaveGame> {
+        let start = Date::now();
+        let mut sections = Vec::new();
+        let save_game = SaveGame::parse(file_path, input, &mut sections)?;
+        Self::log_profile(Date::now() - start, sections);
+        Ok(save_game)
+    }
+
+    // Skips everything but the plot table, to flip a few flags without the wait
+    fn deserialize_quick(file_path: PathBuf, input: &[u8]) -> Result<SaveGame> {
+        let start = Date::now();
+        let save_game = SaveGame::parse_quick(file_path, input)?;
+        Self::log_profile(Date::now() - start, Vec::new());
+        Ok(save_game)
+    }
+
+    // Slowest sections first, to tell what makes a save long to open
+    fn log_profile(millis: f64, mut sections: Vec<Section>) {
+        sections.sort_by(|a, b| b.millis.partial_cmp(&a.millis).unwrap_or(Ordering::Equal));
+        let sections = sections
+            .iter()
+            .map(|Section { name, bytes, millis }| {
+                format!("{} {:.1} ms ({} bytes)", name, millis, bytes)
+            })
+            .collect::<Vec<_>>();
+
+        if sections.is_empty() {
+            logger::info(format!("Parsed in {:.1} ms", millis));
+        } else {
+            logger::info(format!("Parsed in {:.1} ms: {}", millis, sections.join(", ")));
+        }
+    }
+
+    fn serialize(path: PathBuf, save_game: Rc<SaveGame>) -> Result<RpcFile> {
+        let output = save_game.to_bytes(&path)?;
 
         let rpc_file = RpcFile {
             path,
@@ -440,6 +1176,49 @@ impl SaveHandlerProvider {
         Ok(rpc_file)
     }
 
+    // Each field of the edited save with the same field of the opened file
+    fn pair_originals(&mut self) {
+        self.originals = match (&self.save_handler.save_game, &self.original_save) {
+            (Some(edited), Some(original)) => save_diff::pair_originals(edited, original),
+            _ => Originals::default(),
+        };
+    }
+
+    // The plots changed since the research snapshot, exported right away
+    fn compare_research(&mut self, ctx: &Context<Self>) {
+        let (research, snapshot, save_game) = match (
+            &self.save_handler.research,
+            &self.research_snapshot,
+            &self.save_handler.save_game,
+        ) {
+            (Some(research), Some(snapshot), Some(save_game)) => (research, snapshot, save_game),
+            _ => return,
+        };
+
+        let game = save_game.plot_game();
+        if game != research.game {
+            logger::warn(format!(
+                "Not compared with the research snapshot, this is a {} save and not a {} one",
+                game, research.game
+            ));
+            return;
+        }
+
+        let changes = diff::diff_plots(snapshot, &save_diff::plot_values(save_game));
+        logger::info(format!("{} plots changed since the research snapshot", changes.len()));
+        let result = Rc::new(PlotResearch {
+            game,
+            before: research.snapshot_path.clone(),
+            after: save_game.file_path().clone(),
+            changes,
+        });
+        if !result.changes.is_empty() {
+            Self::export_plot_research(ctx, Rc::clone(&result));
+        }
+        self.save_handler.research =
+            Some(Rc::new(Research { result: Some(result), ..Research::clone(research) }));
+    }
+
     fn import_head_morph(ctx: &Context<Self>, callback: Callback<HeadMorph>) {
         ctx.link().send_future(async move {
             let handle_save = async {
@@ -447,14 +1226,14 @@ impl SaveHandlerProvider {
                 let result = match has_rpc_file {
                     Some(rpc_file) => {
                         let file = rpc_file.file.decode()?;
-                        if file.starts_with(b"GIBBEDMASSEFFECT2HEADMORPH")
-                            || file.starts_with(b"GIBBEDMASSEFFECT3HEADMORPH")
-                        {
+                        if HeadMorph::is_gibbed(&file) {
                             // Gibbed's head morph
-                            unreal::Deserializer::from_bytes(&file[31..]).map(Some)?
+                            HeadMorph::from_gibbed(&file).map(Some)?
                         } else {
                             // TSE head morph
-                            let ron = String::from_utf8(file)?;
+                            let ron = String::from_utf8(file).context(
+                                "Unknown head morph format, expected a TSE or a Gibbed head morph",
+                            )?;
                             ron::from_str(&ron).map(Some)?
                         }
                     }
@@ -471,20 +1250,24 @@ impl SaveHandlerProvider {
         });
     }
 
-    fn export_head_morph(ctx: &Context<Self>, head_morph: RcRef<HeadMorph>) {
+    // Asks where to write the file, only then makes what goes in it
+    fn export_file<D, O, T>(
+        ctx: &Context<Self>, dialog: D, output: O, context: &'static str, done: Msg,
+    ) where
+        D: Future<Output = Result<Option<PathBuf>>> + 'static,
+        O: FnOnce() -> Result<T> + 'static,
+        T: AsRef<[u8]>,
+    {
         ctx.link().send_future(async move {
             let handle_save = async {
-                let has_path = rpc::export_head_morph_dialog().await?;
+                let has_path = dialog.await?;
                 let cancelled = match has_path {
                     Some(path) => {
-                        let pretty_config =
-                            PrettyConfig::new().enumerate_arrays(true).new_line(String::from('\n'));
-
-                        let output = ron::ser::to_string_pretty(&head_morph, pretty_config)?;
+                        let output = output()?;
                         let rpc_file = RpcFile {
                             path,
                             file: Base64File {
-                                unencoded_size: output.len(),
+                                unencoded_size: output.as_ref().len(),
                                 base64: base64::encode(output),
                             },
                         };
@@ -496,22 +1279,178 @@ impl SaveHandlerProvider {
                 Ok::<_, Error>(cancelled)
             };
 
-            match handle_save.await.context("Failed to export the head morph") {
-                Ok(false) => Msg::HeadMorphExported,
+            match handle_save.await.context(context) {
+                Ok(false) => done,
                 Ok(true) => Msg::Noop,
                 Err(err) => Msg::Error(err),
             }
         });
     }
 
+    fn export_head_morph(ctx: &Context<Self>, head_morph: RcRef<HeadMorph>) {
+        let output = move || -> Result<String> {
+            let pretty_config =
+                PrettyConfig::new().enumerate_arrays(true).new_line(String::from('\n'));
+            Ok(ron::ser::to_string_pretty(&head_morph, pretty_config)?)
+        };
+        Self::export_file(
+            ctx,
+            rpc::export_head_morph_dialog(),
+            output,
+            "Failed to export the head morph",
+            Msg::HeadMorphExported,
+        );
+    }
+
+    fn export_gibbed_head_morph(
+        ctx: &Context<Self>, head_morph: RcRef<HeadMorph>, game: GibbedGame,
+    ) {
+        Self::export_file(
+            ctx,
+            rpc::export_gibbed_head_morph_dialog(game.extension()),
+            move || head_morph.borrow().to_gibbed(game),
+            "Failed to export the head morph",
+            Msg::HeadMorphExported,
+        );
+    }
+
+    fn import_war_assets(ctx: &Context<Self>, callback: Callback<WarAssetBundle>) {
+        ctx.link().send_future(async move {
+            let handle_save = async {
+                let has_rpc_file = rpc::import_war_assets().await?;
+                let result = match has_rpc_file {
+                    Some(rpc_file) => {
+                        let ron = String::from_utf8(rpc_file.file.decode()?)?;
+                        ron::from_str(&ron).map(Some)?
+                    }
+                    None => None,
+                };
+                Ok::<_, Error>(result)
+            };
+
+            match handle_save.await.context("Failed to import the war assets") {
+                Ok(Some(bundle)) => Msg::WarAssetsImported(bundle, callback),
+                Ok(None) => Msg::Noop,
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn export_war_assets(ctx: &Context<Self>, bundle: WarAssetBundle) {
+        let output = move || -> Result<String> {
+            let pretty_config = PrettyConfig::new().new_line(String::from('\n'));
+            Ok(ron::ser::to_string_pretty(&bundle, pretty_config)?)
+        };
+        Self::export_file(
+            ctx,
+            rpc::export_war_assets_dialog(),
+            output,
+            "Failed to export the war assets",
+            Msg::WarAssetsExported,
+        );
+    }
+
+    fn export_statistics(ctx: &Context<Self>, save_game: &SaveGame) {
+        let statistics = match save_game {
+            SaveGame::MassEffect1 { save_game, .. } => Statistics::me1(&save_game.borrow()),
+            SaveGame::MassEffect1Le { save_game, .. } => {
+                Statistics::me1_le(&save_game.borrow().save_data.borrow())
+            }
+            SaveGame::MassEffect1LePs4 { save_game, .. } => Statistics::me1_le(&save_game.borrow()),
+            SaveGame::MassEffect2 { save_game, .. } => Statistics::me2(&save_game.borrow()),
+            SaveGame::MassEffect2Le { save_game, .. } => Statistics::me2_le(&save_game.borrow()),
+            SaveGame::MassEffect3 { save_game, .. } => Statistics::me3(&save_game.borrow()),
+            SaveGame::MassEffect2Quick { .. } | SaveGame::MassEffect3Quick { .. } => {
+                let err = Error::msg("The statistics need the whole save, open it normally");
+                Self::emit_error(ctx, err.context("Failed to export the statistics"));
+                return;
+            }
+        };
+
+        Self::export_file(
+            ctx,
+            rpc::export_statistics_dialog(),
+            move || Ok(serde_json::to_string_pretty(&statistics)?),
+            "Failed to export the statistics",
+            Msg::StatisticsExported,
+        );
+    }
+
+    // The whole save for the bulk edits done with other tools
+    fn export_json(ctx: &Context<Self>, save_game: &SaveGame) {
+        let output = match save_json::export(save_game) {
+            Ok(output) => output,
+            Err(err) => {
+                Self::emit_error(ctx, err.context("Failed to export the save as JSON"));
+                return;
+            }
+        };
+
+        Self::export_file(
+            ctx,
+            rpc::export_save_json_dialog(),
+            move || Ok(output),
+            "Failed to export the save as JSON",
+            Msg::JsonExported,
+        );
+    }
+
+    // Opened as if it was the save next to the JSON, written only once saved
+    fn import_json(ctx: &Context<Self>) {
+        ctx.link().send_future(async move {
+            let handle_save = async {
+                let has_rpc_file = rpc::import_save_json().await?;
+                let result = match has_rpc_file {
+                    Some(rpc_file) => {
+                        let RpcFile { path, file } = rpc_file;
+                        let json = String::from_utf8(file.decode()?)?;
+                        Some(save_json::import(path, &json)?)
+                    }
+                    None => None,
+                };
+                Ok::<_, Error>(result)
+            };
+
+            match handle_save.await.context("Failed to import the save from JSON") {
+                Ok(Some((save_game, input))) => Msg::SaveOpened(save_game, input),
+                Ok(None) => Msg::Noop,
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn export_plot_coverage(ctx: &Context<Self>, coverage: PlotCoverage) {
+        Self::export_file(
+            ctx,
+            rpc::export_plot_coverage_dialog(),
+            move || Ok(serde_json::to_string_pretty(&coverage)?),
+            "Failed to export the plot coverage",
+            Msg::PlotCoverageExported,
+        );
+    }
+
+    fn export_plot_research(ctx: &Context<Self>, research: Rc<PlotResearch>) {
+        Self::export_file(
+            ctx,
+            rpc::export_plot_research_dialog(),
+            move || Ok(serde_json::to_string_pretty(&*research)?),
+            "Failed to export the plot research",
+            Msg::PlotResearchExported,
+        );
+    }
+
     fn change_theme(&self) {
         if let Some(ref save_game) = self.save_handler.save_game {
             let theme = match save_game.as_ref() {
                 SaveGame::MassEffect1 { .. }
                 | SaveGame::MassEffect1Le { .. }
                 | SaveGame::MassEffect1LePs4 { .. } => Theme::MassEffect1,
-                SaveGame::MassEffect2 { .. } | SaveGame::MassEffect2Le { .. } => Theme::MassEffect2,
-                SaveGame::MassEffect3 { .. } => Theme::MassEffect3,
+                SaveGame::MassEffect2 { .. }
+                | SaveGame::MassEffect2Le { .. }
+                | SaveGame::MassEffect2Quick { .. } => Theme::MassEffect2,
+                SaveGame::MassEffect3 { .. } | SaveGame::MassEffect3Quick { .. } => {
+                    Theme::MassEffect3
+                }
             };
 
             let body = utils::document().body().unwrap();
@@ -519,6 +1458,36 @@ impl SaveHandlerProvider {
 
             let _ = classes.remove_3(&Theme::MassEffect1, &Theme::MassEffect2, &Theme::MassEffect3);
             let _ = classes.add_1(&theme);
+
+            let accent = Self::class_name(save_game).and_then(|name| Accent::class_color(&name));
+            Accent::set_color(accent);
+        }
+    }
+
+    fn class_name(save_game: &SaveGame) -> Option<String> {
+        let me1_le = |save_data: &Me1LeSaveData| {
+            let class = save_data.player().player_class().clone() as usize;
+            Me1LeClass::variants()[class].to_owned()
+        };
+
+        match save_game {
+            SaveGame::MassEffect1Le { save_game, .. } => {
+                Some(me1_le(&save_game.borrow().save_data.borrow()))
+            }
+            SaveGame::MassEffect1LePs4 { save_game, .. } => Some(me1_le(&save_game.borrow())),
+            SaveGame::MassEffect2 { save_game, .. } => {
+                Some(save_game.borrow().player().class_name().to_owned())
+            }
+            SaveGame::MassEffect2Le { save_game, .. } => {
+                Some(save_game.borrow().player().class_name().to_owned())
+            }
+            SaveGame::MassEffect3 { save_game, .. } => {
+                Some(save_game.borrow().player().class_name().to_owned())
+            }
+            // ME1 only has an id of the class, the quick plot edit doesn't parse the player
+            SaveGame::MassEffect1 { .. }
+            | SaveGame::MassEffect2Quick { .. }
+            | SaveGame::MassEffect3Quick { .. } => None,
         }
     }
 }
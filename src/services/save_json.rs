@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+
+use crate::{
+    save_data::json::{self, JsonGame},
+    services::save_game::SaveGame,
+};
+
+/// The whole save for the bulk edits done with other tools
+pub fn export(save_game: &SaveGame) -> Result<String> {
+    let extension = save_game.file_path().extension().and_then(|ext| ext.to_str());
+    let extension = extension.unwrap_or_default();
+    match save_game {
+        SaveGame::MassEffect2 { save_game, .. } => {
+            json::to_string(JsonGame::MassEffect2, extension, &*save_game.borrow())
+        }
+        SaveGame::MassEffect2Le { save_game, .. } => {
+            json::to_string(JsonGame::MassEffect2Le, extension, &*save_game.borrow())
+        }
+        SaveGame::MassEffect3 { save_game, .. } => {
+            json::to_string(JsonGame::MassEffect3, extension, &*save_game.borrow())
+        }
+        SaveGame::MassEffect2Quick { .. } | SaveGame::MassEffect3Quick { .. } => {
+            bail!("The JSON export needs the whole save, open it normally")
+        }
+        SaveGame::MassEffect1 { .. }
+        | SaveGame::MassEffect1Le { .. }
+        | SaveGame::MassEffect1LePs4 { .. } => bail!(
+            "Only the Mass Effect 2 and 3 saves can be exported as JSON, \
+            the Mass Effect 1 ones keep compressed parts it can't describe"
+        ),
+    }
+}
+
+/// The binary save rebuilt from the JSON stands for the file it was exported from, for the
+/// same platform
+pub fn import(json_path: PathBuf, input: &str) -> Result<(SaveGame, Vec<u8>)> {
+    let (game, extension) = json::header(input)?;
+    let file_path = json_path.with_extension(extension);
+    let save_game = match game {
+        JsonGame::MassEffect2 => {
+            SaveGame::MassEffect2 { file_path, save_game: json::from_str(input)? }
+        }
+        JsonGame::MassEffect2Le => {
+            SaveGame::MassEffect2Le { file_path, save_game: json::from_str(input)? }
+        }
+        JsonGame::MassEffect3 => {
+            SaveGame::MassEffect3 { file_path, save_game: json::from_str(input)? }
+        }
+    };
+    let output = save_game.to_bytes(save_game.file_path())?;
+    Ok((save_game, output))
+}
@@ -0,0 +1,253 @@
+use std::{cell::RefCell, rc::Rc};
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use rhai::{Dynamic, Engine, EvalAltResult, AST};
+
+use crate::services::save_game::SaveGame;
+
+// A script is stopped past these, a loop left running would freeze the editor
+const MAX_OPERATIONS: u64 = 5_000_000;
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_EXPR_DEPTH: usize = 64;
+const MAX_STRING_LEN: usize = 1 << 20;
+const MAX_ARRAY_LEN: usize = 1 << 16;
+
+/// A [Rhai](https://rhai.rs) script editing the opened save, through these functions:
+/// - `boolean(id)`, `integer(id)` and `float(id)` read a plot, `set_boolean(id, value)`,
+/// `set_integer(id, value)` and `set_float(id, value)` set it, like the Raw Plot tab does
+/// - `get(path)` reads a value of the Raw Data tab by its path, `Player > Credits`, as text and
+/// `set(path, value)` sets it, numbers taking what their input does, `current + 1000` included
+///
+/// The engine has nothing else to reach, no file, module nor clock.
+pub struct Script {
+    ast: AST,
+}
+
+/// A value changed by a script, named like the Raw Plot and Raw Data tabs do
+#[derive(Clone, PartialEq, Debug)]
+pub struct ScriptEdit {
+    pub target: String,
+    pub before: String,
+    pub after: String,
+}
+
+type Edits = Rc<RefCell<Vec<ScriptEdit>>>;
+
+impl Script {
+    pub fn compile(text: &str) -> Result<Self> {
+        let ast = engine().compile(text).map_err(|err| anyhow!("{}", err))?;
+        Ok(Script { ast })
+    }
+
+    /// Runs the script, an error stops it and what came before it stays edited
+    pub fn run(&self, save_game: &SaveGame) -> Result<Vec<ScriptEdit>> {
+        let edits = Edits::default();
+        let mut engine = engine();
+        register_save(&mut engine, save_game, &edits);
+        engine.run_ast(&self.ast).map_err(|err| anyhow!("{}", err))?;
+
+        let edits = edits.take();
+        Ok(edits)
+    }
+
+    /// Tried on a copy first, the save is only edited if the whole script runs
+    pub fn try_run(&self, save_game: &SaveGame) -> Result<Vec<ScriptEdit>> {
+        let bytes = save_game.to_bytes(save_game.file_path())?;
+        let copy = save_game.reparse(&bytes)?;
+        self.run(&copy)?;
+
+        self.run(save_game)
+    }
+}
+
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(MAX_CALL_LEVELS)
+        .set_max_expr_depths(MAX_EXPR_DEPTH, MAX_EXPR_DEPTH)
+        .set_max_string_size(MAX_STRING_LEN)
+        .set_max_array_size(MAX_ARRAY_LEN)
+        .set_max_map_size(MAX_ARRAY_LEN);
+    engine
+}
+
+// The functions the scripts edit the save with, each one a handle on it
+fn register_save(engine: &mut Engine, save_game: &SaveGame, edits: &Edits) {
+    fn script_result<T>(result: Result<T>) -> Result<T, Box<EvalAltResult>> {
+        result.map_err(|err| format!("{:#}", err).into())
+    }
+
+    let save = save_game.clone();
+    engine.register_fn("boolean", move |id: i64| {
+        script_result(plot_id(id).and_then(|id| save.with_plots(|plots| plots.boolean(id))))
+    });
+    let save = save_game.clone();
+    engine.register_fn("integer", move |id: i64| {
+        script_result(plot_id(id).and_then(|id| {
+            let integer = save.with_plots(|plots| plots.integer(id, false))?;
+            Ok(i64::from(integer.get()))
+        }))
+    });
+    let save = save_game.clone();
+    engine.register_fn("float", move |id: i64| {
+        script_result(plot_id(id).and_then(|id| {
+            let float = save.with_plots(|plots| plots.float(id, false))?;
+            Ok(f64::from(float.get()))
+        }))
+    });
+    let save = save_game.clone();
+    engine.register_fn("get", move |path: &str| {
+        script_result(save.raw_value(path, None).map(|(value, _)| value))
+    });
+
+    let (save, edits_ref) = (save_game.clone(), Rc::clone(edits));
+    engine.register_fn("set_boolean", move |id: i64, value: bool| {
+        script_result(plot_id(id).and_then(|id| {
+            let before = save.with_plots(|plots| plots.set_boolean(id, value))?;
+            edits_ref.borrow_mut().push(ScriptEdit {
+                target: format!("Boolean {}", id),
+                before: before.to_string(),
+                after: value.to_string(),
+            });
+            Ok(())
+        }))
+    });
+    let (save, edits_ref) = (save_game.clone(), Rc::clone(edits));
+    engine.register_fn("set_integer", move |id: i64, value: i64| {
+        script_result(plot_id(id).and_then(|id| {
+            let value = i32::try_from(value)
+                .with_context(|| format!("{} doesn't fit in an integer plot", value))?;
+            let before = save.with_plots(|plots| plots.set_integer(id, value))?;
+            edits_ref.borrow_mut().push(ScriptEdit {
+                target: format!("Integer {}", id),
+                before: before.to_string(),
+                after: value.to_string(),
+            });
+            Ok(())
+        }))
+    });
+    let (save, edits_ref) = (save_game.clone(), Rc::clone(edits));
+    engine.register_fn("set_float", move |id: i64, value: f64| {
+        script_result(plot_id(id).and_then(|id| {
+            let value = value as f32;
+            ensure!(value.is_finite(), "{} doesn't fit in a float plot", value);
+            let before = save.with_plots(|plots| plots.set_float(id, value))?;
+            edits_ref.borrow_mut().push(ScriptEdit {
+                target: format!("Float {}", id),
+                before: before.to_string(),
+                after: value.to_string(),
+            });
+            Ok(())
+        }))
+    });
+    let (save, edits_ref) = (save_game.clone(), Rc::clone(edits));
+    engine.register_fn("set", move |path: &str, value: Dynamic| {
+        let value = value.to_string();
+        script_result(save.raw_value(path, Some(&value)).map(|(before, after)| {
+            edits_ref.borrow_mut().push(ScriptEdit { target: path.to_owned(), before, after })
+        }))
+    });
+}
+
+fn plot_id(id: i64) -> Result<usize> {
+    match i32::try_from(id) {
+        Ok(id) if id >= 0 => Ok(id as usize),
+        _ => bail!("Invalid plot id {}", id),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::PathBuf};
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::{
+            mass_effect_1::Me1SaveGame, mass_effect_1_le::Me1LeSaveGame,
+            mass_effect_2::Me2LeSaveGame, RcRef,
+        },
+        unreal,
+    };
+
+    #[test]
+    fn run_script() -> Result<()> {
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let save_game: RcRef<Me2LeSaveGame> = unreal::Deserializer::from_bytes(&input)?;
+        let credits = save_game.borrow().player().credits();
+        let save_game = SaveGame::MassEffect2Le {
+            file_path: PathBuf::new(),
+            save_game: RcRef::clone(&save_game),
+        };
+
+        let script = Script::compile(
+            r#"
+            // Loyalties and a fortune
+            for id in [1, 3, 4] {
+                set_boolean(id, true);
+            }
+            set_integer(2, integer(2) + 5);
+            let credits = parse_int(get("Player > Credits"));
+            set("Player > Credits", credits + 999999);
+            "#,
+        )?;
+        let edits = script.run(&save_game)?;
+        let targets: Vec<_> = edits.iter().map(|edit| edit.target.as_str()).collect();
+        assert_eq!(
+            targets,
+            ["Boolean 1", "Boolean 3", "Boolean 4", "Integer 2", "Player > Credits"]
+        );
+        assert_eq!(edits[4].before, credits.to_string());
+        assert_eq!(edits[4].after, (credits + 999999).to_string());
+
+        if let SaveGame::MassEffect2Le { save_game, .. } = &save_game {
+            let save_game = save_game.borrow();
+            let plot = save_game.plot();
+            assert!(plot.booleans()[3]);
+            assert_eq!(edits[3].after, plot.integers()[2].get().to_string());
+            assert_eq!(save_game.player().credits(), credits + 999999);
+        }
+
+        // Numbers as their input takes them, enums by name
+        Script::compile(
+            r#"set("Player > Credits", "current + 1"); set("Difficulty", "insanity");"#,
+        )?
+        .run(&save_game)?;
+
+        // Stopped with where it failed
+        let err = Script::compile("set_boolean(1, true);\nset_boolean(2, \"maybe\");")?
+            .run(&save_game)
+            .unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+        assert!(Script::compile("set_boolean(1, ").is_err());
+
+        // Within the plot tables and the limits of the engine
+        assert!(Script::compile("set_boolean(2000000, true);")?.run(&save_game).is_err());
+        assert!(Script::compile("set_integer(-1, 0);")?.run(&save_game).is_err());
+        assert!(Script::compile("loop {}")?.run(&save_game).is_err());
+        assert!(Script::compile(r#"import "plots" as plots;"#).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn run_script_on_me1() -> Result<()> {
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1_le: RcRef<Me1LeSaveGame> = unreal::Deserializer::from_bytes(&input)?;
+        let save_game =
+            SaveGame::MassEffect1Le { file_path: PathBuf::new(), save_game: RcRef::clone(&me1_le) };
+
+        // The fields of the save data, as the Raw Data tab shows them
+        Script::compile(r#"set("Player > Credits", 1234);"#)?.run(&save_game)?;
+        assert_eq!(me1_le.borrow().save_data.borrow().player().credits(), 1234);
+
+        // Only the plots of an original save
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let me1: RcRef<Me1SaveGame> = unreal::Deserializer::from_bytes(&input)?;
+        let save_game = SaveGame::MassEffect1 { file_path: PathBuf::new(), save_game: me1 };
+        assert!(Script::compile("set_boolean(1, true);")?.run(&save_game).is_ok());
+        assert!(Script::compile(r#"set("Player > Credits", 1234);"#)?.run(&save_game).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+use std::{
+    cell::Cell,
+    path::{Path, PathBuf},
+};
+
+use gloo::storage::{LocalStorage, Storage};
+
+use super::rpc::FileTimestamp;
+
+const REOPEN_LAST_SAVE_KEY: &str = "session_reopen_last_save";
+const LAST_SAVE_KEY: &str = "session_last_save";
+const LAST_TAB_KEY: &str = "session_last_tab";
+const SCROLL_TOP_KEY: &str = "session_scroll_top";
+const SAVE_TIMESTAMP_KEY: &str = "session_save_timestamp";
+const LAUNCH_KEY: &str = "session_launch";
+const WRITE_PROTECTION_KEY: &str = "session_write_protection";
+const RECENT_SAVES_KEY: &str = "session_recent_saves";
+// Enough to go back and forth between a few careers
+const MAX_RECENT_SAVES: usize = 8;
+// Added to the name of the copy saved next to a write protected save
+const COPY_SUFFIX: &str = "_edited";
+
+thread_local! {
+    // From the moment the last save is reopened until its tab is shown again
+    static RESTORING: Cell<bool> = Cell::new(false);
+    // Lifted from the menu bar, until the editor is closed
+    static OVERWRITE_ALLOWED: Cell<bool> = Cell::new(false);
+}
+
+pub fn reopen_last_save() -> bool {
+    LocalStorage::get(REOPEN_LAST_SAVE_KEY).unwrap_or_default()
+}
+
+pub fn set_reopen_last_save(reopen: bool) {
+    let _ = LocalStorage::set(REOPEN_LAST_SAVE_KEY, reopen);
+}
+
+pub fn save_timestamp() -> FileTimestamp {
+    LocalStorage::get(SAVE_TIMESTAMP_KEY).unwrap_or_default()
+}
+
+pub fn set_save_timestamp(timestamp: FileTimestamp) {
+    let _ = LocalStorage::set(SAVE_TIMESTAMP_KEY, timestamp);
+}
+
+pub fn write_protection() -> bool {
+    LocalStorage::get(WRITE_PROTECTION_KEY).unwrap_or_default()
+}
+
+pub fn set_write_protection(protect: bool) {
+    let _ = LocalStorage::set(WRITE_PROTECTION_KEY, protect);
+}
+
+pub fn allow_overwrite(allow: bool) {
+    OVERWRITE_ALLOWED.with(|allowed| allowed.set(allow));
+}
+
+pub fn is_write_protected() -> bool {
+    write_protection() && !OVERWRITE_ALLOWED.with(Cell::get)
+}
+
+// The opened save itself, or a copy next to it while it is write protected
+pub fn save_target(opened_path: &Path) -> PathBuf {
+    if !is_write_protected() {
+        return opened_path.to_owned();
+    }
+
+    let stem = opened_path.file_stem().unwrap_or_default().to_string_lossy();
+    let mut file_name = format!("{}{}", stem, COPY_SUFFIX);
+    if let Some(extension) = opened_path.extension() {
+        file_name.push('.');
+        file_name.push_str(&extension.to_string_lossy());
+    }
+    opened_path.with_file_name(file_name)
+}
+
+// What `Save and launch` starts, the Legendary Edition launcher covers its three games
+#[derive(Clone, Copy, PartialEq)]
+pub enum LaunchGame {
+    MassEffect1,
+    MassEffect2,
+    MassEffect3,
+    Legendary,
+}
+
+impl LaunchGame {
+    pub fn all() -> [LaunchGame; 4] {
+        [
+            LaunchGame::MassEffect1,
+            LaunchGame::MassEffect2,
+            LaunchGame::MassEffect3,
+            LaunchGame::Legendary,
+        ]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LaunchGame::MassEffect1 => "Mass Effect",
+            LaunchGame::MassEffect2 => "Mass Effect 2",
+            LaunchGame::MassEffect3 => "Mass Effect 3",
+            LaunchGame::Legendary => "Legendary Edition",
+        }
+    }
+
+    // The Steam release
+    pub fn default_target(self) -> &'static str {
+        match self {
+            LaunchGame::MassEffect1 => "steam://rungameid/17460",
+            LaunchGame::MassEffect2 => "steam://rungameid/24980",
+            LaunchGame::MassEffect3 => "steam://rungameid/1238020",
+            LaunchGame::Legendary => "steam://rungameid/1328670",
+        }
+    }
+
+    fn key(self) -> String {
+        let game = match self {
+            LaunchGame::MassEffect1 => "me1",
+            LaunchGame::MassEffect2 => "me2",
+            LaunchGame::MassEffect3 => "me3",
+            LaunchGame::Legendary => "le",
+        };
+        format!("{}_{}", LAUNCH_KEY, game)
+    }
+}
+
+// An executable path or a URI
+pub fn launch_target(game: LaunchGame) -> String {
+    LocalStorage::get(game.key()).unwrap_or_else(|_| game.default_target().to_owned())
+}
+
+// Empty goes back to the default
+pub fn set_launch_target(game: LaunchGame, target: &str) {
+    let target = target.trim();
+    if target.is_empty() {
+        LocalStorage::delete(game.key());
+    } else {
+        let _ = LocalStorage::set(game.key(), target);
+    }
+}
+
+// Only when the user asked for it
+pub fn save_to_reopen() -> Option<PathBuf> {
+    reopen_last_save().then(|| LocalStorage::get(LAST_SAVE_KEY).ok()).flatten()
+}
+
+pub fn set_last_save(path: &Path) {
+    let _ = LocalStorage::set(LAST_SAVE_KEY, path);
+
+    let mut recent_saves = recent_saves();
+    recent_saves.retain(|recent| recent != path);
+    recent_saves.insert(0, path.to_owned());
+    recent_saves.truncate(MAX_RECENT_SAVES);
+    let _ = LocalStorage::set(RECENT_SAVES_KEY, recent_saves);
+}
+
+// The last opened first
+pub fn recent_saves() -> Vec<PathBuf> {
+    LocalStorage::get(RECENT_SAVES_KEY).unwrap_or_default()
+}
+
+pub fn forget_recent_save(path: &Path) {
+    let mut recent_saves = recent_saves();
+    recent_saves.retain(|recent| recent != path);
+    let _ = LocalStorage::set(RECENT_SAVES_KEY, recent_saves);
+}
+
+pub fn forget_last_save() {
+    LocalStorage::delete(LAST_SAVE_KEY);
+    RESTORING.with(|restoring| restoring.set(false));
+}
+
+pub fn start_restoring() {
+    RESTORING.with(|restoring| restoring.set(true));
+}
+
+// The tab and its scroll position, once, for the reopened save
+pub fn take_tab_to_restore() -> Option<(String, i32)> {
+    RESTORING.with(|restoring| restoring.replace(false)).then(|| {
+        let tab = LocalStorage::get(LAST_TAB_KEY).ok()?;
+        Some((tab, LocalStorage::get(SCROLL_TOP_KEY).unwrap_or_default()))
+    })?
+}
+
+pub fn set_last_tab(title: &str) {
+    let _ = LocalStorage::set(LAST_TAB_KEY, title);
+    set_scroll_top(0);
+}
+
+pub fn set_scroll_top(scroll_top: i32) {
+    let _ = LocalStorage::set(SCROLL_TOP_KEY, scroll_top);
+}
@@ -0,0 +1,182 @@
+use std::{path::PathBuf, rc::Rc};
+
+use anyhow::{Context as ErrorContext, Error, Result};
+use gloo::storage::{LocalStorage, Storage};
+use yew::{context::ContextHandle, prelude::*, ContextProvider};
+
+use crate::{
+    services::{
+        rpc::{self, RpcFile},
+        save_game::SaveGame,
+        save_handler::SaveHandler,
+    },
+    tlk::TlkFile,
+};
+
+const TLK_PATH_KEY: &str = "tlk_path";
+
+pub enum Msg {
+    Import,
+    Loaded(TlkFile, PathBuf),
+    SaveHandler(SaveHandler),
+    Noop,
+    Error(Error),
+}
+
+#[derive(Clone)]
+pub struct StringTable {
+    tlk: Option<Rc<TlkFile>>,
+    // Only the talk tables of ME2 and ME3 are standalone `.tlk` files, the ones of ME1 live in
+    // its packages. The refs of an ME1 save would be looked up in the wrong game.
+    resolves: bool,
+    import_callback: Callback<()>,
+}
+
+impl StringTable {
+    pub fn get(&self, id: i32) -> Option<&str> {
+        if !self.resolves {
+            return None;
+        }
+        self.tlk.as_ref().and_then(|tlk| tlk.get(id))
+    }
+
+    pub fn resolve(&self, id: i32) -> String {
+        match self.get(id) {
+            Some(string) => string.to_owned(),
+            None => format!("#{}", id),
+        }
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        self.tlk.is_some()
+    }
+
+    pub fn import(&self) {
+        self.import_callback.emit(());
+    }
+}
+
+impl PartialEq for StringTable {
+    fn eq(&self, other: &StringTable) -> bool {
+        match (&self.tlk, &other.tlk) {
+            (Some(tlk), Some(other)) => Rc::ptr_eq(tlk, other) && self.resolves == other.resolves,
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub children: Children,
+    pub onerror: Callback<Error>,
+}
+
+pub struct StringTableProvider {
+    _save_handle: ContextHandle<SaveHandler>,
+    string_table: StringTable,
+}
+
+impl Component for StringTableProvider {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        // Reload the last imported TLK
+        if let Ok(path) = LocalStorage::get::<PathBuf>(TLK_PATH_KEY) {
+            ctx.link().send_future(async move {
+                let handle_tlk = async {
+                    let rpc_file = rpc::load_tlk(path).await?;
+                    Self::parse_tlk(rpc_file)
+                };
+                match handle_tlk.await {
+                    Ok((tlk, path)) => Msg::Loaded(tlk, path),
+                    // The file may have been moved since, forget it silently
+                    Err(_) => {
+                        LocalStorage::delete(TLK_PATH_KEY);
+                        Msg::Noop
+                    }
+                }
+            });
+        }
+
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+
+        let import_callback = ctx.link().callback(|_| Msg::Import);
+        let resolves = Self::resolves(&save_handler);
+        StringTableProvider {
+            _save_handle,
+            string_table: StringTable { tlk: None, resolves, import_callback },
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Import => {
+                ctx.link().send_future(async {
+                    let handle_tlk = async {
+                        let has_rpc_file = rpc::import_tlk().await?;
+                        has_rpc_file.map(Self::parse_tlk).transpose()
+                    };
+                    match handle_tlk.await.context("Failed to import the TLK") {
+                        Ok(Some((tlk, path))) => Msg::Loaded(tlk, path),
+                        Ok(None) => Msg::Noop,
+                        Err(err) => Msg::Error(err),
+                    }
+                });
+                false
+            }
+            Msg::Loaded(tlk, path) => {
+                // Only the reload at startup is lost
+                if let Err(err) = LocalStorage::set(TLK_PATH_KEY, path) {
+                    gloo::console::error!(format!("Failed to remember the TLK: {}", err));
+                }
+                self.string_table.tlk = Some(Rc::new(tlk));
+                true
+            }
+            Msg::SaveHandler(save_handler) => {
+                let resolves = Self::resolves(&save_handler);
+                let changed = resolves != self.string_table.resolves;
+                self.string_table.resolves = resolves;
+                changed
+            }
+            Msg::Noop => false,
+            Msg::Error(err) => {
+                ctx.props().onerror.emit(err);
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <ContextProvider<StringTable> context={self.string_table.clone()}>
+                { ctx.props().children.clone() }
+            </ContextProvider<StringTable>>
+        }
+    }
+}
+
+impl StringTableProvider {
+    fn resolves(save_handler: &SaveHandler) -> bool {
+        let save_game = match save_handler.save_game {
+            Some(ref save_game) => save_game,
+            None => return false,
+        };
+        !matches!(
+            **save_game,
+            SaveGame::MassEffect1 { .. }
+                | SaveGame::MassEffect1Le { .. }
+                | SaveGame::MassEffect1LePs4 { .. }
+        )
+    }
+
+    fn parse_tlk(rpc_file: RpcFile) -> Result<(TlkFile, PathBuf)> {
+        let RpcFile { path, file } = rpc_file;
+        let tlk = TlkFile::from_bytes(&file.decode()?)?;
+        Ok((tlk, path))
+    }
+}
@@ -0,0 +1,162 @@
+use anyhow::{ensure, Context, Result};
+use indexmap::IndexMap;
+
+const TLK_MAGIC: i32 = 0x006B_6C54; // "Tlk\0"
+
+// ME2 / ME3 talk table
+//
+// Strings are Huffman encoded, the tree leaves store the bitwise NOT of an UTF-16 code unit
+#[derive(Default)]
+pub struct TlkFile {
+    male: IndexMap<i32, String>,
+    female: IndexMap<i32, String>,
+}
+
+impl TlkFile {
+    pub fn from_bytes(input: &[u8]) -> Result<Self> {
+        let mut reader = Reader { input, cursor: 0 };
+
+        let magic = reader.read_i32()?;
+        ensure!(magic == TLK_MAGIC, "Invalid TLK file, wrong magic number: 0x{:08x}", magic);
+        let _version = reader.read_i32()?;
+        let _min_version = reader.read_i32()?;
+        let male_count = reader.read_len()?;
+        let female_count = reader.read_len()?;
+        let node_count = reader.read_len()?;
+        let data_len = reader.read_len()?;
+
+        let mut read_refs = |count| -> Result<Vec<(i32, i32)>> {
+            (0..count).map(|_| Ok((reader.read_i32()?, reader.read_i32()?))).collect()
+        };
+        let male_refs = read_refs(male_count)?;
+        let female_refs = read_refs(female_count)?;
+
+        let nodes = (0..node_count)
+            .map(|_| Ok((reader.read_i32()?, reader.read_i32()?)))
+            .collect::<Result<Vec<_>>>()?;
+        let data = reader.read_bytes(data_len)?;
+
+        let decoder = Decoder { nodes: &nodes, data };
+        let decode_refs = |refs: Vec<(i32, i32)>| -> Result<IndexMap<i32, String>> {
+            refs.into_iter()
+                .filter(|&(_, bit_offset)| bit_offset >= 0)
+                .map(|(id, bit_offset)| {
+                    let string = decoder
+                        .decode(bit_offset as usize)
+                        .with_context(|| format!("Failed to decode string ref {}", id))?;
+                    Ok((id, string))
+                })
+                .collect()
+        };
+
+        Ok(TlkFile { male: decode_refs(male_refs)?, female: decode_refs(female_refs)? })
+    }
+
+    pub fn get(&self, id: i32) -> Option<&str> {
+        self.male.get(&id).or_else(|| self.female.get(&id)).map(String::as_str)
+    }
+}
+
+struct Reader<'a> {
+    input: &'a [u8],
+    cursor: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let bytes = self
+            .input
+            .get(self.cursor..self.cursor + len)
+            .context("Invalid TLK file, unexpected end of file")?;
+        self.cursor += len;
+        Ok(bytes)
+    }
+
+    fn read_i32(&mut self) -> Result<i32> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.read_bytes(4)?);
+        Ok(i32::from_le_bytes(bytes))
+    }
+
+    fn read_len(&mut self) -> Result<usize> {
+        let len = self.read_i32()?;
+        ensure!(len >= 0, "Invalid TLK file, negative length: {}", len);
+        Ok(len as usize)
+    }
+}
+
+struct Decoder<'a> {
+    nodes: &'a [(i32, i32)],
+    data: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    fn decode(&self, mut bit_offset: usize) -> Result<String> {
+        let mut string = Vec::new();
+        let mut node = 0;
+        loop {
+            let byte = self.data.get(bit_offset / 8).context("unterminated string")?;
+            let bit = (byte >> (bit_offset % 8)) & 1;
+            bit_offset += 1;
+
+            let (left, right) = *self.nodes.get(node).context("invalid Huffman tree")?;
+            let next = if bit == 1 { right } else { left };
+
+            if next >= 0 {
+                // Internal node
+                node = next as usize;
+            } else {
+                // Leaf
+                let c = !next as u16;
+                if c == 0 {
+                    break;
+                }
+                string.push(c);
+                node = 0;
+            }
+        }
+        Ok(String::from_utf16_lossy(&string))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode_tlk() -> Result<()> {
+        // Tree : `a` = 1, `b` = 01, `\0` = 00
+        let nodes: [(i32, i32); 2] = [(1, !(b'a' as i32)), (!0, !(b'b' as i32))];
+        // "ab" at bit 0, "b" at bit 5
+        let data = [0x45, 0x00];
+
+        let mut input = Vec::new();
+        for value in [TLK_MAGIC, 3, 2, 2, 1, nodes.len() as i32, data.len() as i32] {
+            input.extend(value.to_le_bytes());
+        }
+        // Male refs
+        for value in [100, 0, 101, 5] {
+            input.extend(i32::to_le_bytes(value));
+        }
+        // Female refs
+        for value in [102, 5] {
+            input.extend(i32::to_le_bytes(value));
+        }
+        for (left, right) in nodes {
+            input.extend(left.to_le_bytes());
+            input.extend(right.to_le_bytes());
+        }
+        input.extend(data);
+
+        let tlk = TlkFile::from_bytes(&input)?;
+        assert_eq!(tlk.get(100), Some("ab"));
+        assert_eq!(tlk.get(101), Some("b"));
+        assert_eq!(tlk.get(102), Some("b"));
+        assert_eq!(tlk.get(103), None);
+
+        // Truncated
+        assert!(TlkFile::from_bytes(&input[..input.len() - 1]).is_err());
+
+        Ok(())
+    }
+}
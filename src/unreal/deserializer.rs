@@ -9,22 +9,55 @@ use serde::Deserialize;
 
 use super::Result;
 
+// A field of the root struct, with what it took to parse it
+#[derive(Clone, Debug)]
+pub struct Section {
+    pub name: &'static str,
+    pub bytes: usize,
+    pub millis: f64,
+}
+
 pub struct Deserializer<'de> {
     input: &'de [u8],
     is_le: bool,
+    input_len: usize,
+    // Filled by the root struct only
+    sections: Option<Vec<Section>>,
 }
 
 impl<'de> Deserializer<'de> {
     pub fn from_bytes<T: Deserialize<'de>>(input: &'de [u8]) -> Result<T> {
-        let mut deserializer = Deserializer { input, is_le: true };
+        let mut deserializer = Deserializer::new(input, true, false);
         T::deserialize(&mut deserializer)
     }
 
     pub fn from_be_bytes<T: Deserialize<'de>>(input: &'de [u8]) -> Result<T> {
-        let mut deserializer = Deserializer { input, is_le: false };
+        let mut deserializer = Deserializer::new(input, false, false);
         T::deserialize(&mut deserializer)
     }
 
+    pub fn from_bytes_profiled<T: Deserialize<'de>>(
+        input: &'de [u8], is_le: bool,
+    ) -> Result<(T, Vec<Section>)> {
+        let mut deserializer = Deserializer::new(input, is_le, true);
+        let value = T::deserialize(&mut deserializer)?;
+        Ok((value, deserializer.sections.unwrap_or_default()))
+    }
+
+    // Only parses the start of the input, with how many bytes it took
+    pub fn from_bytes_prefix<T: Deserialize<'de>>(
+        input: &'de [u8], is_le: bool,
+    ) -> Result<(T, usize)> {
+        let mut deserializer = Deserializer::new(input, is_le, false);
+        let value = T::deserialize(&mut deserializer)?;
+        Ok((value, input.len() - deserializer.input.len()))
+    }
+
+    fn new(input: &'de [u8], is_le: bool, profiled: bool) -> Self {
+        let sections = profiled.then(Vec::new);
+        Deserializer { input, is_le, input_len: input.len(), sections }
+    }
+
     fn read(&mut self, len: usize) -> Result<&[u8]> {
         if len > self.input.len() {
             return Err(super::Error::Eof);
@@ -39,6 +72,20 @@ impl<'de> Deserializer<'de> {
     fn read_to_end(&mut self) -> Result<&[u8]> {
         self.read(self.input.len())
     }
+
+    // Every element takes at least a byte, a bigger length is a corrupted one
+    fn read_len(&mut self) -> Result<usize> {
+        let len: u32 = de::Deserialize::deserialize(&mut *self)?;
+        let len = len as usize;
+        if len > self.input.len() {
+            return Err(Error::custom(format!(
+                "length of {} exceeds the {} remaining bytes",
+                len,
+                self.input.len()
+            )));
+        }
+        Ok(len)
+    }
 }
 
 macro_rules! unimpl_deserialize {
@@ -139,7 +186,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
 
         let mut string = if len < 0 {
             // Unicode
-            let string_len = (len.abs() * 2) as usize;
+            let string_len = (len.unsigned_abs() as usize).saturating_mul(2);
             let bytes = self.read(string_len)?.to_owned();
 
             let (decoded, _, had_errors) =
@@ -218,16 +265,16 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        let len: u32 = de::Deserialize::deserialize(&mut *self)?;
-        visitor.visit_seq(SizedSeqMap::new(self, len as usize))
+        let len = self.read_len()?;
+        visitor.visit_seq(SizedSeqMap::new(self, len))
     }
 
     fn deserialize_map<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        let len: u32 = de::Deserialize::deserialize(&mut *self)?;
-        visitor.visit_map(SizedSeqMap::new(self, len as usize))
+        let len = self.read_len()?;
+        visitor.visit_map(SizedSeqMap::new(self, len))
     }
 
     fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value>
@@ -252,7 +299,18 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        self.deserialize_tuple_struct(name, fields.len(), visitor)
+        let is_root = self.input.len() == self.input_len;
+        match self.sections.take() {
+            Some(mut sections) if is_root => {
+                let value = visitor.visit_seq(ProfiledSeq::new(&mut *self, fields, &mut sections));
+                self.sections = Some(sections);
+                value
+            }
+            sections => {
+                self.sections = sections;
+                self.deserialize_tuple_struct(name, fields.len(), visitor)
+            }
+        }
     }
 
     fn deserialize_enum<V>(
@@ -373,3 +431,60 @@ impl<'de, 'a> VariantAccess<'de> for &'a mut Deserializer<'de> {
         self.deserialize_tuple(fields.len(), visitor)
     }
 }
+
+struct ProfiledSeq<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+    fields: &'static [&'static str],
+    sections: &'a mut Vec<Section>,
+}
+
+impl<'a, 'de> ProfiledSeq<'a, 'de> {
+    fn new(
+        de: &'a mut Deserializer<'de>, fields: &'static [&'static str],
+        sections: &'a mut Vec<Section>,
+    ) -> Self {
+        ProfiledSeq { de, fields, sections }
+    }
+}
+
+impl<'de, 'a> SeqAccess<'de> for ProfiledSeq<'a, 'de> {
+    type Error = super::Error;
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len() - self.sections.len())
+    }
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let name = match self.fields.get(self.sections.len()) {
+            Some(&name) => name,
+            None => return Ok(None),
+        };
+
+        let (start, remaining) = (now(), self.de.input.len());
+        let value = seed.deserialize(&mut *self.de)?;
+        self.sections.push(Section {
+            name,
+            bytes: remaining - self.de.input.len(),
+            millis: now() - start,
+        });
+        Ok(Some(value))
+    }
+}
+
+// `std::time::Instant` panics in the browser
+#[cfg(target_arch = "wasm32")]
+pub(super) fn now() -> f64 {
+    js_sys::Date::now()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn now() -> f64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs_f64() * 1000.0)
+        .unwrap_or(0.0)
+}
@@ -0,0 +1,215 @@
+use std::{
+    fmt,
+    ops::Range,
+    panic::{self, AssertUnwindSafe},
+};
+
+use super::{deserializer::now, Section};
+
+// Deterministic, a finding replays from the seed it was logged with
+pub struct Mutator {
+    state: u64,
+}
+
+impl Mutator {
+    pub fn new(seed: u64) -> Self {
+        // Xorshift never leaves 0
+        Mutator { state: seed.max(1) }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n.max(1) as u64) as usize
+    }
+
+    // Picks a section of the schema, then a few bytes in it
+    pub fn mutate(&mut self, input: &[u8], sections: &[Section]) -> (Vec<u8>, Mutation) {
+        let whole = [Section { name: "save", bytes: input.len(), millis: 0.0 }];
+        let sections = if sections.is_empty() { &whole[..] } else { sections };
+
+        let idx = self.below(sections.len());
+        let start = sections[..idx].iter().map(|section| section.bytes).sum::<usize>();
+        let section = &sections[idx];
+        let section_end = (start + section.bytes).min(input.len());
+
+        let offset = (start + self.below(section.bytes)).min(input.len().saturating_sub(1));
+        let end = (offset + 1 + self.below(8)).min(section_end.max(offset + 1)).min(input.len());
+
+        let kind = match self.below(4) {
+            0 => MutationKind::Random,
+            1 => MutationKind::Zeroes,
+            2 => MutationKind::Ones,
+            _ => MutationKind::Truncate,
+        };
+
+        let mut mutated = input.to_vec();
+        match kind {
+            MutationKind::Random => {
+                for byte in &mut mutated[offset..end] {
+                    *byte = self.next_u64() as u8;
+                }
+            }
+            MutationKind::Zeroes => mutated[offset..end].fill(0),
+            MutationKind::Ones => mutated[offset..end].fill(0xff),
+            MutationKind::Truncate => mutated.truncate(offset),
+        }
+
+        (mutated, Mutation { section: section.name, range: offset..end, kind })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum MutationKind {
+    Random,
+    Zeroes,
+    // Lengths and counts to their max
+    Ones,
+    Truncate,
+}
+
+#[derive(Clone, Debug)]
+pub struct Mutation {
+    pub section: &'static str,
+    pub range: Range<usize>,
+    pub kind: MutationKind,
+}
+
+impl fmt::Display for Mutation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            MutationKind::Truncate => {
+                write!(f, "truncated at 0x{:x} ({})", self.range.start, self.section)
+            }
+            kind => write!(
+                f,
+                "{:?} at 0x{:x}..0x{:x} ({})",
+                kind, self.range.start, self.range.end, self.section
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Finding {
+    Panic(Mutation, String),
+    // Way slower than the untouched save, a huge allocation or a runaway loop
+    Slow(Mutation, f64),
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Finding::Panic(mutation, message) => write!(f, "Panic on {}: {}", mutation, message),
+            Finding::Slow(mutation, millis) => {
+                write!(f, "Parsed in {:.1} ms on {}", millis, mutation)
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Report {
+    pub runs: usize,
+    pub rejected: usize,
+    pub findings: Vec<Finding>,
+}
+
+// `parse` tells if the mutated input was accepted.
+// Panics are only caught on native targets, in the browser they abort the editor.
+pub fn fuzz<F>(input: &[u8], sections: &[Section], seed: u64, runs: usize, parse: F) -> Report
+where
+    F: Fn(&[u8]) -> bool,
+{
+    let start = now();
+    parse(input);
+    let slow = (now() - start) * 10.0 + 100.0;
+
+    let mut mutator = Mutator::new(seed);
+    let mut report = Report { runs, ..Default::default() };
+    for _ in 0..runs {
+        let (mutated, mutation) = mutator.mutate(input, sections);
+
+        let start = now();
+        match panic::catch_unwind(AssertUnwindSafe(|| parse(&mutated))) {
+            Ok(accepted) => {
+                if !accepted {
+                    report.rejected += 1;
+                }
+                let millis = now() - start;
+                if millis > slow {
+                    report.findings.push(Finding::Slow(mutation, millis));
+                }
+            }
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+                report.findings.push(Finding::Panic(mutation, message));
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_2::Me2SaveGame, unreal};
+
+    #[test]
+    fn mutations_stay_in_their_section() {
+        let input = [0u8; 64];
+        let sections = [
+            Section { name: "head", bytes: 16, millis: 0.0 },
+            Section { name: "body", bytes: 48, millis: 0.0 },
+        ];
+
+        let mut mutator = Mutator::new(42);
+        for _ in 0..1000 {
+            let (mutated, Mutation { section, range, kind }) = mutator.mutate(&input, &sections);
+            let bounds = if section == "head" { 0..16 } else { 16..64 };
+            assert!(bounds.contains(&range.start) && range.end <= bounds.end);
+            match kind {
+                MutationKind::Truncate => assert_eq!(mutated.len(), range.start),
+                _ => assert_eq!(mutated.len(), input.len()),
+            }
+        }
+    }
+
+    #[test]
+    fn corrupted_lengths() {
+        // A string and a list claiming far more than what follows
+        let string = [0x00, 0x00, 0x00, 0x80, b'a', 0];
+        assert!(unreal::Deserializer::from_bytes::<String>(&string).is_err());
+
+        let list = [0xff, 0xff, 0xff, 0xff, 1, 2, 3, 4];
+        assert!(unreal::Deserializer::from_bytes::<Vec<u32>>(&list).is_err());
+    }
+
+    #[test]
+    fn fuzz_me2() -> Result<()> {
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let (_, sections) = unreal::Deserializer::from_bytes_profiled::<Me2SaveGame>(&input, true)?;
+
+        let report = fuzz(&input, &sections, 1, 200, |input| {
+            unreal::Deserializer::from_bytes::<Me2SaveGame>(input).is_ok()
+        });
+
+        let findings = report.findings.iter().map(Finding::to_string).collect::<Vec<_>>();
+        assert!(findings.is_empty(), "{:#?}", findings);
+        assert!(report.rejected > 0);
+        Ok(())
+    }
+}
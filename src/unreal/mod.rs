@@ -1,4 +1,5 @@
 mod deserializer;
+pub mod fuzz;
 mod serializer;
 
 pub use self::{deserializer::*, serializer::*};
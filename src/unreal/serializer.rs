@@ -52,6 +52,12 @@ macro_rules! impl_serialize {
     };
 }
 
+// Lengths are written on 32 bits, a longer string or sequence can't be
+fn checked_len<T: TryFrom<usize>>(len: usize) -> Result<T> {
+    T::try_from(len)
+        .map_err(|_| Error::custom(format!("length of {} is too long to be written", len)))
+}
+
 impl<'a> ser::Serializer for &'a mut Serializer {
     type Ok = ();
     type Error = super::Error;
@@ -103,8 +109,8 @@ impl<'a> ser::Serializer for &'a mut Serializer {
                 .flat_map(|c| if self.is_le { u16::to_le_bytes(c) } else { u16::to_be_bytes(c) })
                 .collect();
 
-            let len = encoded.len() as i32;
-            (encoded, -(len / 2))
+            let len: i32 = checked_len(encoded.len() / 2)?;
+            (encoded, -len)
         } else {
             // Ascii
             let (encoded, _, had_errors) = WINDOWS_1252.encode(string);
@@ -115,7 +121,7 @@ impl<'a> ser::Serializer for &'a mut Serializer {
             let mut encoded = encoded.into_owned();
             encoded.push(0);
 
-            let len = encoded.len() as i32;
+            let len = checked_len(encoded.len())?;
             (encoded, len)
         };
 
@@ -173,7 +179,7 @@ impl<'a> ser::Serializer for &'a mut Serializer {
 
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
         if let Some(len) = len {
-            self.serialize_u32(len as u32)?;
+            self.serialize_u32(checked_len(len)?)?;
         }
         Ok(self)
     }
@@ -196,7 +202,7 @@ impl<'a> ser::Serializer for &'a mut Serializer {
 
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
         if let Some(len) = len {
-            self.serialize_u32(len as u32)?;
+            self.serialize_u32(checked_len(len)?)?;
         }
         Ok(self)
     }
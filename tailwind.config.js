@@ -1,5 +1,14 @@
 const defaultTheme = require("tailwindcss/defaultTheme")
 
+// Neutral colors are RGB channels in CSS variables so they can follow the light / dark theme
+// `--min-opacity` makes everything opaque when transparency is reduced
+function themed(variable) {
+  return ({ opacityValue }) =>
+    opacityValue === undefined
+      ? `rgb(var(${variable}))`
+      : `rgb(var(${variable}) / max(${opacityValue}, var(--min-opacity)))`
+}
+
 module.exports = {
   content: [
     "./index.html",
@@ -7,28 +16,32 @@ module.exports = {
   ],
   theme: {
     fontFamily: {
-      default: [defaultTheme.fontFamily.mono],
+      // Set by the appearance service, see `--font-family`
+      default: "var(--font-family)",
+      sans: defaultTheme.fontFamily.sans,
+      serif: defaultTheme.fontFamily.serif,
+      mono: defaultTheme.fontFamily.mono,
     },
     colors: {
-      white: "#FFFFFF",
+      fg: themed("--fg"),
       default: {
-        bg: "#0D0D0D",
-        border: "#333333",
+        bg: themed("--default-bg"),
+        border: themed("--default-border"),
       },
       "title-bar": {
-        bg: "#191919",
+        bg: themed("--title-bar-bg"),
         close: "#AA0000",
       },
-      "menu-bar": "#242424",
+      "menu-bar": themed("--menu-bar"),
       "scroll-bar": {
-        bg: "#090909",
-        fg: "#4F4F4F",
+        bg: themed("--scroll-bar-bg"),
+        fg: themed("--scroll-bar-fg"),
       },
       table: {
-        odd: "#121212",
-        even: "#1A1A1A",
+        odd: themed("--table-odd"),
+        even: themed("--table-even"),
       },
-      popup: "#121212",
+      popup: themed("--popup"),
       me1: {
         bg: "#1C526E",
         tab: "#296B94",
@@ -47,6 +60,24 @@ module.exports = {
         active: "#B30000",
         hover: "#05476E",
       },
+      "me1-light": {
+        bg: "#A8CBE0",
+        tab: "#86B6D4",
+        active: "#6AA3C7",
+        hover: "#F0AE80",
+      },
+      "me2-light": {
+        bg: "#F0BE98",
+        tab: "#E8A87C",
+        active: "#E09060",
+        hover: "#93CC95",
+      },
+      "me3-light": {
+        bg: "#E8A0A0",
+        tab: "#DE8080",
+        active: "#D46060",
+        hover: "#8CB8D6",
+      },
       theme: {
         bg: "var(--bg)",
         tab: "var(--tab)",